@@ -0,0 +1,64 @@
+//! Timings for the per-track artwork work: the vinyl render and the palette pass.
+//! Run with `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use eframe::egui::{Color32, ColorImage};
+use now_playing_gui::{
+    config::{PaletteConfig, VinylThumbnailConfig},
+    palette::{dominant_gradient_colors, dynamic_accent_from_image},
+    vinyl::{render_vinyl, VinylThumbnailOptions},
+};
+
+/// Side of a typical media-session thumbnail.
+const COVER_SIDE: usize = 300;
+
+/// A cover with a diagonal gradient and a few flat blocks, so clustering has several
+/// distinct colors to separate.
+fn cover(side: usize) -> ColorImage {
+    let mut pixels = Vec::with_capacity(side * side);
+    for y in 0..side {
+        for x in 0..side {
+            let t = (x + y) as f32 / (2 * side) as f32;
+            let color = match (x * 4 / side, y * 4 / side) {
+                (0, 0) => Color32::from_rgb(230, 60, 40),
+                (3, 3) => Color32::from_rgb(20, 160, 150),
+                _ => Color32::from_rgb((40.0 + 160.0 * t) as u8, 50, (140.0 - 100.0 * t) as u8),
+            };
+            pixels.push(color);
+        }
+    }
+    ColorImage::new([side, side], pixels)
+}
+
+fn vinyl(c: &mut Criterion) {
+    let image = cover(COVER_SIDE);
+    let config = VinylThumbnailConfig::default();
+    let mut group = c.benchmark_group("render_vinyl");
+    group.sample_size(20);
+    for output_size in [128, 256, 512, 1024] {
+        let options = VinylThumbnailOptions {
+            output_size,
+            ..VinylThumbnailOptions::from_config(&config, COVER_SIDE, COVER_SIDE)
+        };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(output_size),
+            &options,
+            |b, options| b.iter(|| render_vinyl(black_box(&image), options)),
+        );
+    }
+    group.finish();
+}
+
+fn palette(c: &mut Criterion) {
+    let image = cover(COVER_SIDE);
+    let config = PaletteConfig::default();
+    c.bench_function("dominant_gradient_colors", |b| {
+        b.iter(|| dominant_gradient_colors(black_box(&image), &config))
+    });
+    c.bench_function("dynamic_accent_from_image", |b| {
+        b.iter(|| dynamic_accent_from_image(black_box(&image)))
+    });
+}
+
+criterion_group!(benches, vinyl, palette);
+criterion_main!(benches);
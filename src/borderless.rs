@@ -0,0 +1,158 @@
+//! Borderless mode's native window changes: the order they reach the OS in, and a
+//! panic hook that puts the frame back if the widget goes down while it is hidden.
+
+/// One change to the native window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WindowStep {
+    /// Show (`true`) or hide the title bar and frame, sent as a viewport command.
+    Decorations(bool),
+    /// DWM corner rounding, on while borderless.
+    CornerPreference { rounded: bool },
+}
+
+/// Sends a decorations change first and its corner preference on the next frame, once
+/// the viewport has applied the change, so DWM never sees the two out of order.
+#[derive(Debug, Default)]
+pub(crate) struct DecorationSequence {
+    decorated: Option<bool>,
+    corner_pending: Option<bool>,
+}
+
+impl DecorationSequence {
+    /// What to send this frame for the wanted `hidden` state, in order.
+    pub(crate) fn steps(&mut self, hidden: bool) -> Vec<WindowStep> {
+        let mut steps = Vec::new();
+        if let Some(rounded) = self.corner_pending.take() {
+            steps.push(WindowStep::CornerPreference { rounded });
+        }
+        if self.decorated != Some(!hidden) {
+            self.decorated = Some(!hidden);
+            steps.push(WindowStep::Decorations(!hidden));
+            self.corner_pending = Some(hidden);
+        }
+        steps
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) use restore::{install_panic_hook, restore_window, track_window};
+
+#[cfg(target_os = "windows")]
+mod restore {
+    use std::sync::{
+        atomic::{AtomicBool, AtomicIsize, Ordering},
+        OnceLock,
+    };
+    use std::thread::{self, ThreadId};
+
+    use windows::Win32::{
+        Foundation::HWND,
+        Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT},
+        UI::WindowsAndMessaging::{
+            GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_STYLE, SWP_FRAMECHANGED,
+            SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WS_CAPTION, WS_SYSMENU,
+            WS_THICKFRAME,
+        },
+    };
+
+    static WINDOW: AtomicIsize = AtomicIsize::new(0);
+    static BORDERLESS: AtomicBool = AtomicBool::new(false);
+    /// The thread that owns the window; a panic anywhere else leaves the UI running.
+    static UI_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+    /// Remembers the window and whether it is borderless, for the panic hook. Called
+    /// from the UI thread whenever either changes.
+    pub(crate) fn track_window(hwnd: HWND, borderless: bool) {
+        let _ = UI_THREAD.set(thread::current().id());
+        WINDOW.store(hwnd.0 as isize, Ordering::SeqCst);
+        BORDERLESS.store(borderless, Ordering::SeqCst);
+    }
+
+    /// Puts the frame style and square corners back ahead of the existing hook when the
+    /// UI thread panics. No frame change is forced from inside the hook; the window
+    /// redraws its frame the next time it is moved or resized.
+    pub(crate) fn install_panic_hook() {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if UI_THREAD.get() == Some(&thread::current().id()) {
+                if let Some(hwnd) = take_borderless_window() {
+                    restore_style_and_corners(hwnd);
+                }
+            }
+            previous(info);
+        }));
+    }
+
+    /// Best effort: gives a borderless window its frame and square corners back, and
+    /// redraws the frame straight away.
+    pub(crate) fn restore_window() {
+        let Some(hwnd) = take_borderless_window() else {
+            return;
+        };
+        restore_style_and_corners(hwnd);
+        unsafe {
+            let _ = SetWindowPos(
+                hwnd,
+                None,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+            );
+        }
+    }
+
+    /// The tracked window if it is still borderless; restoring it happens only once.
+    fn take_borderless_window() -> Option<HWND> {
+        if !BORDERLESS.swap(false, Ordering::SeqCst) {
+            return None;
+        }
+        let raw = WINDOW.load(Ordering::SeqCst);
+        (raw != 0).then_some(HWND(raw as *mut std::ffi::c_void))
+    }
+
+    fn restore_style_and_corners(hwnd: HWND) {
+        let frame = (WS_CAPTION | WS_SYSMENU | WS_THICKFRAME).0 as isize;
+        let preference = DWMWCP_DEFAULT;
+        unsafe {
+            let style = GetWindowLongPtrW(hwnd, GWL_STYLE);
+            SetWindowLongPtrW(hwnd, GWL_STYLE, style | frame);
+            let _ = DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_WINDOW_CORNER_PREFERENCE,
+                &preference as *const _ as *const _,
+                std::mem::size_of_val(&preference) as u32,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decorations_always_reach_the_window_a_frame_before_their_corners() {
+        let mut sequence = DecorationSequence::default();
+        let mut recorded = Vec::new();
+        for hidden in [false, false, false, true, false, false, false] {
+            recorded.push(sequence.steps(hidden));
+        }
+        use WindowStep::{CornerPreference as Corner, Decorations};
+        assert_eq!(
+            recorded,
+            vec![
+                // First frame: the frame state before any corner rounding.
+                vec![Decorations(true)],
+                vec![Corner { rounded: false }],
+                vec![],
+                vec![Decorations(false)],
+                // Shown again before the rounding went out: the stale corner goes first.
+                vec![Corner { rounded: true }, Decorations(true)],
+                vec![Corner { rounded: false }],
+                vec![],
+            ]
+        );
+    }
+}
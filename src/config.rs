@@ -1,16 +1,34 @@
 use anyhow::Context;
-use serde::Deserialize;
-use std::{env, fs};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fs, path::PathBuf, time::Duration};
+
+use crate::{state::POSITION_EMIT_INTERVAL, theme::OverlayControlsPosition};
+
+/// Floor for `[remote] position_interval_ms`, so subscribers aren't flooded.
+const MIN_POSITION_INTERVAL_MS: u64 = 100;
+/// Floor and default for `[ui] layout_rotation.interval_secs`.
+const MIN_LAYOUT_ROTATION_SECS: u64 = 30;
+const DEFAULT_LAYOUT_ROTATION_SECS: u64 = 300;
+/// Range and default for `[ui] border_pulse_bpm`.
+const MIN_BORDER_PULSE_BPM: u32 = 30;
+const MAX_BORDER_PULSE_BPM: u32 = 240;
+const DEFAULT_BORDER_PULSE_BPM: u32 = 90;
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub ui: UiConfig,
+    pub remote: RemoteConfig,
+    pub window: WindowConfig,
+    source: Option<PathBuf>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             ui: UiConfig::default(),
+            remote: RemoteConfig::default(),
+            window: WindowConfig::default(),
+            source: None,
         }
     }
 }
@@ -39,32 +57,313 @@ impl Config {
                     .with_context(|| format!("Failed to read config file: {}", path.display()))?;
                 let doc: ConfigDocument = toml::from_str(&data)
                     .with_context(|| format!("Failed to parse config: {}", path.display()))?;
-                return Ok(doc.into());
+                let mut config: Config = doc.into();
+                config.source = Some(path);
+                return Ok(config);
             }
         }
 
         Ok(Config::default())
     }
+
+    /// `favorites.toml` beside the config file, or in the working directory when no
+    /// config file was found.
+    pub fn favorites_path(&self) -> anyhow::Result<PathBuf> {
+        let dir = match self.source.as_deref().and_then(|path| path.parent()) {
+            Some(dir) => dir.to_path_buf(),
+            None => env::current_dir().context("Failed to resolve working directory")?,
+        };
+        Ok(dir.join("favorites.toml"))
+    }
+
+    /// Config that is saved to `path`, as if it had been loaded from there.
+    pub fn with_source(mut self, path: PathBuf) -> Self {
+        self.source = Some(path);
+        self
+    }
+
+    /// Writes persisted preferences back to the file the config was loaded from
+    /// (or `config.toml` in the working directory). Only settings that differ from
+    /// what the file says are written; comments, layout and unknown keys stay as the
+    /// user left them.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = match &self.source {
+            Some(path) => path.clone(),
+            None => env::current_dir()
+                .context("Failed to resolve working directory")?
+                .join("config.toml"),
+        };
+
+        let (mut document, on_disk) = if path.exists() {
+            let data = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+            let document = data
+                .parse::<toml_edit::DocumentMut>()
+                .with_context(|| format!("Failed to parse config: {}", path.display()))?;
+            let doc: ConfigDocument = toml::from_str(&data)
+                .with_context(|| format!("Failed to parse config: {}", path.display()))?;
+            (document, Config::from(doc))
+        } else {
+            (toml_edit::DocumentMut::new(), Config::default())
+        };
+
+        let before = toml::Table::try_from(ConfigDocument::from(&on_disk))
+            .context("Failed to serialize config")?;
+        let after = toml::Table::try_from(ConfigDocument::from(self))
+            .context("Failed to serialize config")?;
+        apply_changes(document.as_table_mut(), &before, &after, true);
+
+        fs::write(&path, document.to_string())
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+        Ok(())
+    }
+}
+
+impl Config {
+    /// Resolved settings as dotted `key = value` pairs, for diagnostics output.
+    pub fn flattened(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        if let Ok(table) = toml::Table::try_from(ConfigDocument::from(self)) {
+            flatten_table("", &table, &mut pairs);
+        }
+        pairs
+    }
+}
+
+fn flatten_table(prefix: &str, table: &toml::Table, out: &mut Vec<(String, String)>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::Table(inner) => flatten_table(&path, inner, out),
+            other => out.push((path, other.to_string())),
+        }
+    }
+}
+
+/// Writes the keys that differ between `before` and `after` into `target`, keeping
+/// the decor of values it replaces. `standard` is false inside inline tables, where
+/// nested tables have to stay inline too.
+fn apply_changes(
+    target: &mut dyn toml_edit::TableLike,
+    before: &toml::Table,
+    after: &toml::Table,
+    standard: bool,
+) {
+    for key in before.keys() {
+        if !after.contains_key(key) {
+            target.remove(key);
+        }
+    }
+    for (key, value) in after {
+        let previous = before.get(key);
+        if previous == Some(value) {
+            continue;
+        }
+        if let (Some(toml::Value::Table(previous)), toml::Value::Table(table)) = (previous, value) {
+            // Only the changed entries of a table the file doesn't have yet are added.
+            if !target.contains_key(key) {
+                let empty = edit_item(&toml::Value::Table(toml::Table::new()), standard);
+                target.insert(key, empty);
+            }
+            if let Some(existing) = target.get_mut(key) {
+                let nested_standard = existing.is_table();
+                if let Some(existing) = existing.as_table_like_mut() {
+                    apply_changes(existing, previous, table, nested_standard);
+                    continue;
+                }
+            }
+        }
+        let mut item = edit_item(value, standard);
+        match target.get_mut(key) {
+            // Replacing the item in place keeps the comments above the key.
+            Some(existing) => {
+                if let (Some(old), Some(new)) = (existing.as_value(), item.as_value_mut()) {
+                    *new.decor_mut() = old.decor().clone();
+                }
+                *existing = item;
+            }
+            None => {
+                target.insert(key, item);
+            }
+        }
+    }
+}
+
+/// `value` as a new document item; tables become `[sections]` where `standard` allows.
+fn edit_item(value: &toml::Value, standard: bool) -> toml_edit::Item {
+    match value {
+        toml::Value::Table(table) if standard => {
+            let mut section = toml_edit::Table::new();
+            // No bare header for a table that only holds other tables.
+            section.set_implicit(true);
+            for (key, value) in table {
+                section.insert(key, edit_item(value, true));
+            }
+            toml_edit::Item::Table(section)
+        }
+        value => toml_edit::Item::Value(edit_value(value)),
+    }
+}
+
+fn edit_value(value: &toml::Value) -> toml_edit::Value {
+    match value {
+        toml::Value::String(text) => text.as_str().into(),
+        toml::Value::Integer(number) => (*number).into(),
+        toml::Value::Float(number) => (*number).into(),
+        toml::Value::Boolean(flag) => (*flag).into(),
+        toml::Value::Datetime(datetime) => (*datetime).into(),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(edit_value)
+            .collect::<toml_edit::Array>()
+            .into(),
+        toml::Value::Table(table) => table
+            .iter()
+            .map(|(key, value)| (key.as_str(), edit_value(value)))
+            .collect::<toml_edit::InlineTable>()
+            .into(),
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct UiConfig {
     pub vinyl_thumbnail: VinylThumbnailConfig,
+    /// How artwork colors are clustered for the dynamic gradient.
+    pub palette: PaletteConfig,
+    /// What to do when the media session turns down each transport command.
+    pub rejected_behavior: RejectedBehaviorConfig,
+    /// User accent color (RGB) applied on top of whichever skin is active.
+    pub accent_override: Option<[u8; 3]>,
+    /// Component ids hidden via the settings panel, keyed by `skin_id/layout_id`.
+    pub hidden_components: BTreeMap<String, Vec<String>>,
+    /// Component ids the skin hides but the user showed again, keyed the same way.
+    pub shown_components: BTreeMap<String, Vec<String>>,
+    /// Layout id last picked in each skin, keyed by skin id.
+    pub skin_layouts: BTreeMap<String, String>,
+    /// UI zoom relative to the OS scale factor; `None` follows the system DPI.
+    pub scale: Option<f32>,
+    /// Flash the taskbar button when the track changes while the window is unfocused.
+    pub flash_on_track_change: bool,
+    /// Dim the artwork while playback is paused or stopped.
+    pub dim_when_paused: bool,
+    /// Never show the vinyl disc, whatever the skin or the vinyl toggle says.
+    pub force_disable_vinyl: bool,
+    /// Hide the playback controls group while the session accepts no transport commands.
+    pub hide_unavailable_controls: bool,
+    /// Drift the artwork behind dimmed controls after a long pause.
+    pub idle_screensaver: bool,
+    /// Minutes paused (without input) before the idle screensaver starts.
+    pub idle_screensaver_minutes: u32,
+    /// Track length in minutes past which a session counts as long-form.
+    pub long_form_minutes: u32,
+    /// Read the elapsed and total time out to screen readers while playing.
+    pub announce_position: bool,
+    /// Seconds between position announcements.
+    pub announce_position_secs: u32,
+    /// Corner for the pin/gear buttons; `None` keeps the skin's placement.
+    pub overlay_corner: Option<OverlayControlsPosition>,
+    /// Screen corner last picked from "Move to"; `None` until one is chosen.
+    pub snap_corner: Option<SnapCorner>,
+    /// Gap in pixels between the window and the work area edge when snapping.
+    pub snap_margin: i32,
+    /// Snap back to `snap_corner` when the monitor's work area changes.
+    pub resnap_on_display_change: bool,
+    /// Move the window along when the user switches virtual desktops.
+    pub all_desktops: bool,
+    /// What a primary click on the artwork does.
+    pub artwork_click: ArtworkClick,
+    /// Let the mouse select and copy metadata text. Off by default because it takes
+    /// drags on that text away from moving the window.
+    pub selectable_text: bool,
+    /// Hot reload: pick up skin file changes while the widget runs.
+    pub watch_skins: bool,
+    /// Tint buttons and the slider with an artwork accent even when the skin doesn't
+    /// set `dynamic_accent`. Independent of the background gradient.
+    pub accent_from_art: bool,
+    /// The `auto` skin shows the light built-in theme while the system is in light mode.
+    pub follow_system_theme: bool,
+    /// Slowly cycle a dynamic gradient through the artwork's palette.
+    pub animated_gradient: bool,
+    /// Pulse the window border on a fixed tempo while playing.
+    pub border_pulse: bool,
+    /// Beats per minute for `border_pulse`.
+    pub border_pulse_bpm: u32,
+    /// Ambient mode: cycle through these layout variants on a timer. Hand-edited only.
+    pub layout_rotation: Option<LayoutRotationConfig>,
+}
+
+/// `[ui] layout_rotation = { variants = [...], interval_secs = 300 }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutRotationConfig {
+    /// Layout variant ids in rotation order; ids the skin lacks are skipped.
+    pub variants: Vec<String>,
+    pub interval: Duration,
 }
 
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
             vinyl_thumbnail: VinylThumbnailConfig::default(),
+            palette: PaletteConfig::default(),
+            rejected_behavior: RejectedBehaviorConfig::default(),
+            accent_override: None,
+            hidden_components: BTreeMap::new(),
+            shown_components: BTreeMap::new(),
+            skin_layouts: BTreeMap::new(),
+            scale: None,
+            flash_on_track_change: false,
+            dim_when_paused: false,
+            force_disable_vinyl: false,
+            hide_unavailable_controls: false,
+            idle_screensaver: false,
+            idle_screensaver_minutes: 5,
+            long_form_minutes: 20,
+            announce_position: false,
+            announce_position_secs: 30,
+            overlay_corner: None,
+            snap_corner: None,
+            snap_margin: 12,
+            resnap_on_display_change: false,
+            all_desktops: false,
+            artwork_click: ArtworkClick::ToggleVinyl,
+            selectable_text: false,
+            watch_skins: false,
+            accent_from_art: false,
+            follow_system_theme: true,
+            animated_gradient: false,
+            border_pulse: false,
+            border_pulse_bpm: DEFAULT_BORDER_PULSE_BPM,
+            layout_rotation: None,
         }
     }
 }
 
+impl UiConfig {
+    pub const MIN_SCALE: f32 = 0.75;
+    pub const MAX_SCALE: f32 = 2.0;
+
+    pub fn hidden_components_key(skin_id: &str, layout_id: &str) -> String {
+        format!("{skin_id}/{layout_id}")
+    }
+
+    pub fn scale(&self) -> Option<f32> {
+        self.scale
+            .filter(|scale| scale.is_finite())
+            .map(|scale| scale.clamp(Self::MIN_SCALE, Self::MAX_SCALE))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct VinylThumbnailConfig {
     pub enabled: bool,
     pub swirl_strength: f32,
     pub label_ratio: f32,
+    /// Spin counterclockwise.
+    pub reverse: bool,
 }
 
 impl Default for VinylThumbnailConfig {
@@ -73,6 +372,7 @@ impl Default for VinylThumbnailConfig {
             enabled: false,
             swirl_strength: 45.0,
             label_ratio: 0.95,
+            reverse: false,
         }
     }
 }
@@ -87,10 +387,222 @@ impl VinylThumbnailConfig {
     }
 }
 
-#[derive(Debug, Default, Deserialize)]
+/// `[ui.palette]`: k-means settings for picking gradient colors out of the artwork.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteConfig {
+    /// Clusters the artwork's pixels are grouped into.
+    pub clusters: usize,
+    /// Most k-means refinement passes.
+    pub iterations: usize,
+    /// Squared RGB distance two clusters must exceed to count as different colors.
+    pub distinct_threshold: f32,
+}
+
+impl Default for PaletteConfig {
+    fn default() -> Self {
+        Self {
+            clusters: 3,
+            iterations: 10,
+            distinct_threshold: 400.0,
+        }
+    }
+}
+
+impl PaletteConfig {
+    pub fn clusters(&self) -> usize {
+        self.clusters.clamp(2, 8)
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations.clamp(1, 50)
+    }
+
+    pub fn distinct_threshold(&self) -> f32 {
+        self.distinct_threshold.clamp(0.0, 30_000.0)
+    }
+}
+
+/// Screen position the window can be snapped to from the context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl SnapCorner {
+    pub const ALL: [Self; 5] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+        Self::Center,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "top_left" => Some(Self::TopLeft),
+            "top_right" => Some(Self::TopRight),
+            "bottom_left" => Some(Self::BottomLeft),
+            "bottom_right" => Some(Self::BottomRight),
+            "center" => Some(Self::Center),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TopLeft => "top_left",
+            Self::TopRight => "top_right",
+            Self::BottomLeft => "bottom_left",
+            Self::BottomRight => "bottom_right",
+            Self::Center => "center",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top-left",
+            Self::TopRight => "Top-right",
+            Self::BottomLeft => "Bottom-left",
+            Self::BottomRight => "Bottom-right",
+            Self::Center => "Center",
+        }
+    }
+}
+
+/// Action bound to a primary click on the artwork.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkClick {
+    ToggleVinyl,
+    PlayPause,
+    OpenSource,
+    None,
+}
+
+impl ArtworkClick {
+    pub const ALL: [Self; 4] = [
+        Self::ToggleVinyl,
+        Self::PlayPause,
+        Self::OpenSource,
+        Self::None,
+    ];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "toggle_vinyl" => Some(Self::ToggleVinyl),
+            "play_pause" => Some(Self::PlayPause),
+            "open_source" => Some(Self::OpenSource),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ToggleVinyl => "toggle_vinyl",
+            Self::PlayPause => "play_pause",
+            Self::OpenSource => "open_source",
+            Self::None => "none",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ToggleVinyl => "Switch vinyl / artwork",
+            Self::PlayPause => "Play / pause",
+            Self::OpenSource => "Open the player",
+            Self::None => "Nothing",
+        }
+    }
+}
+
+/// How a transport command the media session turned down is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectedBehavior {
+    Silent,
+    Toast,
+    /// Shown with the playback errors until the next poll.
+    ErrorLabel,
+}
+
+impl RejectedBehavior {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "silent" => Some(Self::Silent),
+            "toast" => Some(Self::Toast),
+            "error_label" => Some(Self::ErrorLabel),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Silent => "silent",
+            Self::Toast => "toast",
+            Self::ErrorLabel => "error_label",
+        }
+    }
+}
+
+/// `[ui.rejected_behavior]`: per-command handling of rejected commands. Players turn
+/// down Previous/Next at the ends of a playlist, so those stay quiet by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedBehaviorConfig {
+    pub previous: RejectedBehavior,
+    pub next: RejectedBehavior,
+    pub play: RejectedBehavior,
+    pub pause: RejectedBehavior,
+    pub seek: RejectedBehavior,
+}
+
+impl Default for RejectedBehaviorConfig {
+    fn default() -> Self {
+        Self {
+            previous: RejectedBehavior::Silent,
+            next: RejectedBehavior::Silent,
+            play: RejectedBehavior::Toast,
+            pause: RejectedBehavior::Toast,
+            seek: RejectedBehavior::Toast,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteConfig {
+    pub enabled: bool,
+    pub port: u16,
+    /// How often `position` events go out while playing.
+    pub position_interval: Duration,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7315,
+            position_interval: POSITION_EMIT_INTERVAL,
+        }
+    }
+}
+
+/// Hand-edited `[window]` options; never written back by `save`.
+#[derive(Debug, Clone, Default)]
+pub struct WindowConfig {
+    /// OS title bar text while a track is known, e.g. `"{title} — {artist}"`.
+    pub title_template: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct ConfigDocument {
     #[serde(default)]
     ui: UiSection,
+    #[serde(default)]
+    remote: RemoteSection,
+    #[serde(default, skip_serializing)]
+    window: WindowSection,
 }
 
 impl From<ConfigDocument> for Config {
@@ -100,22 +612,345 @@ impl From<ConfigDocument> for Config {
                 enabled: value.ui.vinyl_thumbnail.enabled.unwrap_or(false),
                 swirl_strength: value.ui.vinyl_thumbnail.swirl_strength.unwrap_or(2.5),
                 label_ratio: value.ui.vinyl_thumbnail.label_ratio.unwrap_or(0.35),
+                reverse: value.ui.vinyl_thumbnail.reverse.unwrap_or(false),
+            },
+            palette: {
+                let defaults = PaletteConfig::default();
+                PaletteConfig {
+                    clusters: value.ui.palette.clusters.unwrap_or(defaults.clusters),
+                    iterations: value.ui.palette.iterations.unwrap_or(defaults.iterations),
+                    distinct_threshold: value
+                        .ui
+                        .palette
+                        .distinct_threshold
+                        .unwrap_or(defaults.distinct_threshold),
+                }
+            },
+            rejected_behavior: {
+                let defaults = RejectedBehaviorConfig::default();
+                let section = &value.ui.rejected_behavior;
+                let parse = |value: &Option<String>, default| {
+                    value
+                        .as_deref()
+                        .and_then(RejectedBehavior::parse)
+                        .unwrap_or(default)
+                };
+                RejectedBehaviorConfig {
+                    previous: parse(&section.previous, defaults.previous),
+                    next: parse(&section.next, defaults.next),
+                    play: parse(&section.play, defaults.play),
+                    pause: parse(&section.pause, defaults.pause),
+                    seek: parse(&section.seek, defaults.seek),
+                }
             },
+            accent_override: value.ui.accent_color.as_deref().and_then(parse_hex_rgb),
+            hidden_components: value.ui.hidden_components,
+            shown_components: value.ui.shown_components,
+            skin_layouts: value.ui.skin_layouts,
+            scale: value.ui.scale,
+            flash_on_track_change: value.ui.flash_on_track_change.unwrap_or(false),
+            dim_when_paused: value.ui.dim_when_paused.unwrap_or(false),
+            force_disable_vinyl: value.ui.force_disable_vinyl.unwrap_or(false),
+            hide_unavailable_controls: value.ui.hide_unavailable_controls.unwrap_or(false),
+            idle_screensaver: value.ui.idle_screensaver.unwrap_or(false),
+            idle_screensaver_minutes: value.ui.idle_screensaver_minutes.unwrap_or(5).max(1),
+            long_form_minutes: value.ui.long_form_minutes.unwrap_or(20).max(1),
+            announce_position: value.ui.announce_position.unwrap_or(false),
+            announce_position_secs: value.ui.announce_position_secs.unwrap_or(30).max(5),
+            overlay_corner: value
+                .ui
+                .overlay_corner
+                .as_deref()
+                .and_then(OverlayControlsPosition::parse),
+            snap_corner: value.ui.snap_corner.as_deref().and_then(SnapCorner::parse),
+            snap_margin: value.ui.snap_margin.unwrap_or(12).max(0),
+            resnap_on_display_change: value.ui.resnap_on_display_change.unwrap_or(false),
+            all_desktops: value.ui.all_desktops.unwrap_or(false),
+            artwork_click: value
+                .ui
+                .artwork_click
+                .as_deref()
+                .and_then(ArtworkClick::parse)
+                .unwrap_or(ArtworkClick::ToggleVinyl),
+            selectable_text: value.ui.selectable_text.unwrap_or(false),
+            watch_skins: value.ui.watch_skins.unwrap_or(false),
+            accent_from_art: value.ui.accent_from_art.unwrap_or(false),
+            follow_system_theme: value.ui.follow_system_theme.unwrap_or(true),
+            animated_gradient: value.ui.animated_gradient.unwrap_or(false),
+            border_pulse: value.ui.border_pulse.unwrap_or(false),
+            border_pulse_bpm: value
+                .ui
+                .border_pulse_bpm
+                .unwrap_or(DEFAULT_BORDER_PULSE_BPM)
+                .clamp(MIN_BORDER_PULSE_BPM, MAX_BORDER_PULSE_BPM),
+            layout_rotation: value.ui.layout_rotation.and_then(|rotation| {
+                let variants: Vec<String> = rotation
+                    .variants
+                    .into_iter()
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty())
+                    .collect();
+                let secs = rotation
+                    .interval_secs
+                    .unwrap_or(DEFAULT_LAYOUT_ROTATION_SECS)
+                    .max(MIN_LAYOUT_ROTATION_SECS);
+                (!variants.is_empty()).then(|| LayoutRotationConfig {
+                    variants,
+                    interval: Duration::from_secs(secs),
+                })
+            }),
+        };
+
+        let defaults = RemoteConfig::default();
+        let remote = RemoteConfig {
+            enabled: value.remote.enabled.unwrap_or(defaults.enabled),
+            port: value.remote.port.unwrap_or(defaults.port),
+            position_interval: value
+                .remote
+                .position_interval_ms
+                .map(|ms| Duration::from_millis(ms.max(MIN_POSITION_INTERVAL_MS)))
+                .unwrap_or(defaults.position_interval),
         };
 
-        Config { ui }
+        let window = WindowConfig {
+            title_template: value
+                .window
+                .title_template
+                .filter(|template| !template.trim().is_empty()),
+        };
+
+        Config {
+            ui,
+            remote,
+            window,
+            source: None,
+        }
+    }
+}
+
+impl From<&Config> for ConfigDocument {
+    fn from(value: &Config) -> Self {
+        let vinyl = &value.ui.vinyl_thumbnail;
+        ConfigDocument {
+            ui: UiSection {
+                vinyl_thumbnail: VinylThumbnailSection {
+                    enabled: Some(vinyl.enabled),
+                    swirl_strength: Some(vinyl.swirl_strength),
+                    label_ratio: Some(vinyl.label_ratio),
+                    reverse: Some(vinyl.reverse),
+                },
+                palette: PaletteSection {
+                    clusters: Some(value.ui.palette.clusters),
+                    iterations: Some(value.ui.palette.iterations),
+                    distinct_threshold: Some(value.ui.palette.distinct_threshold),
+                },
+                rejected_behavior: {
+                    let behavior = &value.ui.rejected_behavior;
+                    let name = |behavior: RejectedBehavior| Some(behavior.as_str().to_string());
+                    RejectedBehaviorSection {
+                        previous: name(behavior.previous),
+                        next: name(behavior.next),
+                        play: name(behavior.play),
+                        pause: name(behavior.pause),
+                        seek: name(behavior.seek),
+                    }
+                },
+                accent_color: value.ui.accent_override.map(format_hex_rgb),
+                hidden_components: value.ui.hidden_components.clone(),
+                shown_components: value.ui.shown_components.clone(),
+                skin_layouts: value.ui.skin_layouts.clone(),
+                scale: value.ui.scale(),
+                flash_on_track_change: Some(value.ui.flash_on_track_change),
+                dim_when_paused: Some(value.ui.dim_when_paused),
+                force_disable_vinyl: Some(value.ui.force_disable_vinyl),
+                hide_unavailable_controls: Some(value.ui.hide_unavailable_controls),
+                idle_screensaver: Some(value.ui.idle_screensaver),
+                idle_screensaver_minutes: Some(value.ui.idle_screensaver_minutes),
+                long_form_minutes: Some(value.ui.long_form_minutes),
+                announce_position: Some(value.ui.announce_position),
+                announce_position_secs: Some(value.ui.announce_position_secs),
+                overlay_corner: value
+                    .ui
+                    .overlay_corner
+                    .map(|corner| corner.as_str().to_string()),
+                snap_corner: value
+                    .ui
+                    .snap_corner
+                    .map(|corner| corner.as_str().to_string()),
+                snap_margin: Some(value.ui.snap_margin),
+                resnap_on_display_change: Some(value.ui.resnap_on_display_change),
+                all_desktops: Some(value.ui.all_desktops),
+                artwork_click: Some(value.ui.artwork_click.as_str().to_string()),
+                selectable_text: Some(value.ui.selectable_text),
+                watch_skins: Some(value.ui.watch_skins),
+                accent_from_art: Some(value.ui.accent_from_art),
+                follow_system_theme: Some(value.ui.follow_system_theme),
+                animated_gradient: Some(value.ui.animated_gradient),
+                border_pulse: Some(value.ui.border_pulse),
+                border_pulse_bpm: Some(value.ui.border_pulse_bpm),
+                layout_rotation: value.ui.layout_rotation.as_ref().map(|rotation| {
+                    LayoutRotationSection {
+                        variants: rotation.variants.clone(),
+                        interval_secs: Some(rotation.interval.as_secs()),
+                    }
+                }),
+            },
+            remote: RemoteSection {
+                enabled: Some(value.remote.enabled),
+                port: Some(value.remote.port),
+                position_interval_ms: Some(value.remote.position_interval.as_millis() as u64),
+            },
+            window: WindowSection::default(),
+        }
+    }
+}
+
+fn parse_hex_rgb(value: &str) -> Option<[u8; 3]> {
+    let hex = value.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
     }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some([(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8])
 }
 
-#[derive(Debug, Default, Deserialize)]
+fn format_hex_rgb(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct UiSection {
     #[serde(default)]
     vinyl_thumbnail: VinylThumbnailSection,
+    #[serde(default)]
+    palette: PaletteSection,
+    #[serde(default)]
+    rejected_behavior: RejectedBehaviorSection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accent_color: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    hidden_components: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    shown_components: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    skin_layouts: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scale: Option<f32>,
+    flash_on_track_change: Option<bool>,
+    dim_when_paused: Option<bool>,
+    force_disable_vinyl: Option<bool>,
+    hide_unavailable_controls: Option<bool>,
+    idle_screensaver: Option<bool>,
+    idle_screensaver_minutes: Option<u32>,
+    long_form_minutes: Option<u32>,
+    announce_position: Option<bool>,
+    announce_position_secs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overlay_corner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snap_corner: Option<String>,
+    snap_margin: Option<i32>,
+    resnap_on_display_change: Option<bool>,
+    all_desktops: Option<bool>,
+    artwork_click: Option<String>,
+    selectable_text: Option<bool>,
+    watch_skins: Option<bool>,
+    accent_from_art: Option<bool>,
+    follow_system_theme: Option<bool>,
+    animated_gradient: Option<bool>,
+    border_pulse: Option<bool>,
+    border_pulse_bpm: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    layout_rotation: Option<LayoutRotationSection>,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct LayoutRotationSection {
+    #[serde(default)]
+    variants: Vec<String>,
+    interval_secs: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct VinylThumbnailSection {
     enabled: Option<bool>,
     swirl_strength: Option<f32>,
     label_ratio: Option<f32>,
+    reverse: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct PaletteSection {
+    clusters: Option<usize>,
+    iterations: Option<usize>,
+    distinct_threshold: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct RejectedBehaviorSection {
+    previous: Option<String>,
+    next: Option<String>,
+    play: Option<String>,
+    pause: Option<String>,
+    seek: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct WindowSection {
+    title_template: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct RemoteSection {
+    enabled: Option<bool>,
+    port: Option<u16>,
+    position_interval_ms: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_writes_only_changed_keys_and_keeps_comments() {
+        let path =
+            std::env::temp_dir().join(format!("nowplaying-config-{}.toml", std::process::id()));
+        let original = "\
+# Widget settings
+[ui]
+# Keep the accent in sync with the album art
+accent_from_art = false  # off for now
+custom_key = \"kept\"
+
+[ui.vinyl_thumbnail]
+swirl_strength = 2.5
+";
+        fs::write(&path, original).unwrap();
+        let doc: ConfigDocument = toml::from_str(original).unwrap();
+        let mut config = Config::from(doc).with_source(path.clone());
+
+        config.ui.accent_from_art = true;
+        config.ui.accent_override = Some([255, 0, 0]);
+        config.ui.palette.clusters = 5;
+        config.save().unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# Widget settings\n[ui]\n"), "{saved}");
+        let kept_comments = "# Keep the accent in sync with the album art\n\
+                             accent_from_art = true  # off for now\n";
+        assert!(saved.contains(kept_comments), "{saved}");
+        assert!(saved.contains("custom_key = \"kept\""));
+        assert!(saved.contains("swirl_strength = 2.5"));
+        assert!(saved.contains("accent_color = \"#ff0000\""));
+        assert!(saved.contains("[ui.palette]\nclusters = 5\n"), "{saved}");
+        // Settings left at their defaults stay out of the file.
+        assert!(!saved.contains("border_pulse"));
+        assert!(!saved.contains("[remote]"));
+
+        config.ui.accent_override = None;
+        config.save().unwrap();
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("accent_color"));
+        assert!(saved.contains("clusters = 5"));
+        let _ = fs::remove_file(&path);
+    }
 }
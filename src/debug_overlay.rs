@@ -0,0 +1,158 @@
+//! Frame and fetch timings for chasing CPU-usage and flicker reports, shown in the
+//! bottom-left corner while toggled on with `Ctrl+Shift+D`.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+/// Weight of the newest frame in the smoothed frame time.
+const FRAME_SMOOTHING: f32 = 0.1;
+const OVERLAY_MARGIN: f32 = 10.0;
+
+/// What the overlay shows, updated every frame whether or not it is visible so the
+/// last fetch and render times are there when it is opened.
+#[derive(Debug, Default)]
+pub(crate) struct DebugMetrics {
+    pub(crate) visible: bool,
+    last_frame: Option<Instant>,
+    /// Seconds between frames, exponentially smoothed.
+    frame_secs: Option<f32>,
+    pub(crate) repaint_interval: Duration,
+    pub(crate) snapshot_poll_interval: Duration,
+    pub(crate) snapshot_inflight: bool,
+    pub(crate) thumbnail_inflight: bool,
+    /// Request to result for the last session snapshot and artwork fetch.
+    pub(crate) snapshot_fetch: Option<Duration>,
+    pub(crate) thumbnail_fetch: Option<Duration>,
+    thumbnail_requested_at: Option<Instant>,
+    pub(crate) texture_count: usize,
+    /// Last vinyl render, on the artwork worker or the UI thread.
+    pub(crate) vinyl_render: Option<Duration>,
+}
+
+impl DebugMetrics {
+    pub(crate) fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub(crate) fn record_frame(&mut self, now: Instant) {
+        if let Some(last) = self.last_frame {
+            let secs = now.saturating_duration_since(last).as_secs_f32();
+            self.frame_secs = Some(match self.frame_secs {
+                Some(smoothed) => smoothed + (secs - smoothed) * FRAME_SMOOTHING,
+                None => secs,
+            });
+        }
+        self.last_frame = Some(now);
+    }
+
+    /// Frames per second from the smoothed frame time; None before the second frame.
+    pub(crate) fn fps(&self) -> Option<f32> {
+        self.frame_secs
+            .filter(|secs| *secs > 0.0)
+            .map(|secs| 1.0 / secs)
+    }
+
+    pub(crate) fn thumbnail_requested(&mut self, now: Instant) {
+        self.thumbnail_requested_at = Some(now);
+    }
+
+    pub(crate) fn thumbnail_arrived(&mut self, now: Instant) {
+        if let Some(requested_at) = self.thumbnail_requested_at.take() {
+            self.thumbnail_fetch = Some(now.saturating_duration_since(requested_at));
+        }
+    }
+
+    /// The overlay's text, one metric per line.
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let fps = self
+            .fps()
+            .map_or_else(|| "-".to_string(), |fps| format!("{fps:.1}"));
+        vec![
+            format!(
+                "fps {fps} (repaint {})",
+                millis(Some(self.repaint_interval))
+            ),
+            format!(
+                "snapshot poll {}",
+                millis(Some(self.snapshot_poll_interval))
+            ),
+            format!(
+                "inflight snapshot {} thumbnail {}",
+                yes_no(self.snapshot_inflight),
+                yes_no(self.thumbnail_inflight)
+            ),
+            format!(
+                "last fetch snapshot {} thumbnail {}",
+                millis(self.snapshot_fetch),
+                millis(self.thumbnail_fetch)
+            ),
+            format!("textures {}", self.texture_count),
+            format!("vinyl render {}", millis(self.vinyl_render)),
+        ]
+    }
+}
+
+fn millis(duration: Option<Duration>) -> String {
+    duration.map_or_else(
+        || "-".to_string(),
+        |duration| format!("{:.1} ms", duration.as_secs_f64() * 1000.0),
+    )
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+/// Draws the metrics in the bottom-left corner of `screen` when they are toggled on.
+pub(crate) fn show(ctx: &egui::Context, metrics: &DebugMetrics, screen: egui::Rect) {
+    if !metrics.visible {
+        return;
+    }
+    egui::Area::new(egui::Id::new("debug-overlay"))
+        .order(egui::Order::Tooltip)
+        .pivot(egui::Align2::LEFT_BOTTOM)
+        .fixed_pos(screen.left_bottom() + egui::vec2(OVERLAY_MARGIN, -OVERLAY_MARGIN))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                for line in metrics.lines() {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(line).monospace().small())
+                            .extend()
+                            .selectable(false),
+                    );
+                }
+            });
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_smooth_frames_and_time_the_artwork_fetch() {
+        let start = Instant::now();
+        let mut metrics = DebugMetrics::default();
+        assert_eq!(metrics.fps(), None);
+        metrics.record_frame(start);
+        metrics.record_frame(start + Duration::from_millis(100));
+        assert!((metrics.fps().unwrap() - 10.0).abs() < 0.01);
+        // One slow frame only nudges the smoothed rate.
+        metrics.record_frame(start + Duration::from_millis(1100));
+        let fps = metrics.fps().unwrap();
+        assert!(fps > 5.0 && fps < 10.0, "{fps}");
+
+        metrics.thumbnail_arrived(start);
+        assert_eq!(metrics.thumbnail_fetch, None);
+        metrics.thumbnail_requested(start);
+        metrics.thumbnail_arrived(start + Duration::from_millis(40));
+        assert_eq!(metrics.thumbnail_fetch, Some(Duration::from_millis(40)));
+        assert!(metrics.lines()[3].ends_with("thumbnail 40.0 ms"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Scripted stand-in for the media session, enabled with `--demo`.
+//!
+//! Plays a fixed set of tracks with a bundled test-pattern cover so skins can be
+//! developed and screenshotted without any player running. Each track pauses once
+//! partway through and the queue loops, so every play state shows up on its own.
+
+use std::time::Instant;
+
+use crate::{
+    ControlsCaps, NowPlaying, OtherSession, PlayState, PlaybackCommand, SessionSnapshot, Timeline,
+};
+
+/// Cover art served for every demo track.
+pub(crate) const COVER: &[u8] = include_bytes!("../assets/demo/cover.png");
+
+/// Position at which the script pauses each track.
+const SCRIPTED_PAUSE_AT: f64 = 12.0;
+/// How long the scripted pause lasts before playback resumes.
+const SCRIPTED_PAUSE_FOR: f64 = 4.0;
+/// "Previous" restarts the track instead of skipping once past this point.
+const RESTART_THRESHOLD: f64 = 3.0;
+
+struct DemoTrack {
+    title: &'static str,
+    artist: &'static str,
+    album: &'static str,
+    duration_secs: f64,
+}
+
+const TRACKS: &[DemoTrack] = &[
+    DemoTrack {
+        title: "Test Pattern",
+        artist: "Color Bars",
+        album: "Calibration, Vol. 1",
+        duration_secs: 42.0,
+    },
+    DemoTrack {
+        title: "An Unreasonably Long Track Title for Checking How Text Overflows",
+        artist: "The Placeholder Ensemble feat. Lorem Ipsum",
+        album: "Extended Edition (Remastered Deluxe Anniversary Reissue)",
+        duration_secs: 64.0,
+    },
+    DemoTrack {
+        title: "Tone",
+        artist: "1 kHz",
+        album: "Reference",
+        duration_secs: 20.0,
+    },
+];
+
+pub(crate) struct DemoSession {
+    track: usize,
+    position_secs: f64,
+    playing: bool,
+    /// Seconds left in the scripted pause, while it is holding playback.
+    scripted_pause: Option<f64>,
+    scripted_pause_done: bool,
+    last_tick: Option<Instant>,
+}
+
+impl DemoSession {
+    pub(crate) fn new() -> Self {
+        Self {
+            track: 0,
+            position_secs: 0.0,
+            playing: true,
+            scripted_pause: None,
+            scripted_pause_done: false,
+            last_tick: None,
+        }
+    }
+
+    /// Advances the script by the wall-clock time since the previous tick.
+    pub(crate) fn tick(&mut self, now: Instant) {
+        let elapsed = self
+            .last_tick
+            .map(|last| now.saturating_duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        self.last_tick = Some(now);
+        self.advance(elapsed);
+    }
+
+    /// Advances the script by `elapsed` seconds.
+    pub(crate) fn advance(&mut self, elapsed: f64) {
+        let mut remaining = elapsed.max(0.0);
+        while remaining > 0.0 {
+            if let Some(hold) = self.scripted_pause {
+                let step = hold.min(remaining);
+                remaining -= step;
+                if step >= hold {
+                    self.scripted_pause = None;
+                    self.playing = true;
+                } else {
+                    self.scripted_pause = Some(hold - step);
+                }
+                continue;
+            }
+            if !self.playing {
+                break;
+            }
+
+            let duration = self.current().duration_secs;
+            let pause_pending = !self.scripted_pause_done && self.position_secs < SCRIPTED_PAUSE_AT;
+            let stop = if pause_pending {
+                SCRIPTED_PAUSE_AT
+            } else {
+                duration
+            };
+            let gap = (stop - self.position_secs).max(0.0);
+            let step = gap.min(remaining);
+            self.position_secs += step;
+            remaining -= step;
+            if step < gap {
+                break;
+            }
+
+            if pause_pending {
+                self.scripted_pause_done = true;
+                self.playing = false;
+                self.scripted_pause = Some(SCRIPTED_PAUSE_FOR);
+            } else {
+                self.load((self.track + 1) % TRACKS.len());
+            }
+        }
+    }
+
+    pub(crate) fn apply(&mut self, command: PlaybackCommand) {
+        match command {
+            PlaybackCommand::Previous => {
+                if self.position_secs > RESTART_THRESHOLD {
+                    self.position_secs = 0.0;
+                } else {
+                    self.load((self.track + TRACKS.len() - 1) % TRACKS.len());
+                }
+            }
+            PlaybackCommand::Next => self.load((self.track + 1) % TRACKS.len()),
+            PlaybackCommand::Play => {
+                self.scripted_pause = None;
+                self.playing = true;
+            }
+            PlaybackCommand::Pause => {
+                self.scripted_pause = None;
+                self.playing = false;
+            }
+            PlaybackCommand::Seek(target) => {
+                self.position_secs = target.clamp(0.0, self.current().duration_secs);
+            }
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> SessionSnapshot {
+        let track = self.current();
+        let now = NowPlaying {
+            title: track.title.to_string(),
+            artist: track.artist.to_string(),
+            album: track.album.to_string(),
+            state: if self.playing {
+                PlayState::Playing
+            } else {
+                PlayState::Paused
+            },
+            caps: ControlsCaps::default(),
+            app_id: "DemoPlayer.exe".to_string(),
+            ..Default::default()
+        };
+        let timeline = Timeline {
+            start_secs: 0.0,
+            end_secs: track.duration_secs,
+            position_secs: self.position_secs,
+            can_seek: true,
+        };
+        // A second, paused player so the `up_next` component has something to show.
+        let others = vec![OtherSession {
+            app_id: "DemoRadio.exe".to_string(),
+            title: "Late Night Static".to_string(),
+            artist: "Carrier Wave".to_string(),
+            state: PlayState::Paused,
+        }];
+        let next = &TRACKS[(self.track + 1) % TRACKS.len()];
+        SessionSnapshot {
+            now,
+            timeline: Some(timeline),
+            others,
+            next: Some(NowPlaying {
+                title: next.title.to_string(),
+                artist: next.artist.to_string(),
+                album: next.album.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn current(&self) -> &'static DemoTrack {
+        &TRACKS[self.track]
+    }
+
+    /// Starts another track from the top. A scripted pause in progress is dropped so
+    /// skipping never lands on a paused track the user didn't pause.
+    fn load(&mut self, index: usize) {
+        self.track = index;
+        self.position_secs = 0.0;
+        self.scripted_pause_done = false;
+        if self.scripted_pause.take().is_some() {
+            self.playing = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_of(session: &DemoSession) -> (String, PlayState, f64) {
+        let snapshot = session.snapshot();
+        let position = snapshot.timeline.expect("timeline").position_secs;
+        (snapshot.now.title, snapshot.now.state, position)
+    }
+
+    #[test]
+    fn script_pauses_resumes_and_moves_to_the_next_track() {
+        let mut session = DemoSession::new();
+        assert_eq!(
+            state_of(&session),
+            (TRACKS[0].title.into(), PlayState::Playing, 0.0)
+        );
+
+        session.advance(SCRIPTED_PAUSE_AT);
+        assert_eq!(
+            state_of(&session),
+            (TRACKS[0].title.into(), PlayState::Paused, SCRIPTED_PAUSE_AT)
+        );
+
+        session.advance(SCRIPTED_PAUSE_FOR + 1.0);
+        assert_eq!(
+            state_of(&session),
+            (
+                TRACKS[0].title.into(),
+                PlayState::Playing,
+                SCRIPTED_PAUSE_AT + 1.0
+            )
+        );
+
+        let rest = TRACKS[0].duration_secs - (SCRIPTED_PAUSE_AT + 1.0);
+        session.advance(rest + 0.5);
+        assert_eq!(
+            state_of(&session),
+            (TRACKS[1].title.into(), PlayState::Playing, 0.5)
+        );
+    }
+
+    #[test]
+    fn transport_commands_override_the_script() {
+        let mut session = DemoSession::new();
+        session.apply(PlaybackCommand::Pause);
+        session.advance(30.0);
+        assert_eq!(state_of(&session).1, PlayState::Paused);
+        assert_eq!(state_of(&session).2, 0.0);
+
+        session.apply(PlaybackCommand::Seek(500.0));
+        assert_eq!(state_of(&session).2, TRACKS[0].duration_secs);
+
+        session.apply(PlaybackCommand::Previous);
+        assert_eq!(state_of(&session).2, 0.0);
+        session.apply(PlaybackCommand::Previous);
+        assert_eq!(state_of(&session).0, TRACKS[TRACKS.len() - 1].title);
+
+        session.apply(PlaybackCommand::Next);
+        session.apply(PlaybackCommand::Play);
+        assert_eq!(
+            state_of(&session),
+            (TRACKS[0].title.into(), PlayState::Playing, 0.0)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, VecDeque};
+
+const ERROR_HISTORY_LIMIT: usize = 20;
+const REDACTED: &str = "<redacted>";
+const SENSITIVE_KEY_PARTS: [&str; 6] = ["url", "token", "key", "secret", "password", "auth"];
+
+/// Bounded log of distinct errors seen by the app, newest last.
+#[derive(Debug, Default)]
+pub struct ErrorHistory {
+    entries: VecDeque<String>,
+    last_seen: HashMap<&'static str, String>,
+}
+
+impl ErrorHistory {
+    /// Records `current` for `source` when it differs from what was last seen there.
+    /// Called every frame with the live error slots, so repeats are ignored.
+    pub fn observe(&mut self, source: &'static str, current: Option<&str>) {
+        let Some(message) = current else {
+            self.last_seen.remove(source);
+            return;
+        };
+        if self.last_seen.get(source).map(String::as_str) == Some(message) {
+            return;
+        }
+        self.last_seen.insert(source, message.to_string());
+        if self.entries.len() == ERROR_HISTORY_LIMIT {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(format!("{source}: {message}"));
+    }
+
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &str> {
+        let skip = self.entries.len().saturating_sub(count);
+        self.entries.iter().skip(skip).map(String::as_str)
+    }
+}
+
+/// Snapshot of the state that goes into a bug report.
+#[derive(Debug, Default)]
+pub struct DiagnosticsInfo {
+    pub app_version: String,
+    pub os: String,
+    pub skin_id: String,
+    pub layout_id: String,
+    pub skin_warnings: Vec<String>,
+    pub recent_errors: Vec<String>,
+    pub play_state: String,
+    pub has_timeline: bool,
+    pub has_thumbnail: bool,
+    pub animations_enabled: bool,
+    pub vinyl_enabled: bool,
+    pub config: Vec<(String, String)>,
+}
+
+pub fn compose_diagnostics(info: &DiagnosticsInfo) -> String {
+    let mut out = String::new();
+    out.push_str("### Now Playing diagnostics\n\n");
+    out.push_str(&format!("- Version: {}\n", info.app_version));
+    out.push_str(&format!("- OS: {}\n", info.os));
+    out.push_str(&format!("- Skin: `{}`\n", info.skin_id));
+    out.push_str(&format!("- Layout: `{}`\n", info.layout_id));
+    out.push_str(&format!("- Play state: {}\n", info.play_state));
+    out.push_str(&format!("- Timeline: {}\n", yes_no(info.has_timeline)));
+    out.push_str(&format!("- Thumbnail: {}\n", yes_no(info.has_thumbnail)));
+    out.push_str(&format!(
+        "- Animations: {}\n",
+        yes_no(info.animations_enabled)
+    ));
+    out.push_str(&format!("- Vinyl: {}\n", yes_no(info.vinyl_enabled)));
+
+    push_list(&mut out, "Skin warnings", &info.skin_warnings);
+    push_list(&mut out, "Recent errors", &info.recent_errors);
+
+    out.push_str("\n#### Config\n\n");
+    if info.config.is_empty() {
+        out.push_str("- (defaults)\n");
+    }
+    for (key, value) in &info.config {
+        out.push_str(&format!("- {key} = {}\n", redact(key, value)));
+    }
+    out
+}
+
+/// Hides values whose key looks like it holds a URL, token, or credential.
+pub fn redact<'a>(key: &str, value: &'a str) -> &'a str {
+    let key = key.to_ascii_lowercase();
+    let leaf = key.rsplit('.').next().unwrap_or(&key);
+    if SENSITIVE_KEY_PARTS.iter().any(|part| leaf.contains(part)) {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+fn push_list(out: &mut String, title: &str, items: &[String]) {
+    out.push_str(&format!("\n#### {title}\n\n"));
+    if items.is_empty() {
+        out.push_str("- none\n");
+    }
+    for item in items {
+        out.push_str(&format!("- {item}\n"));
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_history_skips_repeats_and_caps_length() {
+        let mut history = ErrorHistory::default();
+        history.observe("playback", Some("boom"));
+        history.observe("playback", Some("boom"));
+        history.observe("playback", None);
+        history.observe("playback", Some("boom"));
+        assert_eq!(history.recent(10).count(), 2);
+
+        for i in 0..30 {
+            history.observe("thumbnail", Some(&i.to_string()));
+        }
+        assert_eq!(history.recent(100).count(), ERROR_HISTORY_LIMIT);
+        assert_eq!(history.recent(1).next(), Some("thumbnail: 29"));
+    }
+
+    #[test]
+    fn diagnostics_redact_sensitive_config_values() {
+        let info = DiagnosticsInfo {
+            skin_id: "graphite".to_string(),
+            config: vec![
+                ("remote.port".to_string(), "7315".to_string()),
+                (
+                    "webhook.url".to_string(),
+                    "https://example.com/hook".to_string(),
+                ),
+                ("lastfm.api_key".to_string(), "abc123".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        let text = compose_diagnostics(&info);
+
+        assert!(text.contains("- Skin: `graphite`"));
+        assert!(text.contains("- remote.port = 7315"));
+        assert!(text.contains("- webhook.url = <redacted>"));
+        assert!(text.contains("- lastfm.api_key = <redacted>"));
+        assert!(!text.contains("abc123"));
+        assert!(!text.contains("example.com"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Engine versions declared by skin files (`meta.engine`) and how documents written for
+//! another version are loaded: same major loads as usual, a newer major loads the keys
+//! this build knows and reports the rest, and an older major is migrated first.
+
+/// A `major.minor` engine version; a missing minor reads as 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EngineVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl EngineVersion {
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = match parts.next() {
+            Some(minor) => minor.parse().ok()?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { major, minor })
+    }
+}
+
+/// Keys renamed between one major version and the next, as dotted paths.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Major version the renames upgrade from.
+    pub from_major: u32,
+    pub renames: &'static [(&'static str, &'static str)],
+}
+
+/// What the loader should do with a document after [`reconcile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineAction {
+    Load,
+    /// Load, then warn about keys this version doesn't understand.
+    LoadReportingUnknown,
+    /// The declared version is unreadable; use the built-in document.
+    Ignore,
+}
+
+/// Compares the `declared` engine version of a skin file against `supported`, migrating
+/// `table` in place when it is older. `what` names the file in warnings ("Skin",
+/// "Layout").
+pub fn reconcile(
+    table: &mut toml::Table,
+    declared: &str,
+    supported: &str,
+    migrations: &[Migration],
+    what: &str,
+    warnings: &mut Vec<String>,
+) -> EngineAction {
+    let supported_version =
+        EngineVersion::parse(supported).expect("supported engine version is valid");
+    let Some(version) = EngineVersion::parse(declared) else {
+        warnings.push(format!(
+            "{what} engine version '{declared}' is not a version number; using defaults"
+        ));
+        return EngineAction::Ignore;
+    };
+
+    if version.major > supported_version.major {
+        warnings.push(format!(
+            "{what} engine version {declared} is newer than {supported}; \
+             loading the settings this version understands"
+        ));
+        return EngineAction::LoadReportingUnknown;
+    }
+    if version.major < supported_version.major {
+        let renamed = migrate(table, version.major, migrations);
+        warnings.push(format!(
+            "{what} engine version {declared} is older than {supported}; \
+             updated {renamed} renamed setting(s)"
+        ));
+        return EngineAction::Load;
+    }
+    if version.minor > supported_version.minor {
+        warnings.push(format!(
+            "{what} engine version {declared} is newer than {supported}; \
+             newer settings may be ignored"
+        ));
+    }
+    EngineAction::Load
+}
+
+/// Applies every migration from `from_major` upward in order and returns how many keys
+/// were moved. A rename never overwrites a key the document already sets.
+pub fn migrate(table: &mut toml::Table, from_major: u32, migrations: &[Migration]) -> usize {
+    let mut renamed = 0;
+    for migration in migrations.iter().filter(|m| m.from_major >= from_major) {
+        for (from, to) in migration.renames {
+            if get_path(table, to).is_some() {
+                continue;
+            }
+            if let Some(value) = take_path(table, from) {
+                insert_path(table, to, value);
+                renamed += 1;
+            }
+        }
+    }
+    renamed
+}
+
+/// Dotted paths of keys in `raw` that don't appear in `known`, the same document
+/// re-serialized from the fields this version parsed. Array entries are compared by
+/// position and reported as `path[index].key`.
+pub fn unknown_keys(raw: &toml::Table, known: &toml::Table) -> Vec<String> {
+    fn walk_table(raw: &toml::Table, known: &toml::Table, prefix: &str, out: &mut Vec<String>) {
+        for (key, value) in raw {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match known.get(key) {
+                Some(known_value) => walk_value(value, known_value, &path, out),
+                None => out.push(path),
+            }
+        }
+    }
+
+    fn walk_value(raw: &toml::Value, known: &toml::Value, path: &str, out: &mut Vec<String>) {
+        match (raw, known) {
+            (toml::Value::Table(raw), toml::Value::Table(known)) => {
+                walk_table(raw, known, path, out)
+            }
+            (toml::Value::Array(raw), toml::Value::Array(known)) => {
+                for (index, (raw, known)) in raw.iter().zip(known).enumerate() {
+                    walk_value(raw, known, &format!("{path}[{index}]"), out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk_table(raw, known, "", &mut out);
+    out
+}
+
+fn get_path<'a>(table: &'a toml::Table, path: &str) -> Option<&'a toml::Value> {
+    let (parents, key) = split_path(path);
+    let mut current = table;
+    for parent in parents {
+        current = current.get(parent)?.as_table()?;
+    }
+    current.get(key)
+}
+
+fn take_path(table: &mut toml::Table, path: &str) -> Option<toml::Value> {
+    let (parents, key) = split_path(path);
+    let mut current = table;
+    for parent in parents {
+        current = current.get_mut(parent)?.as_table_mut()?;
+    }
+    current.remove(key)
+}
+
+fn insert_path(table: &mut toml::Table, path: &str, value: toml::Value) {
+    let (parents, key) = split_path(path);
+    let mut current = table;
+    for parent in parents {
+        let entry = current
+            .entry(parent.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        current = entry.as_table_mut().expect("just made a table");
+    }
+    current.insert(key.to_string(), value);
+}
+
+fn split_path(path: &str) -> (Vec<&str>, &str) {
+    let mut parts: Vec<&str> = path.split('.').collect();
+    let key = parts.pop().unwrap_or_default();
+    (parts, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_parse_with_an_optional_minor() {
+        let v = |major, minor| Some(EngineVersion { major, minor });
+        assert_eq!(EngineVersion::parse("1"), v(1, 0));
+        assert_eq!(EngineVersion::parse(" 1.1 "), v(1, 1));
+        assert_eq!(EngineVersion::parse("2.0"), v(2, 0));
+        assert_eq!(EngineVersion::parse("1.2.3"), None);
+        assert_eq!(EngineVersion::parse("one"), None);
+        assert!(EngineVersion::parse("1.1") > EngineVersion::parse("1"));
+    }
+
+    #[test]
+    fn older_documents_are_migrated_without_clobbering() {
+        const MIGRATIONS: &[Migration] = &[Migration {
+            from_major: 1,
+            renames: &[("meta.old", "meta.new"), ("colors.bg", "colors.background")],
+        }];
+        let mut table: toml::Table = toml::from_str(
+            r##"
+[meta]
+old = "moved"
+
+[colors]
+bg = "#000000"
+background = "#ffffff"
+"##,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let action = reconcile(&mut table, "1", "2", MIGRATIONS, "Skin", &mut warnings);
+        assert_eq!(action, EngineAction::Load);
+        assert_eq!(table["meta"]["new"].as_str(), Some("moved"));
+        assert!(table["meta"].get("old").is_none());
+        assert_eq!(table["colors"]["background"].as_str(), Some("#ffffff"));
+        assert!(
+            warnings[0].contains("updated 1 renamed setting(s)"),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn unknown_keys_walk_tables_and_arrays() {
+        let raw: toml::Table = toml::from_str(
+            r#"
+top = 1
+extra = 2
+[section]
+known = "a"
+sparkle = true
+[[items]]
+id = "x"
+[[items]]
+id = "y"
+glow = 3
+"#,
+        )
+        .unwrap();
+        let known: toml::Table = toml::from_str(
+            r#"
+top = 1
+[section]
+known = "a"
+[[items]]
+id = "x"
+[[items]]
+id = "y"
+"#,
+        )
+        .unwrap();
+        let mut unknown = unknown_keys(&raw, &known);
+        unknown.sort();
+        assert_eq!(unknown, ["extra", "items[1].glow", "section.sparkle"]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Tracks the user hearted with the `favorite` component, kept in a small TOML file
+//! beside the config. Tracks are matched by artist and title so a favorite survives the
+//! player reporting a different album for the same song.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// Most favorites kept; hearting past this drops the oldest.
+pub const MAX_FAVORITES: usize = 5_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct FavoriteTrack {
+    artist: String,
+    title: String,
+}
+
+impl FavoriteTrack {
+    fn new(artist: &str, title: &str) -> Self {
+        Self {
+            artist: artist.trim().to_string(),
+            title: title.trim().to_string(),
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct FavoritesDocument {
+    #[serde(default, rename = "track")]
+    tracks: Vec<FavoriteTrack>,
+}
+
+/// Hearted tracks, oldest first.
+#[derive(Debug)]
+pub struct FavoriteStore {
+    path: Option<PathBuf>,
+    tracks: Vec<FavoriteTrack>,
+    capacity: usize,
+}
+
+/// An empty store that lives in memory only, for when the file can't be read and
+/// overwriting it would lose the user's list.
+impl Default for FavoriteStore {
+    fn default() -> Self {
+        Self {
+            path: None,
+            tracks: Vec::new(),
+            capacity: MAX_FAVORITES,
+        }
+    }
+}
+
+impl FavoriteStore {
+    /// Reads the store at `path`; a missing file is an empty store that `save` creates.
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let tracks = if path.exists() {
+            let data = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read favorites: {}", path.display()))?;
+            toml::from_str::<FavoritesDocument>(&data)
+                .with_context(|| format!("Failed to parse favorites: {}", path.display()))?
+                .tracks
+        } else {
+            Vec::new()
+        };
+        let mut store = Self {
+            path: Some(path),
+            ..Self::default()
+        };
+        for track in tracks {
+            if !store.tracks.contains(&track) {
+                store.push(track);
+            }
+        }
+        Ok(store)
+    }
+
+    /// Store that keeps at most `capacity` tracks.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self.tracks
+            .drain(..self.tracks.len().saturating_sub(self.capacity));
+        self
+    }
+
+    pub fn contains(&self, artist: &str, title: &str) -> bool {
+        self.tracks.contains(&FavoriteTrack::new(artist, title))
+    }
+
+    /// Hearts the track, or un-hearts it if it already was. Returns the new state.
+    pub fn toggle(&mut self, artist: &str, title: &str) -> bool {
+        let track = FavoriteTrack::new(artist, title);
+        if let Some(index) = self.tracks.iter().position(|known| *known == track) {
+            self.tracks.remove(index);
+            false
+        } else {
+            self.push(track);
+            true
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// Writes the store back to the file it was loaded from. A store that wasn't
+    /// loaded from a file has nowhere to go and saves nothing.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let doc = FavoritesDocument {
+            tracks: self.tracks.clone(),
+        };
+        let data = toml::to_string(&doc).context("Failed to serialize favorites")?;
+        fs::write(path, data)
+            .with_context(|| format!("Failed to write favorites: {}", path.display()))
+    }
+
+    fn push(&mut self, track: FavoriteTrack) {
+        if self.tracks.len() >= self.capacity {
+            self.tracks.remove(0);
+        }
+        self.tracks.push(track);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "nowplaying-favorites-{}-{name}.toml",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn favorites_toggle_persist_and_drop_the_oldest_past_the_cap() {
+        let path = scratch_file("roundtrip");
+        let mut store = FavoriteStore::load(path.clone()).expect("missing file is empty");
+        assert!(store.is_empty());
+
+        assert!(store.toggle("Band", "Song"));
+        assert!(store.toggle("Band", "Other Song"));
+        // Surrounding whitespace from the session doesn't make a different track.
+        assert!(store.contains(" Band", "Song "));
+        assert!(!store.toggle("Band", "Song"));
+        assert!(!store.contains("Band", "Song"));
+        store.save().unwrap();
+
+        let mut reloaded = FavoriteStore::load(path.clone()).unwrap().with_capacity(2);
+        assert_eq!(reloaded.len(), 1);
+        assert!(reloaded.contains("Band", "Other Song"));
+
+        reloaded.toggle("Band", "Third");
+        reloaded.toggle("Band", "Fourth");
+        assert_eq!(reloaded.len(), 2);
+        assert!(!reloaded.contains("Band", "Other Song"));
+        assert!(reloaded.contains("Band", "Fourth"));
+
+        fs::write(&path, "track = 3").unwrap();
+        assert!(FavoriteStore::load(path.clone()).is_err());
+        let _ = fs::remove_file(&path);
+    }
+}
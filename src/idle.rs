@@ -0,0 +1,205 @@
+//! Idle "screensaver" presentation: after playback has been paused for a while the
+//! widget slowly drifts a softened copy of the artwork behind dimmed controls.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui::{self, ColorImage, Rect};
+
+/// How long the foreground takes to dim after going idle.
+const IDLE_FADE_IN: Duration = Duration::from_secs(3);
+/// Quiet time required after waking before the widget may go idle again.
+const WAKE_DEBOUNCE: Duration = Duration::from_secs(2);
+/// Foreground opacity once fully idle.
+const IDLE_FOREGROUND_OPACITY: f32 = 0.35;
+/// Side length of the softened artwork; upscaling it with linear filtering blurs it.
+const BACKDROP_SIZE: usize = 12;
+/// Extra zoom over a plain cover fit, leaving room for the drift.
+const DRIFT_ZOOM: f32 = 1.2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum IdlePresentation {
+    Active,
+    Idle {
+        since: Instant,
+    },
+    /// Woken by activity or playback; stays out of idle until things settle again.
+    Waking {
+        since: Instant,
+    },
+}
+
+impl IdlePresentation {
+    /// Advances the state machine. `quiet_for` is how long playback has been paused
+    /// with no user activity (`None` while playing); `activity` reports input this frame.
+    pub(crate) fn step(
+        self,
+        quiet_for: Option<Duration>,
+        activity: bool,
+        idle_after: Duration,
+        now: Instant,
+    ) -> Self {
+        match self {
+            IdlePresentation::Active => {
+                if !activity && quiet_for.is_some_and(|quiet| quiet >= idle_after) {
+                    IdlePresentation::Idle { since: now }
+                } else {
+                    self
+                }
+            }
+            IdlePresentation::Idle { .. } => {
+                if activity || quiet_for.is_none() {
+                    IdlePresentation::Waking { since: now }
+                } else {
+                    self
+                }
+            }
+            IdlePresentation::Waking { since } => {
+                if activity {
+                    IdlePresentation::Waking { since: now }
+                } else if now.saturating_duration_since(since) >= WAKE_DEBOUNCE {
+                    IdlePresentation::Active
+                } else {
+                    self
+                }
+            }
+        }
+    }
+
+    pub(crate) fn is_idle(self) -> bool {
+        matches!(self, IdlePresentation::Idle { .. })
+    }
+
+    /// 0 when awake, easing up to 1 as the idle presentation fades in. Waking is
+    /// instant so the controls are usable the moment the pointer moves.
+    pub(crate) fn amount(self, now: Instant) -> f32 {
+        match self {
+            IdlePresentation::Idle { since } => {
+                let elapsed = now.saturating_duration_since(since).as_secs_f32();
+                (elapsed / IDLE_FADE_IN.as_secs_f32()).clamp(0.0, 1.0)
+            }
+            IdlePresentation::Active | IdlePresentation::Waking { .. } => 0.0,
+        }
+    }
+}
+
+/// Opacity for the foreground components at the given idle amount.
+pub(crate) fn foreground_opacity(amount: f32) -> f32 {
+    1.0 - (1.0 - IDLE_FOREGROUND_OPACITY) * amount.clamp(0.0, 1.0)
+}
+
+/// Box-averages the artwork down to a tiny square; drawn stretched, it reads as blur.
+pub(crate) fn soften(image: &ColorImage) -> ColorImage {
+    let [width, height] = image.size;
+    let mut sums = vec![[0u32; 4]; BACKDROP_SIZE * BACKDROP_SIZE];
+    let mut counts = vec![0u32; BACKDROP_SIZE * BACKDROP_SIZE];
+    for y in 0..height {
+        let cell_y = y * BACKDROP_SIZE / height.max(1);
+        for x in 0..width {
+            let cell = cell_y * BACKDROP_SIZE + x * BACKDROP_SIZE / width.max(1);
+            let [r, g, b, a] = image.pixels[y * width + x].to_array();
+            for (sum, channel) in sums[cell].iter_mut().zip([r, g, b, a]) {
+                *sum += u32::from(channel);
+            }
+            counts[cell] += 1;
+        }
+    }
+    let pixels = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, &count)| {
+            let count = count.max(1);
+            let [r, g, b, a] = sum.map(|channel| (channel / count) as u8);
+            egui::Color32::from_rgba_premultiplied(r, g, b, a)
+        })
+        .collect();
+    ColorImage::new([BACKDROP_SIZE, BACKDROP_SIZE], pixels)
+}
+
+/// Texture coordinates of the square artwork for a Ken Burns drift at `time` seconds:
+/// a slow wander plus a gentle zoom, always covering `target` without leaving the image.
+pub(crate) fn drift_uv(target: Rect, time: f64) -> Rect {
+    let aspect = (target.width() / target.height().max(1.0)).max(f32::EPSILON);
+    let zoom = DRIFT_ZOOM + 0.08 * (time / 29.0).sin() as f32;
+    let (cover_w, cover_h) = if aspect >= 1.0 {
+        (1.0, 1.0 / aspect)
+    } else {
+        (aspect, 1.0)
+    };
+    let size = egui::vec2(cover_w / zoom, cover_h / zoom);
+    let slack = egui::vec2(1.0, 1.0) - size;
+    let wander = egui::vec2(
+        0.5 + 0.5 * (time / 17.0).sin() as f32,
+        0.5 + 0.5 * (time / 23.0).cos() as f32,
+    );
+    Rect::from_min_size((slack * wander).to_pos2(), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE_AFTER: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn idle_state_machine_sleeps_wakes_and_debounces() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let quiet = |secs: u64| Some(Duration::from_secs(secs));
+
+        let state = IdlePresentation::Active;
+        assert_eq!(state.step(None, false, IDLE_AFTER, at(0)), state);
+        assert_eq!(state.step(quiet(59), false, IDLE_AFTER, at(59)), state);
+        let idle = state.step(quiet(60), false, IDLE_AFTER, at(60));
+        assert_eq!(idle, IdlePresentation::Idle { since: at(60) });
+        assert_eq!(idle.amount(at(60)), 0.0);
+        assert_eq!(idle.amount(at(70)), 1.0);
+
+        let waking = idle.step(quiet(61), true, IDLE_AFTER, at(61));
+        assert_eq!(waking, IdlePresentation::Waking { since: at(61) });
+        assert_eq!(waking.amount(at(61)), 0.0);
+        // Playback resuming wakes too.
+        assert!(!idle.step(None, false, IDLE_AFTER, at(61)).is_idle());
+
+        let waking = waking.step(quiet(62), true, IDLE_AFTER, at(62));
+        assert_eq!(waking, IdlePresentation::Waking { since: at(62) });
+        assert_eq!(waking.step(quiet(63), false, IDLE_AFTER, at(63)), waking);
+        assert_eq!(
+            waking.step(quiet(64), false, IDLE_AFTER, at(64)),
+            IdlePresentation::Active
+        );
+    }
+
+    #[test]
+    fn foreground_dims_with_idle_amount() {
+        assert_eq!(foreground_opacity(0.0), 1.0);
+        assert!((foreground_opacity(1.0) - IDLE_FOREGROUND_OPACITY).abs() < 1e-6);
+        assert_eq!(foreground_opacity(5.0), foreground_opacity(1.0));
+    }
+
+    #[test]
+    fn soften_averages_into_a_small_square() {
+        let red = egui::Color32::from_rgb(200, 0, 0);
+        let blue = egui::Color32::from_rgb(0, 0, 100);
+        let size = BACKDROP_SIZE * 2;
+        let pixels = (0..size * size)
+            .map(|index| if index % 2 == 0 { red } else { blue })
+            .collect();
+        let softened = soften(&ColorImage::new([size, size], pixels));
+        assert_eq!(softened.size, [BACKDROP_SIZE, BACKDROP_SIZE]);
+        assert_eq!(softened.pixels[0], egui::Color32::from_rgb(100, 0, 50));
+    }
+
+    #[test]
+    fn drift_uv_stays_inside_the_artwork() {
+        for size in [egui::vec2(400.0, 200.0), egui::vec2(200.0, 400.0)] {
+            let target = Rect::from_min_size(egui::Pos2::ZERO, size);
+            for step in 0..200 {
+                let uv = drift_uv(target, step as f64 * 1.7);
+                assert!(uv.min.x >= 0.0 && uv.min.y >= 0.0);
+                assert!(uv.max.x <= 1.0001 && uv.max.y <= 1.0001);
+                let uv_aspect = uv.width() / uv.height();
+                assert!((uv_aspect - size.x / size.y).abs() < 1e-3);
+            }
+        }
+    }
+}
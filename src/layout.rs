@@ -1,8 +1,13 @@
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use eframe::egui::{pos2, Rect, Vec2};
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fs, path::Path};
 
+use crate::engine::{reconcile, unknown_keys, EngineAction, Migration};
+
 pub const LAYOUT_ENGINE_VERSION: &str = "1";
+/// Keys renamed since older engine majors; empty until version 2 renames something.
+const LAYOUT_MIGRATIONS: &[Migration] = &[];
 
 #[derive(Debug, Clone)]
 pub struct LoadedLayout {
@@ -20,12 +25,25 @@ impl LayoutSet {
     pub fn variants(&self) -> &[LayoutVariant] {
         &self.variants
     }
+
+    /// Picks the variant with the largest `min_width` that still fits `width`, or the
+    /// default variant when none of the declared breakpoints apply.
+    pub fn variant_for_width(&self, width: f32) -> &str {
+        self.variants
+            .iter()
+            .filter_map(|variant| variant.min_width.map(|min| (variant, min)))
+            .filter(|(_, min)| *min <= width)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(variant, _)| variant.id.as_str())
+            .unwrap_or(&self.default_variant)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct LayoutVariant {
     pub id: String,
     pub display_name: String,
+    pub min_width: Option<f32>,
     pub root: LayoutNode,
 }
 
@@ -42,6 +60,8 @@ pub struct ContainerNode {
     pub spacing: f32,
     pub align: LayoutAlign,
     pub fill: bool,
+    /// Gives every child the cross-axis extent of the largest one.
+    pub stretch: bool,
     pub children: Vec<LayoutNode>,
 }
 
@@ -57,6 +77,33 @@ pub struct ComponentNode {
     pub component: LayoutComponent,
     pub visible: bool,
     pub params: HashMap<String, String>,
+    /// Fixed width in points from the `width` param; `None` keeps adaptive sizing.
+    pub width: Option<f32>,
+    /// Fixed height in points from the `height` param; `None` keeps adaptive sizing.
+    pub height: Option<f32>,
+    /// From the `require` param: the component is skipped while this datum is missing.
+    pub require: Option<Requirement>,
+}
+
+/// Track data a component can depend on through its `require` param.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    Title,
+    Artist,
+    Album,
+    Artwork,
+}
+
+impl Requirement {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "title" => Some(Self::Title),
+            "artist" => Some(Self::Artist),
+            "album" => Some(Self::Album),
+            "artwork" | "thumbnail" => Some(Self::Artwork),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -68,22 +115,236 @@ pub struct SpacerNode {
 pub enum LayoutComponent {
     Thumbnail,
     Title,
+    CustomText,
     MetadataGroup,
     MetadataArtist,
     MetadataAlbum,
     MetadataState,
+    StateBadge,
+    UpNext,
+    NextUp,
+    PreviousTrack,
+    SourceApp,
+    SessionElapsed,
     PlaybackControlsGroup,
     PlaybackButtonPrevious,
     PlaybackButtonPlayPause,
     PlaybackButtonNext,
     PlaybackButtonStop,
+    FavoriteButton,
     Timeline,
+    ProgressEdge,
     SkinWarnings,
     SkinError,
     NowPlayingError,
     ThumbnailError,
 }
 
+impl LayoutComponent {
+    /// Canonical `layout.toml` id; `from_id` accepts this plus the legacy aliases.
+    pub fn id(self) -> &'static str {
+        match self {
+            LayoutComponent::Thumbnail => "thumbnail",
+            LayoutComponent::Title => "title",
+            LayoutComponent::CustomText => "text",
+            LayoutComponent::MetadataGroup => "metadata",
+            LayoutComponent::MetadataArtist => "metadata.artist",
+            LayoutComponent::MetadataAlbum => "metadata.album",
+            LayoutComponent::MetadataState => "metadata.state",
+            LayoutComponent::StateBadge => "state_badge",
+            LayoutComponent::UpNext => "up_next",
+            LayoutComponent::NextUp => "next_up",
+            LayoutComponent::PreviousTrack => "previous_track",
+            LayoutComponent::SourceApp => "source_app",
+            LayoutComponent::SessionElapsed => "session_time",
+            LayoutComponent::PlaybackControlsGroup => "playback_controls",
+            LayoutComponent::PlaybackButtonPrevious => "button.previous",
+            LayoutComponent::PlaybackButtonPlayPause => "button.play",
+            LayoutComponent::PlaybackButtonNext => "button.next",
+            LayoutComponent::PlaybackButtonStop => "button.stop",
+            LayoutComponent::FavoriteButton => "favorite",
+            LayoutComponent::Timeline => "timeline",
+            LayoutComponent::ProgressEdge => "progress_edge",
+            LayoutComponent::SkinWarnings => "skin_warnings",
+            LayoutComponent::SkinError => "skin_error",
+            LayoutComponent::NowPlayingError => "error",
+            LayoutComponent::ThumbnailError => "thumbnail_error",
+        }
+    }
+
+    pub fn from_id(value: &str) -> Option<Self> {
+        parse_component(value)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            LayoutComponent::Thumbnail => "Album art",
+            LayoutComponent::Title => "Title",
+            LayoutComponent::CustomText => "Custom text",
+            LayoutComponent::MetadataGroup => "Track details",
+            LayoutComponent::MetadataArtist => "Artist",
+            LayoutComponent::MetadataAlbum => "Album",
+            LayoutComponent::MetadataState => "Playback state",
+            LayoutComponent::StateBadge => "Playback state badge",
+            LayoutComponent::UpNext => "Paused elsewhere",
+            LayoutComponent::NextUp => "Next track",
+            LayoutComponent::PreviousTrack => "Previous track",
+            LayoutComponent::SourceApp => "Player name",
+            LayoutComponent::SessionElapsed => "Listening time",
+            LayoutComponent::PlaybackControlsGroup => "Playback controls",
+            LayoutComponent::PlaybackButtonPrevious => "Previous button",
+            LayoutComponent::PlaybackButtonPlayPause => "Play/Pause button",
+            LayoutComponent::PlaybackButtonNext => "Next button",
+            LayoutComponent::PlaybackButtonStop => "Stop button",
+            LayoutComponent::FavoriteButton => "Favorite button",
+            LayoutComponent::Timeline => "Timeline",
+            LayoutComponent::ProgressEdge => "Edge progress line",
+            LayoutComponent::SkinWarnings => "Skin warnings",
+            LayoutComponent::SkinError => "Skin errors",
+            LayoutComponent::NowPlayingError => "Playback errors",
+            LayoutComponent::ThumbnailError => "Artwork errors",
+        }
+    }
+}
+
+/// Lists the components in a layout tree in document order, without duplicates.
+pub fn collect_components(node: &LayoutNode) -> Vec<LayoutComponent> {
+    fn walk(node: &LayoutNode, out: &mut Vec<LayoutComponent>) {
+        match node {
+            LayoutNode::Row(container) | LayoutNode::Column(container) => {
+                for child in &container.children {
+                    walk(child, out);
+                }
+            }
+            LayoutNode::Component(component) => {
+                if !out.contains(&component.component) {
+                    out.push(component.component);
+                }
+            }
+            LayoutNode::Spacer(_) => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(node, &mut out);
+    out
+}
+
+/// Lists the components in a layout tree in document order with the visibility the skin
+/// gives them; a component counts as visible if any of its nodes is.
+pub fn collect_component_visibility(node: &LayoutNode) -> Vec<(LayoutComponent, bool)> {
+    fn walk(node: &LayoutNode, out: &mut Vec<(LayoutComponent, bool)>) {
+        match node {
+            LayoutNode::Row(container) | LayoutNode::Column(container) => {
+                for child in &container.children {
+                    walk(child, out);
+                }
+            }
+            LayoutNode::Component(component) => {
+                match out
+                    .iter_mut()
+                    .find(|(seen, _)| *seen == component.component)
+                {
+                    Some((_, visible)) => *visible |= component.visible,
+                    None => out.push((component.component, component.visible)),
+                }
+            }
+            LayoutNode::Spacer(_) => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(node, &mut out);
+    out
+}
+
+/// Sets `visible` on every node whose component has an entry in `overrides`, so users
+/// can hide components and bring back ones the skin hides.
+pub fn apply_component_overrides(
+    node: &mut LayoutNode,
+    overrides: &HashMap<LayoutComponent, bool>,
+) {
+    if overrides.is_empty() {
+        return;
+    }
+    match node {
+        LayoutNode::Row(container) | LayoutNode::Column(container) => {
+            for child in &mut container.children {
+                apply_component_overrides(child, overrides);
+            }
+        }
+        LayoutNode::Component(component) => {
+            if let Some(&visible) = overrides.get(&component.component) {
+                component.visible = visible;
+            }
+        }
+        LayoutNode::Spacer(_) => {}
+    }
+}
+
+/// Rects for a container's children relative to its top-left corner, given each child's
+/// natural size: laid along the main axis `spacing` apart and start-aligned across it.
+/// With `stretch`, every rect spans the cross-axis extent of the largest child.
+pub fn place_children(sizes: &[Vec2], spacing: f32, is_row: bool, stretch: bool) -> Vec<Rect> {
+    let cross = |size: Vec2| if is_row { size.y } else { size.x };
+    let extent = sizes.iter().map(|&size| cross(size)).fold(0.0, f32::max);
+    let mut offset = 0.0;
+    sizes
+        .iter()
+        .map(|&size| {
+            let cross_size = if stretch { extent } else { cross(size) };
+            let (min, size) = if is_row {
+                (pos2(offset, 0.0), Vec2::new(size.x, cross_size))
+            } else {
+                (pos2(0.0, offset), Vec2::new(cross_size, size.y))
+            };
+            offset += if is_row { size.x } else { size.y } + spacing;
+            Rect::from_min_size(min, size)
+        })
+        .collect()
+}
+
+/// Parses `layout.toml`, reconciling its declared engine version with this build's.
+/// `None` means the document is ignored in favor of the built-in layout.
+fn parse_layout_document(
+    data: &str,
+    warnings: &mut Vec<String>,
+) -> std::result::Result<Option<LayoutDocument>, toml::de::Error> {
+    let mut table: toml::Table = toml::from_str(data)?;
+    let declared = table
+        .get("meta")
+        .and_then(|meta| meta.get("engine"))
+        .and_then(|engine| engine.as_str())
+        .map(str::to_string);
+    let action = match declared {
+        Some(engine) => reconcile(
+            &mut table,
+            &engine,
+            LAYOUT_ENGINE_VERSION,
+            LAYOUT_MIGRATIONS,
+            "Layout",
+            warnings,
+        ),
+        None => {
+            warnings.push("layout.meta.engine missing; assuming version 1".to_string());
+            EngineAction::Load
+        }
+    };
+    if action == EngineAction::Ignore {
+        return Ok(None);
+    }
+
+    let doc: LayoutDocument = toml::Value::Table(table.clone()).try_into()?;
+    if action == EngineAction::LoadReportingUnknown {
+        if let Ok(known) = toml::Table::try_from(&doc) {
+            for key in unknown_keys(&table, &known) {
+                warnings.push(format!("Unknown layout setting '{key}' ignored"));
+            }
+        }
+    }
+    Ok(Some(doc))
+}
+
 pub fn load_layout_from_dir(skin_dir: &Path) -> Result<LoadedLayout> {
     let mut warnings = Vec::new();
     let layout_path = skin_dir.join("layout.toml");
@@ -92,22 +353,9 @@ pub fn load_layout_from_dir(skin_dir: &Path) -> Result<LoadedLayout> {
         let data = fs::read_to_string(&layout_path).with_context(|| {
             format!("Failed to read layout file from {}", layout_path.display())
         })?;
-        match toml::from_str::<LayoutDocument>(&data) {
-            Ok(doc) => {
-                if let Some(engine) = doc.meta.engine.as_deref() {
-                    if engine != LAYOUT_ENGINE_VERSION {
-                        warnings.push(format!(
-                            "Layout engine version {engine} does not match {LAYOUT_ENGINE_VERSION}; using defaults"
-                        ));
-                        builtin_layout_document()
-                    } else {
-                        doc
-                    }
-                } else {
-                    warnings.push("layout.meta.engine missing; assuming version 1".to_string());
-                    doc
-                }
-            }
+        match parse_layout_document(&data, &mut warnings) {
+            Ok(Some(doc)) => doc,
+            Ok(None) => builtin_layout_document(),
             Err(err) => {
                 warnings.push(format!("Failed to parse layout: {err}"));
                 builtin_layout_document()
@@ -148,6 +396,16 @@ fn resolve_document(doc: LayoutDocument, warnings: &mut Vec<String>) -> Result<L
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
             .unwrap_or_else(|| id.clone());
+        let min_width = match variant_cfg.min_width {
+            Some(width) if width.is_finite() && width >= 0.0 => Some(width),
+            Some(width) => {
+                warnings.push(format!(
+                    "Layout variant '{id}' has invalid min_width {width}; ignoring"
+                ));
+                None
+            }
+            None => None,
+        };
 
         if variants
             .iter()
@@ -161,6 +419,7 @@ fn resolve_document(doc: LayoutDocument, warnings: &mut Vec<String>) -> Result<L
             Some(root) => variants.push(LayoutVariant {
                 id,
                 display_name,
+                min_width,
                 root,
             }),
             None => warnings.push(format!(
@@ -175,18 +434,25 @@ fn resolve_document(doc: LayoutDocument, warnings: &mut Vec<String>) -> Result<L
         return Ok(default_set);
     }
 
-    let default_variant = doc
+    let requested_default = doc
         .layout
         .default
-        .as_ref()
-        .and_then(|candidate| {
-            let trimmed = candidate.trim();
-            variants
-                .iter()
-                .position(|variant| variant.id == trimmed)
-                .map(|idx| variants[idx].id.clone())
-        })
-        .unwrap_or_else(|| variants[0].id.clone());
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty());
+    let default_variant = match requested_default {
+        Some(id) if variants.iter().any(|variant| variant.id == id) => id.to_string(),
+        Some(id) => {
+            let available: Vec<&str> = variants.iter().map(|variant| variant.id.as_str()).collect();
+            warnings.push(format!(
+                "Layout default '{id}' matches no variant (available: {}); using '{}'",
+                available.join(", "),
+                variants[0].id
+            ));
+            variants[0].id.clone()
+        }
+        None => variants[0].id.clone(),
+    };
 
     Ok(LayoutSet {
         default_variant,
@@ -237,6 +503,7 @@ fn resolve_container(
 
     let spacing = cfg.spacing.unwrap_or(8.0).max(0.0);
     let fill = cfg.fill.unwrap_or(false);
+    let stretch = cfg.stretch.unwrap_or(false);
 
     let mut children = Vec::new();
     for (child_idx, child_cfg) in cfg.children.into_iter().enumerate() {
@@ -255,6 +522,7 @@ fn resolve_container(
         spacing,
         align,
         fill,
+        stretch,
         children,
     })
 }
@@ -264,21 +532,36 @@ fn resolve_component(
     warnings: &mut Vec<String>,
     context: &str,
 ) -> Option<ComponentNode> {
-    if cfg.visible == Some(false) {
-        return None;
-    }
-
     let Some(id) = cfg.id.as_deref() else {
         warnings.push(format!("{context} component missing id"));
         return None;
     };
 
     match parse_component(id) {
-        Some(component) => Some(ComponentNode {
-            component,
-            visible: true,
-            params: cfg.params.unwrap_or_default(),
-        }),
+        Some(component) => {
+            let params = cfg.params.unwrap_or_default();
+            let width = parse_size_param(&params, "width", id, context, warnings);
+            let height = parse_size_param(&params, "height", id, context, warnings);
+            let require = params.get("require").and_then(|value| {
+                let requirement = Requirement::parse(value);
+                if requirement.is_none() {
+                    warnings.push(format!(
+                        "Component '{id}' in {context} has unknown require '{value}'; \
+                         expected title, artist, album, or artwork"
+                    ));
+                }
+                requirement
+            });
+            Some(ComponentNode {
+                component,
+                // Kept in the tree when hidden so the settings panel can show it again.
+                visible: cfg.visible.unwrap_or(true),
+                params,
+                width,
+                height,
+                require,
+            })
+        }
         None => {
             warnings.push(format!("Unknown component '{id}' in {context}; skipping"));
             None
@@ -286,6 +569,25 @@ fn resolve_component(
     }
 }
 
+fn parse_size_param(
+    params: &HashMap<String, String>,
+    key: &str,
+    id: &str,
+    context: &str,
+    warnings: &mut Vec<String>,
+) -> Option<f32> {
+    let value = params.get(key)?;
+    match value.trim().trim_end_matches("px").trim().parse::<f32>() {
+        Ok(size) if size.is_finite() && size > 0.0 => Some(size),
+        _ => {
+            warnings.push(format!(
+                "Component '{id}' in {context} has invalid {key} '{value}'; using adaptive size"
+            ));
+            None
+        }
+    }
+}
+
 fn parse_align(value: &str) -> Option<LayoutAlign> {
     match value.trim().to_ascii_lowercase().as_str() {
         "start" | "top" | "left" => Some(LayoutAlign::Start),
@@ -299,10 +601,17 @@ fn parse_component(value: &str) -> Option<LayoutComponent> {
     match value.trim().to_ascii_lowercase().as_str() {
         "thumbnail" | "artwork" => Some(LayoutComponent::Thumbnail),
         "title" => Some(LayoutComponent::Title),
+        "text" | "custom_text" => Some(LayoutComponent::CustomText),
         "metadata" | "metadata_group" | "details" => Some(LayoutComponent::MetadataGroup),
         "metadata.artist" | "artist" => Some(LayoutComponent::MetadataArtist),
         "metadata.album" | "album" => Some(LayoutComponent::MetadataAlbum),
         "metadata.state" | "state" | "playstate" => Some(LayoutComponent::MetadataState),
+        "state_badge" | "badge" => Some(LayoutComponent::StateBadge),
+        "up_next" | "upnext" => Some(LayoutComponent::UpNext),
+        "next_up" | "queue.next" => Some(LayoutComponent::NextUp),
+        "previous_track" | "queue.previous" => Some(LayoutComponent::PreviousTrack),
+        "source_app" | "app" => Some(LayoutComponent::SourceApp),
+        "session_time" | "session_elapsed" => Some(LayoutComponent::SessionElapsed),
         "playback_controls" | "controls" => Some(LayoutComponent::PlaybackControlsGroup),
         "button.previous" | "previous" => Some(LayoutComponent::PlaybackButtonPrevious),
         "button.play" | "playpause" | "button.playpause" | "button.pause" => {
@@ -310,7 +619,9 @@ fn parse_component(value: &str) -> Option<LayoutComponent> {
         }
         "button.next" | "next" => Some(LayoutComponent::PlaybackButtonNext),
         "button.stop" | "stop" => Some(LayoutComponent::PlaybackButtonStop),
+        "favorite" | "button.favorite" | "heart" => Some(LayoutComponent::FavoriteButton),
         "timeline" | "progress" => Some(LayoutComponent::Timeline),
+        "progress_edge" => Some(LayoutComponent::ProgressEdge),
         "skin_warnings" | "warnings" => Some(LayoutComponent::SkinWarnings),
         "skin_error" => Some(LayoutComponent::SkinError),
         "error" | "now_playing_error" => Some(LayoutComponent::NowPlayingError),
@@ -319,35 +630,36 @@ fn parse_component(value: &str) -> Option<LayoutComponent> {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct LayoutDocument {
     meta: LayoutMeta,
     layout: LayoutVariants,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct LayoutMeta {
     engine: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 struct LayoutVariants {
     default: Option<String>,
     variants: Vec<LayoutVariantConfig>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct LayoutVariantConfig {
     id: Option<String>,
     display_name: Option<String>,
+    min_width: Option<f32>,
     structure: Option<LayoutNodeConfig>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum LayoutNodeConfig {
     Row(ContainerConfig),
@@ -356,17 +668,18 @@ enum LayoutNodeConfig {
     Spacer(SpacerConfig),
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ContainerConfig {
     align: Option<String>,
     spacing: Option<f32>,
     fill: Option<bool>,
+    stretch: Option<bool>,
     visible: Option<bool>,
     children: Vec<LayoutNodeConfig>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ComponentConfig {
     id: Option<String>,
@@ -374,7 +687,7 @@ struct ComponentConfig {
     params: Option<HashMap<String, String>>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct SpacerConfig {
     size: Option<f32>,
@@ -397,20 +710,12 @@ impl Default for LayoutMeta {
     }
 }
 
-impl Default for LayoutVariants {
-    fn default() -> Self {
-        LayoutVariants {
-            default: Some("art_left".to_string()),
-            variants: Vec::new(),
-        }
-    }
-}
-
 impl Default for LayoutVariantConfig {
     fn default() -> Self {
         LayoutVariantConfig {
             id: None,
             display_name: None,
+            min_width: None,
             structure: None,
         }
     }
@@ -422,6 +727,7 @@ impl Default for ContainerConfig {
             align: None,
             spacing: None,
             fill: None,
+            stretch: None,
             visible: None,
             children: Vec::new(),
         }
@@ -448,6 +754,47 @@ fn builtin_layout_document() -> LayoutDocument {
     toml::from_str(DEFAULT_LAYOUT_TOML).expect("Embedded default layout must parse")
 }
 
+/// The built-in layouts, for embedded skins that keep the default arrangement.
+pub fn builtin_layout() -> LayoutSet {
+    let mut warnings = Vec::new();
+    let layout = resolve_document(builtin_layout_document(), &mut warnings)
+        .expect("Embedded default layout must resolve");
+    debug_assert!(warnings.is_empty(), "default layout warnings: {warnings:?}");
+    layout
+}
+
+/// Layout of the embedded `stealth` skin: the artwork alone, with the transport
+/// controls over it on hover at any size.
+pub fn stealth_layout() -> LayoutSet {
+    let doc = toml::from_str(STEALTH_LAYOUT_TOML).expect("Embedded stealth layout must parse");
+    let mut warnings = Vec::new();
+    let layout =
+        resolve_document(doc, &mut warnings).expect("Embedded stealth layout must resolve");
+    debug_assert!(warnings.is_empty(), "stealth layout warnings: {warnings:?}");
+    layout
+}
+
+const STEALTH_LAYOUT_TOML: &str = r##"
+[meta]
+engine = "1"
+
+[layout]
+default = "artwork"
+
+[[layout.variants]]
+id = "artwork"
+display_name = "Artwork Only"
+
+[layout.variants.structure]
+type = "row"
+align = "center"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "thumbnail"
+params = { always_show_overlay = "true" }
+"##;
+
 const DEFAULT_LAYOUT_TOML: &str = r##"
 [meta]
 engine = "1"
@@ -458,6 +805,7 @@ default = "art_left"
 [[layout.variants]]
 id = "art_left"
 display_name = "Artwork Left"
+min_width = 360
 
 [layout.variants.structure]
 type = "row"
@@ -605,4 +953,349 @@ id = "thumbnail_error"
 [[layout.variants.structure.children]]
 type = "component"
 id = "error"
+
+[[layout.variants]]
+id = "ticker"
+display_name = "Ticker"
+min_width = 0
+
+[layout.variants.structure]
+type = "row"
+spacing = 10
+fill = true
+align = "center"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "thumbnail"
+params = { width = "56", height = "56", require = "artwork" }
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "button.play"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "text"
+params = { template = "{title} — {artist}", marquee = "true", style = "title" }
 "##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn default_root() -> LayoutNode {
+        let mut warnings = Vec::new();
+        let layout = resolve_document(builtin_layout_document(), &mut warnings).unwrap();
+        layout.variants[0].root.clone()
+    }
+
+    fn visibility(node: &LayoutNode, target: LayoutComponent) -> Vec<bool> {
+        match node {
+            LayoutNode::Row(container) | LayoutNode::Column(container) => container
+                .children
+                .iter()
+                .flat_map(|child| visibility(child, target))
+                .collect(),
+            LayoutNode::Component(component) if component.component == target => {
+                vec![component.visible]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn collect_components_walks_nested_containers_in_order() {
+        let components = collect_components(&default_root());
+        assert_eq!(components.first(), Some(&LayoutComponent::Thumbnail));
+        assert!(components.contains(&LayoutComponent::Title));
+        assert!(components.contains(&LayoutComponent::Timeline));
+        let unique: HashSet<_> = components.iter().copied().collect();
+        assert_eq!(unique.len(), components.len());
+    }
+
+    #[test]
+    fn require_param_is_parsed_and_validated() {
+        let doc: LayoutDocument = toml::from_str(
+            r#"
+[[layout.variants]]
+id = "main"
+
+[layout.variants.structure]
+type = "column"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "source_app"
+params = { require = " Album " }
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "title"
+params = { require = "lyrics" }
+"#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let layout = resolve_document(doc, &mut warnings).unwrap();
+        let LayoutNode::Column(column) = &layout.variants[0].root else {
+            panic!("expected a column");
+        };
+        let requirements: Vec<_> = column
+            .children
+            .iter()
+            .map(|child| match child {
+                LayoutNode::Component(component) => component.require,
+                _ => panic!("expected components"),
+            })
+            .collect();
+        assert_eq!(requirements, [Some(Requirement::Album), None]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("unknown require 'lyrics'"));
+    }
+
+    #[test]
+    fn component_overrides_hide_and_reveal() {
+        let doc: LayoutDocument = toml::from_str(
+            r#"
+[[layout.variants]]
+id = "main"
+
+[layout.variants.structure]
+type = "column"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "title"
+
+[[layout.variants.structure.children]]
+type = "component"
+id = "album"
+visible = false
+"#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let mut root = resolve_document(doc, &mut warnings).unwrap().variants[0]
+            .root
+            .clone();
+        assert_eq!(
+            collect_component_visibility(&root),
+            [
+                (LayoutComponent::Title, true),
+                (LayoutComponent::MetadataAlbum, false)
+            ]
+        );
+
+        let overrides = HashMap::from([
+            (LayoutComponent::Title, false),
+            (LayoutComponent::MetadataAlbum, true),
+        ]);
+        apply_component_overrides(&mut root, &overrides);
+        assert_eq!(visibility(&root, LayoutComponent::Title), vec![false]);
+        assert_eq!(
+            visibility(&root, LayoutComponent::MetadataAlbum),
+            vec![true]
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn unknown_default_variant_warns_and_falls_back_to_the_first() {
+        let doc: LayoutDocument = toml::from_str(
+            r#"
+[layout]
+default = " stackd "
+
+[[layout.variants]]
+id = "stacked"
+structure = { type = "component", id = "title" }
+
+[[layout.variants]]
+id = "wide"
+structure = { type = "component", id = "title" }
+"#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let layout = resolve_document(doc, &mut warnings).unwrap();
+        assert_eq!(layout.default_variant, "stacked");
+        assert_eq!(
+            warnings,
+            vec![
+                "Layout default 'stackd' matches no variant (available: stacked, wide); \
+                 using 'stacked'"
+                    .to_string()
+            ]
+        );
+
+        let doc: LayoutDocument = toml::from_str(
+            r#"
+[layout]
+default = " wide "
+
+[[layout.variants]]
+id = "stacked"
+structure = { type = "component", id = "title" }
+
+[[layout.variants]]
+id = "wide"
+structure = { type = "component", id = "title" }
+"#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let layout = resolve_document(doc, &mut warnings).unwrap();
+        assert_eq!(layout.default_variant, "wide");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn layout_without_a_default_picks_the_first_variant_quietly() {
+        let data = r#"
+[meta]
+engine = "1"
+
+[[layout.variants]]
+id = "stacked"
+structure = { type = "component", id = "title" }
+
+[[layout.variants]]
+id = "wide"
+structure = { type = "component", id = "title" }
+"#;
+        let mut warnings = Vec::new();
+        let doc = parse_layout_document(data, &mut warnings).unwrap().unwrap();
+        let layout = resolve_document(doc, &mut warnings).unwrap();
+        assert_eq!(layout.default_variant, "stacked");
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn variant_for_width_picks_widest_fitting_breakpoint() {
+        let doc: LayoutDocument = toml::from_str(
+            r#"
+[layout]
+default = "stacked"
+
+[[layout.variants]]
+id = "stacked"
+min_width = 0
+structure = { type = "component", id = "title" }
+
+[[layout.variants]]
+id = "wide"
+min_width = 480
+structure = { type = "component", id = "title" }
+
+[[layout.variants]]
+id = "manual_only"
+structure = { type = "component", id = "title" }
+"#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let layout = resolve_document(doc, &mut warnings).unwrap();
+
+        assert_eq!(layout.variant_for_width(320.0), "stacked");
+        assert_eq!(layout.variant_for_width(480.0), "wide");
+        assert_eq!(layout.variant_for_width(1200.0), "wide");
+    }
+
+    #[test]
+    fn builtin_layouts_resolve_cleanly_with_ticker_below_the_breakpoint() {
+        let mut warnings = Vec::new();
+        let layout = resolve_document(builtin_layout_document(), &mut warnings).unwrap();
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let ticker = layout.variants.iter().find(|v| v.id == "ticker").unwrap();
+        assert_eq!(
+            collect_components(&ticker.root),
+            vec![
+                LayoutComponent::Thumbnail,
+                LayoutComponent::PlaybackButtonPlayPause,
+                LayoutComponent::CustomText,
+            ]
+        );
+        assert_eq!(layout.default_variant, "art_left");
+        assert_eq!(layout.variant_for_width(300.0), "ticker");
+        assert_eq!(layout.variant_for_width(480.0), "art_left");
+    }
+
+    #[test]
+    fn stealth_layout_is_the_artwork_alone() {
+        let layout = stealth_layout();
+        assert_eq!(layout.variants.len(), 1);
+        let root = &layout.variants[0].root;
+        assert_eq!(collect_components(root), vec![LayoutComponent::Thumbnail]);
+    }
+
+    #[test]
+    fn component_ids_round_trip() {
+        for component in collect_components(&default_root()) {
+            assert_eq!(LayoutComponent::from_id(component.id()), Some(component));
+        }
+    }
+
+    #[test]
+    fn component_size_params_parse_and_warn_on_invalid_values() {
+        let params = HashMap::from([
+            ("width".to_string(), "180".to_string()),
+            ("height".to_string(), "-5".to_string()),
+        ]);
+        let cfg = ComponentConfig {
+            id: Some("thumbnail".to_string()),
+            visible: None,
+            params: Some(params),
+        };
+        let mut warnings = Vec::new();
+
+        let node = resolve_component(cfg, &mut warnings, "test").unwrap();
+
+        assert_eq!(node.width, Some(180.0));
+        assert_eq!(node.height, None);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("invalid height"));
+    }
+
+    #[test]
+    fn stretch_gives_every_child_the_tallest_extent() {
+        let sizes = [Vec2::new(120.0, 120.0), Vec2::new(200.0, 60.0)];
+
+        let plain = place_children(&sizes, 16.0, true, false);
+        assert_eq!(plain[0], Rect::from_min_size(pos2(0.0, 0.0), sizes[0]));
+        assert_eq!(plain[1], Rect::from_min_size(pos2(136.0, 0.0), sizes[1]));
+
+        let stretched = place_children(&sizes, 16.0, true, true);
+        assert_eq!(stretched[0], plain[0]);
+        assert_eq!(
+            stretched[1],
+            Rect::from_min_size(pos2(136.0, 0.0), Vec2::new(200.0, 120.0))
+        );
+
+        // Columns stretch across their width instead.
+        let column = place_children(&sizes, 8.0, false, true);
+        assert_eq!(column[1].min, pos2(0.0, 128.0));
+        assert_eq!(column[1].width(), 200.0);
+        assert_eq!(column[0].width(), 200.0);
+    }
+
+    #[test]
+    fn stretch_is_read_from_container_config() {
+        let doc: LayoutNodeConfig = toml::from_str(
+            r#"
+            type = "row"
+            stretch = true
+            children = [{ type = "component", id = "title" }]
+            "#,
+        )
+        .unwrap();
+        let mut warnings = Vec::new();
+        let Some(LayoutNode::Row(row)) = resolve_node(doc, &mut warnings, "test") else {
+            panic!("expected a row");
+        };
+        assert!(row.stretch);
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+//! Platform-neutral parts of the widget: configuration, skin loading, the non-UI
+//! widget state, favorites, artwork processing and small layout helpers. The binary in
+//! `main.rs` adds the window and the media session backend.
+
+pub mod config;
+pub mod engine;
+pub mod favorites;
+pub mod layout;
+pub mod palette;
+pub mod state;
+pub mod strip;
+pub mod theme;
+pub mod vinyl;
@@ -1,32 +1,77 @@
-mod config;
-mod layout;
-mod theme;
+mod borderless;
+mod debug_overlay;
+mod demo;
+mod diagnostics;
+mod idle;
+mod remote;
+mod settings;
+mod skin_import;
+mod snap;
+mod source_app;
+#[cfg(target_os = "windows")]
+mod taskbar;
+mod toast;
 mod ui_skin;
-mod vinyl;
+
+use now_playing_gui::{config, layout, theme};
 
 use crate::{
-    config::Config,
-    layout::{ComponentNode, ContainerNode, LayoutAlign, LayoutComponent, LayoutNode},
-    theme::{AreaBackground, GradientDirection, GradientSpec},
-    vinyl::{render_vinyl, VinylSpin, VinylThumbnailOptions},
+    borderless::{DecorationSequence, WindowStep},
+    config::{
+        ArtworkClick, Config, PaletteConfig, RejectedBehavior, RejectedBehaviorConfig, SnapCorner,
+        UiConfig, VinylThumbnailConfig,
+    },
+    debug_overlay::DebugMetrics,
+    demo::DemoSession,
+    diagnostics::{compose_diagnostics, DiagnosticsInfo, ErrorHistory},
+    idle::IdlePresentation,
+    layout::{
+        apply_component_overrides, place_children, ComponentNode, ContainerNode, LayoutAlign,
+        LayoutComponent, LayoutNode, Requirement,
+    },
+    remote::{
+        send_command, PlaybackEvent, RemoteCommand, RemoteEntry, RemoteResponse, RemoteServer,
+    },
+    theme::{
+        AreaBackground, ButtonShape, GradientDirection, GradientSpec, OverlayControlsPosition,
+        ThumbnailOverlay, WindowBackdrop,
+    },
+    toast::{ErrorKind, ToastLevel, ToastQueue},
 };
 use eframe::egui::{
     self, Align2, ColorImage, CornerRadius, FontId, LayerId, PointerButton, ResizeDirection,
     TextureHandle, TextureOptions, UiBuilder, ViewportCommand, WindowLevel, ViewportBuilder,
 };
 use futures::executor::block_on;
+use now_playing_gui::favorites::FavoriteStore;
+use now_playing_gui::palette::{
+    cycle_gradient, dominant_gradient_colors, dynamic_accent_from_image, palette_colors,
+};
+use now_playing_gui::state::{
+    fill_track_template, humanize_duration, spoken_position, AppCore, ControlsCaps, LayoutRotation,
+    NowPlaying, OtherSession, PendingThumbnail, PlayState, SessionSnapshot, ThumbnailOverlayAction,
+    Timeline,
+};
+use now_playing_gui::strip::StripMetrics;
+use now_playing_gui::vinyl::{render_vinyl, vinyl_rerender_size, VinylSpin, VinylThumbnailOptions};
 #[cfg(target_os = "windows")]
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use settings::{SettingsModel, SettingsPanel, SettingsRequest};
+use source_app::friendly_app_name;
 use std::future::IntoFuture;
 use std::{
-    cmp::Reverse,
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
+    path::{Path, PathBuf},
     sync::mpsc::{self, TryRecvError},
     thread,
     time::{Duration, Instant},
 };
-use ui_skin::{default_skin_root, paint_area_background, SkinManager};
+use ui_skin::{
+    default_skin_root, edge_strip_fraction, edge_strip_hit_band, edge_strip_rect,
+    paint_area_background, paint_area_shadow, rotated_image_mesh, thumbnail_overlay_rect,
+    SkinManager, StripEdge, MAX_BUTTON_SCALE, MIN_BUTTON_SCALE,
+};
 use windows::{
     core::Result as WinResult,
     Foundation::TimeSpan,
@@ -47,16 +92,28 @@ use windows::{
 };
 
 #[cfg(target_os = "windows")]
-use windows::UI::ViewManagement::UISettings;
+use taskbar::ThumbBar;
+#[cfg(target_os = "windows")]
+use windows::UI::ViewManagement::{UIColorType, UISettings};
 
+#[cfg(target_os = "windows")]
+use windows::core::GUID;
 #[cfg(target_os = "windows")]
 use windows::Win32::{
     Foundation::HWND,
     Graphics::Dwm::{
-        DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR, DWMWA_TEXT_COLOR,
+        DwmSetWindowAttribute, DWMSBT_AUTO, DWMSBT_MAINWINDOW, DWMSBT_TRANSIENTWINDOW,
+        DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR,
         DWMWA_USE_IMMERSIVE_DARK_MODE, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT,
         DWMWCP_ROUND,
     },
+    System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER},
+    UI::{
+        Shell::{IVirtualDesktopManager, VirtualDesktopManager},
+        WindowsAndMessaging::{
+            FlashWindowEx, GetForegroundWindow, FLASHWINFO, FLASHW_TIMERNOFG, FLASHW_TRAY,
+        },
+    },
 };
 
 const TICKS_PER_SECOND: f64 = 10_000_000.0;
@@ -69,7 +126,86 @@ const TIMELINE_PADDING_MAX: f32 = 32.0;
 const TIMELINE_MIN_CONTENT_WIDTH: f32 = 160.0;
 const TIMELINE_MAX_CONTENT_WIDTH: f32 = 720.0;
 const TIMELINE_LABEL_GAP: f32 = 16.0;
+/// Gap between the slider and the readouts beside it in an inline timeline.
+const TIMELINE_INLINE_GAP: f32 = 8.0;
+/// Default and largest `thickness` of a `progress_edge` line, in points.
+const PROGRESS_EDGE_THICKNESS: f32 = 3.0;
+const PROGRESS_EDGE_MAX_THICKNESS: f32 = 12.0;
 const DWM_COLOR_UNSET: u32 = 0xFFFFFFFF;
+const BORDERLESS_RESIZE_EDGE: f32 = 6.0;
+const BORDERLESS_DRAG_HEIGHT: f32 = 36.0;
+const SETTINGS_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Comma);
+const SCREENSHOT_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+    egui::Key::S,
+);
+const REFRESH_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::NONE, egui::Key::F5);
+const DEBUG_OVERLAY_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+    egui::Key::D,
+);
+const OVERLAY_CONTROLS_MARGIN: f32 = 8.0;
+const OVERLAY_CONTROLS_SPACING: f32 = 6.0;
+/// Quiet time after pointer or key input before position announcements resume.
+const ANNOUNCE_QUIET_AFTER_INPUT: Duration = Duration::from_secs(3);
+const IDLE_REPAINT_INTERVAL: Duration = Duration::from_millis(100);
+/// Repaint cap for an animated gradient when nothing else needs frames (~15 fps).
+const GRADIENT_REPAINT_INTERVAL: Duration = Duration::from_millis(66);
+/// Time an animated gradient takes to ease from one palette pair to the next.
+const GRADIENT_CYCLE_STEP: Duration = Duration::from_secs(10);
+/// Palette colors an animated gradient cycles through.
+const GRADIENT_PALETTE_SIZE: usize = 3;
+const LOADING_CONTROLS_OPACITY: f32 = 0.5;
+const PRESS_RIPPLE_SECS: f64 = 0.35;
+const PRESS_RIPPLE_SPREAD: f32 = 8.0;
+const TITLE_TRANSITION_SECS: f32 = 0.25;
+/// How long a new display scale must hold before the artwork is re-rendered for it.
+const THUMBNAIL_RESCALE_DEBOUNCE: Duration = Duration::from_millis(400);
+/// Slide distance of the title transition, as a fraction of the title text size.
+const TITLE_SLIDE_DISTANCE: f32 = 0.8;
+/// Upper bound on interval ticks so a tiny interval on a long track stays readable.
+const MAX_TIMELINE_TICKS: usize = 100;
+/// Image types the thumbnail decoder handles; local artwork is limited to these.
+const ARTWORK_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "bmp", "webp"];
+/// Cover files looked for next to a playing local file, in order of preference.
+const ARTWORK_SIDECARS: &[&str] = &[
+    "cover.jpg",
+    "cover.png",
+    "folder.jpg",
+    "folder.png",
+    "front.jpg",
+];
+/// Larger local images are skipped rather than read into memory.
+const MAX_LOCAL_ARTWORK_BYTES: u64 = 20 * 1024 * 1024;
+/// Default tick spacing on long-form timelines without chapters.
+const LONG_FORM_TICK_INTERVAL_SECS: f64 = 10.0 * 60.0;
+const DYNAMIC_BORDER_WIDTH: f32 = 1.5;
+/// Reflection height as a fraction of the artwork height.
+const REFLECTION_HEIGHT_RATIO: f32 = 0.35;
+const REFLECTION_OPACITY: f32 = 0.35;
+const REFLECTION_ROWS: usize = 12;
+/// How long a paused background session's title is trusted before it is read again.
+const OTHER_SESSION_REFRESH: Duration = Duration::from_secs(30);
+/// How long a requested screenshot may take to arrive before the request is dropped.
+const SCREENSHOT_TIMEOUT: Duration = Duration::from_secs(5);
+const WINDOW_TITLE: &str = "Now Playing";
+const WINDOW_TITLE_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+/// How often the widget checks it is on the active virtual desktop while `all_desktops`
+/// is on, between focus changes.
+#[cfg(target_os = "windows")]
+const VIRTUAL_DESKTOP_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+/// How often the system light/dark mode is checked while `follow_system_theme` is on.
+const SYSTEM_THEME_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const STATE_BADGE_FILL_ALPHA: f32 = 0.2;
+const STATE_BADGE_PULSE_PERIOD: f64 = 1.6;
+/// Lowest opacity `border_pulse` fades to between beats, and how sharply it gets there.
+const BORDER_PULSE_FLOOR: f32 = 0.35;
+const BORDER_PULSE_DECAY: f32 = 5.0;
+const TRACK_FLASH_DEBOUNCE: Duration = Duration::from_millis(1500);
+#[cfg(target_os = "windows")]
+const THUMB_BAR_MAX_ATTEMPTS: u32 = 120;
 
 #[cfg(target_os = "windows")]
 #[derive(Default)]
@@ -78,6 +214,97 @@ struct WindowsTitlebarState {
     last_text: Option<u32>,
     last_border: Option<u32>,
     last_dark_mode: Option<bool>,
+    last_backdrop: Option<WindowBackdrop>,
+}
+
+/// Multiplier for artwork pixels: white leaves them untouched, gray dims them while
+/// playback is paused or stopped (when enabled).
+fn artwork_tint(dim_when_paused: bool, state: PlayState) -> egui::Color32 {
+    const PAUSED_ARTWORK_GRAY: u8 = 110;
+
+    if dim_when_paused && matches!(state, PlayState::Paused | PlayState::Stopped) {
+        egui::Color32::from_gray(PAUSED_ARTWORK_GRAY)
+    } else {
+        egui::Color32::WHITE
+    }
+}
+
+fn overlay_controls_anchor(position: OverlayControlsPosition) -> (Align2, egui::Vec2) {
+    let margin = OVERLAY_CONTROLS_MARGIN;
+    match position {
+        OverlayControlsPosition::TopLeft => (Align2::LEFT_TOP, egui::vec2(margin, margin)),
+        OverlayControlsPosition::TopRight => (Align2::RIGHT_TOP, egui::vec2(-margin, margin)),
+        OverlayControlsPosition::BottomLeft => (Align2::LEFT_BOTTOM, egui::vec2(margin, -margin)),
+        OverlayControlsPosition::BottomRight => {
+            (Align2::RIGHT_BOTTOM, egui::vec2(-margin, -margin))
+        }
+    }
+}
+
+fn overlay_corner_label(position: OverlayControlsPosition) -> &'static str {
+    match position {
+        OverlayControlsPosition::TopLeft => "Top left",
+        OverlayControlsPosition::TopRight => "Top right",
+        OverlayControlsPosition::BottomLeft => "Bottom left",
+        OverlayControlsPosition::BottomRight => "Bottom right",
+    }
+}
+
+/// Screen rect covered by the overlay buttons, matching where `overlay_controls_anchor`
+/// places them. `None` when no buttons are shown.
+fn overlay_controls_rect(
+    root_rect: egui::Rect,
+    position: OverlayControlsPosition,
+    icon_count: usize,
+    icon_extent: f32,
+) -> Option<egui::Rect> {
+    if icon_count == 0 {
+        return None;
+    }
+    let width =
+        icon_count as f32 * icon_extent + (icon_count - 1) as f32 * OVERLAY_CONTROLS_SPACING;
+    let size = egui::vec2(width, icon_extent);
+    let (anchor, offset) = overlay_controls_anchor(position);
+    let corner = anchor.pos_in_rect(&root_rect) + offset;
+    Some(anchor.anchor_size(corner, size))
+}
+
+/// Keeps the window on whichever virtual desktop is active. The public shell API has
+/// no "pin", so the window is moved after the user switches desktops.
+#[cfg(target_os = "windows")]
+#[derive(Default)]
+struct VirtualDesktopState {
+    manager: Option<IVirtualDesktopManager>,
+    /// The manager couldn't be created; stop trying for this session.
+    unavailable: bool,
+    last_desktop: Option<GUID>,
+    /// When the desktop was last looked at, and the focus seen then.
+    checked_at: Option<Instant>,
+    focused: Option<bool>,
+}
+
+#[cfg(target_os = "windows")]
+fn frame_hwnd(frame: &eframe::Frame) -> Option<HWND> {
+    let window_handle = frame.window_handle().ok()?;
+    match window_handle.as_raw() {
+        RawWindowHandle::Win32(handle) => Some(HWND(handle.hwnd.get() as *mut std::ffi::c_void)),
+        _ => None,
+    }
+}
+
+/// Flashes the taskbar button a few times until the window is brought to the foreground.
+#[cfg(target_os = "windows")]
+fn flash_taskbar(hwnd: HWND) {
+    let info = FLASHWINFO {
+        cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+        hwnd,
+        dwFlags: FLASHW_TRAY | FLASHW_TIMERNOFG,
+        uCount: 3,
+        dwTimeout: 0,
+    };
+    unsafe {
+        let _ = FlashWindowEx(&info);
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -87,10 +314,102 @@ fn color32_to_colorref(color: egui::Color32) -> u32 {
 }
 
 #[cfg(target_os = "windows")]
-fn is_dark_color(color: egui::Color32) -> bool {
+fn color_luminance(color: egui::Color32) -> f32 {
     let [r, g, b, _] = color.to_array();
-    let luminance = 0.2126 * (r as f32) + 0.7152 * (g as f32) + 0.0722 * (b as f32);
-    luminance < 128.0
+    0.2126 * (r as f32) + 0.7152 * (g as f32) + 0.0722 * (b as f32)
+}
+
+#[cfg(target_os = "windows")]
+fn is_dark_color(color: egui::Color32) -> bool {
+    color_luminance(color) < 128.0
+}
+
+/// Artwork-derived caption color for the native titlebar: the darker stop of the
+/// gradient painted right under it (the root, unless the skin leaves the root
+/// transparent, then the panel). `None` keeps the theme's window fill.
+#[cfg(target_os = "windows")]
+fn dynamic_caption_color(
+    use_gradient: bool,
+    root_painted: bool,
+    root: Option<&GradientSpec>,
+    panel: Option<&GradientSpec>,
+) -> Option<egui::Color32> {
+    if !use_gradient {
+        return None;
+    }
+    let root = root.filter(|_| root_painted);
+    root.or(panel).map(|spec| {
+        if color_luminance(spec.start) <= color_luminance(spec.end) {
+            spec.start
+        } else {
+            spec.end
+        }
+    })
+}
+
+/// Texture coordinate seen at `pos` in a reflection mirrored about the top edge of
+/// `reflection`, for artwork drawn in `artwork` rotated by `angle` (the vinyl spin).
+fn reflection_uv(
+    artwork: egui::Rect,
+    reflection: egui::Rect,
+    angle: f32,
+    pos: egui::Pos2,
+) -> egui::Pos2 {
+    let mirrored = egui::pos2(pos.x, artwork.bottom() - (pos.y - reflection.top()));
+    let d = mirrored - artwork.center();
+    let (sin, cos) = angle.sin_cos();
+    let unrotated = egui::vec2(d.x * cos + d.y * sin, -d.x * sin + d.y * cos);
+    egui::pos2(
+        unrotated.x / artwork.width() + 0.5,
+        unrotated.y / artwork.height() + 0.5,
+    )
+}
+
+/// Horizontal strips covering `reflection`, textured with the mirrored artwork and
+/// fading from `REFLECTION_OPACITY` to transparent.
+fn reflection_mesh(
+    texture: egui::TextureId,
+    artwork: egui::Rect,
+    reflection: egui::Rect,
+    angle: f32,
+    tint: egui::Color32,
+) -> egui::Mesh {
+    let mut mesh = egui::Mesh::with_texture(texture);
+    for row in 0..=REFLECTION_ROWS {
+        let t = row as f32 / REFLECTION_ROWS as f32;
+        let y = egui::lerp(reflection.top()..=reflection.bottom(), t);
+        let color = tint.gamma_multiply(REFLECTION_OPACITY * (1.0 - t));
+        for x in [reflection.left(), reflection.right()] {
+            let pos = egui::pos2(x, y);
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos,
+                uv: reflection_uv(artwork, reflection, angle, pos),
+                color,
+            });
+        }
+        if row > 0 {
+            let base = (row as u32 - 1) * 2;
+            mesh.indices
+                .extend_from_slice(&[base, base + 1, base + 3, base, base + 3, base + 2]);
+        }
+    }
+    mesh
+}
+
+/// Window outline for `root.dynamic_border`: the artwork accent while the dynamic
+/// gradient is active, else the root's own border color. `None` draws nothing.
+fn dynamic_border_color(
+    dynamic_border: bool,
+    use_gradient: bool,
+    artwork_accent: Option<egui::Color32>,
+    border_color: egui::Color32,
+) -> Option<egui::Color32> {
+    if !dynamic_border {
+        return None;
+    }
+    artwork_accent
+        .filter(|_| use_gradient)
+        .or((border_color.a() > 0).then_some(border_color))
 }
 
 #[cfg(target_os = "windows")]
@@ -108,121 +427,33 @@ fn animations_enabled_from_system() -> bool {
     true
 }
 
-#[derive(Debug, Copy, Clone)]
-struct StripMetrics {
-    total_width: f32,
-    content_width: f32,
-    margin: f32,
+/// The system light/dark mode, read through one `UISettings` kept for the app's life.
+#[derive(Default)]
+struct SystemTheme {
+    #[cfg(target_os = "windows")]
+    settings: Option<UISettings>,
 }
 
-impl StripMetrics {
-    fn from_content(total_width: f32, content_width: f32) -> Self {
-        let total = total_width.max(1.0);
-        let content = content_width.clamp(1.0, total);
-        let margin = ((total - content) / 2.0).max(0.0);
-        Self {
-            total_width: total,
-            content_width: content,
-            margin,
-        }
-    }
-
-    #[allow(dead_code)]
-    fn center_with_max(total_width: f32, max_content_width: f32) -> Self {
-        let target = max_content_width.max(1.0).min(total_width.max(1.0));
-        Self::from_content(total_width, target)
-    }
-
-    fn padded(total_width: f32, padding: f32) -> Self {
-        let total = total_width.max(1.0);
-        let margin = padding.clamp(0.0, total / 2.0);
-        let content = (total - 2.0 * margin).max(1.0);
+impl SystemTheme {
+    fn new() -> Self {
         Self {
-            total_width: total,
-            content_width: content,
-            margin,
+            #[cfg(target_os = "windows")]
+            settings: UISettings::new().ok(),
         }
     }
 
-    fn content_width(&self) -> f32 {
-        self.content_width
-    }
-
-    fn show<R>(&self, ui: &mut egui::Ui, builder: impl FnOnce(&mut egui::Ui) -> R) -> R {
-        ui.allocate_ui_with_layout(
-            egui::vec2(self.total_width, 0.0),
-            egui::Layout::left_to_right(egui::Align::Center),
-            |row| {
-                if self.margin > 0.0 {
-                    row.add_space(self.margin);
-                }
-                let result = row
-                    .allocate_ui_with_layout(
-                        egui::vec2(self.content_width, 0.0),
-                        egui::Layout::top_down(egui::Align::Center),
-                        builder,
-                    )
-                    .inner;
-                if self.margin > 0.0 {
-                    row.add_space(self.margin);
-                }
-                result
-            },
-        )
-        .inner
-    }
-
-    #[allow(dead_code)]
-    fn show_with_layout<R>(
-        &self,
-        ui: &mut egui::Ui,
-        layout: egui::Layout,
-        builder: impl FnOnce(&mut egui::Ui) -> R,
-    ) -> R {
-        self.show(ui, |inner| inner.with_layout(layout, builder).inner)
+    /// Whether apps are set to light mode; Windows paints app text black then.
+    #[cfg(target_os = "windows")]
+    fn prefers_light(&self) -> bool {
+        self.settings
+            .as_ref()
+            .and_then(|settings| settings.GetColorValue(UIColorType::Foreground).ok())
+            .is_some_and(|color| is_dark_color(egui::Color32::from_rgb(color.R, color.G, color.B)))
     }
 
-    /// Show the strip and anchor its content left/center/right within the total width.
-    ///
-    /// `align` controls which side the content is anchored to. This replaces manual
-    /// left/right spacer calculations and makes anchoring explicit and robust.
-    fn show_anchored<R>(
-        &self,
-        ui: &mut egui::Ui,
-        align: egui::Align,
-        builder: impl FnOnce(&mut egui::Ui) -> R,
-    ) -> R {
-        ui.allocate_ui_with_layout(
-            egui::vec2(self.total_width, 0.0),
-            egui::Layout::left_to_right(egui::Align::Center),
-            |row| {
-                let extra = (self.total_width - self.content_width).max(0.0);
-                let (left_space, right_space) = match align {
-                    egui::Align::Min => (0.0, extra),
-                    egui::Align::Center => (extra / 2.0, extra / 2.0),
-                    egui::Align::Max => (extra, 0.0),
-                };
-
-                if left_space > 0.0 {
-                    row.add_space(left_space);
-                }
-
-                let result = row
-                    .allocate_ui_with_layout(
-                        egui::vec2(self.content_width, 0.0),
-                        egui::Layout::top_down(egui::Align::Center),
-                        builder,
-                    )
-                    .inner;
-
-                if right_space > 0.0 {
-                    row.add_space(right_space);
-                }
-
-                result
-            },
-        )
-        .inner
+    #[cfg(not(target_os = "windows"))]
+    fn prefers_light(&self) -> bool {
+        false
     }
 }
 
@@ -244,51 +475,41 @@ fn timeline_strip_metrics(total_width: f32, centered: bool) -> StripMetrics {
     }
 }
 
-type SnapshotResult = std::result::Result<(NowPlaying, Option<Timeline>), String>;
-
-#[derive(Clone, Default)]
-struct NowPlaying {
-    title: String,
-    artist: String,
-    album: String,
-    state: PlayState,
-}
-
-impl PartialEq for NowPlaying {
-    fn eq(&self, other: &Self) -> bool {
-        self.title == other.title && self.artist == other.artist && self.album == other.album
+/// Where a timeline strip sits: a width-limited (`centered`) strip stays in the middle
+/// unless its container aligns children to the end. Padded strips are symmetric anyway.
+fn timeline_strip_align(centered: bool, container_align: egui::Align) -> egui::Align {
+    if centered && container_align == egui::Align::Max {
+        egui::Align::Max
+    } else {
+        egui::Align::Center
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum PlayState {
-    Closed,
-    Opened,
-    Changing,
-    Stopped,
-    Playing,
-    Paused,
-    Unknown,
-}
+type SnapshotResult = std::result::Result<SessionSnapshot, String>;
 
-impl Default for PlayState {
-    fn default() -> Self {
-        PlayState::Unknown
+/// Names the track whose artwork failed, so a stale failure isn't pinned on the next one.
+fn thumbnail_error_message(track: &NowPlaying, err: &str) -> String {
+    let title = track.title.trim();
+    if title.is_empty() {
+        format!("Couldn't load the artwork: {err}")
+    } else {
+        format!("Couldn't load the artwork for \"{title}\": {err}")
     }
 }
 
-#[derive(Clone, Debug)]
-struct Timeline {
-    start_secs: f64,
-    end_secs: f64,
-    position_secs: f64,
-    can_seek: bool,
-}
-
-impl Timeline {
-    fn duration_secs(&self) -> f64 {
-        (self.end_secs - self.start_secs).max(0.0)
+/// Reduces a burst of queued snapshot results to the newest one, which is all the widget
+/// shows. Errors that arrived before it are returned so they still reach the error log.
+fn coalesce_snapshots(
+    results: impl IntoIterator<Item = SnapshotResult>,
+) -> (Option<SnapshotResult>, Vec<String>) {
+    let mut latest = None;
+    let mut skipped_errors = Vec::new();
+    for result in results {
+        if let Some(Err(err)) = latest.replace(result) {
+            skipped_errors.push(err);
+        }
     }
+    (latest, skipped_errors)
 }
 
 struct ThumbnailMessage {
@@ -297,39 +518,122 @@ struct ThumbnailMessage {
     hash: Option<u64>,
     base_image: Option<ColorImage>,
     vinyl_image: Option<ColorImage>,
+    /// How long the worker spent rendering `vinyl_image`.
+    vinyl_render: Option<Duration>,
     error: Option<String>,
 }
 
-#[derive(Clone)]
-enum PendingThumbnail {
-    Update {
-        track: NowPlaying,
-        hash: u64,
-        base_image: ColorImage,
-        vinyl_image: Option<ColorImage>,
-    },
-    Clear {
-        track: Option<NowPlaying>,
-    },
-}
-
 enum SnapshotCommand {
     Fetch,
     Shutdown,
 }
 
-#[derive(Clone, Copy)]
-enum PlaybackButtonKind {
+/// Transport request for the media session.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PlaybackCommand {
     Previous,
-    PlayPause,
     Next,
+    Play,
+    Pause,
+    Seek(f64),
+}
+
+impl PlaybackCommand {
+    fn label(self) -> &'static str {
+        match self {
+            PlaybackCommand::Previous => "Previous",
+            PlaybackCommand::Next => "Next",
+            PlaybackCommand::Play => "Play",
+            PlaybackCommand::Pause => "Pause",
+            PlaybackCommand::Seek(_) => "Seek",
+        }
+    }
+
+    /// How `[ui.rejected_behavior]` reports this command being turned down.
+    fn rejected_behavior(self, policy: &RejectedBehaviorConfig) -> RejectedBehavior {
+        match self {
+            PlaybackCommand::Previous => policy.previous,
+            PlaybackCommand::Next => policy.next,
+            PlaybackCommand::Play => policy.play,
+            PlaybackCommand::Pause => policy.pause,
+            PlaybackCommand::Seek(_) => policy.seek,
+        }
+    }
+
+    /// Sends the command; Play and Pause go out as the toggle when `caps` says that is
+    /// all the session takes.
+    fn send(
+        self,
+        session: &GlobalSystemMediaTransportControlsSession,
+        caps: &ControlsCaps,
+    ) -> WinResult<bool> {
+        let play_pause = match self {
+            PlaybackCommand::Play => Some(ThumbnailOverlayAction::Play),
+            PlaybackCommand::Pause => Some(ThumbnailOverlayAction::Pause),
+            _ => None,
+        };
+        if play_pause.is_some_and(|action| caps.needs_toggle(action)) {
+            return block_on_operation(session.TryTogglePlayPauseAsync()?);
+        }
+        match self {
+            PlaybackCommand::Previous => block_on_operation(session.TrySkipPreviousAsync()?),
+            PlaybackCommand::Next => block_on_operation(session.TrySkipNextAsync()?),
+            PlaybackCommand::Play => block_on_operation(session.TryPlayAsync()?),
+            PlaybackCommand::Pause => block_on_operation(session.TryPauseAsync()?),
+            PlaybackCommand::Seek(target_secs) => block_on_operation(
+                session.TryChangePlaybackPositionAsync(secs_to_ticks(target_secs))?,
+            ),
+        }
+    }
+}
+
+/// What the UI does after sending a transport command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CommandFeedback {
+    /// Accepted; poll right away so the change shows.
+    Refresh,
+    /// Turned down by the player; polled again and reported as configured.
+    Rejected(RejectedBehavior),
+    /// Couldn't be sent at all.
+    Failed,
+}
+
+fn command_feedback<E>(
+    command: PlaybackCommand,
+    result: &Result<bool, E>,
+    policy: &RejectedBehaviorConfig,
+) -> CommandFeedback {
+    match result {
+        Ok(true) => CommandFeedback::Refresh,
+        Ok(false) => CommandFeedback::Rejected(command.rejected_behavior(policy)),
+        Err(_) => CommandFeedback::Failed,
+    }
+}
+
+/// Progress of a "Save screenshot" request. Capture waits one frame so the settings
+/// drawer that triggered it is gone from the picture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ScreenshotStage {
+    Scheduled,
+    Requested { deadline: Instant },
+}
+
+struct PendingSkinImport {
+    archive: PathBuf,
+    folder_name: String,
 }
 
 #[derive(Clone, Copy)]
-enum ThumbnailOverlayAction {
+enum SkinImportChoice {
+    Overwrite,
+    Rename,
+    Cancel,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PlaybackButtonKind {
     Previous,
-    Play,
-    Pause,
+    PlayPause,
     Next,
 }
 
@@ -385,213 +689,565 @@ fn format_timestamp(seconds: f64) -> String {
     }
 }
 
-fn playstate_to_str(state: PlayState) -> &'static str {
-    match state {
-        PlayState::Closed => "Closed",
-        PlayState::Opened => "Opened",
-        PlayState::Changing => "Changing",
-        PlayState::Stopped => "Stopped",
-        PlayState::Playing => "Playing",
-        PlayState::Paused => "Paused",
-        PlayState::Unknown => "Unknown",
+/// Diameter of the hero play/pause button relative to the regular button height.
+const HERO_BUTTON_RATIO: f32 = 1.6;
+
+/// How the playback controls row draws its buttons.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum PlaybackControlsStyle {
+    /// Three matching skin buttons.
+    #[default]
+    Buttons,
+    /// A large round play/pause button between regular previous/next buttons.
+    Hero,
+}
+
+impl PlaybackControlsStyle {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "buttons" | "default" => Some(Self::Buttons),
+            "hero" => Some(Self::Hero),
+            _ => None,
+        }
     }
 }
 
-fn hash_bytes(data: &[u8]) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    data.hash(&mut hasher);
-    hasher.finish()
+/// `timeline` params, parsed once per frame in the component match.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TimelineParams {
+    centered: bool,
+    show_separator: bool,
+    tick_interval: Option<f64>,
+    /// End readout counts down instead of showing the duration.
+    show_remaining: bool,
+    /// `layout = "inline"`: readouts either side of the slider in one row.
+    inline: bool,
 }
 
-fn decode_thumbnail_image(bytes: &[u8]) -> std::result::Result<ColorImage, String> {
-    let image =
-        image::load_from_memory(bytes).map_err(|e| format!("Failed to decode thumbnail: {e}"))?;
-    let image = image.to_rgba8();
-    let size = [image.width() as usize, image.height() as usize];
-    let pixels = image.into_raw();
-    Ok(ColorImage::from_rgba_unmultiplied(size, &pixels))
+/// End readout of a timeline: the duration, or the time left as `-m:ss`.
+fn timeline_end_label(relative: f64, duration: f64, remaining: bool) -> String {
+    if remaining {
+        format!("-{}", format_timestamp(duration - relative))
+    } else {
+        format_timestamp(duration)
+    }
 }
 
-#[derive(Clone, Copy)]
-struct Cluster {
-    centroid: [f32; 3],
-    count: usize,
+/// `playback_controls` params. The defaults reproduce the built-in row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PlaybackControlsParams {
+    style: PlaybackControlsStyle,
+    /// Size the row aims for.
+    scale: f32,
+    /// Floor the row may shrink to when space runs out.
+    min_scale: f32,
+    /// Widest the row may grow; `None` widens [`PLAYBACK_CONTROLS_MAX_WIDTH`] with `scale`.
+    max_width: Option<f32>,
+    /// Gap between buttons at scale 1.
+    spacing: f32,
 }
 
-fn sample_pixels(image: &ColorImage, max_samples: usize) -> Vec<[f32; 3]> {
-    if max_samples == 0 {
-        return Vec::new();
+impl Default for PlaybackControlsParams {
+    fn default() -> Self {
+        Self {
+            style: PlaybackControlsStyle::Buttons,
+            scale: 1.0,
+            min_scale: MIN_BUTTON_SCALE,
+            max_width: None,
+            spacing: PLAYBACK_CONTROL_SPACING_X,
+        }
     }
+}
 
-    let total = image.pixels.len();
-    if total == 0 {
-        return Vec::new();
+impl PlaybackControlsParams {
+    /// Reads `style`, `scale`, `min_scale`, `max_width`, and `spacing`, keeping the
+    /// default for any value that doesn't parse or is out of range.
+    fn parse(params: &HashMap<String, String>, warnings: &mut Vec<String>) -> Self {
+        let defaults = Self::default();
+        let style = params.get("style").and_then(|raw| {
+            let style = PlaybackControlsStyle::parse(raw);
+            if style.is_none() {
+                warnings.push(format!(
+                    "playback_controls: style must be 'buttons' or 'hero', got '{raw}'"
+                ));
+            }
+            style
+        });
+        let mut number = |key: &str, valid: &dyn Fn(f32) -> bool, expected: &str| {
+            let raw = params.get(key)?;
+            match raw.trim().parse::<f32>() {
+                Ok(value) if value.is_finite() && valid(value) => Some(value),
+                _ => {
+                    warnings.push(format!(
+                        "playback_controls: {key} must be {expected}, got '{raw}'"
+                    ));
+                    None
+                }
+            }
+        };
+        let scale_range = MIN_BUTTON_SCALE..=MAX_BUTTON_SCALE;
+        let in_scale_range = |value: f32| scale_range.contains(&value);
+        let scale_expected = format!("between {MIN_BUTTON_SCALE} and {MAX_BUTTON_SCALE}");
+        let scale = number("scale", &in_scale_range, &scale_expected).unwrap_or(defaults.scale);
+        let min_scale =
+            number("min_scale", &in_scale_range, &scale_expected).unwrap_or(defaults.min_scale);
+        let max_width = number("max_width", &|value| value > 0.0, "positive");
+        let spacing =
+            number("spacing", &|value| value >= 0.0, "zero or more").unwrap_or(defaults.spacing);
+        Self {
+            style: style.unwrap_or(defaults.style),
+            scale,
+            min_scale: min_scale.min(scale),
+            max_width,
+            spacing,
+        }
     }
 
-    let step = (total / max_samples).max(1);
-    let mut samples = Vec::with_capacity(max_samples.min(total));
-
-    for pixel in image.pixels.iter().step_by(step) {
-        if pixel.a() < 16 {
-            continue;
-        }
-        samples.push([pixel.r() as f32, pixel.g() as f32, pixel.b() as f32]);
-        if samples.len() >= max_samples {
-            break;
+    /// Final scale of the row: `scale`, shrunk toward `min_scale` to fit the available
+    /// width capped at `max_width`.
+    fn scale_for(&self, available_width: f32, base_row_width: f32) -> f32 {
+        if base_row_width <= f32::EPSILON {
+            return self.scale;
         }
+        let max_width = self
+            .max_width
+            .unwrap_or(PLAYBACK_CONTROLS_MAX_WIDTH * self.scale.max(1.0));
+        let fit = available_width.min(max_width) / base_row_width;
+        fit.min(self.scale).max(self.min_scale)
     }
-
-    samples
 }
 
-fn squared_distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
-    let dr = a[0] - b[0];
-    let dg = a[1] - b[1];
-    let db = a[2] - b[2];
-    dr * dr + dg * dg + db * db
+/// Button sizes of the playback controls row for one available width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PlaybackRowMetrics {
+    scale: f32,
+    button_size: egui::Vec2,
+    /// The play/pause cell; larger than `button_size` in the hero style.
+    center_size: egui::Vec2,
+    spacing: f32,
 }
 
-fn kmeans_clusters(samples: &[[f32; 3]], k: usize, max_iter: usize) -> Vec<Cluster> {
-    if samples.is_empty() || k == 0 {
-        return Vec::new();
-    }
-
-    let mut centroids = Vec::with_capacity(k);
-    for i in 0..k {
-        let idx = (i * samples.len()) / k;
-        let idx = idx.min(samples.len() - 1);
-        centroids.push(samples[idx]);
-    }
-
-    let mut assignments = vec![0usize; samples.len()];
-
-    for iter in 0..max_iter {
-        let mut sums = vec![[0f32; 3]; k];
-        let mut counts = vec![0usize; k];
-
-        for (sample_idx, sample) in samples.iter().enumerate() {
-            let mut best = 0usize;
-            let mut best_dist = f32::MAX;
-            for (centroid_idx, centroid) in centroids.iter().enumerate() {
-                let dist = squared_distance(sample, centroid);
-                if dist < best_dist {
-                    best_dist = dist;
-                    best = centroid_idx;
-                }
-            }
-
-            assignments[sample_idx] = best;
-            for channel in 0..3 {
-                sums[best][channel] += sample[channel];
-            }
-            counts[best] += 1;
-        }
-
-        let mut changed = false;
-        for i in 0..k {
-            if counts[i] == 0 {
-                centroids[i] = samples[(i + iter) % samples.len()];
-                changed = true;
-                continue;
-            }
-            let new_centroid = [
-                sums[i][0] / counts[i] as f32,
-                sums[i][1] / counts[i] as f32,
-                sums[i][2] / counts[i] as f32,
-            ];
-            if squared_distance(&centroids[i], &new_centroid) > 1e-2 {
-                changed = true;
-            }
-            centroids[i] = new_centroid;
-        }
+impl PlaybackRowMetrics {
+    /// Fits the row into `available_width`. Circle buttons are as wide as they are tall,
+    /// so their row starts much narrower than the pill one at the same scale.
+    fn new(
+        params: &PlaybackControlsParams,
+        shape: ButtonShape,
+        interact_size: egui::Vec2,
+        available_width: f32,
+    ) -> Self {
+        let base_height = interact_size.y.max(40.0);
+        let base_button_width = match shape {
+            ButtonShape::Pill => interact_size.x.max(96.0),
+            ButtonShape::Circle => base_height,
+        };
+        let hero = params.style == PlaybackControlsStyle::Hero;
+        let base_center_width = if hero {
+            base_height * HERO_BUTTON_RATIO
+        } else {
+            base_button_width
+        };
+        let base_row_width = 2.0 * base_button_width + base_center_width + 2.0 * params.spacing;
+        let scale = params.scale_for(available_width, base_row_width);
 
-        if !changed {
-            break;
+        let button_height = (base_height * scale).max(28.0);
+        let button_size = match shape {
+            ButtonShape::Pill => egui::vec2((base_button_width * scale).max(60.0), button_height),
+            ButtonShape::Circle => egui::Vec2::splat(button_height),
+        };
+        let center_size = if hero {
+            egui::Vec2::splat(button_height * HERO_BUTTON_RATIO)
+        } else {
+            button_size
+        };
+        Self {
+            scale,
+            button_size,
+            center_size,
+            spacing: (params.spacing * scale).max(params.spacing.min(6.0)),
         }
     }
 
-    let mut counts = vec![0usize; k];
-    for &assignment in &assignments {
-        counts[assignment] += 1;
+    fn row_size(&self) -> egui::Vec2 {
+        egui::vec2(
+            2.0 * self.button_size.x + self.center_size.x + 2.0 * self.spacing,
+            self.button_size.y.max(self.center_size.y),
+        )
     }
-
-    centroids
-        .into_iter()
-        .enumerate()
-        .map(|(idx, centroid)| Cluster {
-            centroid,
-            count: counts[idx],
-        })
-        .collect()
-}
-
-fn color_from_centroid(centroid: [f32; 3]) -> egui::Color32 {
-    let r = centroid[0].clamp(0.0, 255.0).round() as u8;
-    let g = centroid[1].clamp(0.0, 255.0).round() as u8;
-    let b = centroid[2].clamp(0.0, 255.0).round() as u8;
-    egui::Color32::from_rgb(r, g, b)
 }
 
-fn color_distance_sq(a: egui::Color32, b: egui::Color32) -> f32 {
-    let dr = a.r() as f32 - b.r() as f32;
-    let dg = a.g() as f32 - b.g() as f32;
-    let db = a.b() as f32 - b.b() as f32;
-    dr * dr + dg * dg + db * db
+fn long_form_threshold(config: &Config) -> Duration {
+    Duration::from_secs(u64::from(config.ui.long_form_minutes.max(1)) * 60)
 }
 
-fn luminance(color: egui::Color32) -> f32 {
-    0.2126 * color.r() as f32 + 0.7152 * color.g() as f32 + 0.0722 * color.b() as f32
+/// Tick positions for a timeline of `duration` seconds: the chapter starts when the
+/// session has any, otherwise every `interval` seconds.
+fn timeline_ticks(duration: f64, interval: Option<f64>, chapters: &[f64]) -> Vec<f64> {
+    if !chapters.is_empty() {
+        return chapters
+            .iter()
+            .copied()
+            .filter(|start| *start > 0.0 && *start < duration)
+            .collect();
+    }
+    let Some(interval) = interval.filter(|interval| *interval > 0.0) else {
+        return Vec::new();
+    };
+    (1..=MAX_TIMELINE_TICKS)
+        .map(|index| index as f64 * interval)
+        .take_while(|tick| *tick < duration)
+        .collect()
 }
 
-fn order_by_luminance(a: egui::Color32, b: egui::Color32) -> (egui::Color32, egui::Color32) {
-    if luminance(a) <= luminance(b) {
-        (a, b)
+/// Maps a pointer position near the window border to the edge or corner to resize from.
+fn borderless_resize_direction(
+    root_rect: egui::Rect,
+    pos: egui::Pos2,
+    edge: f32,
+) -> Option<ResizeDirection> {
+    let near_left = pos.x <= root_rect.left() + edge;
+    let near_right = pos.x >= root_rect.right() - edge;
+    let near_top = pos.y <= root_rect.top() + edge;
+    let near_bottom = pos.y >= root_rect.bottom() - edge;
+
+    if near_left && near_top {
+        Some(ResizeDirection::NorthWest)
+    } else if near_right && near_top {
+        Some(ResizeDirection::NorthEast)
+    } else if near_left && near_bottom {
+        Some(ResizeDirection::SouthWest)
+    } else if near_right && near_bottom {
+        Some(ResizeDirection::SouthEast)
+    } else if near_left {
+        Some(ResizeDirection::West)
+    } else if near_right {
+        Some(ResizeDirection::East)
+    } else if near_top {
+        Some(ResizeDirection::North)
+    } else if near_bottom {
+        Some(ResizeDirection::South)
     } else {
-        (b, a)
+        None
     }
 }
 
-fn dominant_gradient_colors(image: &ColorImage) -> Option<[egui::Color32; 2]> {
-    const MAX_SAMPLES: usize = 6_000;
-    const K: usize = 3;
-    const MAX_ITER: usize = 10;
-    const DISTINCT_THRESHOLD: f32 = 400.0;
-
-    let samples = sample_pixels(image, MAX_SAMPLES);
-    if samples.len() < 2 {
-        return None;
+/// Windows product name and build from the registry, e.g. "Windows 11 Pro 23H2 (build 22631)".
+#[cfg(target_os = "windows")]
+fn os_description() -> String {
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_LOCAL_MACHINE, RRF_RT_REG_SZ};
+
+    fn read(value: PCWSTR) -> Option<String> {
+        let mut buffer = [0u16; 128];
+        let mut size = (buffer.len() * std::mem::size_of::<u16>()) as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_LOCAL_MACHINE,
+                w!("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion"),
+                value,
+                RRF_RT_REG_SZ,
+                None,
+                Some(buffer.as_mut_ptr().cast()),
+                Some(&mut size),
+            )
+        };
+        if status.is_err() {
+            return None;
+        }
+        let len = (size as usize / std::mem::size_of::<u16>()).saturating_sub(1);
+        Some(String::from_utf16_lossy(&buffer[..len.min(buffer.len())]))
     }
 
-    let k = K.min(samples.len()).max(1);
-    let mut clusters = kmeans_clusters(&samples, k, MAX_ITER);
-    if clusters.is_empty() {
-        return None;
-    }
+    let product = read(w!("ProductName")).unwrap_or_else(|| "Windows".to_string());
+    let release = read(w!("DisplayVersion")).unwrap_or_default();
+    let build = read(w!("CurrentBuild")).unwrap_or_else(|| "unknown".to_string());
+    format!("{product} {release} (build {build})")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
-    clusters.sort_by_key(|cluster| Reverse(cluster.count));
+#[cfg(not(target_os = "windows"))]
+fn os_description() -> String {
+    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+}
 
-    let mut unique = Vec::new();
-    for cluster in clusters {
-        if cluster.count == 0 {
-            continue;
+fn playstate_to_str(state: PlayState) -> &'static str {
+    match state {
+        PlayState::Closed => "Closed",
+        PlayState::Opened => "Opened",
+        PlayState::Changing => "Changing",
+        PlayState::Stopped => "Stopped",
+        PlayState::Playing => "Playing",
+        PlayState::Paused => "Paused",
+        PlayState::Unknown => "Unknown",
+    }
+}
+
+/// Ripple progress `elapsed` seconds after a button press, as (spread fraction, opacity);
+/// `None` once it has faded out.
+fn press_ripple(elapsed: f64) -> Option<(f32, f32)> {
+    if !(0.0..PRESS_RIPPLE_SECS).contains(&elapsed) {
+        return None;
+    }
+    let t = (elapsed / PRESS_RIPPLE_SECS) as f32;
+    let spread = 1.0 - (1.0 - t) * (1.0 - t);
+    Some((spread, 1.0 - t))
+}
+
+/// Track-change animation of the `title` component, from its `transition` param.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TitleTransition {
+    None,
+    Slide,
+    Fade,
+}
+
+impl TitleTransition {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "slide" => Some(Self::Slide),
+            "fade" => Some(Self::Fade),
+            _ => None,
         }
-        let color = color_from_centroid(cluster.centroid);
-        if unique
-            .iter()
-            .all(|&(existing, _)| color_distance_sq(existing, color) > DISTINCT_THRESHOLD)
-        {
-            unique.push((color, cluster.count));
+    }
+}
+
+/// Title being replaced after a track change.
+struct TitleTransitionState {
+    previous: String,
+    started: Instant,
+    /// Opacity the outgoing title had when the transition began; below 1 when a change
+    /// interrupted an earlier transition.
+    outgoing_start: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TitleBlend {
+    /// Eased progress, 0 at the change and 1 once the new title has settled.
+    progress: f32,
+    outgoing_alpha: f32,
+    incoming_alpha: f32,
+}
+
+/// Blend `t` (0..=1) of the way through a title transition.
+fn title_transition_blend(t: f32, outgoing_start: f32) -> TitleBlend {
+    let t = t.clamp(0.0, 1.0);
+    let progress = 1.0 - (1.0 - t) * (1.0 - t);
+    TitleBlend {
+        progress,
+        outgoing_alpha: outgoing_start.clamp(0.0, 1.0) * (1.0 - progress),
+        incoming_alpha: progress,
+    }
+}
+
+impl TitleTransitionState {
+    /// Current blend, or `None` once the transition has finished.
+    fn blend(&self, now: Instant) -> Option<TitleBlend> {
+        let t = now.duration_since(self.started).as_secs_f32() / TITLE_TRANSITION_SECS;
+        (t < 1.0).then(|| title_transition_blend(t, self.outgoing_start))
+    }
+}
+
+/// Glyph, label, and colors for the `state_badge` component.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct StateBadgeStyle {
+    icon: &'static str,
+    label: &'static str,
+    fill: egui::Color32,
+    text: egui::Color32,
+}
+
+/// Themes tint the badge through `colors.state_playing`, `colors.state_paused`, and
+/// `colors.state_stopped`; without them it follows the panel foreground.
+fn state_badge_style(
+    state: PlayState,
+    colors: &HashMap<String, egui::Color32>,
+    panel_foreground: egui::Color32,
+) -> StateBadgeStyle {
+    let (icon, color_key) = match state {
+        PlayState::Playing => ("▶", "state_playing"),
+        PlayState::Paused => ("⏸", "state_paused"),
+        _ => ("⏹", "state_stopped"),
+    };
+    let text = colors.get(color_key).copied().unwrap_or(panel_foreground);
+    StateBadgeStyle {
+        icon,
+        label: playstate_to_str(state),
+        fill: text.gamma_multiply(STATE_BADGE_FILL_ALPHA),
+        text,
+    }
+}
+
+/// Opacity multiplier for a pulsing badge, easing between 0.6 and 1.0.
+fn state_badge_pulse(time: f64) -> f32 {
+    let phase = (time / STATE_BADGE_PULSE_PERIOD) * std::f64::consts::TAU;
+    (0.8 + 0.2 * phase.cos()) as f32
+}
+
+/// Opacity multiplier for `border_pulse`: full on each beat, then a quick decay to
+/// [`BORDER_PULSE_FLOOR`] so the border keeps a faint outline between beats.
+fn border_pulse_alpha(time: f64, bpm: u32) -> f32 {
+    let phase = (time * f64::from(bpm) / 60.0).fract() as f32;
+    BORDER_PULSE_FLOOR + (1.0 - BORDER_PULSE_FLOOR) * (-phase * BORDER_PULSE_DECAY).exp()
+}
+
+/// Remembers which texture and display scale the artwork was last checked against, so
+/// sharpness is only re-evaluated when either changes. A new scale must hold for
+/// [`THUMBNAIL_RESCALE_DEBOUNCE`] first, so dragging across monitors doesn't thrash.
+#[derive(Debug, Default)]
+struct ThumbnailScaleWatch {
+    checked: Option<(egui::TextureId, f32)>,
+    pending: Option<(f32, Instant)>,
+}
+
+impl ThumbnailScaleWatch {
+    /// True when `texture` should be checked for sharpness at `pixels_per_point`.
+    fn poll(&mut self, texture: egui::TextureId, pixels_per_point: f32, now: Instant) -> bool {
+        let same_scale = |a: f32, b: f32| (a - b).abs() < 0.01;
+        match self.checked {
+            Some((id, scale)) if id == texture && same_scale(scale, pixels_per_point) => {
+                self.pending = None;
+                false
+            }
+            Some((id, _)) if id == texture => match self.pending {
+                Some((scale, since)) if same_scale(scale, pixels_per_point) => {
+                    if now.saturating_duration_since(since) < THUMBNAIL_RESCALE_DEBOUNCE {
+                        return false;
+                    }
+                    self.checked = Some((texture, pixels_per_point));
+                    self.pending = None;
+                    true
+                }
+                _ => {
+                    self.pending = Some((pixels_per_point, now));
+                    false
+                }
+            },
+            // A new texture has nothing to settle; check it straight away.
+            _ => {
+                self.checked = Some((texture, pixels_per_point));
+                self.pending = None;
+                true
+            }
         }
     }
 
-    if unique.len() < 2 {
-        return None;
+    fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+/// `render_vinyl` along with how long it took, for the debug overlay.
+fn timed_render_vinyl(
+    base_image: &ColorImage,
+    options: &VinylThumbnailOptions,
+) -> (ColorImage, Duration) {
+    let started = Instant::now();
+    let image = render_vinyl(base_image, options);
+    (image, started.elapsed())
+}
+
+/// Decodes fetched artwork (and renders the vinyl variant) into a message for the UI.
+fn thumbnail_message(
+    request_id: u64,
+    track: NowPlaying,
+    result: WinResult<Option<Vec<u8>>>,
+    vinyl_enabled: bool,
+    vinyl_config: &VinylThumbnailConfig,
+) -> ThumbnailMessage {
+    match result {
+        Ok(Some(bytes)) => {
+            let hash = hash_bytes(&bytes);
+            match decode_thumbnail_image(&bytes) {
+                Ok(base_image) => {
+                    let (vinyl_image, vinyl_render) = if vinyl_enabled {
+                        let options = VinylThumbnailOptions::from_config(
+                            vinyl_config,
+                            base_image.size[0],
+                            base_image.size[1],
+                        );
+                        let (image, elapsed) = timed_render_vinyl(&base_image, &options);
+                        (Some(image), Some(elapsed))
+                    } else {
+                        (None, None)
+                    };
+
+                    ThumbnailMessage {
+                        request_id,
+                        track,
+                        hash: Some(hash),
+                        base_image: Some(base_image),
+                        vinyl_image,
+                        vinyl_render,
+                        error: None,
+                    }
+                }
+                Err(err) => ThumbnailMessage {
+                    request_id,
+                    track,
+                    hash: None,
+                    base_image: None,
+                    vinyl_image: None,
+                    vinyl_render: None,
+                    error: Some(err),
+                },
+            }
+        }
+        Ok(None) => ThumbnailMessage {
+            request_id,
+            track,
+            hash: None,
+            base_image: None,
+            vinyl_image: None,
+            vinyl_render: None,
+            error: None,
+        },
+        Err(err) => ThumbnailMessage {
+            request_id,
+            track,
+            hash: None,
+            base_image: None,
+            vinyl_image: None,
+            vinyl_render: None,
+            error: Some(format!("{err:?}")),
+        },
     }
+}
+
+/// Writes a captured frame as a PNG, keeping the window's transparency.
+fn write_screenshot_png(image: &ColorImage, path: &Path) -> std::result::Result<(), String> {
+    let pixels: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    let [width, height] = image.size;
+    image::save_buffer_with_format(
+        path,
+        &pixels,
+        width as u32,
+        height as u32,
+        image::ExtendedColorType::Rgba8,
+        image::ImageFormat::Png,
+    )
+    .map_err(|e| format!("Failed to save screenshot to {}: {e}", path.display()))
+}
+
+fn hash_bytes(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
 
-    let primary = unique[0].0;
-    let secondary = unique[1].0;
-    let (start, end) = order_by_luminance(primary, secondary);
-    Some([start, end])
+fn decode_thumbnail_image(bytes: &[u8]) -> std::result::Result<ColorImage, String> {
+    let image =
+        image::load_from_memory(bytes).map_err(|e| format!("Failed to decode thumbnail: {e}"))?;
+    let image = image.to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    let pixels = image.into_raw();
+    Ok(ColorImage::from_rgba_unmultiplied(size, &pixels))
 }
 
 fn gradient_direction_from_background(background: &AreaBackground) -> GradientDirection {
@@ -604,8 +1260,9 @@ fn gradient_direction_from_background(background: &AreaBackground) -> GradientDi
 fn dynamic_gradient_from_image(
     image: &ColorImage,
     direction: GradientDirection,
+    palette: &PaletteConfig,
 ) -> Option<GradientSpec> {
-    dominant_gradient_colors(image).map(|[start, end]| GradientSpec {
+    dominant_gradient_colors(image, palette).map(|[start, end]| GradientSpec {
         start,
         end,
         direction,
@@ -652,20 +1309,18 @@ where
     block_on(operation.into_future())
 }
 
-fn current_session() -> WinResult<GlobalSystemMediaTransportControlsSession> {
-    let manager =
-        block_on_operation(GlobalSystemMediaTransportControlsSessionManager::RequestAsync()?)?;
-    manager.GetCurrentSession()
+fn session_manager() -> WinResult<GlobalSystemMediaTransportControlsSessionManager> {
+    block_on_operation(GlobalSystemMediaTransportControlsSessionManager::RequestAsync()?)
 }
 
-fn fetch_session_snapshot() -> WinResult<(NowPlaying, Option<Timeline>)> {
-    let session = current_session()?;
-
-    let props = block_on_operation(session.TryGetMediaPropertiesAsync()?)?;
-    let playback_info = session.GetPlaybackInfo()?;
-    let status = playback_info.PlaybackStatus()?;
+fn current_session() -> WinResult<GlobalSystemMediaTransportControlsSession> {
+    session_manager()?.GetCurrentSession()
+}
 
-    let state = match status {
+fn play_state_from_status(
+    status: GlobalSystemMediaTransportControlsSessionPlaybackStatus,
+) -> PlayState {
+    match status {
         GlobalSystemMediaTransportControlsSessionPlaybackStatus::Closed => PlayState::Closed,
         GlobalSystemMediaTransportControlsSessionPlaybackStatus::Opened => PlayState::Opened,
         GlobalSystemMediaTransportControlsSessionPlaybackStatus::Changing => PlayState::Changing,
@@ -673,6 +1328,95 @@ fn fetch_session_snapshot() -> WinResult<(NowPlaying, Option<Timeline>)> {
         GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing => PlayState::Playing,
         GlobalSystemMediaTransportControlsSessionPlaybackStatus::Paused => PlayState::Paused,
         _ => PlayState::Unknown,
+    }
+}
+
+/// Title and artist of paused background sessions. Reading them blocks on a WinRT
+/// call per session, so they are kept until the session resumes, closes or the entry
+/// is [`OTHER_SESSION_REFRESH`] old.
+#[derive(Default)]
+struct OtherSessionCache {
+    entries: HashMap<String, (String, String, Instant)>,
+}
+
+impl OtherSessionCache {
+    fn get(&self, app_id: &str, now: Instant) -> Option<(String, String)> {
+        let (title, artist, fetched_at) = self.entries.get(app_id)?;
+        (now.saturating_duration_since(*fetched_at) < OTHER_SESSION_REFRESH)
+            .then(|| (title.clone(), artist.clone()))
+    }
+
+    fn insert(&mut self, app_id: String, title: String, artist: String, now: Instant) {
+        self.entries.insert(app_id, (title, artist, now));
+    }
+
+    /// Keeps only the sessions still paused in the latest poll.
+    fn retain_paused(&mut self, paused: &[&str]) {
+        self.entries
+            .retain(|app_id, _| paused.contains(&app_id.as_str()));
+    }
+}
+
+fn fetch_other_sessions(
+    manager: &GlobalSystemMediaTransportControlsSessionManager,
+    current: &GlobalSystemMediaTransportControlsSession,
+    cache: &mut OtherSessionCache,
+) -> WinResult<Vec<OtherSession>> {
+    let current_id = current.SourceAppUserModelId()?;
+    let sessions = manager.GetSessions()?;
+    let now = Instant::now();
+    let mut others = Vec::new();
+    for index in 0..sessions.Size()? {
+        let session = sessions.GetAt(index)?;
+        let app_id = session.SourceAppUserModelId()?;
+        if app_id == current_id {
+            continue;
+        }
+        let app_id = app_id.to_string_lossy();
+        let state = play_state_from_status(session.GetPlaybackInfo()?.PlaybackStatus()?);
+        let (title, artist) = if state != PlayState::Paused {
+            (String::new(), String::new())
+        } else if let Some(cached) = cache.get(&app_id, now) {
+            cached
+        } else {
+            let props = block_on_operation(session.TryGetMediaPropertiesAsync()?)?;
+            let title = props.Title()?.to_string_lossy();
+            let artist = props.Artist()?.to_string_lossy();
+            cache.insert(app_id.clone(), title.clone(), artist.clone(), now);
+            (title, artist)
+        };
+        others.push(OtherSession {
+            app_id,
+            title,
+            artist,
+            state,
+        });
+    }
+    let paused: Vec<&str> = others
+        .iter()
+        .filter(|other| other.state == PlayState::Paused)
+        .map(|other| other.app_id.as_str())
+        .collect();
+    cache.retain_paused(&paused);
+    Ok(others)
+}
+
+fn fetch_session_snapshot(other_sessions: &mut OtherSessionCache) -> WinResult<SessionSnapshot> {
+    let manager = session_manager()?;
+    let session = manager.GetCurrentSession()?;
+
+    let props = block_on_operation(session.TryGetMediaPropertiesAsync()?)?;
+    let playback_info = session.GetPlaybackInfo()?;
+    let state = play_state_from_status(playback_info.PlaybackStatus()?);
+
+    let controls = playback_info.Controls()?;
+    let caps = ControlsCaps {
+        previous: controls.IsPreviousEnabled()?,
+        next: controls.IsNextEnabled()?,
+        play: controls.IsPlayEnabled()?,
+        pause: controls.IsPauseEnabled()?,
+        play_pause_toggle: controls.IsPlayPauseToggleEnabled()?,
+        seek: controls.IsPlaybackPositionEnabled()?,
     };
 
     let now = NowPlaying {
@@ -680,6 +1424,19 @@ fn fetch_session_snapshot() -> WinResult<(NowPlaying, Option<Timeline>)> {
         artist: props.Artist()?.to_string_lossy(),
         album: props.AlbumTitle()?.to_string_lossy(),
         state,
+        caps,
+        app_id: session.SourceAppUserModelId()?.to_string_lossy(),
+        // Plenty of players never fill genres in; that only affects classification.
+        genres: props
+            .Genres()
+            .map(|genres| {
+                genres
+                    .into_iter()
+                    .map(|genre| genre.to_string_lossy())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..NowPlaying::default()
     };
 
     let timeline_props = session.GetTimelineProperties()?;
@@ -695,118 +1452,284 @@ fn fetch_session_snapshot() -> WinResult<(NowPlaying, Option<Timeline>)> {
     }
     position_secs = position_secs.clamp(start_secs, end_secs.max(start_secs));
 
-    let can_seek = (end_secs - start_secs).abs() > f64::EPSILON;
+    let has_range = (end_secs - start_secs).abs() > f64::EPSILON;
 
     let timeline = Timeline {
         start_secs,
         end_secs,
         position_secs,
-        can_seek,
+        can_seek: has_range && caps.seek,
     };
 
-    let timeline = if timeline.duration_secs() <= f64::EPSILON && !can_seek {
+    let timeline = if timeline.duration_secs() <= f64::EPSILON && !has_range {
         None
     } else {
         Some(timeline)
     };
 
-    Ok((now, timeline))
+    // Other sessions only feed the optional up-next hint; never fail the snapshot on them.
+    let others = fetch_other_sessions(&manager, &session, other_sessions).unwrap_or_default();
+
+    Ok(SessionSnapshot {
+        now,
+        timeline,
+        others,
+        next: None,
+    })
 }
 
 fn fetch_thumbnail_bytes() -> WinResult<Option<Vec<u8>>> {
     let session = current_session()?;
     let props = block_on_operation(session.TryGetMediaPropertiesAsync()?)?;
-    load_thumbnail_bytes(&props)
+    let streamed = load_thumbnail_bytes(&props);
+    if matches!(streamed, Ok(Some(_))) {
+        return streamed;
+    }
+    // Local players without embedded art often report the file path as the title.
+    let local = [props.Title(), props.Subtitle()]
+        .into_iter()
+        .filter_map(Result::ok)
+        .flat_map(|reported| local_artwork_candidates(&reported.to_string_lossy()))
+        .find_map(|path| read_local_artwork(&path));
+    match local {
+        Some(bytes) => Ok(Some(bytes)),
+        None => streamed,
+    }
+}
+
+/// Image files that may hold artwork for a path a session reported: the path itself
+/// when it is an image, otherwise the usual cover sidecars next to it. Anything that
+/// isn't an absolute path yields nothing, and only image extensions are ever returned.
+fn local_artwork_candidates(reported: &str) -> Vec<PathBuf> {
+    let reported = reported.trim();
+    let path = Path::new(reported.strip_prefix("file:///").unwrap_or(reported));
+    if !path.is_absolute() || path.extension().is_none() {
+        return Vec::new();
+    }
+    if has_artwork_extension(path) {
+        return vec![path.to_path_buf()];
+    }
+    let Some(folder) = path.parent() else {
+        return Vec::new();
+    };
+    ARTWORK_SIDECARS
+        .iter()
+        .map(|name| folder.join(name))
+        .collect()
+}
+
+fn has_artwork_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ARTWORK_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
+fn read_local_artwork(path: &Path) -> Option<Vec<u8>> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_LOCAL_ARTWORK_BYTES {
+        return None;
+    }
+    std::fs::read(path).ok()
 }
 
 struct App {
-    now: NowPlaying,
-    last_pull: Instant,
-    err: Option<String>,
-    timeline: Option<Timeline>,
-    last_position_update: Instant,
-    last_position_secs: f64,
-    is_user_seeking: bool,
-    pending_seek_target: Option<f64>,
-    pending_seek_deadline: Option<Instant>,
+    core: AppCore,
     thumbnail_texture: Option<TextureHandle>,
     thumbnail_base_texture: Option<TextureHandle>,
     thumbnail_base_image: Option<ColorImage>,
     thumbnail_vinyl_image: Option<ColorImage>,
-    thumbnail_hash: Option<u64>,
-    pending_thumbnail: Option<PendingThumbnail>,
     thumbnail_rx: Option<mpsc::Receiver<ThumbnailMessage>>,
     thumbnail_err: Option<String>,
     thumbnail_inflight_request: Option<u64>,
     thumbnail_inflight_track: Option<NowPlaying>,
     next_thumbnail_request_id: u64,
-    current_thumbnail_track: Option<NowPlaying>,
     snapshot_rx: Option<mpsc::Receiver<SnapshotResult>>,
     snapshot_request_tx: Option<mpsc::Sender<SnapshotCommand>>,
     snapshot_inflight: bool,
     last_snapshot_request: Option<Instant>,
+    /// For the synchronous refresh after a transport command; the worker keeps its own.
+    other_sessions: OtherSessionCache,
     skin_manager: SkinManager,
     dynamic_root_gradient: Option<GradientSpec>,
     dynamic_panel_gradient: Option<GradientSpec>,
+    /// Artwork colors `animated_gradient` cycles the dynamic gradients through.
+    artwork_palette: Vec<egui::Color32>,
+    /// Artwork accent for a `root.dynamic_border` outline.
+    dynamic_border_accent: Option<egui::Color32>,
     skin_warnings: Vec<String>,
     skin_error: Option<String>,
-    watch_skins: bool,
-    settings_panel_open: bool,
+    /// The skins folder vanished at runtime and the built-in skin took over.
+    skins_root_lost: bool,
+    settings: SettingsPanel,
     always_on_top: bool,
     last_window_level: Option<WindowLevel>,
+    #[cfg(target_os = "windows")]
+    virtual_desktop_state: VirtualDesktopState,
     window_decorations_hidden: bool,
-    last_window_decorations: Option<bool>,
+    decoration_sequence: DecorationSequence,
+    /// Window handle and borderless state last handed to the panic hook.
+    #[cfg(target_os = "windows")]
+    tracked_window: Option<(isize, bool)>,
     show_pin_button: bool,
     viewport_size: egui::Vec2,
+    /// This frame's panel card and the content area inside it, which `progress_edge`
+    /// paints against from wherever it sits in the layout.
+    panel_rect: egui::Rect,
+    panel_content_rect: egui::Rect,
     thumbnail_overlay_alpha: f32,
     config: Config,
     animations_enabled: bool,
     vinyl_spin: VinylSpin,
     vinyl_last_frame: Option<Instant>,
     vinyl_pending_refresh: bool,
+    thumbnail_scale_watch: ThumbnailScaleWatch,
+    remote: Option<RemoteServer>,
+    /// Tracks hearted with the `favorite` component.
+    favorites: FavoriteStore,
+    pending_skin_import: Option<PendingSkinImport>,
+    error_history: ErrorHistory,
+    /// Transient failures shown briefly in the corner.
+    toasts: ToastQueue,
+    /// Timings shown by the `Ctrl+Shift+D` debug overlay.
+    debug: DebugMetrics,
+    layout_auto: bool,
+    /// Window limits of the skin being left, while the new skin's `[window]` sizes wait
+    /// for the next frame.
+    pending_window_size: Option<theme::WindowSizing>,
+    /// Ambient mode from `[ui] layout_rotation`; None when off or unusable for this skin.
+    layout_rotation: Option<LayoutRotation>,
+    pending_track_flash: Option<Instant>,
+    title_transition: Option<TitleTransitionState>,
+    /// OS title bar text last sent, and a newer one waiting out the rate limit.
+    window_title: String,
+    pending_window_title: Option<String>,
+    last_window_title_update: Option<Instant>,
+    system_theme: SystemTheme,
+    /// Last look at the system light/dark mode for the `auto` skin.
+    system_theme_checked: Option<Instant>,
+    /// Corner picked from the "Move to" menu, applied on the next frame.
+    pending_snap: Option<SnapCorner>,
+    /// Monitor and work area seen last frame, to notice display changes.
+    #[cfg(target_os = "windows")]
+    snap_work_area: Option<(isize, snap::ScreenRect)>,
+    /// Scripted stand-in for the media session when launched with `--demo`.
+    demo: Option<DemoSession>,
+    screenshot: Option<ScreenshotStage>,
+    idle: IdlePresentation,
+    paused_at: Option<Instant>,
+    last_activity: Instant,
+    /// Spoken position in the screen-reader live region, and when it was last updated.
+    position_announcement: Option<(String, Instant)>,
+    /// Softened artwork for the idle screensaver, keyed by artwork hash.
+    idle_backdrop: Option<(u64, TextureHandle)>,
     #[cfg(target_os = "windows")]
     titlebar_state: WindowsTitlebarState,
+    #[cfg(target_os = "windows")]
+    thumb_bar: Option<ThumbBar>,
+    #[cfg(target_os = "windows")]
+    thumb_bar_attempts: u32,
 }
 
-impl Default for App {
-    fn default() -> Self {
-        let mut config = Config::load().unwrap_or_default();
-        let animations_enabled = animations_enabled_from_system();
-        let vinyl_spin = VinylSpin::new();
+/// Layout id that turns on width-based layout switching.
+const AUTO_LAYOUT_ID: &str = "auto";
 
-        let (snapshot_tx, snapshot_rx) = mpsc::channel();
-        let (request_tx, request_rx) = mpsc::channel();
+#[derive(Debug, PartialEq)]
+enum LayoutRequest {
+    Auto,
+    Variant(String),
+}
 
-        thread::spawn(move || {
-            let com_initialized = unsafe {
-                let hr = CoInitializeEx(None, COINIT_MULTITHREADED);
-                if hr.is_ok() {
-                    true
-                } else if hr == RPC_E_CHANGED_MODE {
-                    false
-                } else {
-                    let _ = snapshot_tx.send(Err(format!("COM init failed: {hr:?}")));
-                    return;
-                }
-            };
+/// Checks a requested layout id against the active skin's variants. "auto" needs at
+/// least one variant with a `min_width` to switch between.
+fn parse_layout_request<'a>(
+    id: &str,
+    mut available: impl Iterator<Item = &'a str>,
+    responsive: bool,
+) -> Result<LayoutRequest, String> {
+    if id == AUTO_LAYOUT_ID {
+        if responsive {
+            Ok(LayoutRequest::Auto)
+        } else {
+            Err("This skin has no responsive layouts".to_string())
+        }
+    } else if available.any(|candidate| candidate == id) {
+        Ok(LayoutRequest::Variant(id.to_string()))
+    } else {
+        Err(format!("Layout '{id}' not found"))
+    }
+}
 
-            while let Ok(command) = request_rx.recv() {
-                match command {
-                    SnapshotCommand::Fetch => {
-                        let res = fetch_session_snapshot().map_err(|e| format!("{e:?}"));
-                        let _ = snapshot_tx.send(res);
-                    }
-                    SnapshotCommand::Shutdown => break,
-                }
+/// Polls the media session on a background thread so slow COM calls never stall a
+/// frame.
+fn spawn_snapshot_worker() -> (
+    mpsc::Receiver<SnapshotResult>,
+    mpsc::Sender<SnapshotCommand>,
+) {
+    let (snapshot_tx, snapshot_rx) = mpsc::channel();
+    let (request_tx, request_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let com_initialized = unsafe {
+            let hr = CoInitializeEx(None, COINIT_MULTITHREADED);
+            if hr.is_ok() {
+                true
+            } else if hr == RPC_E_CHANGED_MODE {
+                false
+            } else {
+                let _ = snapshot_tx.send(Err(format!("COM init failed: {hr:?}")));
+                return;
             }
+        };
 
-            if com_initialized {
-                unsafe {
-                    CoUninitialize();
+        let mut other_sessions = OtherSessionCache::default();
+        while let Ok(command) = request_rx.recv() {
+            match command {
+                SnapshotCommand::Fetch => {
+                    let res =
+                        fetch_session_snapshot(&mut other_sessions).map_err(|e| format!("{e:?}"));
+                    let _ = snapshot_tx.send(res);
                 }
+                SnapshotCommand::Shutdown => break,
             }
-        });
+        }
+
+        if com_initialized {
+            unsafe {
+                CoUninitialize();
+            }
+        }
+    });
+
+    (snapshot_rx, request_tx)
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl App {
+    /// With `demo` set the media backend is never touched; a scripted session feeds
+    /// snapshots, artwork and transport commands instead.
+    fn new(demo: bool) -> Self {
+        let mut config = Config::load().unwrap_or_default();
+        let animations_enabled = animations_enabled_from_system();
+        let mut vinyl_spin = VinylSpin::new();
+        vinyl_spin.set_reverse(config.ui.vinyl_thumbnail.reverse);
+
+        let (snapshot_rx, request_tx) = if demo {
+            (None, None)
+        } else {
+            let (rx, tx) = spawn_snapshot_worker();
+            (Some(rx), Some(tx))
+        };
 
+        let system_theme = SystemTheme::new();
         let skin_root = default_skin_root();
         let (skin_manager, skin_error) = match SkinManager::discover(&skin_root, None) {
             Ok(manager) => (manager, None),
@@ -815,66 +1738,139 @@ impl Default for App {
                 (fallback, Some(format!("{err:?}")))
             }
         };
+        let skin_manager = skin_manager
+            .with_accent_override(
+                config
+                    .ui
+                    .accent_override
+                    .map(|[r, g, b]| egui::Color32::from_rgb(r, g, b)),
+            )
+            .with_layout_preferences(config.ui.skin_layouts.clone())
+            .with_selectable_text(config.ui.selectable_text)
+            .with_accent_from_art(config.ui.accent_from_art)
+            .with_light_theme(config.ui.follow_system_theme && system_theme.prefers_light());
         let skin_warnings = skin_manager.warnings().to_vec();
 
         let mut vinyl_pending_refresh = false;
         let skin_disables_vinyl = skin_manager.current_theme().disable_vinyl_thumbnail;
-        let vinyl_should_be_enabled = !skin_disables_vinyl;
+        let vinyl_should_be_enabled = !skin_disables_vinyl && !config.ui.force_disable_vinyl;
         if config.ui.vinyl_thumbnail.enabled != vinyl_should_be_enabled {
             config.ui.vinyl_thumbnail.enabled = vinyl_should_be_enabled;
             vinyl_pending_refresh = true;
         }
 
+        let mut core = AppCore::new(Instant::now());
+        core.clock.emit_interval = config.remote.position_interval;
+        core.long_form_threshold = long_form_threshold(&config);
+
+        let mut toasts = ToastQueue::default();
+        let remote = if config.remote.enabled {
+            match RemoteServer::start(config.remote.port) {
+                Ok(server) => Some(server),
+                Err(err) => {
+                    eprintln!("Remote control disabled: {err:?}");
+                    toasts.push(
+                        ToastLevel::Warning,
+                        format!("Remote control disabled: {err}"),
+                        Instant::now(),
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // A store that fails to load stays in memory so the broken file isn't overwritten.
+        let favorites = match config.favorites_path().and_then(FavoriteStore::load) {
+            Ok(store) => store,
+            Err(err) => {
+                eprintln!("Favorites unavailable: {err:?}");
+                toasts.push(
+                    ToastLevel::Warning,
+                    format!("Favorites won't be saved: {err}"),
+                    Instant::now(),
+                );
+                FavoriteStore::default()
+            }
+        };
+
         let mut app = Self {
-            now: NowPlaying {
-                state: PlayState::Unknown,
-                ..Default::default()
-            },
-            last_pull: Instant::now() - Duration::from_secs(1),
-            err: None,
-            timeline: None,
-            last_position_update: Instant::now(),
-            last_position_secs: 0.0,
-            is_user_seeking: false,
-            pending_seek_target: None,
-            pending_seek_deadline: None,
+            core,
             thumbnail_texture: None,
             thumbnail_base_texture: None,
             thumbnail_base_image: None,
             thumbnail_vinyl_image: None,
-            thumbnail_hash: None,
-            pending_thumbnail: None,
             thumbnail_rx: None,
             thumbnail_err: None,
             thumbnail_inflight_request: None,
             thumbnail_inflight_track: None,
             next_thumbnail_request_id: 1,
-            current_thumbnail_track: None,
-            snapshot_rx: Some(snapshot_rx),
-            snapshot_request_tx: Some(request_tx),
+            snapshot_rx,
+            snapshot_request_tx: request_tx,
             snapshot_inflight: false,
             last_snapshot_request: None,
+            other_sessions: OtherSessionCache::default(),
             skin_manager,
             dynamic_root_gradient: None,
             dynamic_panel_gradient: None,
+            artwork_palette: Vec::new(),
+            dynamic_border_accent: None,
             skin_warnings,
             skin_error,
-            watch_skins: false,
-            settings_panel_open: false,
+            skins_root_lost: false,
+            settings: SettingsPanel::default(),
             always_on_top: false,
             last_window_level: None,
+            #[cfg(target_os = "windows")]
+            virtual_desktop_state: VirtualDesktopState::default(),
             window_decorations_hidden: false,
-            last_window_decorations: None,
+            decoration_sequence: DecorationSequence::default(),
+            #[cfg(target_os = "windows")]
+            tracked_window: None,
             show_pin_button: true,
             viewport_size: egui::vec2(800.0, 600.0),
+            panel_rect: egui::Rect::NOTHING,
+            panel_content_rect: egui::Rect::NOTHING,
             thumbnail_overlay_alpha: 0.0,
             config,
             animations_enabled,
             vinyl_spin,
             vinyl_last_frame: None,
             vinyl_pending_refresh,
+            thumbnail_scale_watch: ThumbnailScaleWatch::default(),
+            remote,
+            favorites,
+            pending_skin_import: None,
+            error_history: ErrorHistory::default(),
+            toasts,
+            debug: DebugMetrics::default(),
+            layout_auto: false,
+            pending_window_size: Some(theme::WindowSizing::default()),
+            layout_rotation: None,
+            pending_track_flash: None,
+            title_transition: None,
+            window_title: WINDOW_TITLE.to_string(),
+            pending_window_title: None,
+            last_window_title_update: None,
+            system_theme,
+            system_theme_checked: Some(Instant::now()),
+            pending_snap: None,
+            #[cfg(target_os = "windows")]
+            snap_work_area: None,
+            demo: demo.then(DemoSession::new),
+            screenshot: None,
+            idle: IdlePresentation::Active,
+            paused_at: None,
+            last_activity: Instant::now(),
+            position_announcement: None,
+            idle_backdrop: None,
             #[cfg(target_os = "windows")]
             titlebar_state: WindowsTitlebarState::default(),
+            #[cfg(target_os = "windows")]
+            thumb_bar: None,
+            #[cfg(target_os = "windows")]
+            thumb_bar_attempts: 0,
         };
 
         if let Some(tx) = app.snapshot_request_tx.as_ref() {
@@ -885,21 +1881,68 @@ impl Default for App {
                 app.snapshot_request_tx = None;
             }
         }
+        if app.demo.is_some() {
+            app.refresh_now_playing();
+        }
+        app.rebuild_layout_rotation();
 
         app
     }
 }
 
 impl eframe::App for App {
+    /// Skins that ask for see-through backgrounds get a fully transparent clear, so the
+    /// desktop shows through at exactly the skin's `background_alpha`. Opaque skins keep
+    /// eframe's default.
+    fn clear_color(&self, _visuals: &egui::Visuals) -> [f32; 4] {
+        if self.skin_manager.current_theme().background_alpha < 1.0 {
+            egui::Rgba::TRANSPARENT.to_array()
+        } else {
+            egui::Color32::from_rgba_unmultiplied(12, 12, 12, 180).to_normalized_gamma_f32()
+        }
+    }
+
+    /// Leaves the window framed for whatever runs next in the process.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        #[cfg(target_os = "windows")]
+        borderless::restore_window();
+    }
+
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.skin_manager.apply_style(ctx);
         self.update_window_decorations(ctx, frame);
+        self.update_window_title(ctx);
+        self.update_system_theme(ctx);
         #[cfg(target_os = "windows")]
         if !self.window_decorations_hidden {
             self.update_windows_titlebar(ctx, frame);
         }
+        #[cfg(target_os = "windows")]
+        self.update_windows_backdrop(frame);
         self.update_window_level(ctx);
+        #[cfg(target_os = "windows")]
+        self.update_virtual_desktop(ctx, frame);
+        #[cfg(target_os = "windows")]
+        self.update_window_snap(ctx, frame);
+        #[cfg(target_os = "windows")]
+        self.maybe_flash_taskbar(ctx, frame);
         self.maintain_skin_watcher(ctx);
+        self.process_remote_commands(ctx);
+        self.process_screenshot(ctx);
+        if ctx.input_mut(|i| i.consume_shortcut(&SETTINGS_SHORTCUT)) {
+            self.settings.toggle();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&SCREENSHOT_SHORTCUT)) {
+            self.request_screenshot(ctx);
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&REFRESH_SHORTCUT)) {
+            self.force_refresh();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&DEBUG_OVERLAY_SHORTCUT)) {
+            self.debug.toggle();
+        }
+
+        self.apply_pending_window_size(ctx);
 
         let mut snapshots = Vec::new();
         if let Some(rx) = self.snapshot_rx.as_mut() {
@@ -918,45 +1961,53 @@ impl eframe::App for App {
             }
         }
 
-        for res in snapshots {
+        let (latest, skipped_errors) = coalesce_snapshots(snapshots);
+        for err in &skipped_errors {
+            self.error_history
+                .observe(ErrorKind::Playback.source(), Some(err));
+        }
+        if let Some(res) = latest {
+            if let Some(sent_at) = self.last_snapshot_request {
+                self.debug.snapshot_fetch = Some(sent_at.elapsed());
+            }
             self.snapshot_inflight = false;
             self.last_snapshot_request = None;
             match res {
-                Ok((now, timeline)) => self.apply_snapshot(now, timeline),
-                Err(e) => {
-                    self.err = Some(e);
-                    self.timeline = None;
-                    self.last_pull = Instant::now();
-                }
+                Ok(snapshot) => self.apply_snapshot(snapshot),
+                Err(e) => self.core.apply_error(e, Instant::now()),
             }
         }
 
+        #[cfg(target_os = "windows")]
+        self.update_thumb_bar(ctx, frame);
         self.maybe_refresh_vinyl_thumbnail();
         self.process_pending_thumbnail(ctx);
 
-        if let Some(timeline) = &mut self.timeline {
-            let is_playing = self.now.state == PlayState::Playing;
-            if is_playing && self.pending_seek_target.is_none() {
-                let now = Instant::now();
-                let elapsed = now.duration_since(self.last_position_update).as_secs_f64();
-                let new_pos = (self.last_position_secs + elapsed)
-                    .clamp(timeline.start_secs, timeline.end_secs);
-                timeline.position_secs = new_pos;
-                self.last_position_secs = new_pos;
-                self.last_position_update = now;
-            } else {
-                self.last_position_update = Instant::now();
-                self.last_position_secs = timeline.position_secs;
-            }
-        }
+        self.update_idle_presentation(ctx);
+
+        self.core.advance_timeline(Instant::now());
+        self.publish_position_event(Instant::now());
+        self.update_position_announcement(ctx, Instant::now());
+        self.advance_layout_rotation(ctx, Instant::now());
 
+        let animated_stops = self.animated_gradient_stops(ctx);
+        let dynamic_background = |spec: &GradientSpec| {
+            AreaBackground::Gradient(match animated_stops {
+                Some([start, end]) => GradientSpec {
+                    start,
+                    end,
+                    direction: spec.direction,
+                },
+                None => spec.clone(),
+            })
+        };
         let theme = self.skin_manager.current_theme();
         let theme_components = &theme.components;
         let use_dynamic_gradient = theme.use_gradient;
         let root_background = if use_dynamic_gradient {
             self.dynamic_root_gradient
                 .as_ref()
-                .map(|spec| AreaBackground::Gradient(spec.clone()))
+                .map(dynamic_background)
                 .unwrap_or_else(|| theme_components.root.background.clone())
         } else {
             theme_components.root.background.clone()
@@ -964,16 +2015,28 @@ impl eframe::App for App {
         let panel_background = if use_dynamic_gradient {
             self.dynamic_panel_gradient
                 .as_ref()
-                .map(|spec| AreaBackground::Gradient(spec.clone()))
+                .map(dynamic_background)
                 .unwrap_or_else(|| theme_components.panel.background.clone())
         } else {
             theme_components.panel.background.clone()
         };
 
+        // `inset`/`elevation` turn the panel into a raised, rounded card.
+        let panel_inset = theme_components.panel.inset;
+        let panel_elevation = theme_components.panel.elevation;
+        let panel_rounding = if panel_inset > 0.0 || panel_elevation > 0.0 {
+            let radius = theme_components.panel.border_radius;
+            CornerRadius::same(radius.clamp(0.0, u8::MAX as f32).round() as u8)
+        } else {
+            CornerRadius::same(0)
+        };
+
         let root_rect = ctx.screen_rect();
         self.viewport_size = root_rect.size();
         
-        let transparent_bg = theme.transparent_background;
+        // Zero for `transparent_background` skins, which skip the fills entirely.
+        let background_alpha = theme.background_alpha;
+        let transparent_bg = background_alpha <= 0.0;
         
         if !transparent_bg {
             let root_painter = ctx.layer_painter(LayerId::background());
@@ -981,39 +2044,155 @@ impl eframe::App for App {
                 &root_painter,
                 root_rect,
                 CornerRadius::same(0),
-                &root_background,
+                &root_background.with_alpha(background_alpha),
             );
         }
 
         let mut panel_frame = egui::Frame::central_panel(&ctx.style());
         panel_frame.fill = egui::Color32::TRANSPARENT;
+        panel_frame.inner_margin += panel_inset.round() as i8;
 
         egui::CentralPanel::default()
             .frame(panel_frame)
             .show(ctx, |ui| {
-                let panel_rect = ui.max_rect();
-                
+                // The card around the content; the content itself sits `inset` inside it.
+                let panel_rect = ui.max_rect().expand(panel_inset.round());
+                self.panel_rect = panel_rect;
+                self.panel_content_rect = ui.max_rect();
+
                 if !transparent_bg {
                     let panel_painter = ui.painter();
+                    paint_area_shadow(
+                        panel_painter,
+                        panel_rect,
+                        panel_rounding,
+                        panel_elevation,
+                        background_alpha,
+                    );
                     paint_area_background(
                         &panel_painter,
                         panel_rect,
-                        CornerRadius::same(0),
-                        &panel_background,
+                        panel_rounding,
+                        &panel_background.with_alpha(background_alpha),
                     );
                 }
 
-                ui.spacing_mut().item_spacing.y = 12.0;
+                let idle_amount = self.idle.amount(Instant::now());
+                if idle_amount > 0.0 {
+                    if !transparent_bg {
+                        if let Some(texture) = self.idle_backdrop_texture(ctx) {
+                            let uv = idle::drift_uv(panel_rect, ui.input(|i| i.time));
+                            ui.painter().image(
+                                texture,
+                                panel_rect,
+                                uv,
+                                egui::Color32::WHITE.gamma_multiply(idle_amount * background_alpha),
+                            );
+                        }
+                    }
+                    ui.multiply_opacity(idle::foreground_opacity(idle_amount));
+                }
+
+                ui.spacing_mut().item_spacing.y = 12.0;
+
+                // Registered before the content so widgets keep their own clicks; this keeps
+                // settings reachable when a skin hides the overlay buttons.
+                let background = ui.interact(
+                    panel_rect,
+                    ui.id().with("panel-context-menu"),
+                    egui::Sense::click(),
+                );
+                background.context_menu(|menu| {
+                    let settings_label = if self.settings.is_open() {
+                        "Hide settings"
+                    } else {
+                        "Show settings"
+                    };
+                    if menu.button(settings_label).clicked() {
+                        self.settings.toggle();
+                        menu.close();
+                    }
+                    let pin_label = if self.always_on_top {
+                        "Unpin window"
+                    } else {
+                        "Pin window (stay on top)"
+                    };
+                    if menu.button(pin_label).clicked() {
+                        self.always_on_top = !self.always_on_top;
+                        menu.close();
+                    }
+                    menu.menu_button("Move to", |submenu| {
+                        for corner in SnapCorner::ALL {
+                            let selected = self.config.ui.snap_corner == Some(corner);
+                            if submenu.selectable_label(selected, corner.label()).clicked() {
+                                self.snap_to(corner);
+                                submenu.close();
+                            }
+                        }
+                    });
+                    let layout_options = self.skin_manager.layout_options();
+                    if layout_options.len() > 1 {
+                        let current = self.skin_manager.current_layout_id();
+                        let mut picked = None;
+                        menu.menu_button("Layout", |submenu| {
+                            for option in layout_options {
+                                let selected = !self.layout_auto && option.id == current;
+                                if submenu
+                                    .selectable_label(selected, &option.display_name)
+                                    .clicked()
+                                {
+                                    picked = Some(option.id.clone());
+                                    submenu.close();
+                                }
+                            }
+                        });
+                        if let Some(id) = picked {
+                            if let Err(err) = self.request_layout(&id, ctx) {
+                                self.skin_error = Some(err);
+                            }
+                        }
+                    }
+                });
 
-                self.render_skin_controls(ui, ctx);
+                self.render_skin_controls(ctx);
                 //ui.separator();
                 self.render_now_playing(ui);
             });
 
+        let pulse = self.border_pulse(ctx);
+        let outline = self
+            .window_border_color()
+            .filter(|_| self.window_decorations_hidden)
+            .or_else(|| pulse.and_then(|_| self.pulse_border_color()));
+        if let Some(color) = outline {
+            let color = pulse.map_or(color, |alpha| color.gamma_multiply(alpha));
+            // Foreground layer so panel content never covers the outline.
+            let root = &self.skin_manager.current_theme().components.root;
+            let radius = root.border_radius.clamp(0.0, u8::MAX as f32).round() as u8;
+            let layer = LayerId::new(egui::Order::Foreground, egui::Id::new("root-border"));
+            ctx.layer_painter(layer).rect_stroke(
+                root_rect,
+                CornerRadius::same(radius),
+                egui::Stroke::new(DYNAMIC_BORDER_WIDTH, color),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        toast::show(
+            ctx,
+            &mut self.toasts,
+            root_rect,
+            &self.skin_manager.current_theme().colors,
+            Instant::now(),
+        );
         self.handle_borderless_window_interactions(ctx, root_rect);
+        self.record_error_history();
 
         self.maybe_request_snapshot();
-        ctx.request_repaint_after(self.desired_repaint_interval());
+        let repaint_interval = self.desired_repaint_interval();
+        self.update_debug_metrics(ctx, repaint_interval);
+        debug_overlay::show(ctx, &self.debug, root_rect);
+        ctx.request_repaint_after(repaint_interval);
     }
 }
 
@@ -1027,29 +2206,147 @@ impl App {
         }
     }
 
+    /// The player is opening or switching media, so the timeline is not meaningful yet.
+    fn is_loading(&self) -> bool {
+        matches!(self.core.now.state, PlayState::Changing | PlayState::Opened)
+    }
+
+    /// Refreshes the debug overlay's per-frame readings.
+    fn update_debug_metrics(&mut self, ctx: &egui::Context, repaint_interval: Duration) {
+        self.debug.record_frame(Instant::now());
+        self.debug.repaint_interval = repaint_interval;
+        self.debug.snapshot_poll_interval = self.snapshot_poll_interval();
+        self.debug.snapshot_inflight = self.snapshot_inflight;
+        self.debug.thumbnail_inflight = self.thumbnail_inflight_request.is_some();
+        self.debug.texture_count = ctx.tex_manager().read().num_allocated();
+    }
+
     fn desired_repaint_interval(&self) -> Duration {
-        if self.animations_enabled && self.now.state == PlayState::Playing {
+        let interval = self.base_repaint_interval();
+        if self.gradient_animating() || self.border_pulsing() {
+            interval.min(GRADIENT_REPAINT_INTERVAL)
+        } else {
+            interval
+        }
+    }
+
+    fn base_repaint_interval(&self) -> Duration {
+        if self.idle.is_idle() {
+            return IDLE_REPAINT_INTERVAL;
+        }
+        let playing = self.display_state() == PlayState::Playing;
+        if self.animations_enabled && playing && !self.settings.is_open() {
             Duration::from_millis(16)
-        } else if matches!(self.now.state, PlayState::Changing | PlayState::Opened) {
+        } else if playing {
+            // Wake for the next whole second so the timestamp ticks evenly.
+            let base = Duration::from_millis(200);
+            self.core
+                .until_next_position_second(Instant::now())
+                .map_or(base, |until| until.min(base))
+        } else if self.is_loading() {
+            // Keeps the timeline's busy sweep moving.
             Duration::from_millis(120)
-        } else if self.now.state == PlayState::Paused {
+        } else if self.core.now.state == PlayState::Paused || self.settings.is_open() {
             Duration::from_millis(250)
         } else {
             Duration::from_millis(200)
         }
     }
 
+    fn update_idle_presentation(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        let paused = matches!(self.display_state(), PlayState::Paused | PlayState::Stopped);
+        if !paused {
+            self.paused_at = None;
+        } else if self.paused_at.is_none() {
+            self.paused_at = Some(now);
+        }
+        let activity = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::PointerMoved(_)
+                        | egui::Event::PointerButton { .. }
+                        | egui::Event::MouseWheel { .. }
+                        | egui::Event::Key { .. }
+                )
+            })
+        });
+        if activity {
+            self.last_activity = now;
+        }
+
+        if !(self.config.ui.idle_screensaver && self.animations_enabled) {
+            self.idle = IdlePresentation::Active;
+            return;
+        }
+        let quiet_for = self
+            .paused_at
+            .filter(|_| !self.settings.is_open())
+            .map(|paused_at| now.saturating_duration_since(paused_at.max(self.last_activity)));
+        let minutes = u64::from(self.config.ui.idle_screensaver_minutes.max(1));
+        let idle_after = Duration::from_secs(minutes * 60);
+        self.idle = self.idle.step(quiet_for, activity, idle_after, now);
+    }
+
+    /// Refreshes the spoken position every `announce_position_secs` while playing and
+    /// keeps it in a polite live region, so screen readers read it out unfocused. Waits
+    /// while the user is seeking, typing or moving the pointer.
+    fn update_position_announcement(&mut self, ctx: &egui::Context, now: Instant) {
+        if !self.config.ui.announce_position {
+            self.position_announcement = None;
+            return;
+        }
+        let secs = self.config.ui.announce_position_secs.max(5);
+        let interval = Duration::from_secs(u64::from(secs));
+        let due = self
+            .position_announcement
+            .as_ref()
+            .is_none_or(|(_, at)| now.saturating_duration_since(*at) >= interval);
+        let interacting = self.core.is_user_seeking
+            || now.saturating_duration_since(self.last_activity) < ANNOUNCE_QUIET_AFTER_INPUT
+            || ctx.memory(|memory| memory.focused().is_some());
+        if due && !interacting && self.display_state() == PlayState::Playing {
+            if let Some(timeline) = &self.core.timeline {
+                let duration = timeline.duration_secs();
+                let position = (timeline.position_secs - timeline.start_secs).clamp(0.0, duration);
+                self.position_announcement = Some((spoken_position(position, duration), now));
+            }
+        }
+
+        let Some((text, _)) = &self.position_announcement else {
+            return;
+        };
+        egui::Area::new(egui::Id::new("position-announcement"))
+            .interactable(false)
+            .fixed_pos(egui::Pos2::ZERO)
+            .show(ctx, |ui| {
+                let response = ui.allocate_response(egui::Vec2::ZERO, egui::Sense::hover());
+                response
+                    .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, text));
+                ctx.accesskit_node_builder(response.id, |node| {
+                    node.set_live(egui::accesskit::Live::Polite);
+                });
+            });
+    }
+
+    fn idle_backdrop_texture(&mut self, ctx: &egui::Context) -> Option<egui::TextureId> {
+        let hash = self.core.thumbnail_hash?;
+        if self.idle_backdrop.as_ref().map(|(cached, _)| *cached) != Some(hash) {
+            let image = self.thumbnail_base_image.as_ref()?;
+            let texture =
+                ctx.load_texture("idle.backdrop", idle::soften(image), TextureOptions::LINEAR);
+            self.idle_backdrop = Some((hash, texture));
+        }
+        self.idle_backdrop.as_ref().map(|(_, texture)| texture.id())
+    }
+
     fn snapshot_poll_interval(&self) -> Duration {
-        // Poll more aggressively while playback is active or changing, but
-        // back off in idle states to avoid unnecessary COM traffic.
-        match self.now.state {
-            PlayState::Playing => Duration::from_millis(800),
-            PlayState::Changing => Duration::from_millis(500),
-            PlayState::Opened => Duration::from_secs(2),
-            PlayState::Paused => Duration::from_secs(3),
-            PlayState::Stopped => Duration::from_secs(4),
-            PlayState::Closed | PlayState::Unknown => Duration::from_secs(5),
+        if self.demo.is_some() {
+            // The demo session is free to query and its script changes state on its own.
+            return Duration::from_millis(250);
         }
+        self.core.snapshot_poll_interval()
     }
 
     fn maybe_request_snapshot(&mut self) {
@@ -1070,7 +2367,16 @@ impl App {
             return;
         }
 
-        if now.duration_since(self.last_pull) < self.snapshot_poll_interval() {
+        if now.duration_since(self.core.last_pull) < self.snapshot_poll_interval() {
+            return;
+        }
+
+        self.send_snapshot_request(now);
+    }
+
+    fn send_snapshot_request(&mut self, now: Instant) {
+        if self.demo.is_some() {
+            self.refresh_now_playing();
             return;
         }
 
@@ -1087,6 +2393,20 @@ impl App {
         }
     }
 
+    /// Polls the session and refetches its artwork right away instead of waiting for
+    /// the poll interval. Ignored while a poll is already in flight.
+    fn force_refresh(&mut self) {
+        if self.snapshot_inflight {
+            return;
+        }
+        let now = Instant::now();
+        self.core.last_pull = now;
+        self.send_snapshot_request(now);
+        if self.thumbnail_inflight_request.is_none() {
+            self.request_thumbnail_for(self.core.now.clone());
+        }
+    }
+
     fn update_window_level(&mut self, ctx: &egui::Context) {
         let desired = if self.always_on_top {
             WindowLevel::AlwaysOnTop
@@ -1100,49 +2420,268 @@ impl App {
         }
     }
 
+    /// Moves the window to `corner` of its monitor and remembers the choice.
+    fn snap_to(&mut self, corner: SnapCorner) {
+        self.pending_snap = Some(corner);
+        if self.config.ui.snap_corner != Some(corner) {
+            self.config.ui.snap_corner = Some(corner);
+            self.save_config("snap corner");
+        }
+    }
+
+    /// Applies a corner picked from the menu, and re-applies the remembered one when the
+    /// work area of the window's monitor changes (resolution, scaling, or taskbar moves).
+    #[cfg(target_os = "windows")]
+    fn update_window_snap(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let Some(hwnd) = frame_hwnd(frame) else {
+            return;
+        };
+        let Some(placement) = snap::window_placement(hwnd) else {
+            return;
+        };
+        // Moving to another monitor isn't a display change; only the same monitor
+        // reporting a different work area is.
+        let display_changed = self.snap_work_area.is_some_and(|(monitor, area)| {
+            monitor == placement.monitor && area != placement.work_area
+        });
+        self.snap_work_area = Some((placement.monitor, placement.work_area));
+
+        let corner = match self.pending_snap.take() {
+            Some(corner) => corner,
+            None if display_changed && self.config.ui.resnap_on_display_change => {
+                match self.config.ui.snap_corner {
+                    Some(corner) => corner,
+                    None => return,
+                }
+            }
+            None => return,
+        };
+        let (x, y) = snap::snap_position(&placement, corner, self.config.ui.snap_margin);
+        let pixels_per_point = ctx.native_pixels_per_point().unwrap_or(1.0);
+        ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(
+            x as f32 / pixels_per_point,
+            y as f32 / pixels_per_point,
+        )));
+    }
+
+    /// Looks at the desktop when focus changes and otherwise every
+    /// [`VIRTUAL_DESKTOP_CHECK_INTERVAL`], since each look is a COM call.
+    #[cfg(target_os = "windows")]
+    fn update_virtual_desktop(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let state = &mut self.virtual_desktop_state;
+        if !self.config.ui.all_desktops || state.unavailable {
+            state.last_desktop = None;
+            state.checked_at = None;
+            return;
+        }
+        let now = Instant::now();
+        let focused = ctx.input(|i| i.viewport().focused);
+        if focused == state.focused
+            && state
+                .checked_at
+                .is_some_and(|checked| now.duration_since(checked) < VIRTUAL_DESKTOP_CHECK_INTERVAL)
+        {
+            return;
+        }
+        state.checked_at = Some(now);
+        state.focused = focused;
+        let Some(hwnd) = frame_hwnd(frame) else {
+            return;
+        };
+        if state.manager.is_none() {
+            match unsafe { CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_INPROC_SERVER) } {
+                Ok(manager) => state.manager = Some(manager),
+                Err(err) => {
+                    eprintln!("Virtual desktop manager unavailable: {err}");
+                    state.unavailable = true;
+                    return;
+                }
+            }
+        }
+        let Some(manager) = state.manager.as_ref() else {
+            return;
+        };
+
+        let on_current = unsafe { manager.IsWindowOnCurrentVirtualDesktop(hwnd) };
+        if matches!(on_current, Ok(visible) if visible.as_bool()) {
+            return;
+        }
+        // The foreground window lives on the desktop the user just switched to.
+        let foreground = unsafe { GetForegroundWindow() };
+        if foreground.is_invalid() || foreground == hwnd {
+            return;
+        }
+        let Ok(desktop) = (unsafe { manager.GetWindowDesktopId(foreground) }) else {
+            return;
+        };
+        if desktop == GUID::zeroed() || state.last_desktop == Some(desktop) {
+            return;
+        }
+        if let Err(err) = unsafe { manager.MoveWindowToDesktop(hwnd, &desktop) } {
+            eprintln!("Failed to move widget to the current desktop: {err}");
+        }
+        state.last_desktop = Some(desktop);
+    }
+
     fn update_window_decorations(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
-        let desired = !self.window_decorations_hidden;
-        if self.last_window_decorations != Some(desired) {
-            ctx.send_viewport_cmd(ViewportCommand::Decorations(desired));
-            self.last_window_decorations = Some(desired);
-            #[cfg(target_os = "windows")]
-            {
-                if desired {
-                    self.titlebar_state = WindowsTitlebarState::default();
+        for step in self
+            .decoration_sequence
+            .steps(self.window_decorations_hidden)
+        {
+            match step {
+                WindowStep::Decorations(decorated) => {
+                    ctx.send_viewport_cmd(ViewportCommand::Decorations(decorated));
+                    #[cfg(target_os = "windows")]
+                    if decorated {
+                        self.titlebar_state = WindowsTitlebarState::default();
+                    }
                 }
-                self.apply_windows_corner_preference(frame);
+                WindowStep::CornerPreference { rounded } => {
+                    self.apply_windows_corner_preference(frame, rounded)
+                }
+            }
+        }
+        #[cfg(target_os = "windows")]
+        if let Some(hwnd) = frame_hwnd(frame) {
+            let tracked = (hwnd.0 as isize, self.window_decorations_hidden);
+            if self.tracked_window != Some(tracked) {
+                self.tracked_window = Some(tracked);
+                borderless::track_window(hwnd, self.window_decorations_hidden);
+            }
+        }
+    }
+
+    /// Sends the title composed in `apply_snapshot`, at most once per second and only
+    /// while the title bar is visible.
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        if self.window_decorations_hidden {
+            return;
+        }
+        let Some(title) = self.pending_window_title.take() else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some(last) = self.last_window_title_update {
+            let ready_at = last + WINDOW_TITLE_UPDATE_INTERVAL;
+            if now < ready_at {
+                ctx.request_repaint_after(ready_at - now);
+                self.pending_window_title = Some(title);
+                return;
             }
         }
+        ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+        self.window_title = title;
+        self.last_window_title_update = Some(now);
+    }
+
+    /// Keeps the `auto` skin on the light or dark default theme to match the system.
+    fn update_system_theme(&mut self, ctx: &egui::Context) {
+        let light = if self.config.ui.follow_system_theme {
+            let now = Instant::now();
+            if self
+                .system_theme_checked
+                .is_some_and(|checked| now.duration_since(checked) < SYSTEM_THEME_POLL_INTERVAL)
+            {
+                return;
+            }
+            self.system_theme_checked = Some(now);
+            self.system_theme.prefers_light()
+        } else {
+            // Look again as soon as the option comes back on.
+            self.system_theme_checked = None;
+            false
+        };
+        if self.skin_manager.set_light_theme(light) {
+            self.skin_manager.apply_style(ctx);
+            self.refresh_after_skin_change(ctx);
+            ctx.request_repaint();
+        }
     }
 
     #[cfg(target_os = "windows")]
-    fn update_windows_titlebar(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
-        let Ok(window_handle) = frame.window_handle() else {
+    fn maybe_flash_taskbar(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let Some(deadline) = self.pending_track_flash else {
             return;
         };
+        if Instant::now() < deadline {
+            return;
+        }
+        self.pending_track_flash = None;
 
-        let hwnd = match window_handle.as_raw() {
-            RawWindowHandle::Win32(handle) => HWND(handle.hwnd.get() as *mut std::ffi::c_void),
-            _ => return,
+        let focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        if focused || !self.config.ui.flash_on_track_change {
+            return;
+        }
+        if let Some(hwnd) = frame_hwnd(frame) {
+            flash_taskbar(hwnd);
+        }
+    }
+
+    /// Installs the taskbar thumbnail buttons once the taskbar knows about the window,
+    /// keeps the play/pause image in sync, and forwards clicks to playback.
+    #[cfg(target_os = "windows")]
+    fn update_thumb_bar(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let playing = self.display_state() == PlayState::Playing;
+        if self.thumb_bar.is_none() && self.thumb_bar_attempts < THUMB_BAR_MAX_ATTEMPTS {
+            if let Some(hwnd) = frame_hwnd(frame) {
+                self.thumb_bar_attempts += 1;
+                match ThumbBar::install(hwnd, ctx.clone(), playing) {
+                    Ok(bar) => self.thumb_bar = Some(bar),
+                    Err(err) if self.thumb_bar_attempts == THUMB_BAR_MAX_ATTEMPTS => {
+                        eprintln!("Failed to add taskbar buttons: {err}");
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+
+        let Some(bar) = self.thumb_bar.as_mut() else {
+            return;
+        };
+        let actions = bar.take_actions(playing);
+        bar.set_playing(playing);
+        for action in actions {
+            self.handle_thumbnail_overlay_action(action);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn update_windows_titlebar(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        let Some(hwnd) = frame_hwnd(frame) else {
+            return;
         };
 
         let style = ctx.style();
         let visuals = &style.visuals;
-        let caption_color = visuals.window_fill;
+        let dynamic_caption = {
+            let theme = self.skin_manager.current_theme();
+            dynamic_caption_color(
+                theme.use_gradient,
+                theme.background_alpha > 0.0,
+                self.dynamic_root_gradient.as_ref(),
+                self.dynamic_panel_gradient.as_ref(),
+            )
+        };
+        let caption_color = dynamic_caption.unwrap_or(visuals.window_fill);
         let caption_ref = color32_to_colorref(caption_color);
         let window_stroke = visuals.window_stroke;
         let has_window_border = window_stroke.width > f32::EPSILON;
 
         let dark_caption = is_dark_color(caption_color);
-        let text_color = visuals.override_text_color.unwrap_or_else(|| {
-            if dark_caption {
-                egui::Color32::WHITE
-            } else {
-                egui::Color32::BLACK
-            }
-        });
+        let contrast_text = if dark_caption {
+            egui::Color32::WHITE
+        } else {
+            egui::Color32::BLACK
+        };
+        // The skin's text color was picked for its own fill, not for artwork colors.
+        let text_color = match dynamic_caption {
+            Some(_) => contrast_text,
+            None => visuals.override_text_color.unwrap_or(contrast_text),
+        };
         let text_ref = color32_to_colorref(text_color);
-        let border_ref = if has_window_border {
+        let border_ref = if let Some(color) = self.window_border_color() {
+            color32_to_colorref(color)
+        } else if has_window_border {
             color32_to_colorref(window_stroke.color)
         } else {
             DWM_COLOR_UNSET
@@ -1198,11 +2737,43 @@ impl App {
         }
     }
 
+    /// Applies the skin's Mica/Acrylic backdrop. Windows before 11 22H2 ignore the
+    /// attribute, leaving the plain transparent window.
+    #[cfg(target_os = "windows")]
+    fn update_windows_backdrop(&mut self, frame: &eframe::Frame) {
+        let backdrop = self
+            .skin_manager
+            .current_theme()
+            .effective_window_backdrop();
+        if self.titlebar_state.last_backdrop == Some(backdrop) {
+            return;
+        }
+        let Some(hwnd) = frame_hwnd(frame) else {
+            return;
+        };
+        let kind = match backdrop {
+            WindowBackdrop::None => DWMSBT_AUTO,
+            WindowBackdrop::Mica => DWMSBT_MAINWINDOW,
+            WindowBackdrop::Acrylic => DWMSBT_TRANSIENTWINDOW,
+        };
+        unsafe {
+            let _ = DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                &kind as *const _ as *const _,
+                std::mem::size_of_val(&kind) as u32,
+            );
+        }
+        self.titlebar_state.last_backdrop = Some(backdrop);
+    }
+
     fn handle_borderless_window_interactions(
         &mut self,
         ctx: &egui::Context,
         root_rect: egui::Rect,
     ) {
+        // Selectable metadata keeps its presses for highlighting text.
+        let over_selectable_text = self.skin_manager.take_selectable_text_hovered();
         if !self.window_decorations_hidden {
             return;
         }
@@ -1219,8 +2790,10 @@ impl App {
             return;
         };
 
-        let edge = 6.0;
-        let drag_height = 36.0;
+        // Both the pointer and `root_rect` are in logical points, so the thresholds stay
+        // the same physical size relative to the UI at any scale factor.
+        let edge = BORDERLESS_RESIZE_EDGE;
+        let drag_height = BORDERLESS_DRAG_HEIGHT;
 
         if !primary_down {
             // Allow resizing when hovering near the border even if the pointer is just outside.
@@ -1231,30 +2804,7 @@ impl App {
             return;
         }
 
-        let near_left = pos.x <= root_rect.left() + edge;
-        let near_right = pos.x >= root_rect.right() - edge;
-        let near_top = pos.y <= root_rect.top() + edge;
-        let near_bottom = pos.y >= root_rect.bottom() - edge;
-
-        let resize_dir = if near_left && near_top {
-            Some(ResizeDirection::NorthWest)
-        } else if near_right && near_top {
-            Some(ResizeDirection::NorthEast)
-        } else if near_left && near_bottom {
-            Some(ResizeDirection::SouthWest)
-        } else if near_right && near_bottom {
-            Some(ResizeDirection::SouthEast)
-        } else if near_left {
-            Some(ResizeDirection::West)
-        } else if near_right {
-            Some(ResizeDirection::East)
-        } else if near_top {
-            Some(ResizeDirection::North)
-        } else if near_bottom {
-            Some(ResizeDirection::South)
-        } else {
-            None
-        };
+        let resize_dir = borderless_resize_direction(root_rect, pos, edge);
 
         if let Some(direction) = resize_dir {
             let cursor = match direction {
@@ -1274,7 +2824,7 @@ impl App {
             return;
         }
 
-        // Drag zone across the top excluding the overlay controls.
+        // Drag zone across the top excluding the overlay controls, wherever the skin puts them.
         let icon_size = ctx
             .style()
             .text_styles
@@ -1282,20 +2832,21 @@ impl App {
             .map(|style| style.size)
             .unwrap_or(14.0);
         let icon_extent = icon_size + 8.0;
-        let icon_spacing = 6.0;
-        let icon_count = 1 + usize::from(self.show_pin_button);
-        let overlay_width = if icon_count > 0 {
-            icon_count as f32 * icon_extent + (icon_count.saturating_sub(1) as f32) * icon_spacing
+        let icon_count = if self.skin_manager.current_theme().overlay_controls.visible {
+            1 + usize::from(self.show_pin_button)
         } else {
-            0.0
+            0
         };
-        let overlay_rect = egui::Rect::from_min_size(
-            egui::pos2(root_rect.left() + 8.0, root_rect.top() + 8.0),
-            egui::vec2(overlay_width, icon_extent),
+        let overlay_rect = overlay_controls_rect(
+            root_rect,
+            self.overlay_controls_position(),
+            icon_count,
+            icon_extent,
         );
 
         let in_drag_strip = pos.y <= root_rect.top() + drag_height
-            && !overlay_rect.contains(pos)
+            && !overlay_rect.is_some_and(|rect| rect.contains(pos))
+            && !over_selectable_text
             && root_rect.contains(pos);
 
         if in_drag_strip {
@@ -1369,16 +2920,13 @@ impl App {
         let visuals = ui.visuals().clone();
         
         // Show play or pause based on current state
-        let play_pause_action = if self.now.state == PlayState::Playing {
+        let is_playing = self.display_state() == PlayState::Playing;
+        let play_pause_action = if is_playing {
             ThumbnailOverlayAction::Pause
         } else {
             ThumbnailOverlayAction::Play
         };
-        let play_pause_icon = if self.now.state == PlayState::Playing {
-            "⏸"
-        } else {
-            "⏵"
-        };
+        let play_pause_icon = if is_playing { "⏸" } else { "⏵" };
         
         let icons = [
             (ThumbnailOverlayAction::Previous, "⏮"),
@@ -1386,8 +2934,13 @@ impl App {
             (ThumbnailOverlayAction::Next, "⏭"),
         ];
 
+        // Circle-button skins get their own buttons over the art instead of bare glyphs on
+        // a dark pill.
+        let circle =
+            self.skin_manager.current_theme().components.button.shape == ButtonShape::Circle;
+
         let background_alpha = (alpha * 110.0).round() as u8;
-        if background_alpha > 0 {
+        if background_alpha > 0 && !circle {
             let bg_color = egui::Color32::from_rgba_unmultiplied(15, 23, 42, background_alpha);
             let rounding = CornerRadius::same((geometry.height / 2.0).round() as u8);
             ui.painter_at(geometry.rect)
@@ -1405,14 +2958,48 @@ impl App {
         overlay_ui.set_min_height(geometry.height);
 
         for (action, symbol) in icons {
-            let (icon_rect, icon_response) = overlay_ui.allocate_exact_size(
-                egui::vec2(geometry.icon_slot, geometry.height),
-                egui::Sense::click(),
-            );
+            let enabled = self.core.now.caps.allows(action);
+            if circle {
+                let slot = egui::vec2(geometry.icon_slot, geometry.height);
+                let diameter = slot.min_elem();
+                let response = overlay_ui
+                    .allocate_ui_with_layout(
+                        slot,
+                        egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                        |cell| {
+                            cell.multiply_opacity(alpha);
+                            self.skin_manager
+                                .skin_icon_button(cell, symbol, diameter, enabled)
+                        },
+                    )
+                    .inner
+                    .on_disabled_hover_text(ControlsCaps::disabled_hint(action));
+                if response.clicked() {
+                    self.handle_thumbnail_overlay_action(action);
+                }
+                continue;
+            }
+            let sense = if enabled {
+                egui::Sense::click()
+            } else {
+                egui::Sense::hover()
+            };
+            let (icon_rect, icon_response) = overlay_ui
+                .allocate_exact_size(egui::vec2(geometry.icon_slot, geometry.height), sense);
 
             let mut icon_color = visuals.widgets.inactive.fg_stroke.color;
 
-            if icon_response.hovered() {
+            if !enabled {
+                icon_color = self
+                    .skin_manager
+                    .current_theme()
+                    .components
+                    .button
+                    .disabled_foreground;
+                icon_response
+                    .clone()
+                    .on_hover_text(ControlsCaps::disabled_hint(action));
+            } else if icon_response.hovered() {
                 overlay_ui
                     .ctx()
                     .set_cursor_icon(egui::CursorIcon::PointingHand);
@@ -1437,30 +3024,90 @@ impl App {
     fn handle_thumbnail_overlay_action(&mut self, action: ThumbnailOverlayAction) {
         match action {
             ThumbnailOverlayAction::Previous => {
-                self.playback_command("Previous", |session| {
-                    block_on_operation(session.TrySkipPreviousAsync()?)
-                });
+                self.playback_command(PlaybackCommand::Previous);
             }
             ThumbnailOverlayAction::Next => {
-                self.playback_command("Next", |session| {
-                    block_on_operation(session.TrySkipNextAsync()?)
-                });
-            }
-            ThumbnailOverlayAction::Play => {
-                self.playback_command("Play", |session| {
-                    block_on_operation(session.TryPlayAsync()?)
-                });
-            }
-            ThumbnailOverlayAction::Pause => {
-                self.playback_command("Pause", |session| {
-                    block_on_operation(session.TryPauseAsync()?)
-                });
+                self.playback_command(PlaybackCommand::Next);
             }
+            ThumbnailOverlayAction::Play => self.set_playing(true),
+            ThumbnailOverlayAction::Pause => self.set_playing(false),
+        }
+    }
+
+    /// Sends play or pause and shows the expected state right away.
+    fn set_playing(&mut self, playing: bool) {
+        let expected = if playing {
+            PlayState::Playing
+        } else {
+            PlayState::Paused
+        };
+        self.core.expect_state(expected, Instant::now());
+        self.playback_command(if playing {
+            PlaybackCommand::Play
+        } else {
+            PlaybackCommand::Pause
+        });
+    }
+
+    /// Outline color for a `root.dynamic_border` skin, if it wants one right now.
+    fn window_border_color(&self) -> Option<egui::Color32> {
+        let theme = self.skin_manager.current_theme();
+        dynamic_border_color(
+            theme.components.root.dynamic_border,
+            theme.use_gradient,
+            self.dynamic_border_accent,
+            theme.components.root.border_color,
+        )
+    }
+
+    fn border_pulsing(&self) -> bool {
+        self.config.ui.border_pulse
+            && self.animations_enabled
+            && self.display_state() == PlayState::Playing
+            && self.pulse_border_color().is_some()
+    }
+
+    /// The skin's border color a `border_pulse` outline uses when the window has no
+    /// dynamic border of its own.
+    fn pulse_border_color(&self) -> Option<egui::Color32> {
+        let root = &self.skin_manager.current_theme().components.root;
+        self.window_border_color()
+            .or((root.border_color.a() > 0).then_some(root.border_color))
+    }
+
+    /// This frame's `border_pulse` opacity, or `None` while the border holds still.
+    fn border_pulse(&self, ctx: &egui::Context) -> Option<f32> {
+        self.border_pulsing()
+            .then(|| border_pulse_alpha(ctx.input(|i| i.time), self.config.ui.border_pulse_bpm))
+    }
+
+    /// Focuses the player that owns the current session, launching it if needed.
+    fn open_source_app(&mut self) {
+        if self.demo.is_some() || self.core.now.app_id.is_empty() {
+            return;
+        }
+        #[cfg(target_os = "windows")]
+        if let Err(err) = source_app::open(&self.core.now.app_id) {
+            self.show_toast(ToastLevel::Error, err);
         }
     }
 
+    /// Corner for the pin/gear buttons: the user's choice, else the skin's.
+    fn overlay_controls_position(&self) -> OverlayControlsPosition {
+        self.config
+            .ui
+            .overlay_corner
+            .unwrap_or(self.skin_manager.current_theme().overlay_controls.position)
+    }
+
+    /// Play state for rendering: the provisional guess while it is live, otherwise the
+    /// last confirmed snapshot.
+    fn display_state(&self) -> PlayState {
+        self.core.display_state(Instant::now())
+    }
+
     #[cfg(target_os = "windows")]
-    fn apply_windows_corner_preference(&self, frame: &eframe::Frame) {
+    fn apply_windows_corner_preference(&self, frame: &eframe::Frame, rounded: bool) {
         let Ok(window_handle) = frame.window_handle() else {
             return;
         };
@@ -1469,7 +3116,7 @@ impl App {
             _ => return,
         };
 
-        let preference = if self.window_decorations_hidden {
+        let preference = if rounded {
             DWMWCP_ROUND
         } else {
             DWMWCP_DEFAULT
@@ -1486,7 +3133,7 @@ impl App {
     }
 
     #[cfg(not(target_os = "windows"))]
-    fn apply_windows_corner_preference(&self, _frame: &eframe::Frame) {}
+    fn apply_windows_corner_preference(&self, _frame: &eframe::Frame, _rounded: bool) {}
 
     #[allow(dead_code)]
     fn is_mobile_stack_layout(&self) -> bool {
@@ -1532,24 +3179,30 @@ impl App {
     }
 
     fn maintain_skin_watcher(&mut self, ctx: &egui::Context) {
-        if self.watch_skins {
+        if self.skin_manager.take_lost_root() {
+            self.fall_back_to_builtin_skin(ctx);
+        }
+        if self.config.ui.watch_skins {
             if !self.skin_manager.hot_reload_enabled() {
-                match self.skin_manager.enable_hot_reload() {
-                    Ok(()) => {
-                        self.skin_error = None;
-                    }
-                    Err(err) => {
-                        self.skin_error = Some(err.to_string());
-                        self.watch_skins = false;
-                    }
+                if let Err(err) = self.skin_manager.enable_hot_reload() {
+                    self.stop_watching_skins();
+                    self.show_toast(ToastLevel::Warning, format!("Hot reload is off: {err}"));
                 }
             }
         } else if self.skin_manager.hot_reload_enabled() {
             self.skin_manager.disable_hot_reload();
         }
 
-        if self.skin_manager.hot_reload_enabled() && self.skin_manager.poll_hot_reload(ctx) {
+        if !self.skin_manager.hot_reload_enabled() {
+            return;
+        }
+        if let Some(delta) = self.skin_manager.poll_hot_reload(ctx) {
             self.skin_warnings = self.skin_manager.warnings().to_vec();
+            self.refresh_theme_diff();
+            self.rebuild_layout_rotation();
+            if !delta.new.is_empty() {
+                self.show_toast(ToastLevel::Warning, delta.summary());
+            }
         }
     }
 
@@ -1558,449 +3211,651 @@ impl App {
         let root = default_skin_root();
         let mut manager =
             SkinManager::discover(&root, selected.as_deref()).map_err(|err| format!("{err:?}"))?;
-        if self.watch_skins {
+        if self.config.ui.watch_skins {
             if let Err(err) = manager.enable_hot_reload() {
-                self.watch_skins = false;
+                self.stop_watching_skins();
                 return Err(err.to_string());
             }
         }
+        self.install_skin_manager(ctx, manager);
+        self.skins_root_lost = !self.skin_manager.has_folder_skins();
+        Ok(())
+    }
+
+    /// Swaps in `manager` with the user's skin preferences carried over.
+    fn install_skin_manager(&mut self, ctx: &egui::Context, manager: SkinManager) {
+        let manager = manager
+            .with_accent_override(self.skin_manager.accent_override())
+            .with_layout_preferences(self.config.ui.skin_layouts.clone())
+            .with_selectable_text(self.config.ui.selectable_text)
+            .with_accent_from_art(self.config.ui.accent_from_art)
+            .with_light_theme(self.skin_manager.light_theme());
         manager.apply_style(ctx);
         self.skin_warnings = manager.warnings().to_vec();
         self.skin_manager = manager;
         self.clear_dynamic_gradients();
-        Ok(())
+        self.refresh_theme_diff();
+        self.rebuild_layout_rotation();
     }
 
-    fn render_skin_controls(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        let skins: Vec<(String, String)> = self
-            .skin_manager
-            .skin_list()
-            .iter()
-            .map(|info| (info.id.clone(), info.display_name.clone()))
-            .collect();
-        let current_skin_display = self.skin_manager.current_skin_display_name().to_string();
-        let current_skin_id = self.skin_manager.current_skin_id().map(|id| id.to_string());
-        let layout_options = self.skin_manager.layout_options().to_vec();
-        let current_layout_display = self.skin_manager.current_layout_display_name().to_string();
-        let current_layout_id = self.skin_manager.current_layout_id().to_string();
-
-        let mut requested_skin: Option<String> = None;
-        let mut requested_layout: Option<String> = None;
-
-        const SETTINGS_PANEL_MAX_WIDTH: f32 = 360.0;
-        const SETTINGS_PANEL_ITEM_SPACING: f32 = 18.0;
-        const SETTINGS_CONTROL_SPACING: f32 = 12.0;
-        const SETTINGS_SECTION_GAP: f32 = 24.0;
-        const SETTINGS_HEADER_GAP: f32 = 8.0;
-        const SETTINGS_PANEL_PADDING_X: i8 = 20;
-        const SETTINGS_PANEL_PADDING_Y: i8 = 18;
-        const SETTINGS_PANEL_CORNER_RADIUS: u8 = 14;
-
-        fn settings_section<R>(
-            ui: &mut egui::Ui,
-            visuals: &egui::Visuals,
-            title: &str,
-            header_gap: f32,
-            control_spacing: f32,
-            content_width: f32,
-            build: impl FnOnce(&mut egui::Ui) -> R,
-        ) -> R {
-            ui.label(
-                egui::RichText::new(title)
-                    .size(13.0)
-                    .color(visuals.strong_text_color()),
-            );
-            ui.add_space(header_gap);
-            ui.vertical(|section| {
-                section.set_min_width(content_width);
-                section.set_max_width(content_width);
-                section.spacing_mut().item_spacing = egui::vec2(0.0, control_spacing);
-                build(section)
-            })
-            .inner
+    /// Turns hot reload off and forgets the preference, for when watching can't work.
+    fn stop_watching_skins(&mut self) {
+        if self.config.ui.watch_skins {
+            self.config.ui.watch_skins = false;
+            self.save_config("hot reload setting");
         }
+    }
 
-        fn settings_separator(ui: &mut egui::Ui, gap: f32) {
-            ui.add_space(gap * 0.5);
-            ui.separator();
-            ui.add_space(gap * 0.5);
-        }
-
-        egui::Area::new(egui::Id::new("overlay-controls"))
-            .anchor(egui::Align2::LEFT_TOP, egui::vec2(8.0, 8.0))
-            .order(egui::Order::Foreground)
-            .interactable(true)
-            .movable(false)
-            .show(ui.ctx(), |overlay| {
-                overlay.spacing_mut().item_spacing.x = 6.0;
-                overlay.horizontal(|row| {
-                    row.spacing_mut().item_spacing.x = 6.0;
-
-                    let overlay_icon_button =
-                        |ui: &mut egui::Ui, icon: &str, tooltip: &str, active: bool| {
-                            let icon_size = ui
-                                .style()
-                                .text_styles
-                                .get(&egui::TextStyle::Body)
-                                .map(|style| style.size)
-                                .unwrap_or(14.0);
-                            let desired_size = egui::Vec2::splat(icon_size + 8.0);
-                            let (rect, response) =
-                                ui.allocate_exact_size(desired_size, egui::Sense::click());
-
-                            if response.hovered() {
-                                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                            }
+    /// The skins folder is gone, so every asset load would fail: switch to the built-in
+    /// skin, stop watching, and leave a Retry in the Skins settings.
+    fn fall_back_to_builtin_skin(&mut self, ctx: &egui::Context) {
+        let manager = match SkinManager::fallback() {
+            Ok(manager) => manager,
+            Err(err) => {
+                self.skin_error = Some(format!("{err:?}"));
+                return;
+            }
+        };
+        let warning = format!(
+            "Skins folder {} is no longer available; using the built-in skin",
+            default_skin_root().display()
+        );
+        eprintln!("{warning}");
+        self.stop_watching_skins();
+        self.install_skin_manager(ctx, manager);
+        self.skin_warnings.insert(0, warning.clone());
+        self.skin_error = None;
+        self.skins_root_lost = true;
+        self.show_toast(ErrorKind::Skin.level(), warning);
+    }
 
-                            let visuals = ui.visuals();
-                            let fg_color = if active {
-                                visuals.widgets.active.fg_stroke.color
-                            } else {
-                                visuals.widgets.inactive.fg_stroke.color
-                            };
-
-                            ui.painter_at(rect).text(
-                                rect.center(),
-                                egui::Align2::CENTER_CENTER,
-                                icon,
-                                egui::FontId::proportional(icon_size),
-                                fg_color,
-                            );
+    /// Recomputes the skin-vs-default diff if it is on screen.
+    fn refresh_theme_diff(&mut self) {
+        self.settings
+            .refresh_theme_diff(self.skin_manager.current_theme());
+    }
 
-                            response.on_hover_text(tooltip)
-                        };
+    fn render_skin_controls(&mut self, ctx: &egui::Context) {
+        let mut model = SettingsModel {
+            overlay_position: self.overlay_controls_position(),
+            component_overrides: self.component_overrides(),
+            skin_manager: &self.skin_manager,
+            config: &mut self.config,
+            always_on_top: &mut self.always_on_top,
+            window_decorations_hidden: &mut self.window_decorations_hidden,
+            show_pin_button: &mut self.show_pin_button,
+            layout_auto: self.layout_auto,
+            skins_root_lost: self.skins_root_lost,
+        };
+        for request in self.settings.show(ctx, &mut model) {
+            self.apply_settings_request(request, ctx);
+        }
+        self.render_skin_import_prompt(ctx);
+    }
 
-                    if self.show_pin_button {
-                        let pin_icon = if self.always_on_top { "📌" } else { "📍" };
-                        let pin_tooltip = if self.always_on_top {
-                            "Unpin window"
-                        } else {
-                            "Pin window (stay on top)"
-                        };
-                        if overlay_icon_button(row, pin_icon, pin_tooltip, self.always_on_top)
-                            .clicked()
-                        {
-                            self.always_on_top = !self.always_on_top;
-                        }
-                    }
+    /// Carries out what the settings panel asked for this frame.
+    fn apply_settings_request(&mut self, request: SettingsRequest, ctx: &egui::Context) {
+        match request {
+            SettingsRequest::Skin(id) => {
+                if let Err(err) = self.apply_skin_selection(&id, ctx) {
+                    self.skin_error = Some(err);
+                }
+            }
+            SettingsRequest::Layout(layout_id) => {
+                if let Err(err) = self.request_layout(&layout_id, ctx) {
+                    self.skin_error = Some(err);
+                }
+            }
+            SettingsRequest::LayoutAuto => {
+                let _ = self.request_layout(AUTO_LAYOUT_ID, ctx);
+            }
+            SettingsRequest::LayoutDefault => {
+                self.layout_auto = false;
+                self.reset_layout_preference(ctx);
+            }
+            SettingsRequest::Accent(accent) => self.set_accent_override(accent, ctx),
+            SettingsRequest::UiScale(scale) => self.set_ui_scale(scale, ctx),
+            SettingsRequest::ComponentVisible {
+                component,
+                visible,
+                skin_visible,
+            } => self.set_component_visible(component, visible, skin_visible),
+            SettingsRequest::ResetComponents => self.reset_component_overrides(),
+            SettingsRequest::SelectableText(selectable) => {
+                self.config.ui.selectable_text = selectable;
+                self.skin_manager.set_selectable_text(selectable);
+                self.save_config("selectable text setting");
+            }
+            SettingsRequest::TrackFlash(enabled) => {
+                self.config.ui.flash_on_track_change = enabled;
+                if !enabled {
+                    self.pending_track_flash = None;
+                }
+                self.save_config("taskbar flash setting");
+            }
+            SettingsRequest::AccentFromArt(enabled) => self.set_accent_from_art(enabled),
+            SettingsRequest::LongFormMinutes(minutes) => {
+                self.config.ui.long_form_minutes = minutes;
+                self.core.long_form_threshold = long_form_threshold(&self.config);
+                self.save_config("long-form threshold setting");
+            }
+            SettingsRequest::NeverVinyl(never) => {
+                self.config.ui.force_disable_vinyl = never;
+                self.set_vinyl_enabled(ctx, !never);
+                self.force_thumbnail_refresh();
+                self.save_config("vinyl preference");
+            }
+            SettingsRequest::Vinyl(enabled) => self.set_vinyl_enabled(ctx, enabled),
+            SettingsRequest::VinylReverse(reverse) => {
+                self.config.ui.vinyl_thumbnail.reverse = reverse;
+                self.vinyl_spin.set_reverse(reverse);
+                self.save_config("vinyl spin direction");
+            }
+            SettingsRequest::ReloadSkins => self.skin_error = self.reload_skins(ctx).err(),
+            SettingsRequest::ImportSkin => self.begin_skin_import(ctx),
+            SettingsRequest::CopyDiagnostics => {
+                ctx.copy_text(compose_diagnostics(&self.diagnostics_info()));
+            }
+            SettingsRequest::Refresh => self.force_refresh(),
+            SettingsRequest::Screenshot => self.request_screenshot(ctx),
+            SettingsRequest::SaveConfig(what) => self.save_config(what),
+        }
+    }
 
-                    let gear_tooltip = if self.settings_panel_open {
-                        "Hide settings"
-                    } else {
-                        "Show settings"
-                    };
-                    if overlay_icon_button(row, "⚙", gear_tooltip, self.settings_panel_open)
-                        .clicked()
-                    {
-                        self.settings_panel_open = !self.settings_panel_open;
-                    }
+    fn request_screenshot(&mut self, ctx: &egui::Context) {
+        if self.screenshot.is_some() {
+            return;
+        }
+        self.settings.set_open(false);
+        self.screenshot = Some(ScreenshotStage::Scheduled);
+        ctx.request_repaint();
+    }
+
+    fn process_screenshot(&mut self, ctx: &egui::Context) {
+        match self.screenshot {
+            Some(ScreenshotStage::Scheduled) => {
+                ctx.send_viewport_cmd(ViewportCommand::Screenshot(egui::UserData::default()));
+                self.screenshot = Some(ScreenshotStage::Requested {
+                    deadline: Instant::now() + SCREENSHOT_TIMEOUT,
+                });
+            }
+            Some(ScreenshotStage::Requested { deadline }) => {
+                let image = ctx.input(|i| {
+                    i.events.iter().find_map(|event| match event {
+                        egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                        _ => None,
+                    })
                 });
+                let now = Instant::now();
+                if let Some(image) = image {
+                    self.screenshot = None;
+                    self.save_screenshot(&image);
+                } else if now >= deadline {
+                    self.screenshot = None;
+                    self.show_toast(ToastLevel::Error, "Screenshot timed out".to_string());
+                } else {
+                    ctx.request_repaint_after(deadline - now);
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn save_screenshot(&mut self, image: &ColorImage) {
+        let file_name = format!(
+            "now-playing-{}.png",
+            self.skin_manager.current_skin_id().unwrap_or("builtin")
+        );
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("PNG image", &["png"])
+            .set_file_name(file_name)
+            .save_file()
+        else {
+            return;
+        };
+
+        if let Err(err) = write_screenshot_png(image, &path) {
+            self.show_toast(ToastLevel::Error, err);
+        }
+    }
+
+    fn begin_skin_import(&mut self, ctx: &egui::Context) {
+        let Some(archive) = rfd::FileDialog::new()
+            .add_filter("Skin archive", &["zip"])
+            .pick_file()
+        else {
+            return;
+        };
+
+        let folder_name = match skin_import::inspect_archive(&archive) {
+            Ok(name) => name,
+            Err(err) => {
+                self.skin_error = Some(format!("{err:?}"));
+                return;
+            }
+        };
+
+        if default_skin_root().join(&folder_name).exists() {
+            self.pending_skin_import = Some(PendingSkinImport {
+                archive,
+                folder_name,
             });
+        } else {
+            self.install_skin_archive(&archive, &folder_name, false, ctx);
+        }
+    }
 
-        if self.settings_panel_open {
-            let visuals = ctx.style().visuals.clone();
-            
-            let mut window_frame = egui::Frame::window(&ctx.style());
-            window_frame.inner_margin = egui::Margin {
-                left: SETTINGS_PANEL_PADDING_X,
-                right: SETTINGS_PANEL_PADDING_X,
-                top: SETTINGS_PANEL_PADDING_Y,
-                bottom: SETTINGS_PANEL_PADDING_Y,
-            };
-            window_frame.corner_radius = CornerRadius::same(SETTINGS_PANEL_CORNER_RADIUS);
-            window_frame.shadow = egui::Shadow {
-                offset: [0, 6],
-                blur: 28,
-                spread: 4,
-                color: if visuals.dark_mode {
-                    egui::Color32::from_rgba_unmultiplied(0, 0, 0, 120)
-                } else {
-                    egui::Color32::from_rgba_unmultiplied(0, 0, 0, 72)
-                },
-            };
-            window_frame.fill = if visuals.dark_mode {
-                egui::Color32::from_rgba_unmultiplied(28, 28, 32, 240)
-            } else {
-                egui::Color32::from_rgba_unmultiplied(244, 246, 249, 245)
-            };
+    fn render_skin_import_prompt(&mut self, ctx: &egui::Context) {
+        let Some(pending) = self.pending_skin_import.as_ref() else {
+            return;
+        };
 
-            egui::Window::new("Settings")
-                .id(egui::Id::new("settings-window"))
-                .collapsible(false)
-                .resizable(false)
-                .title_bar(false)
-                .frame(window_frame)
-                .fixed_size([SETTINGS_PANEL_MAX_WIDTH, 0.0])
-                .show(ctx, |panel| {
-                    let content_width = SETTINGS_PANEL_MAX_WIDTH - 2.0 * f32::from(SETTINGS_PANEL_PADDING_X);
-                    panel.set_min_width(SETTINGS_PANEL_MAX_WIDTH);
-                    panel.set_max_width(SETTINGS_PANEL_MAX_WIDTH);
-                    panel.spacing_mut().item_spacing = egui::vec2(0.0, SETTINGS_PANEL_ITEM_SPACING);
-
-                        panel.horizontal(|row| {
-                            row.spacing_mut().item_spacing.x = 12.0;
-                            row.label(egui::RichText::new("Settings").heading());
-
-                            row.allocate_ui_with_layout(
-                                egui::vec2(row.available_width(), 0.0),
-                                egui::Layout::right_to_left(egui::Align::Center),
-                                |actions| {
-                                    let close_icon = egui::RichText::new("×").size(18.0);
-                                    let close = actions
-                                        .add(
-                                            egui::Label::new(close_icon)
-                                                .sense(egui::Sense::click()),
-                                        )
-                                        .on_hover_text("Close settings");
-                                    if close.hovered() {
-                                        actions.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
-                                    }
-                                    if close.clicked() {
-                                        self.settings_panel_open = false;
-                                    }
-                                },
-                            );
-                        });
+        let mut choice = None;
+        egui::Window::new("Skin already installed")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "A skin folder named '{}' already exists.",
+                    pending.folder_name
+                ));
+                ui.add_space(8.0);
+                ui.horizontal(|row| {
+                    if self.skin_manager.skin_button(row, "Overwrite").clicked() {
+                        choice = Some(SkinImportChoice::Overwrite);
+                    }
+                    if self.skin_manager.skin_button(row, "Keep both").clicked() {
+                        choice = Some(SkinImportChoice::Rename);
+                    }
+                    if self.skin_manager.skin_button(row, "Cancel").clicked() {
+                        choice = Some(SkinImportChoice::Cancel);
+                    }
+                });
+            });
 
-                        panel.separator();
-
-                        egui::ScrollArea::vertical()
-                            .max_height(420.0)
-                            .show(panel, |scroll| {
-                                scroll.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
-                                scroll.set_min_width(content_width);
-                                scroll.set_max_width(content_width);
-
-                                settings_section(
-                                    scroll,
-                                    &visuals,
-                                    "Window",
-                                    SETTINGS_HEADER_GAP,
-                                    SETTINGS_CONTROL_SPACING,
-                                    content_width,
-                                    |section| {
-                                        let toggle_label = if self.window_decorations_hidden {
-                                            "Show window title bar"
-                                        } else {
-                                            "Hide window title bar"
-                                        };
-                                        if self
-                                            .skin_manager
-                                            .skin_button(section, toggle_label)
-                                            .clicked()
-                                        {
-                                            self.window_decorations_hidden =
-                                                !self.window_decorations_hidden;
-                                        }
-
-                                        let pin_toggle_label = if self.always_on_top {
-                                            "Disable stay-on-top"
-                                        } else {
-                                            "Pin window (stay on top)"
-                                        };
-                                        if self
-                                            .skin_manager
-                                            .skin_button(section, pin_toggle_label)
-                                            .on_hover_text(
-                                                "Keep the widget above other application windows.",
-                                            )
-                                            .clicked()
-                                        {
-                                            self.always_on_top = !self.always_on_top;
-                                        }
-
-                                        let mut show_pin_button = self.show_pin_button;
-                                        if section
-                                            .checkbox(
-                                                &mut show_pin_button,
-                                                "Show pin button in overlay",
-                                            )
-                                            .on_hover_text(
-                                                "Disable to hide the pin toggle from the top overlay.",
-                                            )
-                                            .changed()
-                                        {
-                                            self.show_pin_button = show_pin_button;
-                                        }
-
-                                        section.label(
-                                            if self.window_decorations_hidden {
-                                                "Title bar hidden. Use the app body to drag the window."
-                                            } else {
-                                                "Hiding the title bar removes the OS chrome."
-                                            },
-                                        );
-                                    },
-                                );
-
-                                settings_separator(scroll, SETTINGS_SECTION_GAP);
-
-                                settings_section(
-                                    scroll,
-                                    &visuals,
-                                    "Appearance",
-                                    SETTINGS_HEADER_GAP,
-                                    SETTINGS_CONTROL_SPACING,
-                                    content_width,
-                                    |section| {
-                                        let combo_width = content_width;
-                                        egui::ComboBox::from_id_salt("skin-select")
-                                            .width(combo_width)
-                                            .selected_text(current_skin_display.clone())
-                                            .show_ui(section, |combo| {
-                                                if skins.is_empty() {
-                                                    combo.label("Embedded default");
-                                                } else {
-                                                    for (id, name) in &skins {
-                                                        let selected = current_skin_id
-                                                            .as_deref()
-                                                            .map(|current| current == id.as_str())
-                                                            .unwrap_or(false);
-                                                        if combo
-                                                            .selectable_label(selected, name)
-                                                            .clicked()
-                                                            && !selected
-                                                        {
-                                                            requested_skin = Some(id.clone());
-                                                        }
-                                                    }
-                                                }
-                                            });
-
-                                        if layout_options.len() > 1 {
-                                            egui::ComboBox::from_id_salt("layout-select")
-                                                .width(combo_width)
-                                                .selected_text(current_layout_display.clone())
-                                                .show_ui(section, |combo| {
-                                                    for option in &layout_options {
-                                                        let selected = option.id == current_layout_id;
-                                                        if combo
-                                                            .selectable_label(
-                                                                selected,
-                                                                &option.display_name,
-                                                            )
-                                                            .clicked()
-                                                            && !selected
-                                                        {
-                                                            requested_layout = Some(option.id.clone());
-                                                        }
-                                                    }
-                                                });
-                                        } else if let Some(option) = layout_options.first() {
-                                            section.label(
-                                                format!("Layout: {}", option.display_name),
-                                            );
-                                        }
-                                    },
-                                );
-
-                                settings_separator(scroll, SETTINGS_SECTION_GAP);
-
-                                settings_section(
-                                    scroll,
-                                    &visuals,
-                                    "Artwork",
-                                    SETTINGS_HEADER_GAP,
-                                    SETTINGS_CONTROL_SPACING,
-                                    content_width,
-                                    |section| {
-                                        let theme_disables_vinyl = self
-                                            .skin_manager
-                                            .current_theme()
-                                            .disable_vinyl_thumbnail;
-                                        if theme_disables_vinyl {
-                                            section.label(
-                                                "This skin always shows the original album art.",
-                                            );
-                                        } else {
-                                            let mut vinyl_enabled =
-                                                self.config.ui.vinyl_thumbnail.enabled;
-                                            if section
-                                                .checkbox(&mut vinyl_enabled, "Show spinning vinyl disc")
-                                                .on_hover_text(
-                                                    "Toggle between the animated vinyl and the original thumbnail.",
-                                                )
-                                                .changed()
-                                            {
-                                                self.set_vinyl_enabled(ctx, vinyl_enabled);
-                                            }
-                                            section.label(
-                                                "Tip: You can also click the artwork to switch views.",
-                                            );
-                                        }
-                                    },
-                                );
-
-                                settings_separator(scroll, SETTINGS_SECTION_GAP);
-
-                                settings_section(
-                                    scroll,
-                                    &visuals,
-                                    "Skins",
-                                    SETTINGS_HEADER_GAP,
-                                    SETTINGS_CONTROL_SPACING,
-                                    content_width,
-                                    |section| {
-                                        section.horizontal_wrapped(|row| {
-                                            row.spacing_mut().item_spacing =
-                                                egui::vec2(12.0, SETTINGS_CONTROL_SPACING);
-                                            let toggle_label = if self.watch_skins {
-                                                "Disable hot reload"
-                                            } else {
-                                                "Enable hot reload"
-                                            };
-                                            if self.skin_manager.skin_button(row, toggle_label).clicked() {
-                                                self.watch_skins = !self.watch_skins;
-                                            }
-
-                                            if self
-                                                .skin_manager
-                                                .skin_button(row, "Reload skins")
-                                                .on_hover_text("Re-scan the skin directory")
-                                                .clicked()
-                                            {
-                                                match self.reload_skins(ctx) {
-                                                    Ok(()) => self.skin_error = None,
-                                                    Err(err) => self.skin_error = Some(err),
-                                                }
-                                            }
-                                        });
-                                    },
-                                );
-                            });
+        let Some(choice) = choice else {
+            return;
+        };
+        let Some(pending) = self.pending_skin_import.take() else {
+            return;
+        };
+        match choice {
+            SkinImportChoice::Overwrite => {
+                self.install_skin_archive(&pending.archive, &pending.folder_name, true, ctx);
+            }
+            SkinImportChoice::Rename => {
+                let folder_name =
+                    skin_import::unique_folder_name(&default_skin_root(), &pending.folder_name);
+                self.install_skin_archive(&pending.archive, &folder_name, false, ctx);
+            }
+            SkinImportChoice::Cancel => {}
+        }
+    }
+
+    fn install_skin_archive(
+        &mut self,
+        archive: &Path,
+        folder_name: &str,
+        overwrite: bool,
+        ctx: &egui::Context,
+    ) {
+        let result =
+            skin_import::install_skin(archive, &default_skin_root(), folder_name, overwrite)
+                .map_err(|err| format!("{err:?}"))
+                .and_then(|installed| {
+                    self.reload_skins(ctx)?;
+                    self.apply_skin_selection(&installed, ctx)
                 });
+        match result {
+            Ok(()) => self.skin_error = None,
+            Err(err) => self.skin_error = Some(err),
         }
+    }
 
-        if let Some(id) = requested_skin {
-            match self.skin_manager.set_skin(&id, ctx) {
-                Ok(()) => {
-                    self.skin_warnings = self.skin_manager.warnings().to_vec();
-                    self.skin_error = None;
-                    self.clear_dynamic_gradients();
-                    let skin_disables_vinyl =
-                        self.skin_manager.current_theme().disable_vinyl_thumbnail;
-                    let vinyl_should_be_enabled = !skin_disables_vinyl;
-                    if self.config.ui.vinyl_thumbnail.enabled != vinyl_should_be_enabled {
-                        self.set_vinyl_enabled(ctx, vinyl_should_be_enabled);
-                        self.force_thumbnail_refresh();
-                    }
-                }
-                Err(err) => {
-                    self.skin_error = Some(err.to_string());
+    /// Applies the accent in memory only; the settings panel saves it once the picker
+    /// is let go.
+    fn set_accent_override(&mut self, accent: Option<egui::Color32>, ctx: &egui::Context) {
+        self.skin_manager.set_accent_override(accent);
+        if let Some(image) = self.thumbnail_base_image.clone() {
+            self.update_dynamic_gradients(&image);
+        }
+        self.config.ui.accent_override = accent.map(|color| [color.r(), color.g(), color.b()]);
+        ctx.request_repaint();
+    }
+
+    fn set_accent_from_art(&mut self, enabled: bool) {
+        self.skin_manager.set_accent_from_art(enabled);
+        if let Some(image) = self.thumbnail_base_image.clone() {
+            self.update_dynamic_gradients(&image);
+        }
+        self.config.ui.accent_from_art = enabled;
+        self.save_config("artwork accent setting");
+    }
+
+    fn apply_skin_selection(&mut self, id: &str, ctx: &egui::Context) -> Result<(), String> {
+        let previous_size = self.skin_manager.current_theme().window_size;
+        self.skin_manager
+            .set_skin(id, ctx)
+            .map_err(|err| err.to_string())?;
+        self.pending_window_size.get_or_insert(previous_size);
+        self.skin_error = None;
+        self.refresh_after_skin_change(ctx);
+        Ok(())
+    }
+
+    /// Rebuilds what was derived from the previous theme. Shared by explicit skin picks
+    /// and the `auto` skin's light/dark swap.
+    fn refresh_after_skin_change(&mut self, ctx: &egui::Context) {
+        self.skin_warnings = self.skin_manager.warnings().to_vec();
+        // Recomputed for the new theme from the artwork on screen, not left blank until
+        // the next track.
+        match self.thumbnail_base_image.clone() {
+            Some(image) => self.update_dynamic_gradients(&image),
+            None => self.clear_dynamic_gradients(),
+        }
+        self.refresh_theme_diff();
+        self.rebuild_layout_rotation();
+        let vinyl_should_be_enabled = self.vinyl_allowed();
+        if self.config.ui.vinyl_thumbnail.enabled != vinyl_should_be_enabled {
+            self.set_vinyl_enabled(ctx, vinyl_should_be_enabled);
+            self.force_thumbnail_refresh();
+        }
+    }
+
+    /// Sends the skin's `[window]` sizes once a startup or explicit skin choice has queued
+    /// them. A hot reload doesn't queue anything, so it never undoes the user's resizing.
+    fn apply_pending_window_size(&mut self, ctx: &egui::Context) {
+        let Some(previous) = self.pending_window_size.take() else {
+            return;
+        };
+        let current = ctx.input(|i| i.screen_rect().size());
+        let sizing = self.skin_manager.current_theme().window_size;
+        for command in sizing.viewport_commands(&previous, current) {
+            ctx.send_viewport_cmd(command);
+        }
+    }
+
+    /// Every layout change made on the user's behalf (settings, context menu, remote
+    /// endpoint, `--layout`) goes through here: switches layout, remembers the pick for
+    /// the current skin, and suspends width-based switching. An unknown id changes
+    /// nothing.
+    fn request_layout(&mut self, layout_id: &str, ctx: &egui::Context) -> Result<(), String> {
+        let available = self
+            .skin_manager
+            .layout_options()
+            .iter()
+            .map(|variant| variant.id.as_str());
+        let responsive = self.skin_manager.has_responsive_layouts();
+        match parse_layout_request(layout_id, available, responsive)? {
+            LayoutRequest::Auto => self.layout_auto = true,
+            LayoutRequest::Variant(id) => {
+                if let Some(rotation) = self.layout_rotation.as_mut() {
+                    rotation.manual_pick(&id, Instant::now());
                 }
+                self.skin_manager.set_layout(&id, ctx);
+                self.skin_manager.set_layout_preference(Some(id));
+                self.sync_layout_preferences();
+                self.layout_auto = false;
             }
         }
+        Ok(())
+    }
 
-        if let Some(layout_id) = requested_layout {
-            self.skin_manager.set_layout(&layout_id, ctx);
+    /// Sets up `[ui] layout_rotation` for the current skin, warning about variant ids it
+    /// lacks. Fewer than two usable variants leaves rotation off.
+    fn rebuild_layout_rotation(&mut self) {
+        self.layout_rotation = None;
+        let Some(config) = self.config.ui.layout_rotation.clone() else {
+            return;
+        };
+        let mut variants = Vec::new();
+        for id in config.variants {
+            let known = self
+                .skin_manager
+                .layout_options()
+                .iter()
+                .any(|variant| variant.id == id);
+            if !known {
+                self.warn_once(format!(
+                    "layout_rotation: this skin has no layout '{id}'; skipping it"
+                ));
+            } else if !variants.contains(&id) {
+                variants.push(id);
+            }
+        }
+        if variants.len() < 2 {
+            self.warn_once("layout_rotation: needs at least two layouts to rotate".to_string());
+            return;
+        }
+        self.layout_rotation = Some(LayoutRotation::new(
+            variants,
+            config.interval,
+            self.skin_manager.current_layout_id(),
+            Instant::now(),
+        ));
+    }
+
+    /// Moves to the next rotation layout when one is due, holding off while settings are
+    /// open or a seek is in flight.
+    fn advance_layout_rotation(&mut self, ctx: &egui::Context, now: Instant) {
+        let Some(rotation) = self.layout_rotation.as_mut() else {
+            return;
+        };
+        if self.settings.is_open() {
+            rotation.pause(now);
+            return;
+        }
+        let seeking = self.core.clock.pending_seek_target.is_some();
+        if let Some(id) = rotation.poll(now, seeking).map(str::to_owned) {
+            self.skin_manager.set_layout(&id, ctx);
+        }
+    }
+
+    fn reset_layout_preference(&mut self, ctx: &egui::Context) {
+        self.skin_manager.set_layout_preference(None);
+        if let Some(default_id) = self.skin_manager.default_layout_id().map(str::to_owned) {
+            self.skin_manager.set_layout(&default_id, ctx);
+        }
+        self.sync_layout_preferences();
+    }
+
+    fn sync_layout_preferences(&mut self) {
+        let preferences = self.skin_manager.layout_preferences();
+        if &self.config.ui.skin_layouts != preferences {
+            self.config.ui.skin_layouts = preferences.clone();
+            self.save_config("layout preference");
+        }
+    }
+
+    fn available_skins(&self) -> Vec<RemoteEntry> {
+        self.skin_manager
+            .skin_list()
+            .iter()
+            .map(|skin| RemoteEntry {
+                id: skin.id.clone(),
+                name: skin.display_name.clone(),
+            })
+            .collect()
+    }
+
+    fn available_layouts(&self) -> Vec<RemoteEntry> {
+        self.skin_manager
+            .layout_options()
+            .iter()
+            .map(|variant| RemoteEntry {
+                id: variant.id.clone(),
+                name: variant.display_name.clone(),
+            })
+            .collect()
+    }
+
+    fn process_remote_commands(&mut self, ctx: &egui::Context) {
+        let Some(remote) = self.remote.as_ref() else {
+            return;
+        };
+
+        for request in remote.poll() {
+            let response = match request.command.clone() {
+                RemoteCommand::ListSkins => RemoteResponse::Skins {
+                    current: self.skin_manager.current_skin_id().map(str::to_string),
+                    available: self.available_skins(),
+                },
+                RemoteCommand::ListLayouts => RemoteResponse::Layouts {
+                    current: self.skin_manager.current_layout_id().to_string(),
+                    available: self.available_layouts(),
+                },
+                RemoteCommand::SetSkin(id) => match self.apply_skin_selection(&id, ctx) {
+                    Ok(()) => RemoteResponse::Applied { kind: "skin", id },
+                    Err(message) => RemoteResponse::Rejected {
+                        message,
+                        available: self.available_skins(),
+                    },
+                },
+                RemoteCommand::SetLayout(id) => match self.request_layout(&id, ctx) {
+                    Ok(()) => RemoteResponse::Applied { kind: "layout", id },
+                    Err(message) => RemoteResponse::Rejected {
+                        message,
+                        available: self.available_layouts(),
+                    },
+                },
+            };
+            request.respond(response);
         }
     }
 
     fn render_now_playing(&mut self, ui: &mut egui::Ui) {
-        let layout_root = self.skin_manager.current_layout_variant().root.clone();
+        // Rotation picks the layout while it runs; width-based switching would undo it.
+        if self.layout_auto && self.layout_rotation.is_none() {
+            let id = self
+                .skin_manager
+                .layout_for_width(self.viewport_size.x)
+                .to_string();
+            self.skin_manager.set_layout(&id, ui.ctx());
+        }
+        let mut layout_root = self.skin_manager.current_layout_variant().root.clone();
+        apply_component_overrides(&mut layout_root, &self.component_overrides());
         self.render_layout_node(ui, &layout_root);
     }
 
+    fn hidden_components_key(&self) -> String {
+        UiConfig::hidden_components_key(
+            self.skin_manager.current_skin_id().unwrap_or("builtin"),
+            self.skin_manager.current_layout_id(),
+        )
+    }
+
+    /// Visibility the user picked for components of the current skin and layout.
+    fn component_overrides(&self) -> HashMap<LayoutComponent, bool> {
+        let key = self.hidden_components_key();
+        let ui = &self.config.ui;
+        let mut overrides = HashMap::new();
+        for (map, visible) in [(&ui.hidden_components, false), (&ui.shown_components, true)] {
+            for id in map.get(&key).into_iter().flatten() {
+                if let Some(component) = LayoutComponent::from_id(id) {
+                    overrides.insert(component, visible);
+                }
+            }
+        }
+        overrides
+    }
+
+    /// Records `visible` for `component`, dropping the override when it matches what the
+    /// skin does anyway.
+    fn set_component_visible(
+        &mut self,
+        component: LayoutComponent,
+        visible: bool,
+        skin_visible: bool,
+    ) {
+        let key = self.hidden_components_key();
+        let ui = &mut self.config.ui;
+        for map in [&mut ui.hidden_components, &mut ui.shown_components] {
+            if let Some(ids) = map.get_mut(&key) {
+                ids.retain(|id| LayoutComponent::from_id(id) != Some(component));
+                if ids.is_empty() {
+                    map.remove(&key);
+                }
+            }
+        }
+        if visible != skin_visible {
+            let map = if visible {
+                &mut ui.shown_components
+            } else {
+                &mut ui.hidden_components
+            };
+            map.entry(key).or_default().push(component.id().to_string());
+        }
+        self.save_config("hidden elements");
+    }
+
+    fn reset_component_overrides(&mut self) {
+        let key = self.hidden_components_key();
+        let hidden = self.config.ui.hidden_components.remove(&key).is_some();
+        let shown = self.config.ui.shown_components.remove(&key).is_some();
+        if hidden || shown {
+            self.save_config("hidden elements");
+        }
+    }
+
+    /// Applies the configured UI scale. egui's zoom factor multiplies the native
+    /// (OS-reported) pixels-per-point, so "auto" is simply a zoom of 1.0.
+    fn apply_ui_scale(&self, ctx: &egui::Context) {
+        let zoom = self.config.ui.scale().unwrap_or(1.0);
+        if (ctx.zoom_factor() - zoom).abs() > f32::EPSILON {
+            ctx.set_zoom_factor(zoom);
+        }
+    }
+
+    fn set_ui_scale(&mut self, scale: Option<f32>, ctx: &egui::Context) {
+        self.config.ui.scale = scale;
+        self.apply_ui_scale(ctx);
+        self.save_config("UI scale");
+    }
+
+    /// Shows a transient failure as a toast; it also goes into the diagnostics history.
+    fn show_toast(&mut self, level: ToastLevel, message: String) {
+        self.error_history.observe("toast", Some(&message));
+        self.toasts.push(level, message, Instant::now());
+    }
+
+    fn record_error_history(&mut self) {
+        for (kind, err) in [
+            (ErrorKind::Playback, &self.core.err),
+            (ErrorKind::Thumbnail, &self.thumbnail_err),
+            (ErrorKind::Skin, &self.skin_error),
+        ] {
+            self.error_history.observe(kind.source(), err.as_deref());
+        }
+    }
+
+    fn diagnostics_info(&self) -> DiagnosticsInfo {
+        DiagnosticsInfo {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: os_description(),
+            skin_id: self
+                .skin_manager
+                .current_skin_id()
+                .unwrap_or("builtin")
+                .to_string(),
+            layout_id: self.skin_manager.current_layout_id().to_string(),
+            skin_warnings: self.skin_warnings.clone(),
+            recent_errors: self.error_history.recent(5).map(str::to_string).collect(),
+            play_state: playstate_to_str(self.core.now.state).to_string(),
+            has_timeline: self.core.timeline.is_some(),
+            has_thumbnail: self.core.thumbnail_hash.is_some(),
+            animations_enabled: self.animations_enabled,
+            vinyl_enabled: self.config.ui.vinyl_thumbnail.enabled,
+            config: self.config.flattened(),
+        }
+    }
+
+    fn save_config(&self, what: &str) {
+        if let Err(err) = self.config.save() {
+            eprintln!("Failed to save {what}: {err:?}");
+        }
+    }
+
     fn render_layout_node(&mut self, ui: &mut egui::Ui, node: &LayoutNode) {
         match node {
             LayoutNode::Row(container) => self.render_container(ui, container, true),
@@ -2034,11 +3889,11 @@ impl App {
         if container.fill {
             let width = ui.available_width();
             ui.allocate_ui_with_layout(egui::Vec2::new(width, 0.0), layout, |child_ui| {
-                self.render_container_children(child_ui, &container.children, container.spacing);
+                self.render_container_children(child_ui, container, is_row);
             });
         } else {
             ui.with_layout(layout, |child_ui| {
-                self.render_container_children(child_ui, &container.children, container.spacing);
+                self.render_container_children(child_ui, container, is_row);
             });
         }
     }
@@ -2046,29 +3901,147 @@ impl App {
     fn render_container_children(
         &mut self,
         ui: &mut egui::Ui,
-        children: &[LayoutNode],
+        container: &ContainerNode,
+        is_row: bool,
+    ) {
+        let children: Vec<&LayoutNode> = container
+            .children
+            .iter()
+            .filter(
+                |child| !matches!(child, LayoutNode::Component(component) if !component.visible),
+            )
+            .collect();
+        if container.stretch {
+            self.render_stretched_children(ui, &children, container.spacing, is_row);
+            return;
+        }
+        for (index, child) in children.into_iter().enumerate() {
+            if index > 0 {
+                ui.add_space(container.spacing);
+            }
+            self.render_layout_node(ui, child);
+        }
+    }
+
+    /// Lays out a `stretch` container in two phases: the children's natural sizes are
+    /// measured as they render, then each child is placed in a slot spanning the largest
+    /// cross-axis extent. A measurement only reaches the screen a frame later, so a
+    /// change in the sizes discards the frame to avoid a visible jump.
+    fn render_stretched_children(
+        &mut self,
+        ui: &mut egui::Ui,
+        children: &[&LayoutNode],
         spacing: f32,
+        is_row: bool,
     ) {
-        let mut first = true;
-        for child in children {
-            if !first {
+        let id = ui.id().with("stretch_sizes");
+        let measured: Vec<egui::Vec2> = ui
+            .data(|data| data.get_temp(id))
+            .filter(|sizes: &Vec<egui::Vec2>| sizes.len() == children.len())
+            .unwrap_or_else(|| vec![egui::Vec2::ZERO; children.len()]);
+        let slots = place_children(&measured, spacing, is_row, true);
+
+        let layout = *ui.layout();
+        let mut natural = Vec::with_capacity(children.len());
+        for (index, (child, slot)) in children.iter().zip(&slots).enumerate() {
+            if index > 0 {
                 ui.add_space(spacing);
             }
-            first = false;
-            self.render_layout_node(ui, child);
+            let size = ui
+                .allocate_ui_with_layout(ui.available_size(), layout, |slot_ui| {
+                    self.render_layout_node(slot_ui, child);
+                    let size = slot_ui.min_size();
+                    if is_row {
+                        slot_ui.set_min_height(slot.height());
+                    } else {
+                        slot_ui.set_min_width(slot.width());
+                    }
+                    size
+                })
+                .inner;
+            natural.push(size);
+        }
+
+        let settled = natural
+            .iter()
+            .zip(&measured)
+            .all(|(now, before)| (*now - *before).length() < 0.5);
+        if !settled {
+            ui.data_mut(|data| data.insert_temp(id, natural));
+            ui.ctx()
+                .request_discard("stretch container measured new sizes");
         }
     }
 
     fn render_component_node(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
-        if !component.visible {
+        if !component.visible
+            || component
+                .require
+                .is_some_and(|requirement| !self.has_requirement(requirement))
+        {
             return;
         }
 
-        match component.component {
-            LayoutComponent::Thumbnail => self.paint_thumbnail(ui),
-            LayoutComponent::Title => {
-                self.skin_manager.skin_text(ui, &self.now.title, true);
+        if component.width.is_none() && component.height.is_none() {
+            self.render_component_content(ui, component);
+            return;
+        }
+
+        let available = ui.available_size();
+        let exceeds = component
+            .width
+            .is_some_and(|width| width > available.x + 0.5)
+            || component
+                .height
+                .is_some_and(|height| height > available.y + 0.5);
+        if exceeds {
+            self.warn_once(format!(
+                "Component '{}' requests more space than is available; clamping",
+                component.component.id()
+            ));
+        }
+
+        let width = component.width.map(|width| width.min(available.x));
+        let height = component.height.map(|height| height.min(available.y));
+        let max_size = egui::vec2(width.unwrap_or(available.x), height.unwrap_or(available.y));
+        ui.allocate_ui(max_size, |inner| {
+            if let Some(width) = width {
+                inner.set_min_width(width);
+                inner.set_max_width(width);
             }
+            if let Some(height) = height {
+                inner.set_min_height(height);
+                inner.set_max_height(height);
+            }
+            self.render_component_content(inner, component);
+        });
+    }
+
+    /// Whether the datum a component's `require` param names is present right now.
+    fn has_requirement(&self, requirement: Requirement) -> bool {
+        match requirement {
+            Requirement::Title => !self.core.now.title.is_empty(),
+            Requirement::Artist => !self.core.now.artist.is_empty(),
+            Requirement::Album => !self.core.now.album.is_empty(),
+            Requirement::Artwork => {
+                self.thumbnail_texture.is_some() || self.thumbnail_base_texture.is_some()
+            }
+        }
+    }
+
+    /// Adds a skin warning unless the same message is already listed; used for issues
+    /// only detectable while rendering, which would otherwise repeat every frame.
+    fn warn_once(&mut self, message: String) {
+        if !self.skin_warnings.contains(&message) {
+            self.skin_warnings.push(message);
+        }
+    }
+
+    fn render_component_content(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
+        match component.component {
+            LayoutComponent::Thumbnail => self.paint_thumbnail(ui, component),
+            LayoutComponent::Title => self.render_title(ui, component),
+            LayoutComponent::CustomText => self.render_custom_text(ui, component),
             LayoutComponent::MetadataGroup => self.render_metadata_group(ui, component),
             LayoutComponent::MetadataArtist => self.render_metadata_artist(ui),
             LayoutComponent::MetadataAlbum => self.render_metadata_album(ui),
@@ -2083,32 +4056,109 @@ impl App {
                     self.render_metadata_state(ui, show_label);
                 }
             }
+            LayoutComponent::StateBadge => self.render_state_badge(ui, component),
+            LayoutComponent::UpNext => self.render_up_next(ui),
+            LayoutComponent::NextUp => {
+                self.render_adjacent_track(ui, "Next", self.core.next_track.as_ref());
+            }
+            LayoutComponent::PreviousTrack => {
+                self.render_adjacent_track(ui, "Previous", self.core.previous_track.as_ref());
+            }
+            LayoutComponent::SourceApp => self.render_source_app(ui),
+            LayoutComponent::SessionElapsed => self.render_session_elapsed(ui),
             LayoutComponent::PlaybackControlsGroup => {
                 let centered = Self::component_param_bool(component, "centered").unwrap_or(false);
-                self.render_playback_controls_group(ui, centered);
+                let mut warnings = Vec::new();
+                let params = PlaybackControlsParams::parse(&component.params, &mut warnings);
+                for warning in warnings {
+                    self.warn_once(warning);
+                }
+                self.render_playback_controls_group(ui, centered, params);
             }
             LayoutComponent::PlaybackButtonPrevious => {
-                self.render_playback_button(ui, PlaybackButtonKind::Previous, 1.0);
+                self.render_playback_button(
+                    ui,
+                    PlaybackButtonKind::Previous,
+                    PlaybackControlsStyle::Buttons,
+                    1.0,
+                );
             }
             LayoutComponent::PlaybackButtonPlayPause => {
-                self.render_playback_button(ui, PlaybackButtonKind::PlayPause, 1.0);
+                self.render_playback_button(
+                    ui,
+                    PlaybackButtonKind::PlayPause,
+                    PlaybackControlsStyle::Buttons,
+                    1.0,
+                );
             }
             LayoutComponent::PlaybackButtonNext => {
-                self.render_playback_button(ui, PlaybackButtonKind::Next, 1.0);
+                self.render_playback_button(
+                    ui,
+                    PlaybackButtonKind::Next,
+                    PlaybackControlsStyle::Buttons,
+                    1.0,
+                );
             }
             LayoutComponent::PlaybackButtonStop => {
                 // Stop button retired; keep layout compatibility with no output.
             }
+            LayoutComponent::FavoriteButton => {
+                let scale = component
+                    .params
+                    .get("scale")
+                    .and_then(|raw| raw.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                self.render_favorite_button(ui, scale);
+            }
             LayoutComponent::Timeline => {
                 let centered = Self::component_param_bool(component, "centered").unwrap_or(false);
                 let show_separator =
                     Self::component_param_bool(component, "separator").unwrap_or(true);
-                self.render_timeline_component(ui, centered, show_separator);
+                let inline = match component.params.get("layout").map(|raw| raw.trim()) {
+                    None | Some("stacked") => false,
+                    Some("inline") => true,
+                    Some(other) => {
+                        self.warn_once(format!(
+                            "timeline: layout must be 'stacked' or 'inline', got '{other}'"
+                        ));
+                        false
+                    }
+                };
+                let tick_interval = component.params.get("tick_interval_secs").and_then(|raw| {
+                    match raw.trim().parse::<f64>() {
+                        Ok(secs) if secs > 0.0 && secs.is_finite() => Some(secs),
+                        _ => {
+                            self.warn_once(format!(
+                                "timeline: tick_interval_secs must be positive, got '{raw}'"
+                            ));
+                            None
+                        }
+                    }
+                });
+                // Long-form sessions count down and mark every ten minutes unless the
+                // layout says otherwise; chapters still win over the interval.
+                let long_form = self.core.now.content.is_long_form();
+                let show_remaining =
+                    Self::component_param_bool(component, "remaining").unwrap_or(long_form);
+                let tick_interval =
+                    tick_interval.or_else(|| long_form.then_some(LONG_FORM_TICK_INTERVAL_SECS));
+                self.render_timeline_component(
+                    ui,
+                    TimelineParams {
+                        centered,
+                        show_separator,
+                        tick_interval,
+                        show_remaining,
+                        inline,
+                    },
+                );
             }
+            LayoutComponent::ProgressEdge => self.render_progress_edge(ui, component),
             LayoutComponent::SkinWarnings => self.render_skin_warnings(ui),
             LayoutComponent::SkinError => self.render_skin_error(ui),
             LayoutComponent::NowPlayingError => self.render_now_playing_error(ui),
-            LayoutComponent::ThumbnailError => self.render_thumbnail_error(ui),
+            // Artwork failures are transient and show as toasts instead.
+            LayoutComponent::ThumbnailError => {}
         }
     }
 
@@ -2122,7 +4172,7 @@ impl App {
         })
     }
 
-    fn paint_thumbnail(&mut self, ui: &mut egui::Ui) {
+    fn paint_thumbnail(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
         let (thumbnail_style, panel_style, theme_disables_vinyl) = {
             let theme = self.skin_manager.current_theme();
             (
@@ -2138,7 +4188,8 @@ impl App {
         let stroke_width = thumbnail_style.stroke_width.max(0.0);
         let stroke_color = thumbnail_style.stroke_color;
 
-        let vinyl_active = self.config.ui.vinyl_thumbnail.enabled && !theme_disables_vinyl;
+        let vinyl_allowed = self.vinyl_allowed();
+        let vinyl_active = self.config.ui.vinyl_thumbnail.enabled && vinyl_allowed;
         let primary_texture = if vinyl_active {
             self.thumbnail_texture.as_ref()
         } else {
@@ -2147,26 +4198,35 @@ impl App {
                 .or(self.thumbnail_texture.as_ref())
         };
 
-        let sense = if theme_disables_vinyl {
+        let click_action = match self.config.ui.artwork_click {
+            ArtworkClick::ToggleVinyl if !vinyl_allowed => ArtworkClick::None,
+            action => action,
+        };
+        let sense = if click_action == ArtworkClick::None {
             egui::Sense::hover()
         } else {
             egui::Sense::click()
         };
 
         let viewport_min_side = self.viewport_size.x.min(self.viewport_size.y);
+        let mut sharpen_vinyl_to = None;
 
         if let Some(texture) = primary_texture {
+            let texture_id = texture.id();
             let mut size = texture.size_vec2();
-            if size.x > 0.0 && size.y > 0.0 {
+            // A fixed `width`/`height` is an exact size, even below the usual 140 floor.
+            let max_side = if component.width.is_some() || component.height.is_some() {
+                let fixed = ui.available_size();
+                fixed.x.min(fixed.y)
+            } else {
                 let width_limit = ui.available_width().max(140.0);
                 let view_limit = (viewport_min_side * 0.58).max(140.0);
-                let max_side = width_limit.min(view_limit).min(220.0);
+                width_limit.min(view_limit).min(220.0)
+            };
+            if size.x > 0.0 && size.y > 0.0 {
                 let scale = (max_side / size.x).min(max_side / size.y).min(1.0);
                 size *= scale;
             } else {
-                let width_limit = ui.available_width().max(140.0);
-                let view_limit = (viewport_min_side * 0.58).max(140.0);
-                let max_side = width_limit.min(view_limit).min(220.0);
                 size = egui::vec2(max_side, max_side);
             }
 
@@ -2193,39 +4253,74 @@ impl App {
                     .min(0.25);
                 self.vinyl_last_frame = Some(now);
 
-                let should_spin = self.animations_enabled && self.now.state == PlayState::Playing;
+                // Freeze in place behind the settings panel; the angle is kept for resume.
+                let should_spin = self.animations_enabled
+                    && self.display_state() == PlayState::Playing
+                    && !self.settings.is_open();
                 self.vinyl_spin.advance(dt, should_spin);
                 if should_spin {
                     ui.ctx().request_repaint();
                 }
 
+                let pixels_per_point = ui.ctx().pixels_per_point();
+                if self
+                    .thumbnail_scale_watch
+                    .poll(texture_id, pixels_per_point, now)
+                {
+                    let [width, height] = texture.size();
+                    let display_side = size.x.max(size.y) * pixels_per_point;
+                    sharpen_vinyl_to = vinyl_rerender_size(width.max(height), display_side);
+                } else if self.thumbnail_scale_watch.is_pending() {
+                    ui.ctx().request_repaint_after(THUMBNAIL_RESCALE_DEBOUNCE);
+                }
+
                 self.paint_vinyl_disc(ui, rect, size, texture, self.vinyl_spin.angle());
             } else {
                 self.vinyl_last_frame = None;
                 let image_widget = egui::Image::new((texture.id(), size))
                     .fit_to_exact_size(size)
-                    .corner_radius(rounding);
+                    .corner_radius(rounding)
+                    .tint(self.artwork_tint());
                 let image_response = ui.put(rect, image_widget);
                 response = response.union(image_response);
             }
 
-            if !theme_disables_vinyl {
-                let tooltip = if vinyl_active {
-                    "Click to show the original album artwork"
-                } else {
-                    "Click to switch to the spinning vinyl"
-                };
-                if response.clicked() {
-                    self.set_vinyl_enabled(ui.ctx(), !vinyl_active);
+            let playing = self.display_state() == PlayState::Playing;
+            let tooltip = match click_action {
+                ArtworkClick::ToggleVinyl if vinyl_active => {
+                    Some("Click to show the original album artwork".to_string())
+                }
+                ArtworkClick::ToggleVinyl => {
+                    Some("Click to switch to the spinning vinyl".to_string())
+                }
+                ArtworkClick::PlayPause if playing => Some("Click to pause".to_string()),
+                ArtworkClick::PlayPause => Some("Click to play".to_string()),
+                ArtworkClick::OpenSource => Some(format!(
+                    "Click to open {}",
+                    friendly_app_name(&self.core.now.app_id)
+                )),
+                ArtworkClick::None if theme_disables_vinyl => {
+                    Some("Current skin disables the spinning vinyl overlay.".to_string())
                 }
+                ArtworkClick::None => None,
+            };
+            if response.clicked() {
+                match click_action {
+                    ArtworkClick::ToggleVinyl => self.set_vinyl_enabled(ui.ctx(), !vinyl_active),
+                    ArtworkClick::PlayPause => self.set_playing(!playing),
+                    ArtworkClick::OpenSource => self.open_source_app(),
+                    ArtworkClick::None => {}
+                }
+            }
+            if let Some(tooltip) = tooltip {
                 response = response.on_hover_text(tooltip);
-            } else {
-                response =
-                    response.on_hover_text("Current skin disables the spinning vinyl overlay.");
             }
 
-            let overlay_enabled =
-                size.x <= 200.0 || size.y <= 200.0 || ui.available_width() < 360.0;
+            let overlay_enabled = Self::component_param_bool(component, "always_show_overlay")
+                .unwrap_or(false)
+                || size.x <= 200.0
+                || size.y <= 200.0
+                || ui.available_width() < 360.0;
             let overlay_geometry = if overlay_enabled {
                 self.thumbnail_overlay_geometry(rect, 3)
             } else {
@@ -2248,30 +4343,43 @@ impl App {
 
             if alpha > 0.01 {
                 if let Some(geometry) = overlay_geometry {
+                    // Darkens the art behind the icons so it reads as interactive.
+                    let scrim = thumbnail_style.hover_scrim.gamma_multiply(alpha);
+                    if scrim.a() > 0 {
+                        let painter = ui.painter_at(rect);
+                        if vinyl_active {
+                            painter.circle_filled(rect.center(), rect.width() / 2.0, scrim);
+                        } else {
+                            painter.rect_filled(rect, rounding, scrim);
+                        }
+                    }
                     self.draw_thumbnail_overlay(ui, geometry, alpha);
                 }
             }
 
-            for (overlay, offset) in &overlay_textures {
-                let tex_size = overlay.size_vec2();
-                if tex_size.x <= 0.0 || tex_size.y <= 0.0 {
-                    continue;
-                }
+            Self::paint_thumbnail_overlays(ui, &overlay_textures, response.rect, rounding);
 
-                let scale = (size.x / tex_size.x)
-                    .min(size.y / tex_size.y)
-                    .min(1.0)
-                    .max(0.0);
-                let overlay_size = egui::vec2(tex_size.x * scale, tex_size.y * scale);
-                if overlay_size.x <= 0.0 || overlay_size.y <= 0.0 {
-                    continue;
-                }
-                let center = response.rect.center() + *offset;
-                let overlay_rect = egui::Rect::from_center_size(center, overlay_size);
-                let overlay_widget = egui::Image::new((overlay.id(), overlay_size))
-                    .fit_to_exact_size(overlay_size)
-                    .corner_radius(rounding);
-                ui.put(overlay_rect, overlay_widget);
+            let reflection_height = size.y * REFLECTION_HEIGHT_RATIO;
+            if thumbnail_style.reflection
+                && ui.available_height() >= reflection_height + ui.spacing().item_spacing.y
+            {
+                let (reflection_rect, _) = ui.allocate_exact_size(
+                    egui::vec2(size.x, reflection_height),
+                    egui::Sense::hover(),
+                );
+                let angle = if vinyl_active {
+                    self.vinyl_spin.angle()
+                } else {
+                    0.0
+                };
+                let mesh = reflection_mesh(
+                    texture_id,
+                    rect,
+                    reflection_rect,
+                    angle,
+                    self.artwork_tint(),
+                );
+                ui.painter_at(reflection_rect).add(egui::Shape::mesh(mesh));
             }
         } else {
             let width_limit = ui.available_width().max(96.0);
@@ -2293,43 +4401,72 @@ impl App {
 
             let painter = ui.painter_at(rect);
             paint_area_background(&painter, rect, rounding, &panel_style.background);
-            painter.text(
-                rect.center(),
-                Align2::CENTER_CENTER,
-                "No artwork",
-                egui::TextStyle::Body.resolve(ui.style()),
-                panel_fg,
-            );
-
-            for (overlay, offset) in &overlay_textures {
-                let tex_size = overlay.size_vec2();
-                if tex_size.x <= 0.0 || tex_size.y <= 0.0 {
-                    continue;
-                }
-
-                let scale = (size.x / tex_size.x)
-                    .min(size.y / tex_size.y)
-                    .min(1.0)
-                    .max(0.0);
-                let overlay_size = egui::vec2(tex_size.x * scale, tex_size.y * scale);
-                if overlay_size.x <= 0.0 || overlay_size.y <= 0.0 {
-                    continue;
-                }
-                let center = rect.center() + *offset;
-                let overlay_rect = egui::Rect::from_center_size(center, overlay_size);
-                let overlay_widget = egui::Image::new((overlay.id(), overlay_size))
-                    .fit_to_exact_size(overlay_size)
-                    .corner_radius(rounding);
-                ui.put(overlay_rect, overlay_widget);
+            let loading = self.thumbnail_inflight_request.is_some();
+            if loading && self.animations_enabled {
+                // The spinner animates off the frame clock and keeps requesting repaints
+                // until the fetch lands or fails and this branch stops being drawn.
+                let spinner_size = (size.x * 0.2).clamp(16.0, 32.0);
+                let spinner_rect =
+                    egui::Rect::from_center_size(rect.center(), egui::Vec2::splat(spinner_size));
+                egui::Spinner::new()
+                    .size(spinner_size)
+                    .color(panel_fg.gamma_multiply(0.7))
+                    .paint_at(ui, spinner_rect);
+            } else {
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    if loading {
+                        "Loading artwork…"
+                    } else {
+                        "No artwork"
+                    },
+                    egui::TextStyle::Body.resolve(ui.style()),
+                    panel_fg,
+                );
             }
 
+            Self::paint_thumbnail_overlays(ui, &overlay_textures, rect, rounding);
+
             self.adjust_thumbnail_overlay_alpha(0.0, ui.ctx());
         }
+
+        if let Some(side) = sharpen_vinyl_to {
+            self.rerender_vinyl_thumbnail(ui.ctx(), side);
+        }
+    }
+
+    /// Re-renders the vinyl from the base artwork at `side` pixels, for a denser display
+    /// than the current texture was made for.
+    fn rerender_vinyl_thumbnail(&mut self, ctx: &egui::Context, side: usize) {
+        let Some(base_image) = self.thumbnail_base_image.as_ref() else {
+            return;
+        };
+        let options = VinylThumbnailOptions::from_config(
+            &self.config.ui.vinyl_thumbnail,
+            base_image.size[0],
+            base_image.size[1],
+        )
+        .with_min_output_size(side);
+        let (vinyl_image, elapsed) = timed_render_vinyl(base_image, &options);
+        self.debug.vinyl_render = Some(elapsed);
+        let texture = ctx.load_texture(
+            "now_playing.thumbnail",
+            vinyl_image.clone(),
+            TextureOptions::LINEAR,
+        );
+        self.thumbnail_vinyl_image = Some(vinyl_image);
+        self.thumbnail_texture = Some(texture);
+    }
+
+    /// False when the skin or the user's `force_disable_vinyl` rules the vinyl out.
+    fn vinyl_allowed(&self) -> bool {
+        !self.config.ui.force_disable_vinyl
+            && !self.skin_manager.current_theme().disable_vinyl_thumbnail
     }
 
     fn set_vinyl_enabled(&mut self, ctx: &egui::Context, enabled: bool) {
-        let theme_disables_vinyl = self.skin_manager.current_theme().disable_vinyl_thumbnail;
-        let final_enabled = enabled && !theme_disables_vinyl;
+        let final_enabled = enabled && self.vinyl_allowed();
 
         if self.config.ui.vinyl_thumbnail.enabled == final_enabled {
             return;
@@ -2354,7 +4491,8 @@ impl App {
                     base_image.size[0],
                     base_image.size[1],
                 );
-                let vinyl_image = render_vinyl(&base_image, &options);
+                let (vinyl_image, elapsed) = timed_render_vinyl(&base_image, &options);
+                self.debug.vinyl_render = Some(elapsed);
                 let texture = ctx.load_texture(
                     "now_playing.thumbnail",
                     vinyl_image.clone(),
@@ -2365,7 +4503,7 @@ impl App {
                 self.vinyl_spin.reset();
                 self.vinyl_last_frame = None;
                 self.vinyl_pending_refresh = false;
-            } else if let Some(track) = self.current_thumbnail_track.clone() {
+            } else if let Some(track) = self.core.current_thumbnail_track.clone() {
                 self.thumbnail_inflight_track = None;
                 self.thumbnail_inflight_request = None;
                 self.request_thumbnail_for(track);
@@ -2393,38 +4531,43 @@ impl App {
         texture: &TextureHandle,
         angle: f32,
     ) {
-        let half = size * 0.5;
-        let center = rect.center();
-        let cos_r = angle.cos();
-        let sin_r = angle.sin();
-
-        let offsets = [
-            egui::Vec2::new(-half.x, -half.y),
-            egui::Vec2::new(half.x, -half.y),
-            egui::Vec2::new(half.x, half.y),
-            egui::Vec2::new(-half.x, half.y),
-        ];
-        let uvs = [
-            egui::Pos2::new(0.0, 0.0),
-            egui::Pos2::new(1.0, 0.0),
-            egui::Pos2::new(1.0, 1.0),
-            egui::Pos2::new(0.0, 1.0),
-        ];
+        let disc = egui::Rect::from_center_size(rect.center(), size);
+        let mesh = rotated_image_mesh(texture.id(), disc, angle, self.artwork_tint());
+        ui.painter_at(rect).add(egui::Shape::mesh(mesh));
+    }
 
-        let mut mesh = egui::Mesh::with_texture(texture.id());
-        for (offset, uv) in offsets.into_iter().zip(uvs) {
-            let rotated = egui::Vec2::new(
-                offset.x * cos_r - offset.y * sin_r,
-                offset.x * sin_r + offset.y * cos_r,
-            );
-            mesh.vertices.push(egui::epaint::Vertex {
-                pos: egui::Pos2::new(center.x + rotated.x, center.y + rotated.y),
-                uv,
-                color: egui::Color32::WHITE,
-            });
+    /// Skin overlays over the artwork in `frame`, in theme order. Rotated ones are drawn
+    /// as a mesh, which skips the rounded corners.
+    fn paint_thumbnail_overlays(
+        ui: &mut egui::Ui,
+        overlays: &[(TextureHandle, ThumbnailOverlay)],
+        frame: egui::Rect,
+        rounding: CornerRadius,
+    ) {
+        for (texture, overlay) in overlays {
+            let Some(overlay_rect) = thumbnail_overlay_rect(frame, texture.size_vec2(), overlay)
+            else {
+                continue;
+            };
+            if overlay.rotation == 0.0 {
+                let overlay_widget = egui::Image::new((texture.id(), overlay_rect.size()))
+                    .fit_to_exact_size(overlay_rect.size())
+                    .corner_radius(rounding);
+                ui.put(overlay_rect, overlay_widget);
+            } else {
+                let mesh = rotated_image_mesh(
+                    texture.id(),
+                    overlay_rect,
+                    overlay.rotation,
+                    egui::Color32::WHITE,
+                );
+                ui.painter().add(egui::Shape::mesh(mesh));
+            }
         }
-        mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
-        ui.painter_at(rect).add(egui::Shape::mesh(mesh));
+    }
+
+    fn artwork_tint(&self) -> egui::Color32 {
+        artwork_tint(self.config.ui.dim_when_paused, self.display_state())
     }
 
     fn render_metadata_group(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
@@ -2441,22 +4584,108 @@ impl App {
         }
     }
 
-    fn render_metadata_artist(&mut self, ui: &mut egui::Ui) {
-        if !self.now.artist.is_empty() {
-            self.skin_manager
-                .skin_text(ui, format!("Artist: {}", self.now.artist), false);
+    fn render_metadata_artist(&mut self, ui: &mut egui::Ui) {
+        if !self.core.now.artist.is_empty() {
+            self.skin_manager
+                .skin_text(ui, format!("Artist: {}", self.core.now.artist), false);
+        }
+    }
+
+    fn render_metadata_album(&mut self, ui: &mut egui::Ui) {
+        if !self.core.now.album.is_empty() {
+            self.skin_manager
+                .skin_text(ui, format!("Album: {}", self.core.now.album), false);
+        }
+    }
+
+    fn render_up_next(&mut self, ui: &mut egui::Ui) {
+        let Some(other) = &self.core.up_next else {
+            return;
+        };
+        let mut text = format!(
+            "Paused in {}: {}",
+            friendly_app_name(&other.app_id),
+            other.title
+        );
+        if !other.artist.is_empty() {
+            text.push_str(" — ");
+            text.push_str(&other.artist);
+        }
+        self.skin_manager.skin_text(ui, text, false);
+    }
+
+    /// Name of the session's player; clicking it focuses or launches the player.
+    fn render_source_app(&mut self, ui: &mut egui::Ui) {
+        if self.core.now.app_id.is_empty() {
+            return;
+        }
+        let name = friendly_app_name(&self.core.now.app_id);
+        let style = &self.skin_manager.current_theme().components.text_body;
+        let text = egui::RichText::new(&name)
+            .color(style.color)
+            .size(style.size);
+        let response = ui
+            .add(egui::Label::new(text).sense(egui::Sense::click()))
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text(format!("Open in {name}"));
+        if response.clicked() {
+            self.open_source_app();
+        }
+    }
+
+    /// Heart that toggles the current track in the favorites store. Toggles also go out
+    /// on the remote `/events` stream for helper scripts that forward them to a player.
+    fn render_favorite_button(&mut self, ui: &mut egui::Ui, scale: f32) {
+        let has_track = !self.core.now.is_empty();
+        let (artist, title) = (&self.core.now.artist, &self.core.now.title);
+        let favorited = has_track && self.favorites.contains(artist, title);
+        let (glyph, hover) = if favorited {
+            ("♥", "Remove from favorites")
+        } else {
+            ("♡", "Add to favorites")
+        };
+        let response = self
+            .skin_manager
+            .skin_button_scaled_enabled(ui, glyph, scale, has_track)
+            .on_hover_text(hover);
+        if !response.clicked() || !has_track {
+            return;
+        }
+        let favorited = self.favorites.toggle(artist, title);
+        if let Err(err) = self.favorites.save() {
+            eprintln!("Failed to save favorites: {err:?}");
+            self.show_toast(
+                ToastLevel::Warning,
+                format!("Couldn't save favorites: {err}"),
+            );
         }
+        let kind = if favorited { "favorite" } else { "unfavorite" };
+        self.publish_playback_event(kind, Instant::now());
     }
 
-    fn render_metadata_album(&mut self, ui: &mut egui::Ui) {
-        if !self.now.album.is_empty() {
-            self.skin_manager
-                .skin_text(ui, format!("Album: {}", self.now.album), false);
+    /// "Listening for 1 h 12 m"; the text only changes once a minute.
+    fn render_session_elapsed(&self, ui: &mut egui::Ui) {
+        if let Some(elapsed) = self.core.listening_for(Instant::now()) {
+            let text = format!("Listening for {}", humanize_duration(elapsed));
+            self.skin_manager.skin_text(ui, text, false);
+        }
+    }
+
+    /// "{label}: title — artist" for the queue neighbours; nothing when unknown.
+    fn render_adjacent_track(&self, ui: &mut egui::Ui, label: &str, track: Option<&NowPlaying>) {
+        let Some(track) = track.filter(|track| !track.title.is_empty()) else {
+            return;
+        };
+        let mut text = format!("{label}: {}", track.title);
+        if !track.artist.is_empty() {
+            text.push_str(" — ");
+            text.push_str(&track.artist);
         }
+        self.skin_manager.skin_text(ui, text, false);
     }
 
     fn render_metadata_state(&mut self, ui: &mut egui::Ui, show_label: bool) {
-        let state_text = playstate_to_str(self.now.state);
+        let state_text = playstate_to_str(self.display_state());
         let content = if show_label {
             format!("State: {state_text}")
         } else {
@@ -2465,26 +4694,163 @@ impl App {
         self.skin_manager.skin_text(ui, content, false);
     }
 
-    fn render_playback_controls_group(&mut self, ui: &mut egui::Ui, centered: bool) {
-        let base_height = ui.style().spacing.interact_size.y.max(40.0);
-        let available_width = ui.available_width().max(1.0);
-        let effective_width = available_width.min(PLAYBACK_CONTROLS_MAX_WIDTH);
+    fn render_title(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
+        let param = component.params.get("transition");
+        let transition = param.map_or(Some(TitleTransition::None), |value| {
+            TitleTransition::parse(value)
+        });
+        if transition.is_none() {
+            self.warn_once(
+                "Component 'title' has an unknown transition; expected slide, fade, or none".into(),
+            );
+        }
+        let transition = transition.unwrap_or(TitleTransition::None);
+
+        let blend = self
+            .title_transition
+            .as_ref()
+            .and_then(|state| state.blend(Instant::now()));
+        let blend = match blend {
+            Some(blend) if self.animations_enabled && transition != TitleTransition::None => blend,
+            _ => {
+                self.skin_manager.skin_text(ui, &self.core.now.title, true);
+                return;
+            }
+        };
+
+        let distance = if transition == TitleTransition::Slide {
+            self.skin_manager.current_theme().components.text_title.size * TITLE_SLIDE_DISTANCE
+        } else {
+            0.0
+        };
+        let slide = |offset: f32| {
+            egui::emath::TSTransform::from_translation(egui::vec2(0.0, offset * distance))
+        };
+        let skin = &self.skin_manager;
+        let incoming = ui
+            .with_visual_transform(slide(1.0 - blend.progress), |ui| {
+                ui.multiply_opacity(blend.incoming_alpha);
+                skin.skin_text(ui, &self.core.now.title, true);
+            })
+            .response;
+        if let Some(state) = &self.title_transition {
+            let mut outgoing = ui.new_child(
+                UiBuilder::new()
+                    .max_rect(incoming.rect)
+                    .layout(*ui.layout()),
+            );
+            outgoing.with_visual_transform(slide(-blend.progress), |ui| {
+                ui.multiply_opacity(blend.outgoing_alpha);
+                skin.skin_text(ui, &state.previous, true);
+            });
+        }
+        ui.ctx().request_repaint();
+    }
+
+    fn render_custom_text(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
+        let template = component
+            .params
+            .get("template")
+            .map_or("{title}", String::as_str);
+        let text = fill_track_template(template, &self.core.now);
+        if text.is_empty() {
+            return;
+        }
+        let title = match component
+            .params
+            .get("style")
+            .map(|s| s.trim().to_ascii_lowercase())
+        {
+            None => false,
+            Some(style) if style == "body" => false,
+            Some(style) if style == "title" => true,
+            Some(_) => {
+                self.warn_once(
+                    "Component 'text' has an unknown style; expected title or body".into(),
+                );
+                false
+            }
+        };
+        if Self::component_param_bool(component, "marquee").unwrap_or(false) {
+            self.skin_manager
+                .skin_marquee(ui, &text, title, self.animations_enabled);
+        } else {
+            self.skin_manager.skin_text(ui, text, title);
+        }
+    }
+
+    fn render_state_badge(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
+        let show_label = Self::component_param_bool(component, "show_label").unwrap_or(true);
+        let pulse = Self::component_param_bool(component, "pulse").unwrap_or(false);
+        let size_param = component.params.get("size");
+        let custom_size = size_param
+            .and_then(|value| value.trim().parse::<f32>().ok())
+            .filter(|size| *size > 0.0);
+        if size_param.is_some() && custom_size.is_none() {
+            self.warn_once(
+                "Component 'state_badge' has an invalid size; using the body text size".into(),
+            );
+        }
+
+        let state = self.display_state();
+        let (mut badge, size, radius) = {
+            let theme = self.skin_manager.current_theme();
+            let badge = state_badge_style(state, &theme.colors, theme.components.panel.foreground);
+            let size = custom_size.unwrap_or(theme.components.text_body.size);
+            (badge, size, theme.components.button.border_radius)
+        };
+        if pulse && state == PlayState::Playing && self.animations_enabled {
+            let alpha = state_badge_pulse(ui.input(|i| i.time));
+            badge.fill = badge.fill.gamma_multiply(alpha);
+            badge.text = badge.text.gamma_multiply(alpha);
+        }
 
-        let style = ui.style();
-        let base_button_width = style.spacing.interact_size.x.max(96.0);
-        let base_row_width = 3.0 * base_button_width + 2.0 * PLAYBACK_CONTROL_SPACING_X;
-        let scale = if base_row_width <= f32::EPSILON {
-            1.0
+        let text = if show_label {
+            format!("{} {}", badge.icon, badge.label)
         } else {
-            (effective_width / base_row_width).clamp(0.6, 1.0)
+            badge.icon.to_string()
         };
+        let galley = ui
+            .painter()
+            .layout_no_wrap(text, FontId::proportional(size), badge.text);
+        let padding = egui::vec2(size * 0.6, size * 0.3);
+        let (rect, response) =
+            ui.allocate_exact_size(galley.size() + 2.0 * padding, egui::Sense::hover());
+        let radius = radius.clamp(0.0, rect.height() / 2.0).round() as u8;
+        paint_area_background(
+            ui.painter(),
+            rect,
+            CornerRadius::same(radius),
+            &AreaBackground::Solid(badge.fill),
+        );
+        ui.painter().galley(rect.min + padding, galley, badge.text);
+        if !show_label {
+            response.on_hover_text(badge.label);
+        }
+    }
 
-        let button_width = (base_button_width * scale).clamp(60.0, base_button_width);
-        let button_height = (base_height * scale).clamp(28.0, base_height);
-        let spacing = (PLAYBACK_CONTROL_SPACING_X * scale).clamp(6.0, PLAYBACK_CONTROL_SPACING_X);
-        let row_width = 3.0 * button_width + 2.0 * spacing;
+    /// Previous/play/next row. `scale` is the size the skin asks for; the row still
+    /// shrinks below it when `max_width` (default [`PLAYBACK_CONTROLS_MAX_WIDTH`], widened
+    /// with `scale`) or the available width runs out.
+    fn render_playback_controls_group(
+        &mut self,
+        ui: &mut egui::Ui,
+        centered: bool,
+        params: PlaybackControlsParams,
+    ) {
+        if self.config.ui.hide_unavailable_controls && self.core.now.caps.none_available() {
+            return;
+        }
+        let available_width = ui.available_width().max(1.0);
+        let row = PlaybackRowMetrics::new(
+            &params,
+            self.skin_manager.current_theme().components.button.shape,
+            ui.style().spacing.interact_size,
+            available_width,
+        );
+        let row_size = row.row_size();
 
-        let metrics = StripMetrics::from_content(available_width, row_width);
+        let metrics = StripMetrics::from_content(available_width, row_size.x);
         let align = if centered {
             egui::Align::Center
         } else {
@@ -2493,31 +4859,24 @@ impl App {
 
         metrics.show_anchored(ui, align, |inner| {
             inner.allocate_ui_with_layout(
-                egui::vec2(row_width, button_height),
+                row_size,
                 egui::Layout::left_to_right(egui::Align::Center),
-                |row| {
-                    self.render_playback_buttons_row(
-                        row,
-                        scale,
-                        egui::vec2(button_width, button_height),
-                        spacing,
-                    );
-                },
+                |ui| self.render_playback_buttons_row(ui, params.style, row),
             );
         });
     }
 
+    /// Previous, play/pause and next in a row, in the cells `metrics` lays out.
     fn render_playback_buttons_row(
         &mut self,
         row: &mut egui::Ui,
-        scale: f32,
-        button_size: egui::Vec2,
-        button_spacing: f32,
+        style: PlaybackControlsStyle,
+        metrics: PlaybackRowMetrics,
     ) {
-        let scale = scale.clamp(0.6, 1.0);
-        row.set_height(button_size.y);
+        let scale = metrics.scale.clamp(MIN_BUTTON_SCALE, MAX_BUTTON_SCALE);
+        row.set_height(metrics.row_size().y);
         let spacing_cfg = row.spacing_mut();
-        spacing_cfg.item_spacing.x = button_spacing;
+        spacing_cfg.item_spacing.x = metrics.spacing;
         spacing_cfg.item_spacing.y = 0.0;
 
         for kind in [
@@ -2525,75 +4884,187 @@ impl App {
             PlaybackButtonKind::PlayPause,
             PlaybackButtonKind::Next,
         ] {
+            let size = if kind == PlaybackButtonKind::PlayPause {
+                metrics.center_size
+            } else {
+                metrics.button_size
+            };
             row.allocate_ui_with_layout(
-                button_size,
+                size,
                 egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                 |cell| {
-                    self.render_playback_button(cell, kind, scale);
+                    self.render_playback_button(cell, kind, style, scale);
                 },
             );
         }
     }
 
-    fn render_playback_button(&mut self, ui: &mut egui::Ui, kind: PlaybackButtonKind, scale: f32) {
-        let scale = scale.clamp(0.6, 1.0);
-        match kind {
+    fn render_playback_button(
+        &mut self,
+        ui: &mut egui::Ui,
+        kind: PlaybackButtonKind,
+        style: PlaybackControlsStyle,
+        scale: f32,
+    ) {
+        let scale = scale.clamp(MIN_BUTTON_SCALE, MAX_BUTTON_SCALE);
+        let hero = style == PlaybackControlsStyle::Hero && kind == PlaybackButtonKind::PlayPause;
+        let circle =
+            self.skin_manager.current_theme().components.button.shape == ButtonShape::Circle;
+        // The hero button fills its cell; other circles keep to the regular button height
+        // even when a lone button component gets a larger cell.
+        let diameter = if hero {
+            Some(ui.available_size().min_elem())
+        } else if circle {
+            let height = (ui.style().spacing.interact_size.y.max(40.0) * scale).max(28.0);
+            Some(ui.available_size().min_elem().min(height))
+        } else {
+            None
+        };
+        let round = diameter.is_some();
+        // Dimmed, but still clickable, while the player loads.
+        let opacity = ui.opacity();
+        if self.is_loading() {
+            ui.multiply_opacity(LOADING_CONTROLS_OPACITY);
+        }
+        let response = match kind {
             PlaybackButtonKind::Previous => {
+                let action = ThumbnailOverlayAction::Previous;
+                let enabled = self.core.now.caps.allows(action);
                 let response = self
-                    .skin_manager
-                    .skin_button_scaled(ui, "⏮", scale)
-                    .on_hover_text("Previous track");
+                    .playback_button_widget(ui, "⏮", scale, diameter, enabled)
+                    .on_hover_text("Previous track")
+                    .on_disabled_hover_text(ControlsCaps::disabled_hint(action));
                 if response.clicked() {
-                    self.playback_command("Previous", |session| {
-                        block_on_operation(session.TrySkipPreviousAsync()?)
-                    });
+                    self.playback_command(PlaybackCommand::Previous);
                 }
+                response
             }
             PlaybackButtonKind::PlayPause => {
-                let is_playing = self.now.state == PlayState::Playing;
+                let is_playing = self.display_state() == PlayState::Playing;
                 let glyph = if is_playing { "⏸" } else { "▶" };
                 let hint = if is_playing { "Pause" } else { "Play" };
+                let action = if is_playing {
+                    ThumbnailOverlayAction::Pause
+                } else {
+                    ThumbnailOverlayAction::Play
+                };
+                let enabled = self.core.now.caps.allows(action);
                 let response = self
-                    .skin_manager
-                    .skin_button_scaled(ui, glyph, scale)
-                    .on_hover_text(hint);
+                    .playback_button_widget(ui, glyph, scale, diameter, enabled)
+                    .on_hover_text(hint)
+                    .on_disabled_hover_text(ControlsCaps::disabled_hint(action));
                 if response.clicked() {
-                    if is_playing {
-                        self.playback_command("Pause", |session| {
-                            block_on_operation(session.TryPauseAsync()?)
-                        });
-                    } else {
-                        self.playback_command("Play", |session| {
-                            block_on_operation(session.TryPlayAsync()?)
-                        });
-                    }
+                    self.set_playing(!is_playing);
                 }
+                response
             }
             PlaybackButtonKind::Next => {
+                let action = ThumbnailOverlayAction::Next;
+                let enabled = self.core.now.caps.allows(action);
                 let response = self
-                    .skin_manager
-                    .skin_button_scaled(ui, "⏭", scale)
-                    .on_hover_text("Next track");
+                    .playback_button_widget(ui, "⏭", scale, diameter, enabled)
+                    .on_hover_text("Next track")
+                    .on_disabled_hover_text(ControlsCaps::disabled_hint(action));
                 if response.clicked() {
-                    self.playback_command("Next", |session| {
-                        block_on_operation(session.TrySkipNextAsync()?)
-                    });
+                    self.playback_command(PlaybackCommand::Next);
                 }
+                response
             }
-        }
+        };
+        self.paint_press_ripple(ui, &response, scale, round);
+        ui.set_opacity(opacity);
     }
 
-    fn render_timeline_component(
-        &mut self,
+    /// A round icon button when `diameter` is given, otherwise the skin's pill button.
+    fn playback_button_widget(
+        &self,
         ui: &mut egui::Ui,
-        centered: bool,
-        show_separator: bool,
+        glyph: &str,
+        scale: f32,
+        diameter: Option<f32>,
+        enabled: bool,
+    ) -> egui::Response {
+        match diameter {
+            Some(diameter) => self
+                .skin_manager
+                .skin_icon_button(ui, glyph, diameter, enabled),
+            None => self
+                .skin_manager
+                .skin_button_scaled_enabled(ui, glyph, scale, enabled),
+        }
+    }
+
+    /// Rings the button with a short outward ripple after a click, so presses read
+    /// clearly even on skins whose fills barely change between states.
+    fn paint_press_ripple(
+        &self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        scale: f32,
+        round: bool,
     ) {
+        if !self.animations_enabled {
+            return;
+        }
+        let key = response.id.with("press_ripple");
+        let now = ui.input(|i| i.time);
+        if response.clicked() {
+            ui.data_mut(|data| data.insert_temp(key, now));
+        }
+        let Some(pressed_at) = ui.data(|data| data.get_temp::<f64>(key)) else {
+            return;
+        };
+        let Some((grow, alpha)) = press_ripple(now - pressed_at) else {
+            ui.data_mut(|data| data.remove::<f64>(key));
+            return;
+        };
+
+        let button = &self.skin_manager.current_theme().components.button;
+        let active_fill = self.skin_manager.button_fills().active;
+        let spread = PRESS_RIPPLE_SPREAD * scale * grow;
+        let corner = if round {
+            response.rect.width() / 2.0
+        } else {
+            button.border_radius
+        };
+        let radius = (corner + spread).clamp(0.0, u8::MAX as f32).round() as u8;
+        ui.painter().rect_stroke(
+            response.rect.expand(spread),
+            CornerRadius::same(radius),
+            egui::Stroke::new(2.0, active_fill.gamma_multiply(alpha)),
+            egui::StrokeKind::Outside,
+        );
+        ui.ctx().request_repaint();
+    }
+
+    fn render_timeline_component(&mut self, ui: &mut egui::Ui, params: TimelineParams) {
+        let TimelineParams {
+            centered,
+            show_separator,
+            tick_interval,
+            show_remaining,
+            inline,
+        } = params;
         if show_separator {
             //ui.separator();
         }
 
-        let Some(timeline) = &mut self.timeline else {
+        let align = timeline_strip_align(centered, Self::align_from_layout(ui.layout()));
+
+        if self.is_loading() {
+            let metrics = timeline_strip_metrics(ui.available_width(), centered);
+            let time = ui.input(|i| i.time);
+            let skin = &mut self.skin_manager;
+            metrics.show_anchored(ui, align, |inner| {
+                inner.set_width(metrics.content_width());
+                skin.skin_busy_bar(inner, time, self.animations_enabled)
+                    .on_hover_text("Loading…");
+            });
+            Self::render_timeline_labels_with_skin(skin, ui, &metrics, align, "Loading…", "");
+            return;
+        }
+
+        let Some(timeline) = self.core.timeline.clone() else {
             self.skin_manager
                 .skin_text(ui, "Timeline unavailable for this session.", false);
             return;
@@ -2610,10 +5081,42 @@ impl App {
         let metrics = timeline_strip_metrics(ui.available_width(), centered);
 
         if duration > f64::EPSILON {
+            let ticks = timeline_ticks(duration, tick_interval, &self.core.chapters);
             let mut slider_value = relative;
+            if inline {
+                // The readouts are laid out before the slider moves, so they trail a
+                // drag by one frame.
+                let label_width = self
+                    .timeline_label_width(ui, &timeline_end_label(0.0, duration, show_remaining));
+                let start_label = format_timestamp(relative);
+                let end_label = timeline_end_label(relative, duration, show_remaining);
+                let skin = &mut self.skin_manager;
+                let response = metrics.show_anchored(ui, align, |inner| {
+                    inner.set_width(metrics.content_width());
+                    Self::render_inline_timeline_row(
+                        skin,
+                        inner,
+                        label_width,
+                        (&start_label, &end_label),
+                        |skin, bar| {
+                            Self::render_seek_slider_with_skin(
+                                skin,
+                                bar,
+                                timeline.can_seek,
+                                &mut slider_value,
+                                duration,
+                                &ticks,
+                            )
+                        },
+                    )
+                });
+                relative = slider_value;
+                self.finish_timeline_seek(&timeline, &response, relative, previous_position);
+                return;
+            }
             let response = {
                 let skin = &mut self.skin_manager;
-                metrics.show_anchored(ui, egui::Align::Center, |inner| {
+                metrics.show_anchored(ui, align, |inner| {
                     inner.set_width(metrics.content_width());
                     Self::render_seek_slider_with_skin(
                         skin,
@@ -2621,6 +5124,7 @@ impl App {
                         timeline.can_seek,
                         &mut slider_value,
                         duration,
+                        &ticks,
                     )
                 })
             };
@@ -2628,50 +5132,20 @@ impl App {
             relative = slider_value;
 
             let start_label = format_timestamp(relative);
-            let end_label = format_timestamp(duration);
+            let end_label = timeline_end_label(relative, duration, show_remaining);
             {
                 let skin = &mut self.skin_manager;
                 Self::render_timeline_labels_with_skin(
                     skin,
                     ui,
                     &metrics,
+                    align,
                     &start_label,
                     &end_label,
                 );
             }
 
-            if timeline.can_seek && response.changed() {
-                let new_pos = timeline.start_secs + relative;
-                timeline.position_secs = new_pos;
-                self.is_user_seeking = true;
-                self.pending_seek_target = None;
-                self.pending_seek_deadline = None;
-                self.last_position_secs = timeline.position_secs;
-                self.last_position_update = Instant::now();
-            }
-
-            let commit_seek = timeline.can_seek
-                && (response.drag_stopped() || (response.clicked() && !response.dragged()));
-
-            if commit_seek {
-                let target_secs = timeline.start_secs + relative;
-                if (target_secs - previous_position).abs() > 0.001 {
-                    self.pending_seek_target = Some(target_secs);
-                    self.pending_seek_deadline = Some(Instant::now() + Duration::from_secs(4));
-                    self.is_user_seeking = true;
-                    self.last_position_secs = target_secs;
-                    self.last_position_update = Instant::now();
-                    self.playback_command("Seek", move |session| {
-                        block_on_operation(
-                            session.TryChangePlaybackPositionAsync(secs_to_ticks(target_secs))?,
-                        )
-                    });
-                } else {
-                    self.is_user_seeking = false;
-                    self.pending_seek_target = None;
-                    self.pending_seek_deadline = None;
-                }
-            }
+            self.finish_timeline_seek(&timeline, &response, relative, previous_position);
         } else {
             let fraction = if timeline.end_secs > timeline.start_secs {
                 ((timeline.position_secs - timeline.start_secs)
@@ -2681,17 +5155,195 @@ impl App {
                 0.0
             } as f32;
 
-            metrics.show_anchored(ui, egui::Align::Center, |inner| {
+            let start_label = format_timestamp(relative);
+            if inline {
+                let label_width = self.timeline_label_width(ui, &start_label);
+                let skin = &mut self.skin_manager;
+                metrics.show_anchored(ui, align, |inner| {
+                    inner.set_width(metrics.content_width());
+                    Self::render_inline_timeline_row(
+                        skin,
+                        inner,
+                        label_width,
+                        (&start_label, "Live"),
+                        |_, bar| {
+                            bar.add(egui::ProgressBar::new(fraction).desired_width(f32::INFINITY))
+                        },
+                    );
+                });
+                return;
+            }
+
+            metrics.show_anchored(ui, align, |inner| {
                 inner.set_width(metrics.content_width());
                 inner.add(egui::ProgressBar::new(fraction).desired_width(f32::INFINITY));
             });
 
-            let start_label = format_timestamp(relative);
             {
                 let skin = &mut self.skin_manager;
-                Self::render_timeline_labels_with_skin(skin, ui, &metrics, &start_label, "Live");
+                Self::render_timeline_labels_with_skin(
+                    skin,
+                    ui,
+                    &metrics,
+                    align,
+                    &start_label,
+                    "Live",
+                );
+            }
+        }
+    }
+
+    /// Progress line along the panel's top or bottom edge. It takes no space in the
+    /// layout; pressing it seeks like the timeline slider.
+    fn render_progress_edge(&mut self, ui: &mut egui::Ui, component: &ComponentNode) {
+        let thickness = match component.params.get("thickness") {
+            None => PROGRESS_EDGE_THICKNESS,
+            Some(raw) => match raw.trim().parse::<f32>() {
+                Ok(points) if points > 0.0 && points.is_finite() => {
+                    points.min(PROGRESS_EDGE_MAX_THICKNESS)
+                }
+                _ => {
+                    self.warn_once(format!(
+                        "progress_edge: thickness must be a positive number, got '{raw}'"
+                    ));
+                    PROGRESS_EDGE_THICKNESS
+                }
+            },
+        };
+        let edge = match component.params.get("edge").map(|raw| raw.trim()) {
+            None | Some("bottom") => StripEdge::Bottom,
+            Some("top") => StripEdge::Top,
+            Some(other) => {
+                self.warn_once(format!(
+                    "progress_edge: edge must be 'top' or 'bottom', got '{other}'"
+                ));
+                StripEdge::Bottom
+            }
+        };
+        let padded = match component.params.get("bleed").map(|raw| raw.trim()) {
+            None | Some("full") => false,
+            Some("padded") => true,
+            Some(other) => {
+                self.warn_once(format!(
+                    "progress_edge: bleed must be 'full' or 'padded', got '{other}'"
+                ));
+                false
+            }
+        };
+
+        let Some(timeline) = self.core.timeline.clone() else {
+            return;
+        };
+        let duration = timeline.duration_secs();
+        if duration <= f64::EPSILON || !self.panel_rect.is_positive() {
+            return;
+        }
+        let x_range = if padded {
+            self.panel_content_rect.x_range()
+        } else {
+            self.panel_rect.x_range()
+        };
+        let bounds = egui::Rect::from_x_y_ranges(x_range, self.panel_rect.y_range());
+        let strip = edge_strip_rect(bounds, edge, thickness);
+        let previous_position = timeline.position_secs;
+        let mut fraction = timeline.progress_fraction();
+
+        if timeline.can_seek {
+            let mut response = ui.interact(
+                edge_strip_hit_band(strip, edge),
+                egui::Id::new("progress-edge"),
+                egui::Sense::click_and_drag(),
+            );
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            if response.dragged() || response.drag_started() || response.clicked() {
+                if let Some(pos) = ui.input(|input| input.pointer.interact_pos()) {
+                    fraction = edge_strip_fraction(strip, pos.x);
+                    response.mark_changed();
+                }
             }
+            let relative = duration * f64::from(fraction);
+            self.finish_timeline_seek(&timeline, &response, relative, previous_position);
         }
+
+        let painter = egui::Painter::new(ui.ctx().clone(), ui.layer_id(), self.panel_rect);
+        self.skin_manager
+            .paint_progress_edge(&painter, strip, fraction);
+    }
+
+    /// Sends the seek a slider `response` asked for: drags move the local position, a
+    /// release or click commits it to the session.
+    fn finish_timeline_seek(
+        &mut self,
+        timeline: &Timeline,
+        response: &egui::Response,
+        relative: f64,
+        previous_position: f64,
+    ) {
+        if timeline.can_seek && response.changed() {
+            self.core
+                .drag_seek(timeline.start_secs + relative, Instant::now());
+        }
+
+        let commit_seek = timeline.can_seek
+            && (response.drag_stopped() || (response.clicked() && !response.dragged()));
+
+        if commit_seek {
+            // `start + relative` can round a hair past the end; never ask for that.
+            let target_secs = (timeline.start_secs + relative).min(timeline.end_secs);
+            if self
+                .core
+                .commit_seek(target_secs, previous_position, Instant::now())
+            {
+                self.playback_command(PlaybackCommand::Seek(target_secs));
+            }
+        }
+    }
+
+    /// Width that fits `widest` in the skin's body text with every digit at its widest,
+    /// so inline readouts keep the bar still as the numbers change.
+    fn timeline_label_width(&self, ui: &egui::Ui, widest: &str) -> f32 {
+        let text: String = widest
+            .chars()
+            .map(|c| if c.is_ascii_digit() { '0' } else { c })
+            .collect();
+        let style = &self.skin_manager.current_theme().components.text_body;
+        let galley =
+            ui.painter()
+                .layout_no_wrap(text, FontId::proportional(style.size), style.color);
+        galley.size().x.ceil()
+    }
+
+    /// `elapsed | bar | total` in one row: fixed-width readouts and a bar filling the
+    /// space between them.
+    fn render_inline_timeline_row<R>(
+        skin: &mut SkinManager,
+        ui: &mut egui::Ui,
+        label_width: f32,
+        (start_label, end_label): (&str, &str),
+        add_bar: impl FnOnce(&mut SkinManager, &mut egui::Ui) -> R,
+    ) -> R {
+        ui.horizontal(|row| {
+            row.spacing_mut().item_spacing.x = TIMELINE_INLINE_GAP;
+            let slot = egui::vec2(label_width, row.spacing().interact_size.y);
+            row.allocate_ui_with_layout(
+                slot,
+                egui::Layout::right_to_left(egui::Align::Center),
+                |cell| skin.skin_text(cell, start_label, false),
+            );
+            row.with_layout(egui::Layout::right_to_left(egui::Align::Center), |rest| {
+                // Each readout hugs the bar, so the slot's far side is where it grows.
+                rest.allocate_ui_with_layout(
+                    slot,
+                    egui::Layout::left_to_right(egui::Align::Center),
+                    |cell| skin.skin_text(cell, end_label, false),
+                );
+                add_bar(skin, rest)
+            })
+            .inner
+        })
+        .inner
     }
 
     fn render_seek_slider_with_skin(
@@ -2700,14 +5352,16 @@ impl App {
         can_seek: bool,
         value: &mut f64,
         duration: f64,
+        ticks: &[f64],
     ) -> egui::Response {
         if can_seek {
-            skin.skin_slider(ui, value, 0.0..=duration)
+            skin.skin_slider(ui, value, 0.0..=duration, ticks)
         } else {
             ui.add_enabled_ui(false, |disabled| {
-                skin.skin_slider(disabled, value, 0.0..=duration)
+                skin.skin_slider(disabled, value, 0.0..=duration, ticks)
             })
             .inner
+            .on_disabled_hover_text("The current player doesn't allow seeking")
         }
     }
 
@@ -2715,10 +5369,11 @@ impl App {
         skin: &mut SkinManager,
         ui: &mut egui::Ui,
         metrics: &StripMetrics,
+        align: egui::Align,
         start_label: &str,
         end_label: &str,
     ) {
-        metrics.show_anchored(ui, egui::Align::Center, |inner| {
+        metrics.show_anchored(ui, align, |inner| {
             inner.set_width(metrics.content_width());
             inner.spacing_mut().item_spacing.x = TIMELINE_LABEL_GAP;
             inner.columns(2, |columns| {
@@ -2732,142 +5387,194 @@ impl App {
         });
     }
 
+    fn status_color(&self, level: ToastLevel) -> egui::Color32 {
+        level.color(&self.skin_manager.current_theme().colors)
+    }
+
     fn render_skin_warnings(&mut self, ui: &mut egui::Ui) {
+        let color = self.status_color(ToastLevel::Warning);
         for warn in &self.skin_warnings {
-            ui.colored_label(
-                egui::Color32::from_rgb(240, 200, 80),
-                format!("Skin warning: {warn}"),
-            );
+            ui.colored_label(color, format!("Skin warning: {warn}"));
         }
     }
 
     fn render_skin_error(&mut self, ui: &mut egui::Ui) {
         if let Some(err) = &self.skin_error {
-            ui.colored_label(
-                egui::Color32::from_rgb(220, 80, 80),
-                format!("Skin error: {err}"),
-            );
+            let color = self.status_color(ErrorKind::Skin.level());
+            ui.colored_label(color, format!("Skin error: {err}"));
         }
     }
 
     fn render_now_playing_error(&mut self, ui: &mut egui::Ui) {
-        if let Some(err) = &self.err {
-            ui.colored_label(
-                egui::Color32::from_rgb(220, 80, 80),
-                format!("Error: {err}"),
-            );
-        }
-    }
-
-    fn render_thumbnail_error(&mut self, ui: &mut egui::Ui) {
-        if let Some(err) = &self.thumbnail_err {
-            ui.colored_label(
-                egui::Color32::from_rgb(240, 200, 80),
-                format!("Thumbnail error: {err}"),
-            );
+        if let Some(err) = &self.core.err {
+            let color = self.status_color(ErrorKind::Playback.level());
+            ui.colored_label(color, format!("Error: {err}"));
         }
     }
 
-    fn apply_snapshot(&mut self, now: NowPlaying, timeline: Option<Timeline>) {
+    fn apply_snapshot(&mut self, snapshot: SessionSnapshot) {
         let now_instant = Instant::now();
-        let track_changed = self.now != now;
-        if track_changed {
-            self.pending_thumbnail = Some(PendingThumbnail::Clear { track: None });
-            self.current_thumbnail_track = None;
-            self.thumbnail_hash = None;
+        let previous_title = self.core.now.title.clone();
+        let change = self.core.apply_snapshot(snapshot, now_instant);
+        if previous_title != self.core.now.title && !previous_title.is_empty() {
+            // An interrupted transition hands its half-faded title over as the outgoing
+            // one instead of restarting from fully visible.
+            let outgoing_start = self
+                .title_transition
+                .as_ref()
+                .and_then(|transition| transition.blend(now_instant))
+                .map_or(1.0, |blend| blend.incoming_alpha);
+            self.title_transition = Some(TitleTransitionState {
+                previous: previous_title.clone(),
+                started: now_instant,
+                outgoing_start,
+            });
+        }
+        if change.track_changed
+            && self.config.ui.flash_on_track_change
+            && !previous_title.is_empty()
+            && !self.core.now.title.is_empty()
+        {
+            // Restart the countdown on every change so skipping through tracks flashes once.
+            self.pending_track_flash = Some(now_instant + TRACK_FLASH_DEBOUNCE);
         }
 
-        if track_changed
+        if change.track_changed
             || (self.thumbnail_texture.is_none()
                 && self.thumbnail_inflight_request.is_none()
-                && self.current_thumbnail_track.as_ref() != Some(&now))
+                && self.core.current_thumbnail_track.as_ref() != Some(&self.core.now))
         {
-            self.request_thumbnail_for(now.clone());
+            self.request_thumbnail_for(self.core.now.clone());
         }
 
-        if let Some(target) = self.pending_seek_target {
-            if let Some(mut tl) = timeline.clone() {
-                if (tl.position_secs - target).abs() <= 0.5 {
-                    self.pending_seek_target = None;
-                    self.pending_seek_deadline = None;
-                    self.is_user_seeking = false;
+        let window_title = match &self.config.window.title_template {
+            Some(template) if !self.core.now.title.is_empty() => {
+                let title = fill_track_template(template, &self.core.now);
+                if title.is_empty() {
+                    WINDOW_TITLE.to_string()
                 } else {
-                    tl.position_secs = target;
+                    title
                 }
-                self.last_position_secs = tl.position_secs;
-                self.last_position_update = now_instant;
-                self.timeline = Some(tl);
-            } else {
-                self.last_position_secs = target;
-                self.last_position_update = now_instant;
             }
+            _ => WINDOW_TITLE.to_string(),
+        };
+        self.pending_window_title = (window_title != self.window_title).then_some(window_title);
 
-            if let Some(deadline) = self.pending_seek_deadline {
-                if now_instant >= deadline {
-                    self.pending_seek_target = None;
-                    self.pending_seek_deadline = None;
-                    self.is_user_seeking = false;
-                }
-            }
-        } else if let Some(mut tl) = timeline.clone() {
-            let predicted = self.last_position_secs
-                + now_instant
-                    .duration_since(self.last_position_update)
-                    .as_secs_f64();
-            if now.state == PlayState::Playing {
-                let predicted_clamped = predicted.clamp(tl.start_secs, tl.end_secs);
-                if self.timeline.is_some() {
-                    let discrepancy = (predicted_clamped - tl.position_secs).abs();
-                    let threshold = (tl.duration_secs() * 0.01).clamp(0.2, 7.0);
-                    if discrepancy <= threshold || tl.duration_secs() <= f64::EPSILON {
-                        tl.position_secs = predicted_clamped;
-                    }
-                } else {
-                    tl.position_secs = predicted_clamped;
-                }
+        if change.track_changed {
+            if let Some(rotation) = self.layout_rotation.as_mut() {
+                rotation.track_changed(now_instant);
             }
-            self.last_position_secs = tl.position_secs;
-            self.last_position_update = now_instant;
-            self.timeline = Some(tl);
-        } else {
-            self.last_position_update = now_instant;
-            self.timeline = None;
+            self.publish_playback_event("track", now_instant);
+        } else if change.state_changed {
+            self.publish_playback_event("state", now_instant);
+        }
+    }
+
+    /// Sends the clock's position to integrations at its emit cadence while playing.
+    fn publish_position_event(&mut self, now: Instant) {
+        if self.remote.is_some() && self.core.clock.tick(now).is_some() {
+            self.publish_playback_event("position", now);
         }
+    }
 
-        self.now = now;
-        self.err = None;
-        self.last_pull = Instant::now();
+    fn publish_playback_event(&mut self, kind: &'static str, now: Instant) {
+        let Some(remote) = self.remote.as_ref() else {
+            return;
+        };
+        // The same prediction the timeline shows, not the raw snapshot position.
+        self.core.advance_timeline(now);
+
+        remote.broadcast(&PlaybackEvent {
+            kind,
+            track_id: (!self.core.now.is_empty()).then(|| self.core.now.track_id()),
+            title: &self.core.now.title,
+            artist: &self.core.now.artist,
+            album: &self.core.now.album,
+            state: playstate_to_str(self.core.now.state),
+            position_secs: self.core.timeline.as_ref().map(|tl| tl.position_secs),
+            duration_secs: self.core.timeline.as_ref().map(|tl| tl.duration_secs()),
+        });
+        self.core.clock.mark_emitted(now);
     }
 
     fn update_dynamic_gradients(&mut self, image: &ColorImage) {
+        let accent = self
+            .skin_manager
+            .wants_artwork_accent()
+            .then(|| dynamic_accent_from_image(image))
+            .flatten();
         if !self.skin_manager.current_theme().use_gradient {
             self.clear_dynamic_gradients();
+            // The accent is independent of the background gradient.
+            self.skin_manager.set_artwork_accent(accent);
             return;
         }
+        self.skin_manager.set_artwork_accent(accent);
         let components = &self.skin_manager.current_theme().components;
         let root_direction = gradient_direction_from_background(&components.root.background);
         let panel_direction = gradient_direction_from_background(&components.panel.background);
-        self.dynamic_root_gradient = dynamic_gradient_from_image(image, root_direction);
-        self.dynamic_panel_gradient = dynamic_gradient_from_image(image, panel_direction);
+        let palette = &self.config.ui.palette;
+        self.dynamic_root_gradient = dynamic_gradient_from_image(image, root_direction, palette);
+        self.dynamic_panel_gradient = dynamic_gradient_from_image(image, panel_direction, palette);
+        self.artwork_palette = if self.config.ui.animated_gradient {
+            palette_colors(image, GRADIENT_PALETTE_SIZE, palette)
+        } else {
+            Vec::new()
+        };
+        self.dynamic_border_accent = if components.root.dynamic_border {
+            accent.or_else(|| dynamic_accent_from_image(image))
+        } else {
+            None
+        };
+    }
+
+    fn gradient_animating(&self) -> bool {
+        self.config.ui.animated_gradient
+            && self.animations_enabled
+            && self.skin_manager.current_theme().use_gradient
+            && self.artwork_palette.len() >= 2
+    }
+
+    /// Stops for the dynamic gradients this frame, or `None` to keep them static.
+    fn animated_gradient_stops(&self, ctx: &egui::Context) -> Option<[egui::Color32; 2]> {
+        if !self.gradient_animating() {
+            return None;
+        }
+        let elapsed = Duration::from_secs_f64(ctx.input(|i| i.time).max(0.0));
+        cycle_gradient(&self.artwork_palette, elapsed, GRADIENT_CYCLE_STEP)
     }
 
     fn clear_dynamic_gradients(&mut self) {
         self.dynamic_root_gradient = None;
         self.dynamic_panel_gradient = None;
+        self.artwork_palette.clear();
+        self.dynamic_border_accent = None;
+        self.skin_manager.set_artwork_accent(None);
     }
 
     fn process_pending_thumbnail(&mut self, ctx: &egui::Context) {
         self.drain_thumbnail_channel();
 
-        if let Some(pending) = self.pending_thumbnail.take() {
+        // Texture uploads and gradient recomputes wait for the settings panel to close.
+        // Updates that go stale in the meantime are dropped rather than applied late.
+        if self.settings.is_open() {
+            if let Some(PendingThumbnail::Update { track, .. }) = &self.core.pending_thumbnail {
+                if track != &self.core.now {
+                    self.core.pending_thumbnail = None;
+                }
+                return;
+            }
+        }
+
+        if let Some(pending) = self.core.pending_thumbnail.take() {
             match pending {
                 PendingThumbnail::Clear { track } => {
                     self.thumbnail_texture = None;
                     self.thumbnail_base_texture = None;
                     self.thumbnail_base_image = None;
                     self.thumbnail_vinyl_image = None;
-                    self.thumbnail_hash = None;
-                    self.current_thumbnail_track = track.filter(|t| t == &self.now);
+                    self.core.thumbnail_hash = None;
+                    self.core.current_thumbnail_track = track.filter(|t| t == &self.core.now);
                     self.clear_dynamic_gradients();
                     self.vinyl_spin.reset();
                     self.vinyl_last_frame = None;
@@ -2878,12 +5585,12 @@ impl App {
                     base_image,
                     vinyl_image,
                 } => {
-                    if track != self.now {
+                    if track != self.core.now {
                         return;
                     }
 
-                    if self.thumbnail_hash == Some(hash)
-                        && self.current_thumbnail_track.as_ref() == Some(&track)
+                    if self.core.thumbnail_hash == Some(hash)
+                        && self.core.current_thumbnail_track.as_ref() == Some(&track)
                     {
                         return;
                     }
@@ -2899,10 +5606,8 @@ impl App {
                     );
                     self.thumbnail_base_texture = Some(base_texture);
 
-                    let theme_disables_vinyl =
-                        self.skin_manager.current_theme().disable_vinyl_thumbnail;
-                    let vinyl_allowed = !theme_disables_vinyl;
-                    let use_vinyl_now = self.config.ui.vinyl_thumbnail.enabled && vinyl_allowed;
+                    let use_vinyl_now =
+                        self.config.ui.vinyl_thumbnail.enabled && self.vinyl_allowed();
                     let had_vinyl = vinyl_image.is_some();
                     let display_image = if use_vinyl_now {
                         vinyl_image.clone().unwrap_or_else(|| base_image.clone())
@@ -2916,8 +5621,8 @@ impl App {
                         TextureOptions::LINEAR,
                     );
                     self.thumbnail_texture = Some(texture);
-                    self.thumbnail_hash = Some(hash);
-                    self.current_thumbnail_track = Some(track);
+                    self.core.thumbnail_hash = Some(hash);
+                    self.core.current_thumbnail_track = Some(track);
                     self.thumbnail_err = None;
                     if use_vinyl_now && had_vinyl {
                         self.vinyl_spin.reset();
@@ -2937,7 +5642,7 @@ impl App {
 
     fn maybe_refresh_vinyl_thumbnail(&mut self) {
         if self.vinyl_pending_refresh
-            && self.current_thumbnail_track.is_some()
+            && self.core.current_thumbnail_track.is_some()
             && self.thumbnail_inflight_request.is_none()
         {
             self.force_thumbnail_refresh();
@@ -2949,11 +5654,11 @@ impl App {
         self.thumbnail_base_texture = None;
         self.thumbnail_base_image = None;
         self.thumbnail_vinyl_image = None;
-        self.thumbnail_hash = None;
-        self.pending_thumbnail = None;
+        self.core.thumbnail_hash = None;
+        self.core.pending_thumbnail = None;
         self.vinyl_spin.reset();
         self.vinyl_last_frame = None;
-        if let Some(track) = self.current_thumbnail_track.clone() {
+        if let Some(track) = self.core.current_thumbnail_track.clone() {
             self.thumbnail_inflight_track = None;
             self.thumbnail_inflight_request = None;
             self.request_thumbnail_for(track);
@@ -2982,23 +5687,30 @@ impl App {
                             hash,
                             base_image,
                             vinyl_image,
+                            vinyl_render,
                             error,
                         } = msg;
+                        self.debug.thumbnail_arrived(Instant::now());
+                        if vinyl_render.is_some() {
+                            self.debug.vinyl_render = vinyl_render;
+                        }
 
                         if let Some(err) = error {
-                            self.err = Some(err.clone());
-                            self.thumbnail_err = Some(err);
-                            self.pending_thumbnail =
+                            // Only the artwork is affected, so this stays out of `core.err`.
+                            let message = thumbnail_error_message(&track, &err);
+                            self.show_toast(ErrorKind::Thumbnail.level(), message.clone());
+                            self.thumbnail_err = Some(message);
+                            self.core.pending_thumbnail =
                                 Some(PendingThumbnail::Clear { track: Some(track) });
                         } else if let (Some(base_image), Some(hash)) = (base_image, hash) {
-                            self.pending_thumbnail = Some(PendingThumbnail::Update {
+                            self.core.pending_thumbnail = Some(PendingThumbnail::Update {
                                 track,
                                 hash,
                                 base_image,
                                 vinyl_image,
                             });
                         } else {
-                            self.pending_thumbnail =
+                            self.core.pending_thumbnail =
                                 Some(PendingThumbnail::Clear { track: Some(track) });
                         }
                         break;
@@ -3027,13 +5739,29 @@ impl App {
         let request_id = self.next_thumbnail_request_id;
         self.next_thumbnail_request_id = self.next_thumbnail_request_id.wrapping_add(1);
 
-        let vinyl_enabled = self.config.ui.vinyl_thumbnail.enabled;
+        let vinyl_enabled =
+            self.config.ui.vinyl_thumbnail.enabled && !self.config.ui.force_disable_vinyl;
         let vinyl_config = self.config.ui.vinyl_thumbnail.clone();
 
         let (tx, rx) = mpsc::channel();
         self.thumbnail_rx = Some(rx);
         self.thumbnail_inflight_request = Some(request_id);
         self.thumbnail_inflight_track = Some(track.clone());
+        self.debug.thumbnail_requested(Instant::now());
+
+        if self.demo.is_some() {
+            thread::spawn(move || {
+                let cover = Ok(Some(demo::COVER.to_vec()));
+                let _ = tx.send(thumbnail_message(
+                    request_id,
+                    track,
+                    cover,
+                    vinyl_enabled,
+                    &vinyl_config,
+                ));
+            });
+            return;
+        }
 
         thread::spawn(move || {
             let mut com_initialized = false;
@@ -3049,6 +5777,7 @@ impl App {
                         hash: None,
                         base_image: None,
                         vinyl_image: None,
+                        vinyl_render: None,
                         error: Some(format!("COM init failed: {hr:?}")),
                     });
                     return;
@@ -3056,58 +5785,8 @@ impl App {
             }
 
             let result = fetch_thumbnail_bytes();
-            let message = match result {
-                Ok(Some(bytes)) => {
-                    let hash = hash_bytes(&bytes);
-                    match decode_thumbnail_image(&bytes) {
-                        Ok(base_image) => {
-                            let vinyl_image = if vinyl_enabled {
-                                let options = VinylThumbnailOptions::from_config(
-                                    &vinyl_config,
-                                    base_image.size[0],
-                                    base_image.size[1],
-                                );
-                                Some(render_vinyl(&base_image, &options))
-                            } else {
-                                None
-                            };
-
-                            ThumbnailMessage {
-                                request_id,
-                                track,
-                                hash: Some(hash),
-                                base_image: Some(base_image),
-                                vinyl_image,
-                                error: None,
-                            }
-                        }
-                        Err(err) => ThumbnailMessage {
-                            request_id,
-                            track,
-                            hash: None,
-                            base_image: None,
-                            vinyl_image: None,
-                            error: Some(err),
-                        },
-                    }
-                }
-                Ok(None) => ThumbnailMessage {
-                    request_id,
-                    track,
-                    hash: None,
-                    base_image: None,
-                    vinyl_image: None,
-                    error: None,
-                },
-                Err(err) => ThumbnailMessage {
-                    request_id,
-                    track,
-                    hash: None,
-                    base_image: None,
-                    vinyl_image: None,
-                    error: Some(format!("{err:?}")),
-                },
-            };
+            let message =
+                thumbnail_message(request_id, track, result, vinyl_enabled, &vinyl_config);
             let _ = tx.send(message);
 
             if com_initialized {
@@ -3119,34 +5798,58 @@ impl App {
     }
 
     fn refresh_now_playing(&mut self) {
-        match fetch_session_snapshot() {
-            Ok((now, timeline)) => self.apply_snapshot(now, timeline),
+        if let Some(demo) = self.demo.as_mut() {
+            demo.tick(Instant::now());
+            let snapshot = demo.snapshot();
+            self.apply_snapshot(snapshot);
+            return;
+        }
+
+        match fetch_session_snapshot(&mut self.other_sessions) {
+            Ok(snapshot) => self.apply_snapshot(snapshot),
             Err(e) => {
-                self.err = Some(format!("{e:?}"));
-                self.timeline = None;
+                self.core.err = Some(format!("{e:?}"));
+                self.core.timeline = None;
             }
         }
-        self.last_pull = Instant::now();
+        self.core.last_pull = Instant::now();
     }
 
-    fn playback_command<F>(&mut self, action_name: &str, action: F)
-    where
-        F: FnOnce(&GlobalSystemMediaTransportControlsSession) -> WinResult<bool>,
-    {
-        let result = current_session().and_then(|session| action(&session));
+    fn playback_command(&mut self, command: PlaybackCommand) {
+        if let Some(demo) = self.demo.as_mut() {
+            demo.apply(command);
+            self.refresh_now_playing();
+            return;
+        }
+
+        let action_name = command.label();
+        let caps = self.core.now.caps;
+        let result = current_session().and_then(|session| command.send(&session, &caps));
 
-        match result {
-            Ok(true) => {
+        match command_feedback(command, &result, &self.config.ui.rejected_behavior) {
+            CommandFeedback::Refresh => {
                 self.refresh_now_playing();
             }
-            Ok(false) => {
-                self.err = Some(format!(
-                    "{action_name} command was rejected by the media session."
-                ));
+            CommandFeedback::Rejected(behavior) => {
+                self.core.provisional_state = None;
+                let player = match friendly_app_name(&self.core.now.app_id) {
+                    name if name.is_empty() => "the media session".to_string(),
+                    name => name,
+                };
+                let message = format!("{action_name} command was rejected by {player}.");
                 self.refresh_now_playing();
+                match behavior {
+                    RejectedBehavior::Silent => {}
+                    RejectedBehavior::Toast => self.show_toast(ToastLevel::Error, message),
+                    // After the refresh, which would clear it straight away.
+                    RejectedBehavior::ErrorLabel => self.core.err = Some(message),
+                }
             }
-            Err(e) => {
-                self.err = Some(format!("{action_name} failed: {e:?}"));
+            CommandFeedback::Failed => {
+                self.core.provisional_state = None;
+                if let Err(e) = result {
+                    self.show_toast(ToastLevel::Error, format!("{action_name} failed: {e:?}"));
+                }
             }
         }
     }
@@ -3161,19 +5864,42 @@ impl Drop for App {
 }
 
 fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(index) = args.iter().position(|arg| arg == "--send") {
+        return send_to_running_widget(&args[index + 1..]);
+    }
+    let demo = args.iter().any(|arg| arg == "--demo");
+    let startup_layout = args
+        .iter()
+        .position(|arg| arg == "--layout")
+        .and_then(|index| args.get(index + 1))
+        .cloned();
+    #[cfg(target_os = "windows")]
+    borderless::install_panic_hook();
     let native_options = eframe::NativeOptions {
         viewport: ViewportBuilder::default()
             .with_transparent(true),
         ..Default::default()
     };
     let run_res = eframe::run_native(
-        "Now Playing",
+        WINDOW_TITLE,
         native_options,
         Box::new(
-            |_cc| -> std::result::Result<
+            |cc| -> std::result::Result<
                 Box<dyn eframe::App>,
                 Box<dyn std::error::Error + Send + Sync>,
-            > { Ok(Box::new(App::default())) },
+            > {
+                let mut app = App::new(demo);
+                // Apply the saved scale before the first frame so the window doesn't jump.
+                app.apply_ui_scale(&cc.egui_ctx);
+                if let Some(layout_id) = startup_layout {
+                    if let Err(err) = app.request_layout(&layout_id, &cc.egui_ctx) {
+                        eprintln!("{err}");
+                        app.skin_error = Some(err);
+                    }
+                }
+                Ok(Box::new(app))
+            },
         ),
     );
     if let Err(e) = run_res {
@@ -3183,10 +5909,131 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// `--send <command> [id]`: forwards one command to the running widget's remote control
+/// endpoint, prints its reply, and exits non-zero if the widget rejected it.
+fn send_to_running_widget(args: &[String]) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let command = RemoteCommand::from_cli_args(args)?;
+    let config = Config::load().unwrap_or_default();
+    if !config.remote.enabled {
+        return Err("Remote control is disabled; set `enabled = true` under [remote]".into());
+    }
+    let (status, body) = send_command(config.remote.port, &command)?;
+    if status == 200 {
+        println!("{body}");
+        Ok(())
+    } else {
+        Err(body.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn seek_ticks_round_trip_session_timestamps() {
+        // Every reported end converts back to the same tick, so seeking to the very end
+        // of a track never lands past it.
+        for ticks in [0, 1, 29_999_999, 30_000_000, 30_000_001, 2_147_483_647_123] {
+            let secs = time_span_to_secs(TimeSpan { Duration: ticks });
+            assert_eq!(secs_to_ticks(secs), ticks);
+        }
+        assert_eq!(secs_to_ticks(2.999_999_96), 30_000_000);
+        assert_eq!(secs_to_ticks(f64::INFINITY), i64::MAX);
+        assert_eq!(secs_to_ticks(f64::NEG_INFINITY), i64::MIN);
+    }
+
+    #[test]
+    fn local_artwork_only_considers_images_beside_absolute_paths() {
+        let song = r"C:\Music\Album\01 Intro.flac";
+        let candidates = local_artwork_candidates(song);
+        assert_eq!(candidates.len(), ARTWORK_SIDECARS.len());
+        assert_eq!(
+            candidates[0],
+            Path::new(r"C:\Music\Album").join("cover.jpg")
+        );
+        assert!(candidates.iter().all(|path| has_artwork_extension(path)));
+
+        let image = r"C:\Music\Album\Front.PNG";
+        assert_eq!(local_artwork_candidates(image), vec![PathBuf::from(image)]);
+        assert_eq!(
+            local_artwork_candidates(r"file:///C:\Music\Album\Front.PNG"),
+            vec![PathBuf::from(image)]
+        );
+
+        assert!(local_artwork_candidates("Intro").is_empty());
+        assert!(local_artwork_candidates(r"Album\01 Intro.flac").is_empty());
+        assert!(local_artwork_candidates(r"C:\Music\Album").is_empty());
+    }
+
+    #[test]
+    fn thumbnail_failures_stay_out_of_the_playback_error() {
+        let mut app = App::default();
+        app.snapshot_rx = None;
+        let (tx, rx) = mpsc::channel();
+        app.thumbnail_rx = Some(rx);
+        app.thumbnail_inflight_request = Some(7);
+        let track = NowPlaying {
+            title: "Broken Art".to_string(),
+            ..NowPlaying::default()
+        };
+        tx.send(ThumbnailMessage {
+            request_id: 7,
+            track,
+            hash: None,
+            base_image: None,
+            vinyl_image: None,
+            vinyl_render: None,
+            error: Some("decode failed".to_string()),
+        })
+        .unwrap();
+
+        app.drain_thumbnail_channel();
+        assert!(app.core.err.is_none());
+        assert_eq!(
+            app.thumbnail_err.as_deref(),
+            Some("Couldn't load the artwork for \"Broken Art\": decode failed")
+        );
+        let toast = app.toasts.iter().next().expect("thumbnail toast");
+        assert_eq!(toast.level, ToastLevel::Warning);
+    }
+
+    #[test]
+    fn snapshot_bursts_keep_the_latest_result() {
+        let snapshot = |title: &str| -> SnapshotResult {
+            Ok(SessionSnapshot {
+                now: NowPlaying {
+                    title: title.to_string(),
+                    ..NowPlaying::default()
+                },
+                timeline: None,
+                others: Vec::new(),
+                next: None,
+            })
+        };
+        let title = |result: &Option<SnapshotResult>| match result {
+            Some(Ok(snapshot)) => snapshot.now.title.clone(),
+            _ => panic!("expected a snapshot"),
+        };
+
+        let burst = vec![
+            snapshot("one"),
+            Err("busy".into()),
+            snapshot("two"),
+            snapshot("three"),
+        ];
+        let (latest, skipped) = coalesce_snapshots(burst);
+        assert_eq!(title(&latest), "three");
+        assert_eq!(skipped, ["busy"]);
+
+        let (latest, skipped) = coalesce_snapshots(vec![snapshot("one"), Err("gone".into())]);
+        assert!(matches!(latest, Some(Err(ref err)) if err == "gone"));
+        assert!(skipped.is_empty());
+
+        let (latest, _) = coalesce_snapshots(Vec::new());
+        assert!(latest.is_none());
+    }
+
     #[test]
     fn decode_thumbnail_image_fails_on_garbage_input() {
         let result = decode_thumbnail_image(&[0u8, 1u8, 2u8, 3u8]);
@@ -3212,7 +6059,7 @@ mod tests {
         app.thumbnail_base_image = Some(base_image);
         app.thumbnail_base_texture = Some(base_texture.clone());
         app.thumbnail_texture = Some(base_texture.clone());
-        app.current_thumbnail_track = Some(NowPlaying::default());
+        app.core.current_thumbnail_track = Some(NowPlaying::default());
 
         app.set_vinyl_enabled(&ctx, true);
         assert!(app.config.ui.vinyl_thumbnail.enabled);
@@ -3224,5 +6071,661 @@ mod tests {
             app.thumbnail_texture.as_ref().map(|tex| tex.id()),
             Some(base_texture.id())
         );
+
+        // The user's "never" wins over a skin built around the vinyl.
+        app.config.ui.force_disable_vinyl = true;
+        app.set_vinyl_enabled(&ctx, true);
+        assert!(!app.config.ui.vinyl_thumbnail.enabled);
+    }
+
+    #[test]
+    fn overlay_controls_rect_follows_configured_corner() {
+        let root = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(400.0, 300.0));
+        let extent = 22.0;
+        let width = 2.0 * extent + OVERLAY_CONTROLS_SPACING;
+
+        let top_left =
+            overlay_controls_rect(root, OverlayControlsPosition::TopLeft, 2, extent).expect("rect");
+        assert_eq!(top_left.min, egui::pos2(8.0, 8.0));
+        assert_eq!(top_left.size(), egui::vec2(width, extent));
+
+        let top_right = overlay_controls_rect(root, OverlayControlsPosition::TopRight, 2, extent)
+            .expect("rect");
+        assert_eq!(top_right.max.x, 392.0);
+        assert_eq!(top_right.min, egui::pos2(392.0 - width, 8.0));
+
+        let bottom_right =
+            overlay_controls_rect(root, OverlayControlsPosition::BottomRight, 1, extent)
+                .expect("rect");
+        assert_eq!(bottom_right.max, egui::pos2(392.0, 292.0));
+        assert_eq!(bottom_right.size(), egui::vec2(extent, extent));
+
+        let bottom_left =
+            overlay_controls_rect(root, OverlayControlsPosition::BottomLeft, 2, extent)
+                .expect("rect");
+        assert_eq!(bottom_left.min, egui::pos2(8.0, 292.0 - extent));
+
+        assert!(overlay_controls_rect(root, OverlayControlsPosition::TopLeft, 0, extent).is_none());
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn dynamic_caption_color_prefers_root_then_panel_then_theme() {
+        let dark = egui::Color32::from_rgb(20, 30, 40);
+        let light = egui::Color32::from_rgb(200, 210, 220);
+        let panel_dark = egui::Color32::from_rgb(50, 10, 10);
+        let root = GradientSpec {
+            start: light,
+            end: dark,
+            direction: GradientDirection::Vertical,
+        };
+        let panel = GradientSpec {
+            start: panel_dark,
+            end: light,
+            direction: GradientDirection::Vertical,
+        };
+
+        assert_eq!(
+            dynamic_caption_color(true, true, Some(&root), Some(&panel)),
+            Some(dark)
+        );
+        assert_eq!(
+            dynamic_caption_color(true, false, Some(&root), Some(&panel)),
+            Some(panel_dark)
+        );
+        assert_eq!(
+            dynamic_caption_color(true, true, None, Some(&panel)),
+            Some(panel_dark)
+        );
+        assert_eq!(dynamic_caption_color(true, true, None, None), None);
+        assert_eq!(
+            dynamic_caption_color(false, true, Some(&root), Some(&panel)),
+            None
+        );
+    }
+
+    #[test]
+    fn timeline_ticks_prefer_chapters_and_cap_intervals() {
+        assert_eq!(
+            timeline_ticks(100.0, Some(30.0), &[]),
+            vec![30.0, 60.0, 90.0]
+        );
+        assert_eq!(timeline_ticks(90.0, Some(30.0), &[]), vec![30.0, 60.0]);
+        assert_eq!(
+            timeline_ticks(100.0, Some(30.0), &[0.0, 12.5, 70.0, 120.0]),
+            vec![12.5, 70.0]
+        );
+        assert!(timeline_ticks(100.0, None, &[]).is_empty());
+        assert!(timeline_ticks(100.0, Some(0.0), &[]).is_empty());
+        assert_eq!(
+            timeline_ticks(10_000.0, Some(1.0), &[]).len(),
+            MAX_TIMELINE_TICKS
+        );
+    }
+
+    #[test]
+    fn reflection_uv_mirrors_the_artwork_bottom_first() {
+        let artwork = egui::Rect::from_min_size(egui::pos2(10.0, 10.0), egui::vec2(100.0, 100.0));
+        let reflection =
+            egui::Rect::from_min_size(egui::pos2(10.0, 118.0), egui::vec2(100.0, 35.0));
+        let close = |a: egui::Pos2, b: egui::Pos2| (a - b).length() < 1e-4;
+
+        // The row touching the artwork samples its bottom edge, lower rows move up it.
+        assert!(close(
+            reflection_uv(artwork, reflection, 0.0, reflection.left_top()),
+            egui::pos2(0.0, 1.0)
+        ));
+        assert!(close(
+            reflection_uv(artwork, reflection, 0.0, reflection.right_bottom()),
+            egui::pos2(1.0, 0.65)
+        ));
+        // A half-turned disc shows its top edge at the bottom instead.
+        assert!(close(
+            reflection_uv(
+                artwork,
+                reflection,
+                std::f32::consts::PI,
+                reflection.left_top()
+            ),
+            egui::pos2(1.0, 0.0)
+        ));
+
+        let white = egui::Color32::WHITE;
+        let mesh = reflection_mesh(egui::TextureId::default(), artwork, reflection, 0.0, white);
+        assert_eq!(mesh.vertices.len(), (REFLECTION_ROWS + 1) * 2);
+        assert_eq!(mesh.indices.len(), REFLECTION_ROWS * 6);
+        assert_eq!(
+            mesh.vertices.last().unwrap().color,
+            egui::Color32::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn dynamic_border_color_falls_back_to_the_root_border() {
+        let accent = egui::Color32::from_rgb(200, 40, 90);
+        let border = egui::Color32::from_rgb(60, 60, 60);
+        let none = egui::Color32::TRANSPARENT;
+
+        assert_eq!(
+            dynamic_border_color(true, true, Some(accent), border),
+            Some(accent)
+        );
+        // Without the dynamic gradient the accent is ignored.
+        assert_eq!(
+            dynamic_border_color(true, false, Some(accent), border),
+            Some(border)
+        );
+        assert_eq!(dynamic_border_color(true, true, None, border), Some(border));
+        assert_eq!(dynamic_border_color(true, true, None, none), None);
+        assert_eq!(
+            dynamic_border_color(false, true, Some(accent), border),
+            None
+        );
+    }
+
+    #[test]
+    fn border_pulse_peaks_on_each_beat_and_decays_to_the_floor() {
+        // 120 BPM: a beat every half second.
+        assert_eq!(border_pulse_alpha(0.0, 120), 1.0);
+        assert_eq!(border_pulse_alpha(0.5, 120), 1.0);
+        let mid = border_pulse_alpha(0.25, 120);
+        let late = border_pulse_alpha(0.49, 120);
+        assert!(late < mid && mid < 1.0);
+        assert!(late >= BORDER_PULSE_FLOOR);
+    }
+
+    #[test]
+    fn title_transition_blend_eases_between_titles() {
+        let start = title_transition_blend(0.0, 1.0);
+        assert_eq!((start.outgoing_alpha, start.incoming_alpha), (1.0, 0.0));
+        let end = title_transition_blend(1.0, 1.0);
+        assert_eq!(
+            (end.progress, end.outgoing_alpha, end.incoming_alpha),
+            (1.0, 0.0, 1.0)
+        );
+        assert_eq!(title_transition_blend(2.0, 1.0), end);
+
+        let mut previous = start;
+        for t in [0.25, 0.5, 0.75] {
+            let blend = title_transition_blend(t, 1.0);
+            assert!(blend.progress > previous.progress && blend.progress >= t);
+            assert!(blend.outgoing_alpha < previous.outgoing_alpha);
+            previous = blend;
+        }
+
+        // An interrupted transition fades its outgoing title from where it was.
+        let interrupted = title_transition_blend(0.0, 0.4);
+        assert_eq!(interrupted.outgoing_alpha, 0.4);
+        assert!(title_transition_blend(0.5, 0.4).outgoing_alpha < 0.4);
+    }
+
+    #[test]
+    fn press_ripple_spreads_and_fades_out() {
+        assert_eq!(press_ripple(0.0), Some((0.0, 1.0)));
+        let (early_spread, early_alpha) = press_ripple(PRESS_RIPPLE_SECS * 0.25).unwrap();
+        let (late_spread, late_alpha) = press_ripple(PRESS_RIPPLE_SECS * 0.75).unwrap();
+        assert!(early_spread < late_spread && late_spread < 1.0);
+        assert!(early_alpha > late_alpha && late_alpha > 0.0);
+        assert_eq!(press_ripple(PRESS_RIPPLE_SECS), None);
+        assert_eq!(press_ripple(-0.1), None);
+    }
+
+    #[test]
+    fn state_badge_style_maps_each_play_state() {
+        let panel = egui::Color32::from_rgb(220, 220, 220);
+        let playing = egui::Color32::from_rgb(60, 200, 120);
+        let colors = HashMap::from([("state_playing".to_string(), playing)]);
+
+        let expected = [
+            (PlayState::Playing, "▶", "Playing", playing),
+            (PlayState::Paused, "⏸", "Paused", panel),
+            (PlayState::Stopped, "⏹", "Stopped", panel),
+            (PlayState::Closed, "⏹", "Closed", panel),
+            (PlayState::Opened, "⏹", "Opened", panel),
+            (PlayState::Changing, "⏹", "Changing", panel),
+            (PlayState::Unknown, "⏹", "Unknown", panel),
+        ];
+        for (state, icon, label, text) in expected {
+            let badge = state_badge_style(state, &colors, panel);
+            assert_eq!((badge.icon, badge.label, badge.text), (icon, label, text));
+            assert_eq!(badge.fill, text.gamma_multiply(STATE_BADGE_FILL_ALPHA));
+        }
+
+        let pulse = [0.0, 0.4, 0.8, 1.2].map(state_badge_pulse);
+        assert!(pulse.iter().all(|alpha| (0.6..=1.0).contains(alpha)));
+        assert_eq!(pulse[0], 1.0);
+    }
+
+    #[test]
+    fn write_screenshot_png_keeps_size_and_transparency() {
+        let size = [3, 2];
+        let mut pixels = vec![egui::Color32::from_rgb(200, 40, 10); 6];
+        pixels[5] = egui::Color32::TRANSPARENT;
+        let captured = ColorImage::new(size, pixels);
+        let path =
+            std::env::temp_dir().join(format!("now-playing-screenshot-{}.png", std::process::id()));
+
+        write_screenshot_png(&captured, &path).expect("screenshot saves");
+        let saved = image::open(&path).expect("saved png opens").to_rgba8();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(saved.dimensions(), (3, 2));
+        assert_eq!(saved.get_pixel(0, 0).0, [200, 40, 10, 255]);
+        assert_eq!(saved.get_pixel(2, 1).0[3], 0);
+    }
+
+    #[test]
+    fn rejected_commands_follow_the_per_command_policy() {
+        let policy = RejectedBehaviorConfig::default();
+        let rejected = Ok::<bool, ()>(false);
+        let feedback = |command| command_feedback(command, &rejected, &policy);
+        let silent = CommandFeedback::Rejected(RejectedBehavior::Silent);
+        let toast = CommandFeedback::Rejected(RejectedBehavior::Toast);
+        // The ends of a playlist are normal, so skipping past them stays quiet.
+        for command in [PlaybackCommand::Previous, PlaybackCommand::Next] {
+            assert_eq!(feedback(command), silent);
+        }
+        for command in [
+            PlaybackCommand::Play,
+            PlaybackCommand::Pause,
+            PlaybackCommand::Seek(30.0),
+        ] {
+            assert_eq!(feedback(command), toast);
+        }
+
+        let policy = RejectedBehaviorConfig {
+            next: RejectedBehavior::ErrorLabel,
+            ..policy
+        };
+        assert_eq!(
+            command_feedback(PlaybackCommand::Next, &rejected, &policy),
+            CommandFeedback::Rejected(RejectedBehavior::ErrorLabel)
+        );
+        let accepted = Ok::<bool, ()>(true);
+        let failed = Err::<bool, ()>(());
+        for command in [PlaybackCommand::Next, PlaybackCommand::Play] {
+            let accepted_feedback = command_feedback(command, &accepted, &policy);
+            assert_eq!(accepted_feedback, CommandFeedback::Refresh);
+            let failed_feedback = command_feedback(command, &failed, &policy);
+            assert_eq!(failed_feedback, CommandFeedback::Failed);
+        }
+        let error_label = RejectedBehavior::parse("error-label");
+        assert_eq!(error_label, Some(RejectedBehavior::ErrorLabel));
+        assert_eq!(RejectedBehavior::parse("loud"), None);
+    }
+
+    #[test]
+    fn demo_mode_feeds_scripted_track_and_cover() {
+        let mut app = App::new(true);
+        assert!(app.snapshot_rx.is_none());
+        assert!(!app.core.now.title.is_empty());
+        assert_eq!(app.core.now.state, PlayState::Playing);
+        assert!(app
+            .core
+            .timeline
+            .as_ref()
+            .is_some_and(|timeline| timeline.can_seek));
+        assert!(app.core.up_next.is_some());
+
+        let first = app.core.now.title.clone();
+        app.playback_command(PlaybackCommand::Next);
+        assert_ne!(app.core.now.title, first);
+        app.set_playing(false);
+        assert_eq!(app.core.now.state, PlayState::Paused);
+        assert!(app.core.provisional_state.is_none());
+
+        let cover = decode_thumbnail_image(demo::COVER).expect("bundled cover decodes");
+        assert_eq!(cover.size, [256, 256]);
+    }
+
+    #[test]
+    fn artwork_dims_only_when_enabled_and_not_playing() {
+        let dimmed = artwork_tint(true, PlayState::Paused);
+        assert_ne!(dimmed, egui::Color32::WHITE);
+        assert_eq!(artwork_tint(true, PlayState::Stopped), dimmed);
+        assert_eq!(artwork_tint(true, PlayState::Playing), egui::Color32::WHITE);
+        assert_eq!(
+            artwork_tint(true, PlayState::Changing),
+            egui::Color32::WHITE
+        );
+        assert_eq!(artwork_tint(false, PlayState::Paused), egui::Color32::WHITE);
+    }
+
+    fn pending_update_for(track: &NowPlaying) -> PendingThumbnail {
+        PendingThumbnail::Update {
+            track: track.clone(),
+            hash: 7,
+            base_image: ColorImage::new([2, 2], vec![egui::Color32::WHITE; 4]),
+            vinyl_image: None,
+        }
+    }
+
+    #[test]
+    fn thumbnail_update_is_deferred_until_settings_close() {
+        let ctx = egui::Context::default();
+        let mut app = App::default();
+        app.thumbnail_rx = None;
+        app.core.now.title = "First".to_string();
+        app.settings.set_open(true);
+        app.core.pending_thumbnail = Some(pending_update_for(&app.core.now));
+
+        app.process_pending_thumbnail(&ctx);
+        assert!(app.thumbnail_texture.is_none());
+        assert!(app.core.pending_thumbnail.is_some());
+
+        app.settings.set_open(false);
+        app.process_pending_thumbnail(&ctx);
+        assert!(app.thumbnail_texture.is_some());
+        assert_eq!(app.core.thumbnail_hash, Some(7));
+    }
+
+    #[test]
+    fn deferred_thumbnail_for_stale_track_is_dropped_on_settings_close() {
+        let ctx = egui::Context::default();
+        let mut app = App::default();
+        app.thumbnail_rx = None;
+        app.core.now.title = "First".to_string();
+        app.settings.set_open(true);
+        app.core.pending_thumbnail = Some(pending_update_for(&app.core.now));
+        app.process_pending_thumbnail(&ctx);
+
+        app.core.now.title = "Second".to_string();
+        app.settings.set_open(false);
+        app.process_pending_thumbnail(&ctx);
+
+        assert!(app.core.pending_thumbnail.is_none());
+        assert!(app.thumbnail_texture.is_none());
+        assert!(app.core.current_thumbnail_track.is_none());
+    }
+
+    /// App whose settings saves land in a scratch file instead of the checkout's config.
+    fn app_with_scratch_config(name: &str) -> (App, PathBuf) {
+        let path =
+            std::env::temp_dir().join(format!("nowplaying-app-{}-{name}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut app = App::default();
+        app.config = std::mem::take(&mut app.config).with_source(path.clone());
+        (app, path)
+    }
+
+    #[test]
+    fn settings_requests_reach_the_app_state_they_change() {
+        let ctx = egui::Context::default();
+        let (mut app, config_path) = app_with_scratch_config("settings-requests");
+        app.snapshot_rx = None;
+
+        app.settings.set_open(true);
+        app.apply_settings_request(SettingsRequest::Screenshot, &ctx);
+        assert!(!app.settings.is_open());
+        assert_eq!(app.screenshot, Some(ScreenshotStage::Scheduled));
+
+        app.pending_track_flash = Some(Instant::now());
+        app.apply_settings_request(SettingsRequest::TrackFlash(false), &ctx);
+        assert!(!app.config.ui.flash_on_track_change);
+        assert!(app.pending_track_flash.is_none());
+
+        app.apply_settings_request(SettingsRequest::LongFormMinutes(45), &ctx);
+        assert_eq!(app.config.ui.long_form_minutes, 45);
+        assert_eq!(app.core.long_form_threshold, Duration::from_secs(45 * 60));
+
+        app.apply_settings_request(SettingsRequest::VinylReverse(true), &ctx);
+        assert!(app.config.ui.vinyl_thumbnail.reverse);
+
+        app.layout_auto = true;
+        app.apply_settings_request(SettingsRequest::LayoutDefault, &ctx);
+        assert!(!app.layout_auto);
+
+        app.apply_settings_request(SettingsRequest::Skin("no-such-skin".to_string()), &ctx);
+        assert!(app.skin_error.is_some());
+
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains("long_form_minutes = 45"), "{saved}");
+        let _ = std::fs::remove_file(&config_path);
+    }
+
+    #[test]
+    fn hot_reload_that_cannot_start_clears_the_saved_preference() {
+        let ctx = egui::Context::default();
+        let (mut app, config_path) = app_with_scratch_config("hot-reload-off");
+        let missing_root = config_path.with_extension("no-skins");
+        app.skin_manager = SkinManager::discover(&missing_root, None).unwrap();
+        std::fs::write(&config_path, "[ui]\nwatch_skins = true\n").unwrap();
+        app.config.ui.watch_skins = true;
+
+        app.maintain_skin_watcher(&ctx);
+
+        assert!(!app.config.ui.watch_skins);
+        assert!(!app.skin_manager.hot_reload_enabled());
+        let toast = app.toasts.iter().next().expect("hot reload toast");
+        assert_eq!(toast.level, ToastLevel::Warning);
+        assert!(toast.message.starts_with("Hot reload is off"));
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains("watch_skins = false"), "{saved}");
+        let _ = std::fs::remove_file(&config_path);
+    }
+
+    #[test]
+    fn other_session_titles_are_reused_until_they_go_stale_or_resume() {
+        let start = Instant::now();
+        let mut cache = OtherSessionCache::default();
+        cache.insert("spotify".into(), "Song".into(), "Band".into(), start);
+
+        let cached = Some(("Song".to_string(), "Band".to_string()));
+        assert_eq!(cache.get("spotify", start + Duration::from_secs(5)), cached);
+        assert_eq!(cache.get("spotify", start + OTHER_SESSION_REFRESH), None);
+        assert_eq!(cache.get("vlc", start), None);
+
+        cache.retain_paused(&["vlc"]);
+        assert_eq!(cache.get("spotify", start), None);
+    }
+
+    #[test]
+    fn screenshot_requests_that_never_arrive_time_out() {
+        let ctx = egui::Context::default();
+        let mut app = App::default();
+        app.screenshot = Some(ScreenshotStage::Requested {
+            deadline: Instant::now(),
+        });
+
+        app.process_screenshot(&ctx);
+
+        assert!(app.screenshot.is_none());
+        let toast = app.toasts.iter().next().expect("timeout toast");
+        assert_eq!(toast.level, ToastLevel::Error);
+        assert_eq!(toast.message, "Screenshot timed out");
+    }
+
+    #[test]
+    fn borderless_resize_thresholds_hold_across_scale_factors() {
+        // Same physical window (1200x600 px) and pointer positions at 100%/150%/200%.
+        for scale in [1.0_f32, 1.5, 2.0] {
+            let to_points = |x: f32, y: f32| egui::pos2(x / scale, y / scale);
+            let root_rect = egui::Rect::from_min_max(egui::Pos2::ZERO, to_points(1200.0, 600.0));
+            let edge_px = BORDERLESS_RESIZE_EDGE * scale;
+
+            let direction = |x: f32, y: f32| {
+                borderless_resize_direction(root_rect, to_points(x, y), BORDERLESS_RESIZE_EDGE)
+            };
+            assert!(matches!(
+                direction(1200.0 - edge_px + 1.0, 300.0),
+                Some(ResizeDirection::East)
+            ));
+            assert!(matches!(
+                direction(1.0, 600.0 - 1.0),
+                Some(ResizeDirection::SouthWest)
+            ));
+            assert!(matches!(
+                direction(600.0, edge_px - 1.0),
+                Some(ResizeDirection::North)
+            ));
+            assert!(direction(1200.0 - edge_px - 2.0, 300.0).is_none());
+            assert!(direction(600.0, 300.0).is_none());
+        }
+    }
+
+    #[test]
+    fn layout_requests_are_checked_against_the_skin() {
+        let available = ["art_top", "mini"];
+        assert_eq!(
+            parse_layout_request("mini", available.into_iter(), false),
+            Ok(LayoutRequest::Variant("mini".to_string()))
+        );
+        assert_eq!(
+            parse_layout_request(AUTO_LAYOUT_ID, available.into_iter(), true),
+            Ok(LayoutRequest::Auto)
+        );
+        assert_eq!(
+            parse_layout_request(AUTO_LAYOUT_ID, available.into_iter(), false),
+            Err("This skin has no responsive layouts".to_string())
+        );
+        assert_eq!(
+            parse_layout_request("huge", available.into_iter(), true),
+            Err("Layout 'huge' not found".to_string())
+        );
+    }
+
+    #[test]
+    fn playback_controls_scale_honours_params_and_still_shrinks() {
+        let row = 3.0 * 96.0 + 2.0 * PLAYBACK_CONTROL_SPACING_X;
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        let with = |scale: f32, max_width: Option<f32>| PlaybackControlsParams {
+            scale,
+            max_width,
+            ..PlaybackControlsParams::default()
+        };
+
+        // Default: full size once the row fits, shrinking toward the floor below that.
+        let defaults = PlaybackControlsParams::default();
+        assert_eq!(defaults.scale_for(1000.0, row), 1.0);
+        assert!(close(defaults.scale_for(row * 0.8, row), 0.8));
+        assert_eq!(defaults.scale_for(50.0, row), MIN_BUTTON_SCALE);
+
+        // A small requested scale stays small in wide windows.
+        assert!(close(with(0.7, None).scale_for(1000.0, row), 0.7));
+
+        // Oversized rows widen the default cap with them, but not past the window.
+        assert!(close(with(1.3, None).scale_for(1000.0, row), 1.3));
+        assert!(close(with(1.3, None).scale_for(row * 1.1, row), 1.1));
+
+        // An explicit max_width overrides the cap in both directions.
+        assert!(close(
+            with(1.5, Some(row * 1.2)).scale_for(1000.0, row),
+            1.2
+        ));
+        assert!(close(
+            with(1.0, Some(row * 0.75)).scale_for(1000.0, row),
+            0.75
+        ));
+
+        // A higher floor stops the shrink early.
+        let chunky = PlaybackControlsParams {
+            min_scale: 0.9,
+            ..defaults
+        };
+        assert_eq!(chunky.scale_for(50.0, row), 0.9);
+    }
+
+    #[test]
+    fn playback_row_width_follows_the_button_shape() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        let interact = egui::vec2(40.0, 18.0);
+        let defaults = PlaybackControlsParams::default();
+        let row = |shape, params: &PlaybackControlsParams, width| {
+            PlaybackRowMetrics::new(params, shape, interact, width)
+        };
+
+        // Pills are 96 wide, circles as wide as they are tall.
+        let pill = row(ButtonShape::Pill, &defaults, 1000.0);
+        assert_eq!(pill.scale, 1.0);
+        assert_eq!(pill.row_size(), egui::vec2(3.0 * 96.0 + 24.0, 40.0));
+        let circle = row(ButtonShape::Circle, &defaults, 1000.0);
+        assert_eq!(circle.button_size, egui::Vec2::splat(40.0));
+        assert_eq!(circle.row_size(), egui::vec2(3.0 * 40.0 + 24.0, 40.0));
+
+        // At 200 points the pill row has to shrink to fit; the circle row already fits.
+        let pill = row(ButtonShape::Pill, &defaults, 200.0);
+        assert!(
+            pill.scale < 1.0 && close(pill.row_size().x, 200.0),
+            "{pill:?}"
+        );
+        assert_eq!(row(ButtonShape::Circle, &defaults, 200.0).scale, 1.0);
+
+        let hero = PlaybackControlsParams {
+            style: PlaybackControlsStyle::Hero,
+            ..defaults
+        };
+        let circle_hero = row(ButtonShape::Circle, &hero, 1000.0);
+        assert_eq!(
+            circle_hero.center_size,
+            egui::Vec2::splat(40.0 * HERO_BUTTON_RATIO)
+        );
+        assert!(close(circle_hero.row_size().x, 2.0 * 40.0 + 64.0 + 24.0));
+    }
+
+    #[test]
+    fn thumbnail_scale_watch_debounces_scale_changes() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let texture = egui::TextureId::Managed(1);
+        let mut watch = ThumbnailScaleWatch::default();
+
+        assert!(watch.poll(texture, 1.0, at(0)));
+        assert!(!watch.poll(texture, 1.0, at(16)));
+
+        // Crossing to a denser monitor and back inside the window never fires.
+        assert!(!watch.poll(texture, 1.5, at(100)));
+        assert!(!watch.poll(texture, 1.0, at(200)));
+        assert!(!watch.is_pending());
+
+        // A scale that holds fires once the debounce has passed.
+        assert!(!watch.poll(texture, 1.5, at(300)));
+        assert!(!watch.poll(texture, 1.5, at(500)));
+        assert!(watch.is_pending());
+        assert!(watch.poll(texture, 1.5, at(700)));
+        assert!(!watch.poll(texture, 1.5, at(800)));
+
+        // A new texture is checked immediately.
+        assert!(watch.poll(egui::TextureId::Managed(2), 1.5, at(816)));
+    }
+
+    #[test]
+    fn playback_controls_params_parse_with_defaults_and_warnings() {
+        let params = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let mut warnings = Vec::new();
+        assert_eq!(
+            PlaybackControlsParams::parse(&HashMap::new(), &mut warnings),
+            PlaybackControlsParams::default()
+        );
+
+        let parsed = PlaybackControlsParams::parse(
+            &params(&[("scale", "0.8"), ("min_scale", "1.2"), ("spacing", " 4 ")]),
+            &mut warnings,
+        );
+        assert_eq!(parsed.scale, 0.8);
+        assert_eq!(parsed.min_scale, 0.8, "the floor never exceeds the scale");
+        assert_eq!(parsed.spacing, 4.0);
+        assert_eq!(parsed.style, PlaybackControlsStyle::Buttons);
+        assert!(warnings.is_empty());
+
+        let hero = PlaybackControlsParams::parse(&params(&[("style", "Hero")]), &mut warnings);
+        assert_eq!(hero.style, PlaybackControlsStyle::Hero);
+        assert!(warnings.is_empty());
+
+        let parsed = PlaybackControlsParams::parse(
+            &params(&[
+                ("scale", "3"),
+                ("max_width", "-5"),
+                ("spacing", "wide"),
+                ("style", "round"),
+            ]),
+            &mut warnings,
+        );
+        assert_eq!(parsed, PlaybackControlsParams::default());
+        assert_eq!(warnings.len(), 4);
     }
 }
@@ -0,0 +1,443 @@
+//! Colors picked out of album art: the two-stop background gradient, the palette an
+//! animated gradient cycles through, and the accent used by `dynamic_accent` skins. All
+//! of them cluster a sample of the pixels with k-means.
+
+use std::{cmp::Reverse, time::Duration};
+
+use eframe::egui::{Color32, ColorImage};
+
+use crate::{
+    config::PaletteConfig,
+    vinyl::{darken, lighten},
+};
+
+#[derive(Clone, Copy)]
+struct Cluster {
+    centroid: [f32; 3],
+    count: usize,
+}
+
+fn sample_pixels(image: &ColorImage, max_samples: usize) -> Vec<[f32; 3]> {
+    if max_samples == 0 {
+        return Vec::new();
+    }
+
+    let total = image.pixels.len();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let step = (total / max_samples).max(1);
+    let mut samples = Vec::with_capacity(max_samples.min(total));
+
+    for pixel in image.pixels.iter().step_by(step) {
+        if pixel.a() < 16 {
+            continue;
+        }
+        samples.push([pixel.r() as f32, pixel.g() as f32, pixel.b() as f32]);
+        if samples.len() >= max_samples {
+            break;
+        }
+    }
+
+    samples
+}
+
+fn squared_distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    let dr = a[0] - b[0];
+    let dg = a[1] - b[1];
+    let db = a[2] - b[2];
+    dr * dr + dg * dg + db * db
+}
+
+fn kmeans_clusters(samples: &[[f32; 3]], k: usize, max_iter: usize) -> Vec<Cluster> {
+    if samples.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let mut centroids = Vec::with_capacity(k);
+    for i in 0..k {
+        let idx = (i * samples.len()) / k;
+        let idx = idx.min(samples.len() - 1);
+        centroids.push(samples[idx]);
+    }
+
+    let mut assignments = vec![0usize; samples.len()];
+
+    for iter in 0..max_iter {
+        let mut sums = vec![[0f32; 3]; k];
+        let mut counts = vec![0usize; k];
+
+        for (sample_idx, sample) in samples.iter().enumerate() {
+            let mut best = 0usize;
+            let mut best_dist = f32::MAX;
+            for (centroid_idx, centroid) in centroids.iter().enumerate() {
+                let dist = squared_distance(sample, centroid);
+                if dist < best_dist {
+                    best_dist = dist;
+                    best = centroid_idx;
+                }
+            }
+
+            assignments[sample_idx] = best;
+            for channel in 0..3 {
+                sums[best][channel] += sample[channel];
+            }
+            counts[best] += 1;
+        }
+
+        let mut changed = false;
+        for i in 0..k {
+            if counts[i] == 0 {
+                centroids[i] = samples[(i + iter) % samples.len()];
+                changed = true;
+                continue;
+            }
+            let new_centroid = [
+                sums[i][0] / counts[i] as f32,
+                sums[i][1] / counts[i] as f32,
+                sums[i][2] / counts[i] as f32,
+            ];
+            if squared_distance(&centroids[i], &new_centroid) > 1e-2 {
+                changed = true;
+            }
+            centroids[i] = new_centroid;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut counts = vec![0usize; k];
+    for &assignment in &assignments {
+        counts[assignment] += 1;
+    }
+
+    centroids
+        .into_iter()
+        .enumerate()
+        .map(|(idx, centroid)| Cluster {
+            centroid,
+            count: counts[idx],
+        })
+        .collect()
+}
+
+fn color_from_centroid(centroid: [f32; 3]) -> Color32 {
+    let r = centroid[0].clamp(0.0, 255.0).round() as u8;
+    let g = centroid[1].clamp(0.0, 255.0).round() as u8;
+    let b = centroid[2].clamp(0.0, 255.0).round() as u8;
+    Color32::from_rgb(r, g, b)
+}
+
+fn color_distance_sq(a: Color32, b: Color32) -> f32 {
+    let dr = a.r() as f32 - b.r() as f32;
+    let dg = a.g() as f32 - b.g() as f32;
+    let db = a.b() as f32 - b.b() as f32;
+    dr * dr + dg * dg + db * db
+}
+
+fn luminance(color: Color32) -> f32 {
+    0.2126 * color.r() as f32 + 0.7152 * color.g() as f32 + 0.0722 * color.b() as f32
+}
+
+fn order_by_luminance(a: Color32, b: Color32) -> (Color32, Color32) {
+    if luminance(a) <= luminance(b) {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// The artwork's two most common distinct colors, darker first. A near-monochrome
+/// cover, down to a single opaque pixel, gets a gradient around its one color instead;
+/// only artwork with no opaque pixels at all has no gradient.
+pub fn dominant_gradient_colors(
+    image: &ColorImage,
+    config: &PaletteConfig,
+) -> Option<[Color32; 2]> {
+    const MAX_SAMPLES: usize = 6_000;
+
+    let samples = sample_pixels(image, MAX_SAMPLES);
+    if samples.is_empty() {
+        return None;
+    }
+
+    let unique = distinct_colors(
+        &samples,
+        config.clusters(),
+        config.iterations(),
+        config.distinct_threshold(),
+    );
+    let primary = *unique.first()?;
+    let Some(&secondary) = unique.get(1) else {
+        // Near-monochrome cover: keep dynamic mode on with a gradient around its one
+        // color rather than dropping back to the static theme.
+        return Some(monochrome_gradient(primary));
+    };
+    let (start, end) = order_by_luminance(primary, secondary);
+    Some([start, end])
+}
+
+/// Cluster colors of `samples`, most common first, skipping any too close to one
+/// already picked (within a squared RGB distance of `threshold`).
+fn distinct_colors(
+    samples: &[[f32; 3]],
+    k: usize,
+    max_iter: usize,
+    threshold: f32,
+) -> Vec<Color32> {
+    let mut clusters = kmeans_clusters(samples, k.min(samples.len()).max(1), max_iter);
+    clusters.sort_by_key(|cluster| Reverse(cluster.count));
+
+    let mut unique: Vec<Color32> = Vec::new();
+    for cluster in clusters {
+        if cluster.count == 0 {
+            continue;
+        }
+        let color = color_from_centroid(cluster.centroid);
+        if unique
+            .iter()
+            .all(|&existing| color_distance_sq(existing, color) > threshold)
+        {
+            unique.push(color);
+        }
+    }
+    unique
+}
+
+/// Up to `count` distinct colors of the artwork, most common first.
+pub fn palette_colors(image: &ColorImage, count: usize, config: &PaletteConfig) -> Vec<Color32> {
+    const MAX_SAMPLES: usize = 6_000;
+
+    let samples = sample_pixels(image, MAX_SAMPLES);
+    if samples.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    // A couple of spare clusters leave room for near-duplicates to be dropped.
+    let mut colors = distinct_colors(
+        &samples,
+        count + 2,
+        config.iterations(),
+        config.distinct_threshold(),
+    );
+    colors.truncate(count);
+    colors
+}
+
+/// Gradient stops `elapsed` into a loop through `palette`: each step of `period`
+/// eases the pair `(c[i], c[i+1])` into `(c[i+1], c[i+2])`, wrapping around the
+/// palette. `None` with fewer than two colors, since there is nothing to cycle.
+pub fn cycle_gradient(
+    palette: &[Color32],
+    elapsed: Duration,
+    period: Duration,
+) -> Option<[Color32; 2]> {
+    let len = palette.len();
+    if len < 2 {
+        return None;
+    }
+    let color = |index: usize| palette[index % len];
+    let step = period.as_secs_f64().max(f64::EPSILON);
+    let position = elapsed.as_secs_f64() / step;
+    let index = (position.floor() as usize) % len;
+    let t = smoothstep(position.fract() as f32);
+    Some([
+        lerp_color(color(index), color(index + 1), t),
+        lerp_color(color(index + 1), color(index + 2), t),
+    ])
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp_color(from: Color32, to: Color32, t: f32) -> Color32 {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color32::from_rgba_unmultiplied(
+        channel(from.r(), to.r()),
+        channel(from.g(), to.g()),
+        channel(from.b(), to.b()),
+        channel(from.a(), to.a()),
+    )
+}
+
+/// Subtle gradient around a single `color`, darkest stop first. The light stop is pushed
+/// further until the stops differ by `MIN_SPREAD` luminance, so even black shows depth.
+fn monochrome_gradient(color: Color32) -> [Color32; 2] {
+    const LIGHTEN: f32 = 0.08;
+    const DARKEN: f32 = 0.15;
+    const MIN_SPREAD: f32 = 24.0;
+
+    let dark = darken(color, DARKEN);
+    let mut amount = LIGHTEN;
+    let mut light = lighten(color, amount);
+    while luminance(light) - luminance(dark) < MIN_SPREAD && amount < 1.0 {
+        amount = (amount + 0.04).min(1.0);
+        light = lighten(color, amount);
+    }
+    [dark, light]
+}
+
+/// HSV saturation, 0 for greys up to 1 for pure hues.
+fn saturation(color: Color32) -> f32 {
+    let max = color.r().max(color.g()).max(color.b());
+    let min = color.r().min(color.g()).min(color.b());
+    if max == 0 {
+        0.0
+    } else {
+        (max - min) as f32 / max as f32
+    }
+}
+
+/// Accent for `dynamic_accent` skins: the most saturated cluster that covers a
+/// noticeable share of the artwork and is bright enough to read as a color.
+fn accent_from_clusters(clusters: &[Cluster]) -> Option<Color32> {
+    const MIN_SHARE: f32 = 0.04;
+    const LUMINANCE_FLOOR: f32 = 40.0;
+    const MIN_SATURATION: f32 = 0.25;
+
+    let total: usize = clusters.iter().map(|cluster| cluster.count).sum();
+    clusters
+        .iter()
+        .filter(|cluster| cluster.count as f32 >= total as f32 * MIN_SHARE)
+        .map(|cluster| color_from_centroid(cluster.centroid))
+        .filter(|&color| luminance(color) >= LUMINANCE_FLOOR)
+        .map(|color| (color, saturation(color)))
+        .filter(|&(_, saturation)| saturation >= MIN_SATURATION)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(color, _)| color)
+}
+
+/// Accent color for the artwork, or `None` when no cluster is colorful enough.
+pub fn dynamic_accent_from_image(image: &ColorImage) -> Option<Color32> {
+    const MAX_SAMPLES: usize = 6_000;
+    const K: usize = 5;
+    const MAX_ITER: usize = 10;
+
+    let samples = sample_pixels(image, MAX_SAMPLES);
+    if samples.is_empty() {
+        return None;
+    }
+    accent_from_clusters(&kmeans_clusters(&samples, K.min(samples.len()), MAX_ITER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_colors_fall_back_for_monochrome_covers() {
+        let config = PaletteConfig::default();
+        let solid = |color: Color32| ColorImage::new([32, 32], vec![color; 32 * 32]);
+        let spread = |[start, end]: [Color32; 2]| luminance(end) - luminance(start);
+
+        let teal = dominant_gradient_colors(&solid(Color32::from_rgb(30, 120, 110)), &config)
+            .expect("single-color cover still gets a gradient");
+        assert!(spread(teal) >= 24.0);
+
+        let black = dominant_gradient_colors(&solid(Color32::BLACK), &config).expect("black cover");
+        assert_eq!(black[0], Color32::BLACK);
+        assert!(spread(black) >= 24.0 && luminance(black[1]) < 64.0);
+
+        let white = dominant_gradient_colors(&solid(Color32::WHITE), &config).expect("white cover");
+        assert_eq!(white[1], Color32::WHITE);
+        assert!(spread(white) >= 24.0);
+
+        // Placeholder art a player sends in place of a cover.
+        let pixel = ColorImage::new([1, 1], vec![Color32::from_rgb(90, 40, 120)]);
+        let tint = dominant_gradient_colors(&pixel, &config).expect("one-pixel cover");
+        assert!(spread(tint) >= 24.0);
+        let clear = ColorImage::new([4, 4], vec![Color32::TRANSPARENT; 16]);
+        assert!(dominant_gradient_colors(&clear, &config).is_none());
+
+        let mut pixels = vec![Color32::from_rgb(200, 40, 40); 32 * 16];
+        pixels.extend(vec![Color32::from_rgb(20, 20, 90); 32 * 16]);
+        let two_tone = dominant_gradient_colors(&ColorImage::new([32, 32], pixels), &config)
+            .expect("two-tone cover");
+        assert!(color_distance_sq(two_tone[0], Color32::from_rgb(20, 20, 90)) < 4.0);
+        assert!(color_distance_sq(two_tone[1], Color32::from_rgb(200, 40, 40)) < 4.0);
+    }
+
+    #[test]
+    fn distinct_threshold_decides_when_close_colors_merge() {
+        // Two reds 20 apart on one channel: a squared distance of 400.
+        let mut pixels = vec![Color32::from_rgb(180, 40, 40); 32 * 16];
+        pixels.extend(vec![Color32::from_rgb(160, 40, 40); 32 * 16]);
+        let image = ColorImage::new([32, 32], pixels);
+        let colors = |distinct_threshold| {
+            let config = PaletteConfig {
+                distinct_threshold,
+                ..PaletteConfig::default()
+            };
+            palette_colors(&image, 2, &config)
+        };
+
+        assert_eq!(colors(PaletteConfig::default().distinct_threshold).len(), 1);
+        assert_eq!(colors(100.0).len(), 2);
+        // Out-of-range values clamp instead of disabling clustering.
+        let wild = PaletteConfig {
+            clusters: 0,
+            iterations: 0,
+            distinct_threshold: -5.0,
+        };
+        assert_eq!((wild.clusters(), wild.iterations()), (2, 1));
+        assert_eq!(wild.distinct_threshold(), 0.0);
+    }
+
+    #[test]
+    fn artwork_accent_prefers_the_most_saturated_readable_cluster() {
+        let cluster = |rgb: [f32; 3], count: usize| Cluster {
+            centroid: rgb,
+            count,
+        };
+        // Mostly grey cover with a small but visible teal and a barely-there red speck.
+        let palette = [
+            cluster([120.0, 120.0, 124.0], 700),
+            cluster([20.0, 160.0, 150.0], 200),
+            cluster([60.0, 90.0, 140.0], 80),
+            cluster([250.0, 0.0, 0.0], 20),
+        ];
+        assert_eq!(
+            accent_from_clusters(&palette),
+            Some(Color32::from_rgb(20, 160, 150))
+        );
+
+        // Deeply saturated but nearly black clusters never qualify.
+        let dark = [
+            cluster([0.0, 0.0, 60.0], 500),
+            cluster([128.0, 128.0, 128.0], 500),
+        ];
+        assert_eq!(accent_from_clusters(&dark), None);
+        assert_eq!(accent_from_clusters(&[]), None);
+    }
+
+    #[test]
+    fn gradient_cycle_eases_through_adjacent_palette_pairs() {
+        let [a, b, c] = [
+            Color32::from_rgb(200, 0, 0),
+            Color32::from_rgb(0, 200, 0),
+            Color32::from_rgb(0, 0, 200),
+        ];
+        let step = Duration::from_secs(10);
+        let at = |secs: f64| cycle_gradient(&[a, b, c], Duration::from_secs_f64(secs), step);
+
+        assert_eq!(at(0.0), Some([a, b]));
+        assert_eq!(at(10.0), Some([b, c]));
+        assert_eq!(at(20.0), Some([c, a]));
+        // Wraps around after a full loop through the palette.
+        assert_eq!(at(30.0), at(0.0));
+        // Halfway through a step both stops sit midway; easing keeps the ends slow.
+        assert_eq!(
+            at(5.0),
+            Some([lerp_color(a, b, 0.5), lerp_color(b, c, 0.5)])
+        );
+        let [early, _] = at(1.0).unwrap();
+        assert!(early.g() < 20);
+
+        assert_eq!(cycle_gradient(&[a], Duration::ZERO, step), None);
+        assert_eq!(cycle_gradient(&[], Duration::ZERO, step), None);
+    }
+}
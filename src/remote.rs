@@ -0,0 +1,638 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+
+const MAX_BODY_BYTES: usize = 4 * 1024;
+const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+const EVENT_KEEPALIVE: Duration = Duration::from_secs(15);
+/// Connections served at once, `/events` streams included; more are turned away.
+const MAX_CONNECTIONS: usize = 16;
+
+/// Playback change pushed to `/events` subscribers as a Server-Sent Event.
+#[derive(Debug, Clone)]
+pub struct PlaybackEvent<'a> {
+    pub kind: &'static str,
+    /// `NowPlaying::track_id`; `None` while nothing is playing.
+    pub track_id: Option<u64>,
+    pub title: &'a str,
+    pub artist: &'a str,
+    pub album: &'a str,
+    pub state: &'a str,
+    pub position_secs: Option<f64>,
+    pub duration_secs: Option<f64>,
+}
+
+impl PlaybackEvent<'_> {
+    fn to_json(&self) -> String {
+        let number = |value: Option<f64>| {
+            value
+                .filter(|v| v.is_finite())
+                .map(|v| format!("{v:.3}"))
+                .unwrap_or_else(|| "null".to_string())
+        };
+        format!(
+            "{{\"event\":{},\"track_id\":{},\"title\":{},\"artist\":{},\"album\":{},\"state\":{},\"position\":{},\"duration\":{}}}",
+            json_string(self.kind),
+            self.track_id
+                .map(|id| json_string(&format!("{id:016x}")))
+                .unwrap_or_else(|| "null".to_string()),
+            json_string(self.title),
+            json_string(self.artist),
+            json_string(self.album),
+            json_string(self.state),
+            number(self.position_secs),
+            number(self.duration_secs)
+        )
+    }
+
+    fn to_sse_frame(&self) -> String {
+        format!("event: {}\ndata: {}\n\n", self.kind, self.to_json())
+    }
+}
+
+#[derive(Default)]
+struct EventHub {
+    subscribers: Vec<Sender<String>>,
+    last_frame: Option<String>,
+}
+
+/// Commands accepted by the local automation endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    ListSkins,
+    ListLayouts,
+    SetSkin(String),
+    SetLayout(String),
+}
+
+impl RemoteCommand {
+    /// Parses `--send` arguments: `list-skins`, `list-layouts`, `set-skin <id>`, or
+    /// `set-layout <id>`.
+    pub fn from_cli_args(args: &[String]) -> std::result::Result<Self, String> {
+        let id = || {
+            args.get(1)
+                .filter(|id| !id.trim().is_empty())
+                .cloned()
+                .ok_or_else(|| format!("{} needs an id", args[0]))
+        };
+        match args.first().map(String::as_str) {
+            Some("list-skins") => Ok(RemoteCommand::ListSkins),
+            Some("list-layouts") => Ok(RemoteCommand::ListLayouts),
+            Some("set-skin") => id().map(RemoteCommand::SetSkin),
+            Some("set-layout") => id().map(RemoteCommand::SetLayout),
+            Some(other) => Err(format!(
+                "Unknown command '{other}'; expected list-skins, list-layouts, set-skin, or set-layout"
+            )),
+            None => Err("--send needs a command".to_string()),
+        }
+    }
+
+    /// Method, path, and body of the HTTP request that carries this command.
+    fn to_request(&self) -> (&'static str, &'static str, &str) {
+        match self {
+            RemoteCommand::ListSkins => ("GET", "/skins", ""),
+            RemoteCommand::ListLayouts => ("GET", "/layouts", ""),
+            RemoteCommand::SetSkin(id) => ("POST", "/skin", id),
+            RemoteCommand::SetLayout(id) => ("POST", "/layout", id),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteEntry {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum RemoteResponse {
+    Skins {
+        current: Option<String>,
+        available: Vec<RemoteEntry>,
+    },
+    Layouts {
+        current: String,
+        available: Vec<RemoteEntry>,
+    },
+    Applied {
+        kind: &'static str,
+        id: String,
+    },
+    Rejected {
+        message: String,
+        available: Vec<RemoteEntry>,
+    },
+}
+
+impl RemoteResponse {
+    fn status(&self) -> u16 {
+        match self {
+            RemoteResponse::Rejected { .. } => 404,
+            _ => 200,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            RemoteResponse::Skins { current, available } => format!(
+                "{{\"current\":{},\"skins\":{}}}",
+                current
+                    .as_deref()
+                    .map(json_string)
+                    .unwrap_or_else(|| "null".to_string()),
+                entries_json(available)
+            ),
+            RemoteResponse::Layouts { current, available } => format!(
+                "{{\"current\":{},\"layouts\":{}}}",
+                json_string(current),
+                entries_json(available)
+            ),
+            RemoteResponse::Applied { kind, id } => {
+                format!("{{\"ok\":true,{}:{}}}", json_string(kind), json_string(id))
+            }
+            RemoteResponse::Rejected { message, available } => format!(
+                "{{\"error\":{},\"available\":{}}}",
+                json_string(message),
+                entries_json(available)
+            ),
+        }
+    }
+}
+
+/// A command waiting for the UI thread, paired with the channel its answer goes back on.
+pub struct RemoteRequest {
+    pub command: RemoteCommand,
+    reply: Sender<RemoteResponse>,
+}
+
+impl RemoteRequest {
+    pub fn respond(self, response: RemoteResponse) {
+        let _ = self.reply.send(response);
+    }
+}
+
+/// Localhost-only HTTP listener that forwards skin/layout commands to the running app.
+///
+/// Requests are queued on a channel that `App::update` drains; the connection thread
+/// waits for the UI thread to answer before writing the HTTP response.
+pub struct RemoteServer {
+    requests: Receiver<RemoteRequest>,
+    events: Arc<Mutex<EventHub>>,
+    port: u16,
+}
+
+impl RemoteServer {
+    pub fn start(port: u16) -> Result<Self> {
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let listener = TcpListener::bind(addr)
+            .with_context(|| format!("Failed to bind remote control port {port}"))?;
+        let port = listener.local_addr().map(|a| a.port()).unwrap_or(port);
+        let (tx, rx) = mpsc::channel();
+        let events = Arc::new(Mutex::new(EventHub::default()));
+        let hub = Arc::clone(&events);
+
+        thread::spawn(move || {
+            let active = Arc::new(AtomicUsize::new(0));
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let Some(slot) = ConnectionSlot::claim(&active) else {
+                    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+                    let _ =
+                        write_response(&mut stream, 503, "{\"error\":\"Too many connections\"}");
+                    continue;
+                };
+                let tx = tx.clone();
+                let hub = Arc::clone(&hub);
+                thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(err) = handle_connection(stream, &tx, &hub) {
+                        eprintln!("Remote control request failed: {err}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            requests: rx,
+            events,
+            port,
+        })
+    }
+
+    /// Pushes an event to every connected `/events` client, dropping any that went away.
+    pub fn broadcast(&self, event: &PlaybackEvent) {
+        let frame = event.to_sse_frame();
+        let Ok(mut hub) = self.events.lock() else {
+            return;
+        };
+        hub.subscribers
+            .retain(|subscriber| subscriber.send(frame.clone()).is_ok());
+        hub.last_frame = Some(frame);
+    }
+
+    #[allow(dead_code)]
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn poll(&self) -> Vec<RemoteRequest> {
+        self.requests.try_iter().collect()
+    }
+}
+
+/// One of the [`MAX_CONNECTIONS`] connection threads, given back when dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn claim(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Delivers `command` to a widget already listening on `port` and returns the response
+/// status and JSON body.
+pub fn send_command(port: u16, command: &RemoteCommand) -> Result<(u16, String)> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&addr, IO_TIMEOUT)
+        .with_context(|| format!("No widget is listening on port {port}"))?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT + IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let (method, path, body) = command.to_request();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    parse_response(&response).context("Malformed response from the widget")
+}
+
+fn parse_response(response: &str) -> Option<(u16, String)> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.split_whitespace().nth(1)?.parse().ok()?;
+    Some((status, body.to_string()))
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    tx: &Sender<RemoteRequest>,
+    hub: &Mutex<EventHub>,
+) -> Result<()> {
+    // Before anything is read, so a client that never sends can't hold the slot.
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    let peer = stream.peer_addr()?;
+    if !peer.ip().is_loopback() {
+        return write_response(&mut stream, 403, "{\"error\":\"Forbidden\"}");
+    }
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut content_length = 0usize;
+    let mut host = None;
+    let mut has_origin = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_string());
+            } else if name.eq_ignore_ascii_case("origin") {
+                has_origin = true;
+            }
+        }
+    }
+
+    // Browsers send an Origin on cross-site requests and keep the page's host name
+    // across DNS rebinding, so neither gets through to the UI.
+    if has_origin || !host.as_deref().is_some_and(is_loopback_host) {
+        return write_response(&mut stream, 403, "{\"error\":\"Forbidden\"}");
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return write_response(&mut stream, 413, "{\"error\":\"Request body too large\"}");
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body);
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    if method == "GET" && path.split('?').next() == Some("/events") {
+        return stream_events(stream, hub);
+    }
+
+    let command = match parse_command(method, path, &body) {
+        Ok(command) => command,
+        Err((status, message)) => {
+            let body = format!("{{\"error\":{}}}", json_string(message));
+            return write_response(&mut stream, status, &body);
+        }
+    };
+
+    let (reply_tx, reply_rx) = mpsc::channel();
+    if tx
+        .send(RemoteRequest {
+            command,
+            reply: reply_tx,
+        })
+        .is_err()
+    {
+        return write_response(&mut stream, 503, "{\"error\":\"Widget is shutting down\"}");
+    }
+
+    match reply_rx.recv_timeout(REPLY_TIMEOUT) {
+        Ok(response) => write_response(&mut stream, response.status(), &response.to_json()),
+        Err(_) => write_response(&mut stream, 504, "{\"error\":\"Widget did not respond\"}"),
+    }
+}
+
+fn stream_events(mut stream: TcpStream, hub: &Mutex<EventHub>) -> Result<()> {
+    let (event_tx, event_rx) = mpsc::channel();
+    let replay = {
+        let mut hub = hub
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Event hub poisoned"))?;
+        hub.subscribers.push(event_tx);
+        hub.last_frame.clone()
+    };
+
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    if let Some(frame) = replay {
+        stream.write_all(frame.as_bytes())?;
+    }
+    stream.flush()?;
+
+    loop {
+        let chunk = match event_rx.recv_timeout(EVENT_KEEPALIVE) {
+            Ok(frame) => frame,
+            Err(RecvTimeoutError::Timeout) => ": keep-alive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+        if stream
+            .write_all(chunk.as_bytes())
+            .and_then(|_| stream.flush())
+            .is_err()
+        {
+            // Client disconnected; the hub drops our sender on the next broadcast.
+            return Ok(());
+        }
+    }
+}
+
+fn parse_command(
+    method: &str,
+    path: &str,
+    body: &str,
+) -> std::result::Result<RemoteCommand, (u16, &'static str)> {
+    let path = path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    match (method, path) {
+        ("GET", "/skins") | ("GET", "/skin") => Ok(RemoteCommand::ListSkins),
+        ("GET", "/layouts") | ("GET", "/layout") => Ok(RemoteCommand::ListLayouts),
+        ("POST", "/skin") => parse_id_body(body)
+            .map(RemoteCommand::SetSkin)
+            .ok_or((400, "Expected a skin id in the request body")),
+        ("POST", "/layout") => parse_id_body(body)
+            .map(RemoteCommand::SetLayout)
+            .ok_or((400, "Expected a layout id in the request body")),
+        (_, "/skin" | "/skins" | "/layout" | "/layouts") => Err((405, "Method not allowed")),
+        _ => Err((404, "Not found")),
+    }
+}
+
+/// Whether a `Host` header names this machine: `localhost` or a loopback address, with
+/// or without a port.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Accepts either a bare id (`art_top`) or a small JSON object (`{"id": "art_top"}`).
+fn parse_id_body(body: &str) -> Option<String> {
+    let trimmed = body.trim();
+    let id = if trimmed.starts_with('{') {
+        let key_pos = trimmed.find("\"id\"")?;
+        let rest = trimmed[key_pos + 4..].trim_start().strip_prefix(':')?;
+        let rest = rest.trim_start().strip_prefix('"')?;
+        let end = rest.find('"')?;
+        &rest[..end]
+    } else {
+        trimmed.trim_matches('"')
+    };
+
+    let id = id.trim();
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes())?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn entries_json(entries: &[RemoteEntry]) -> String {
+    let items: Vec<String> = entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{{\"id\":{},\"name\":{}}}",
+                json_string(&entry.id),
+                json_string(&entry.name)
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn id_body_accepts_plain_and_json() {
+        assert_eq!(parse_id_body("art_top\n"), Some("art_top".to_string()));
+        assert_eq!(
+            parse_id_body("{\"id\": \"graphite\"}"),
+            Some("graphite".to_string())
+        );
+        assert_eq!(parse_id_body("{\"name\": \"x\"}"), None);
+        assert_eq!(parse_id_body("   "), None);
+    }
+
+    #[test]
+    fn routes_map_to_commands() {
+        assert_eq!(
+            parse_command("POST", "/layout", "mini"),
+            Ok(RemoteCommand::SetLayout("mini".to_string()))
+        );
+        assert_eq!(
+            parse_command("GET", "/skins/", ""),
+            Ok(RemoteCommand::ListSkins)
+        );
+        assert_eq!(parse_command("DELETE", "/skin", "").unwrap_err().0, 405);
+        assert_eq!(parse_command("GET", "/nope", "").unwrap_err().0, 404);
+    }
+
+    #[test]
+    fn only_loopback_hosts_are_accepted() {
+        assert!(is_loopback_host("127.0.0.1:7315"));
+        assert!(is_loopback_host("localhost"));
+        assert!(is_loopback_host("LocalHost:7315"));
+        assert!(is_loopback_host("[::1]:7315"));
+        assert!(!is_loopback_host("evil.example:7315"));
+        assert!(!is_loopback_host("192.168.1.4"));
+        assert!(!is_loopback_host(""));
+    }
+
+    #[test]
+    fn connection_slots_are_capped_and_given_back() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| ConnectionSlot::claim(&active).expect("under the cap"))
+            .collect();
+        assert!(ConnectionSlot::claim(&active).is_none());
+        drop(slots);
+        assert_eq!(active.load(Ordering::SeqCst), 0);
+        assert!(ConnectionSlot::claim(&active).is_some());
+    }
+
+    #[test]
+    fn cli_args_map_to_commands() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            RemoteCommand::from_cli_args(&args(&["set-layout", "mini"])),
+            Ok(RemoteCommand::SetLayout("mini".to_string()))
+        );
+        assert_eq!(
+            RemoteCommand::from_cli_args(&args(&["list-skins"])),
+            Ok(RemoteCommand::ListSkins)
+        );
+        assert_eq!(
+            RemoteCommand::from_cli_args(&args(&["set-skin"])),
+            Err("set-skin needs an id".to_string())
+        );
+        assert!(RemoteCommand::from_cli_args(&args(&["reboot"])).is_err());
+        assert!(RemoteCommand::from_cli_args(&[]).is_err());
+    }
+
+    #[test]
+    fn responses_split_into_status_and_body() {
+        let response = "HTTP/1.1 404 Not Found\r\nContent-Length: 2\r\n\r\n{}";
+        assert_eq!(parse_response(response), Some((404, "{}".to_string())));
+        assert_eq!(parse_response("garbage"), None);
+    }
+
+    #[test]
+    fn playback_events_serialize_as_sse_frames() {
+        let event = PlaybackEvent {
+            kind: "track",
+            track_id: Some(0xab),
+            title: "Song",
+            artist: "Artist",
+            album: "",
+            state: "Playing",
+            position_secs: Some(1.5),
+            duration_secs: None,
+        };
+        assert_eq!(
+            event.to_sse_frame(),
+            "event: track\ndata: {\"event\":\"track\",\"track_id\":\"00000000000000ab\",\"title\":\"Song\",\"artist\":\"Artist\",\"album\":\"\",\"state\":\"Playing\",\"position\":1.500,\"duration\":null}\n\n"
+        );
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+}
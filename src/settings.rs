@@ -0,0 +1,908 @@
+//! The settings window and the overlay's pin and gear buttons. The panel edits the
+//! fields App lends it through [`SettingsModel`] and hands anything with side effects
+//! back as [`SettingsRequest`]s, which App applies once the frame's UI is built.
+
+use std::collections::HashMap;
+
+use eframe::egui::{self, CornerRadius};
+
+use crate::{
+    config::{ArtworkClick, Config, UiConfig},
+    layout::{collect_component_visibility, LayoutComponent},
+    overlay_controls_anchor, overlay_corner_label,
+    theme::{OverlayControlsPosition, Theme},
+    toast::ToastLevel,
+    ui_skin::SkinManager,
+    REFRESH_SHORTCUT, SCREENSHOT_SHORTCUT,
+};
+
+const PANEL_MAX_WIDTH: f32 = 360.0;
+const PANEL_ITEM_SPACING: f32 = 18.0;
+const PANEL_PADDING_X: i8 = 20;
+const PANEL_PADDING_Y: i8 = 18;
+const PANEL_CORNER_RADIUS: u8 = 14;
+const CONTROL_SPACING: f32 = 12.0;
+const SECTION_GAP: f32 = 24.0;
+const HEADER_GAP: f32 = 8.0;
+
+/// Something the panel asked for that reaches past the fields it was lent.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SettingsRequest {
+    Skin(String),
+    Layout(String),
+    /// Switch layouts by window width.
+    LayoutAuto,
+    /// Forget the layout picked for this skin and use the one it starts with.
+    LayoutDefault,
+    /// `None` goes back to the skin's accent.
+    Accent(Option<egui::Color32>),
+    /// `None` follows the system scale.
+    UiScale(Option<f32>),
+    ComponentVisible {
+        component: LayoutComponent,
+        visible: bool,
+        skin_visible: bool,
+    },
+    ResetComponents,
+    SelectableText(bool),
+    TrackFlash(bool),
+    AccentFromArt(bool),
+    LongFormMinutes(u32),
+    NeverVinyl(bool),
+    Vinyl(bool),
+    VinylReverse(bool),
+    ReloadSkins,
+    ImportSkin,
+    CopyDiagnostics,
+    Refresh,
+    Screenshot,
+    /// A plain setting changed in the lent config; the label names it in save errors.
+    SaveConfig(&'static str),
+}
+
+/// What the panel may read and change on App for one frame.
+pub(crate) struct SettingsModel<'a> {
+    pub(crate) skin_manager: &'a SkinManager,
+    pub(crate) config: &'a mut Config,
+    pub(crate) always_on_top: &'a mut bool,
+    pub(crate) window_decorations_hidden: &'a mut bool,
+    pub(crate) show_pin_button: &'a mut bool,
+    pub(crate) layout_auto: bool,
+    /// The skins folder vanished, so reloading reads as a retry.
+    pub(crate) skins_root_lost: bool,
+    pub(crate) overlay_position: OverlayControlsPosition,
+    /// Element visibility the user set over the skin's, by component.
+    pub(crate) component_overrides: HashMap<LayoutComponent, bool>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct SettingsPanel {
+    open: bool,
+    /// UI scale percent while its slider is dragged; applied on release.
+    scale_draft: Option<f32>,
+    /// The accent picker changed the color and the pointer hasn't let go yet.
+    accent_unsaved: bool,
+    /// Debug builds only: how the current skin differs from the built-in default.
+    theme_diff: Option<Vec<String>>,
+    requests: Vec<SettingsRequest>,
+}
+
+impl SettingsPanel {
+    pub(crate) fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub(crate) fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Recomputes the skin-vs-default diff if it is on screen.
+    pub(crate) fn refresh_theme_diff(&mut self, theme: &Theme) {
+        if self.theme_diff.is_some() {
+            self.theme_diff = Some(theme.diff(&Theme::default_dark()));
+        }
+    }
+
+    fn toggle_theme_diff(&mut self, theme: &Theme) {
+        self.theme_diff = match self.theme_diff {
+            Some(_) => None,
+            None => Some(theme.diff(&Theme::default_dark())),
+        };
+    }
+
+    /// Draws the overlay buttons and, when open, the settings window. Returns what the
+    /// user asked for this frame, in the order they asked.
+    pub(crate) fn show(
+        &mut self,
+        ctx: &egui::Context,
+        model: &mut SettingsModel,
+    ) -> Vec<SettingsRequest> {
+        self.show_overlay_buttons(ctx, model);
+        if self.open {
+            self.show_window(ctx, model);
+        }
+        std::mem::take(&mut self.requests)
+    }
+
+    fn show_overlay_buttons(&mut self, ctx: &egui::Context, model: &mut SettingsModel) {
+        let overlay_style = &model.skin_manager.current_theme().overlay_controls;
+        if !overlay_style.visible {
+            return;
+        }
+        let (anchor, offset) = overlay_controls_anchor(model.overlay_position);
+        egui::Area::new(egui::Id::new("overlay-controls"))
+            .anchor(anchor, offset)
+            .order(egui::Order::Foreground)
+            .interactable(true)
+            .movable(false)
+            .show(ctx, |overlay| {
+                overlay.spacing_mut().item_spacing.x = 6.0;
+                overlay.horizontal(|row| {
+                    row.spacing_mut().item_spacing.x = 6.0;
+
+                    let icon_button = |ui: &mut egui::Ui, icon: &str, tooltip: &str, active| {
+                        let icon_size = ui
+                            .style()
+                            .text_styles
+                            .get(&egui::TextStyle::Body)
+                            .map(|style| style.size)
+                            .unwrap_or(14.0);
+                        let desired_size = egui::Vec2::splat(icon_size + 8.0);
+                        let (rect, response) =
+                            ui.allocate_exact_size(desired_size, egui::Sense::click());
+
+                        if response.hovered() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                        }
+
+                        let visuals = ui.visuals();
+                        let fg_color = if active {
+                            overlay_style
+                                .active_color
+                                .unwrap_or(visuals.widgets.active.fg_stroke.color)
+                        } else {
+                            overlay_style
+                                .color
+                                .unwrap_or(visuals.widgets.inactive.fg_stroke.color)
+                        };
+
+                        ui.painter_at(rect).text(
+                            rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            icon,
+                            egui::FontId::proportional(icon_size),
+                            fg_color,
+                        );
+
+                        response.on_hover_text(tooltip)
+                    };
+
+                    if *model.show_pin_button {
+                        let pinned = *model.always_on_top;
+                        let (pin_icon, pin_tooltip) = if pinned {
+                            ("📌", "Unpin window")
+                        } else {
+                            ("📍", "Pin window (stay on top)")
+                        };
+                        if icon_button(row, pin_icon, pin_tooltip, pinned).clicked() {
+                            *model.always_on_top = !pinned;
+                        }
+                    }
+
+                    let gear_tooltip = if self.open {
+                        "Hide settings"
+                    } else {
+                        "Show settings"
+                    };
+                    if icon_button(row, "⚙", gear_tooltip, self.open).clicked() {
+                        self.open = !self.open;
+                    }
+                });
+            });
+    }
+
+    fn show_window(&mut self, ctx: &egui::Context, model: &mut SettingsModel) {
+        egui::Window::new("Settings")
+            .id(egui::Id::new("settings-window"))
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .frame(window_frame(ctx))
+            .fixed_size([PANEL_MAX_WIDTH, 0.0])
+            .show(ctx, |panel| {
+                let content_width = PANEL_MAX_WIDTH - 2.0 * f32::from(PANEL_PADDING_X);
+                panel.set_min_width(PANEL_MAX_WIDTH);
+                panel.set_max_width(PANEL_MAX_WIDTH);
+                panel.spacing_mut().item_spacing = egui::vec2(0.0, PANEL_ITEM_SPACING);
+
+                panel.horizontal(|row| {
+                    row.spacing_mut().item_spacing.x = 12.0;
+                    row.label(egui::RichText::new("Settings").heading());
+
+                    row.allocate_ui_with_layout(
+                        egui::vec2(row.available_width(), 0.0),
+                        egui::Layout::right_to_left(egui::Align::Center),
+                        |actions| {
+                            let close_icon = egui::RichText::new("×").size(18.0);
+                            let close = actions
+                                .add(egui::Label::new(close_icon).sense(egui::Sense::click()))
+                                .on_hover_text("Close settings");
+                            if close.hovered() {
+                                actions
+                                    .ctx()
+                                    .set_cursor_icon(egui::CursorIcon::PointingHand);
+                            }
+                            if close.clicked() {
+                                self.open = false;
+                            }
+                        },
+                    );
+                });
+
+                panel.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(420.0)
+                    .show(panel, |scroll| {
+                        scroll.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
+                        scroll.set_min_width(content_width);
+                        scroll.set_max_width(content_width);
+
+                        settings_section(scroll, "Window", content_width, |section| {
+                            self.window_section(section, model, content_width)
+                        });
+                        settings_separator(scroll);
+                        settings_section(scroll, "Appearance", content_width, |section| {
+                            self.appearance_section(section, model, content_width)
+                        });
+                        settings_separator(scroll);
+                        settings_section(scroll, "Playback", content_width, |section| {
+                            self.playback_section(section, model)
+                        });
+                        settings_separator(scroll);
+                        settings_section(scroll, "Artwork", content_width, |section| {
+                            self.artwork_section(section, model, content_width)
+                        });
+                        settings_separator(scroll);
+                        settings_section(scroll, "Skins", content_width, |section| {
+                            self.skins_section(section, model)
+                        });
+                        settings_separator(scroll);
+                        settings_section(scroll, "About", content_width, |section| {
+                            self.about_section(section, model)
+                        });
+                    });
+            });
+    }
+
+    fn window_section(&mut self, section: &mut egui::Ui, model: &mut SettingsModel, width: f32) {
+        let skins = model.skin_manager;
+        let toggle_label = if *model.window_decorations_hidden {
+            "Show window title bar"
+        } else {
+            "Hide window title bar"
+        };
+        if skins.skin_button(section, toggle_label).clicked() {
+            *model.window_decorations_hidden = !*model.window_decorations_hidden;
+        }
+
+        let pin_toggle_label = if *model.always_on_top {
+            "Disable stay-on-top"
+        } else {
+            "Pin window (stay on top)"
+        };
+        if skins
+            .skin_button(section, pin_toggle_label)
+            .on_hover_text("Keep the widget above other application windows.")
+            .clicked()
+        {
+            *model.always_on_top = !*model.always_on_top;
+        }
+
+        #[cfg(target_os = "windows")]
+        if section
+            .checkbox(
+                &mut model.config.ui.all_desktops,
+                "Show on all virtual desktops",
+            )
+            .on_hover_text("Follow you when you switch desktops.")
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::SaveConfig("virtual desktop setting"));
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            if section
+                .checkbox(
+                    &mut model.config.ui.resnap_on_display_change,
+                    "Re-snap to corner on display changes",
+                )
+                .on_hover_text(
+                    "Move back to the corner last picked under \"Move to\" when the \
+                     resolution changes.",
+                )
+                .changed()
+            {
+                self.requests
+                    .push(SettingsRequest::SaveConfig("re-snap setting"));
+            }
+        }
+
+        section
+            .checkbox(model.show_pin_button, "Show pin button in overlay")
+            .on_hover_text("Disable to hide the pin toggle from the top overlay.");
+
+        let mut selectable_text = model.config.ui.selectable_text;
+        if section
+            .checkbox(&mut selectable_text, "Selectable text")
+            .on_hover_text(
+                "Highlight and copy the title and artist. Dragging on the text no longer \
+                 moves the window.",
+            )
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::SelectableText(selectable_text));
+        }
+
+        let overlay_corner = model.config.ui.overlay_corner;
+        let mut requested_corner = overlay_corner;
+        egui::ComboBox::from_id_salt("overlay-corner")
+            .width(width)
+            .selected_text(format!(
+                "Overlay buttons: {}",
+                overlay_corner.map_or("skin default", overlay_corner_label)
+            ))
+            .show_ui(section, |combo| {
+                combo.selectable_value(&mut requested_corner, None, "Skin default");
+                for corner in OverlayControlsPosition::ALL {
+                    combo.selectable_value(
+                        &mut requested_corner,
+                        Some(corner),
+                        overlay_corner_label(corner),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Where the pin and settings buttons sit.");
+        if requested_corner != overlay_corner {
+            model.config.ui.overlay_corner = requested_corner;
+            self.requests
+                .push(SettingsRequest::SaveConfig("overlay corner"));
+        }
+
+        let mut flash_on_track_change = model.config.ui.flash_on_track_change;
+        if section
+            .checkbox(&mut flash_on_track_change, "Flash taskbar on track change")
+            .on_hover_text("Only while the widget is in the background.")
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::TrackFlash(flash_on_track_change));
+        }
+
+        let mut auto_scale = model.config.ui.scale().is_none();
+        if section
+            .checkbox(&mut auto_scale, "Follow system scale")
+            .on_hover_text("Use the display's DPI setting.")
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::UiScale((!auto_scale).then_some(1.0)));
+        }
+        if !auto_scale {
+            let mut percent = self
+                .scale_draft
+                .unwrap_or_else(|| model.config.ui.scale().unwrap_or(1.0) * 100.0);
+            let response = section.add(
+                egui::Slider::new(
+                    &mut percent,
+                    UiConfig::MIN_SCALE * 100.0..=UiConfig::MAX_SCALE * 100.0,
+                )
+                .step_by(5.0)
+                .suffix("%")
+                .text("UI scale"),
+            );
+            // Rescaling mid-drag moves the slider under the pointer, so apply once the
+            // drag ends.
+            if response.dragged() {
+                self.scale_draft = Some(percent);
+            } else if response.drag_stopped() || response.changed() {
+                self.scale_draft = None;
+                self.requests
+                    .push(SettingsRequest::UiScale(Some(percent / 100.0)));
+            }
+        }
+
+        section.label(if *model.window_decorations_hidden {
+            "Title bar hidden. Use the app body to drag the window."
+        } else {
+            "Hiding the title bar removes the OS chrome."
+        });
+    }
+
+    fn appearance_section(
+        &mut self,
+        section: &mut egui::Ui,
+        model: &mut SettingsModel,
+        width: f32,
+    ) {
+        let skins = model.skin_manager;
+        let current_skin_id = skins.current_skin_id();
+        egui::ComboBox::from_id_salt("skin-select")
+            .width(width)
+            .selected_text(skins.current_skin_display_name())
+            .show_ui(section, |combo| {
+                if current_skin_id.is_none() {
+                    combo.label("Embedded default");
+                }
+                for info in skins.skin_list() {
+                    let selected = current_skin_id == Some(info.id.as_str());
+                    if combo
+                        .selectable_label(selected, &info.display_name)
+                        .clicked()
+                        && !selected
+                    {
+                        self.requests.push(SettingsRequest::Skin(info.id.clone()));
+                    }
+                }
+            });
+
+        let layout_options = skins.layout_options();
+        if layout_options.len() > 1 {
+            let layout_auto = model.layout_auto;
+            let has_layout_preference = skins.has_layout_preference();
+            let current_layout_display = skins.current_layout_display_name();
+            let selected_text = if layout_auto {
+                format!("Auto ({current_layout_display})")
+            } else {
+                current_layout_display.to_string()
+            };
+            egui::ComboBox::from_id_salt("layout-select")
+                .width(width)
+                .selected_text(selected_text)
+                .show_ui(section, |combo| {
+                    if skins.has_responsive_layouts()
+                        && combo
+                            .selectable_label(layout_auto, "Auto")
+                            .on_hover_text("Switch layouts by window width.")
+                            .clicked()
+                        && !layout_auto
+                    {
+                        self.requests.push(SettingsRequest::LayoutAuto);
+                    }
+                    let skin_default = !layout_auto && !has_layout_preference;
+                    if combo
+                        .selectable_label(skin_default, "(skin default)")
+                        .on_hover_text("Use the layout this skin starts with.")
+                        .clicked()
+                        && !skin_default
+                    {
+                        self.requests.push(SettingsRequest::LayoutDefault);
+                    }
+                    for option in layout_options {
+                        let selected = !layout_auto
+                            && has_layout_preference
+                            && option.id == skins.current_layout_id();
+                        if combo
+                            .selectable_label(selected, &option.display_name)
+                            .clicked()
+                            && !selected
+                        {
+                            self.requests
+                                .push(SettingsRequest::Layout(option.id.clone()));
+                        }
+                    }
+                });
+        } else if let Some(option) = layout_options.first() {
+            section.label(format!("Layout: {}", option.display_name));
+        }
+
+        section.horizontal(|row| {
+            let accent_override = skins.accent_override();
+            let mut accent = accent_override
+                .or_else(|| skins.current_theme().colors.get("accent").copied())
+                .unwrap_or(egui::Color32::from_rgb(0, 120, 212));
+            row.label("Accent color");
+            if row.color_edit_button_srgba(&mut accent).changed() {
+                self.requests.push(SettingsRequest::Accent(Some(accent)));
+                self.accent_unsaved = true;
+            }
+            // A drag changes the color every frame; write the file once it ends.
+            if self.accent_unsaved && !row.input(|i| i.pointer.any_down()) {
+                self.accent_unsaved = false;
+                self.requests
+                    .push(SettingsRequest::SaveConfig("accent color"));
+            }
+            if accent_override.is_some() && row.button("Use skin colors").clicked() {
+                self.requests.push(SettingsRequest::Accent(None));
+                self.requests
+                    .push(SettingsRequest::SaveConfig("accent color"));
+            }
+        });
+
+        let mut accent_from_art = model.config.ui.accent_from_art;
+        if section
+            .checkbox(&mut accent_from_art, "Accent from artwork")
+            .on_hover_text(
+                "Tint buttons and the seek bar with a color from the album art. The \
+                 background keeps the skin's colors.",
+            )
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::AccentFromArt(accent_from_art));
+        }
+
+        if section
+            .checkbox(
+                &mut model.config.ui.follow_system_theme,
+                "Auto skin follows the system theme",
+            )
+            .on_hover_text(
+                "The Auto skin turns light or dark with the Windows app mode. Off, it \
+                 stays dark.",
+            )
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::SaveConfig("system theme setting"));
+        }
+
+        let overrides = &model.component_overrides;
+        section.horizontal(|row| {
+            row.label("Elements");
+            if !overrides.is_empty() {
+                row.label(egui::RichText::new("customized").small().weak());
+                if row
+                    .small_button("Reset")
+                    .on_hover_text("Go back to the skin's elements")
+                    .clicked()
+                {
+                    self.requests.push(SettingsRequest::ResetComponents);
+                }
+            }
+        });
+        let layout_components = collect_component_visibility(&skins.current_layout_variant().root);
+        for (component, skin_visible) in layout_components {
+            let mut visible = overrides.get(&component).copied().unwrap_or(skin_visible);
+            let mut checkbox = section.checkbox(&mut visible, component.display_name());
+            if !skin_visible {
+                checkbox = checkbox.on_hover_text("Hidden by the skin");
+            }
+            if checkbox.changed() {
+                self.requests.push(SettingsRequest::ComponentVisible {
+                    component,
+                    visible,
+                    skin_visible,
+                });
+            }
+        }
+
+        if section
+            .checkbox(
+                &mut model.config.ui.hide_unavailable_controls,
+                "Hide controls the player can't use",
+            )
+            .on_hover_text(
+                "Hide the playback buttons while the current player accepts no commands.",
+            )
+            .changed()
+        {
+            self.requests.push(SettingsRequest::SaveConfig(
+                "hide unavailable controls setting",
+            ));
+        }
+    }
+
+    fn playback_section(&mut self, section: &mut egui::Ui, model: &mut SettingsModel) {
+        section.horizontal(|row| {
+            let mut minutes = model.config.ui.long_form_minutes;
+            row.label("Long-form after");
+            if row
+                .add(
+                    egui::DragValue::new(&mut minutes)
+                        .range(1..=600)
+                        .suffix(" min"),
+                )
+                .on_hover_text(
+                    "Longer tracks, and podcast or audiobook genres, show remaining time and \
+                     chapter ticks on the timeline.",
+                )
+                .changed()
+            {
+                self.requests
+                    .push(SettingsRequest::LongFormMinutes(minutes));
+            }
+        });
+
+        let ui_config = &mut model.config.ui;
+        section.horizontal(|row| {
+            let toggled = row
+                .checkbox(&mut ui_config.announce_position, "Announce position every")
+                .on_hover_text(
+                    "Screen readers read out the elapsed and total time while playing, \
+                     without focusing the timeline.",
+                )
+                .changed();
+            let retimed = row
+                .add_enabled(
+                    ui_config.announce_position,
+                    egui::DragValue::new(&mut ui_config.announce_position_secs)
+                        .range(5..=600)
+                        .suffix(" s"),
+                )
+                .changed();
+            if toggled || retimed {
+                self.requests
+                    .push(SettingsRequest::SaveConfig("position announcement setting"));
+            }
+        });
+    }
+
+    fn artwork_section(&mut self, section: &mut egui::Ui, model: &mut SettingsModel, width: f32) {
+        let ui_config = &mut model.config.ui;
+        let mut never_vinyl = ui_config.force_disable_vinyl;
+        if section
+            .checkbox(&mut never_vinyl, "Never show vinyl")
+            .on_hover_text(
+                "Always show the original album art, even on skins built around the vinyl \
+                 disc.",
+            )
+            .changed()
+        {
+            self.requests.push(SettingsRequest::NeverVinyl(never_vinyl));
+        }
+        if never_vinyl {
+            // Nothing else in this section applies.
+        } else if model.skin_manager.current_theme().disable_vinyl_thumbnail {
+            section.label("This skin always shows the original album art.");
+        } else {
+            let mut vinyl_enabled = ui_config.vinyl_thumbnail.enabled;
+            if section
+                .checkbox(&mut vinyl_enabled, "Show spinning vinyl disc")
+                .on_hover_text("Toggle between the animated vinyl and the original thumbnail.")
+                .changed()
+            {
+                self.requests.push(SettingsRequest::Vinyl(vinyl_enabled));
+            }
+            let mut reverse = ui_config.vinyl_thumbnail.reverse;
+            if section
+                .add_enabled(
+                    vinyl_enabled,
+                    egui::Checkbox::new(&mut reverse, "Spin counterclockwise"),
+                )
+                .changed()
+            {
+                self.requests.push(SettingsRequest::VinylReverse(reverse));
+            }
+            if ui_config.artwork_click == ArtworkClick::ToggleVinyl {
+                section.label("Tip: You can also click the artwork to switch views.");
+            }
+        }
+
+        let artwork_click = ui_config.artwork_click;
+        egui::ComboBox::from_id_salt("artwork-click")
+            .width(width)
+            .selected_text(format!("Artwork click: {}", artwork_click.label()))
+            .show_ui(section, |combo| {
+                for action in ArtworkClick::ALL {
+                    combo.selectable_value(&mut ui_config.artwork_click, action, action.label());
+                }
+            })
+            .response
+            .on_hover_text("What clicking the album art does.");
+        if ui_config.artwork_click != artwork_click {
+            self.requests
+                .push(SettingsRequest::SaveConfig("artwork click setting"));
+        }
+
+        if section
+            .checkbox(&mut ui_config.dim_when_paused, "Dim artwork when paused")
+            .on_hover_text("Also applies while playback is stopped.")
+            .changed()
+        {
+            self.requests
+                .push(SettingsRequest::SaveConfig("dim artwork setting"));
+        }
+
+        section.horizontal(|row| {
+            let toggled = row
+                .checkbox(&mut ui_config.idle_screensaver, "Screensaver after")
+                .on_hover_text(
+                    "Drift the artwork behind dimmed controls while paused. Off when system \
+                     animations are disabled.",
+                )
+                .changed();
+            let retimed = row
+                .add_enabled(
+                    ui_config.idle_screensaver,
+                    egui::DragValue::new(&mut ui_config.idle_screensaver_minutes)
+                        .range(1..=120)
+                        .suffix(" min"),
+                )
+                .changed();
+            if toggled || retimed {
+                self.requests
+                    .push(SettingsRequest::SaveConfig("idle screensaver setting"));
+            }
+        });
+    }
+
+    fn skins_section(&mut self, section: &mut egui::Ui, model: &mut SettingsModel) {
+        let skins = model.skin_manager;
+        section.horizontal_wrapped(|row| {
+            row.spacing_mut().item_spacing = egui::vec2(12.0, CONTROL_SPACING);
+            let watch_skins = &mut model.config.ui.watch_skins;
+            let toggle_label = if *watch_skins {
+                "Disable hot reload"
+            } else {
+                "Enable hot reload"
+            };
+            if skins.skin_button(row, toggle_label).clicked() {
+                *watch_skins = !*watch_skins;
+                self.requests
+                    .push(SettingsRequest::SaveConfig("hot reload setting"));
+            }
+
+            let (reload_label, reload_hint) = if model.skins_root_lost {
+                ("Retry", "Look for the skins folder again")
+            } else {
+                ("Reload skins", "Re-scan the skin directory")
+            };
+            if skins
+                .skin_button(row, reload_label)
+                .on_hover_text(reload_hint)
+                .clicked()
+            {
+                self.requests.push(SettingsRequest::ReloadSkins);
+            }
+
+            if skins
+                .skin_button(row, "Install skin from file…")
+                .on_hover_text("Install a skin from a .zip archive")
+                .clicked()
+            {
+                self.requests.push(SettingsRequest::ImportSkin);
+            }
+
+            if cfg!(debug_assertions) {
+                let label = if self.theme_diff.is_some() {
+                    "Hide default diff"
+                } else {
+                    "Diff against default"
+                };
+                if skins
+                    .skin_button(row, label)
+                    .on_hover_text("List what this skin changes from the built-in theme")
+                    .clicked()
+                {
+                    self.toggle_theme_diff(skins.current_theme());
+                }
+            }
+        });
+
+        if model.config.ui.watch_skins {
+            warning_delta(section, skins);
+        }
+
+        if let Some(lines) = &self.theme_diff {
+            if lines.is_empty() {
+                section.label("Identical to the built-in theme.");
+            }
+            for line in lines {
+                section.label(egui::RichText::new(line).monospace());
+            }
+        }
+    }
+
+    fn about_section(&mut self, section: &mut egui::Ui, model: &mut SettingsModel) {
+        let skins = model.skin_manager;
+        let ctx = section.ctx().clone();
+        section.label(format!("Now Playing {}", env!("CARGO_PKG_VERSION")));
+        if skins
+            .skin_button(section, "Copy diagnostics")
+            .on_hover_text("Copy version, skin, and error details for a bug report.")
+            .clicked()
+        {
+            self.requests.push(SettingsRequest::CopyDiagnostics);
+        }
+        if skins
+            .skin_button(section, "Refresh now")
+            .on_hover_text(format!(
+                "Poll the player for its track and artwork ({}).",
+                ctx.format_shortcut(&REFRESH_SHORTCUT)
+            ))
+            .clicked()
+        {
+            self.requests.push(SettingsRequest::Refresh);
+        }
+        if skins
+            .skin_button(section, "Save screenshot…")
+            .on_hover_text(format!(
+                "Save the widget as a PNG ({}).",
+                ctx.format_shortcut(&SCREENSHOT_SHORTCUT)
+            ))
+            .clicked()
+        {
+            self.requests.push(SettingsRequest::Screenshot);
+        }
+    }
+}
+
+fn window_frame(ctx: &egui::Context) -> egui::Frame {
+    let dark_mode = ctx.style().visuals.dark_mode;
+    let mut frame = egui::Frame::window(&ctx.style());
+    frame.inner_margin = egui::Margin {
+        left: PANEL_PADDING_X,
+        right: PANEL_PADDING_X,
+        top: PANEL_PADDING_Y,
+        bottom: PANEL_PADDING_Y,
+    };
+    frame.corner_radius = CornerRadius::same(PANEL_CORNER_RADIUS);
+    frame.shadow = egui::Shadow {
+        offset: [0, 6],
+        blur: 28,
+        spread: 4,
+        color: if dark_mode {
+            egui::Color32::from_rgba_unmultiplied(0, 0, 0, 120)
+        } else {
+            egui::Color32::from_rgba_unmultiplied(0, 0, 0, 72)
+        },
+    };
+    frame.fill = if dark_mode {
+        egui::Color32::from_rgba_unmultiplied(28, 28, 32, 240)
+    } else {
+        egui::Color32::from_rgba_unmultiplied(244, 246, 249, 245)
+    };
+    frame
+}
+
+fn settings_section<R>(
+    ui: &mut egui::Ui,
+    title: &str,
+    content_width: f32,
+    build: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    let title_color = ui.visuals().strong_text_color();
+    ui.label(egui::RichText::new(title).size(13.0).color(title_color));
+    ui.add_space(HEADER_GAP);
+    ui.vertical(|section| {
+        section.set_min_width(content_width);
+        section.set_max_width(content_width);
+        section.spacing_mut().item_spacing = egui::vec2(0.0, CONTROL_SPACING);
+        build(section)
+    })
+    .inner
+}
+
+fn settings_separator(ui: &mut egui::Ui) {
+    ui.add_space(SECTION_GAP * 0.5);
+    ui.separator();
+    ui.add_space(SECTION_GAP * 0.5);
+}
+
+/// What the last hot reload changed in the skin's warnings, new ones first.
+fn warning_delta(ui: &mut egui::Ui, skins: &SkinManager) {
+    let delta = skins.warning_delta();
+    if delta.is_empty() {
+        return;
+    }
+    ui.label(egui::RichText::new(format!("Last reload: {}", delta.summary())).strong());
+    let color = ToastLevel::Warning.color(&skins.current_theme().colors);
+    for warning in &delta.new {
+        ui.colored_label(
+            color,
+            egui::RichText::new(format!("New: {warning}")).strong(),
+        );
+    }
+    for warning in &delta.fixed {
+        ui.label(egui::RichText::new(format!("Resolved: {warning}")).weak());
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    fs,
+    io::{Read, Seek},
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use zip::ZipArchive;
+
+use crate::{layout::load_layout_from_dir, theme::load_theme_from_dir};
+
+/// Caps on what an archive may unpack to, so a zip bomb can't fill the disk.
+#[derive(Debug, Clone, Copy)]
+struct ArchiveLimits {
+    max_entries: usize,
+    max_total_bytes: u64,
+}
+
+const ARCHIVE_LIMITS: ArchiveLimits = ArchiveLimits {
+    max_entries: 4096,
+    max_total_bytes: 256 * 1024 * 1024,
+};
+
+/// Works out which folder name an archive should be installed under.
+///
+/// Archives either contain a single top-level folder holding `theme.toml`, or keep
+/// `theme.toml` at the root, in which case the archive's file name is used.
+pub fn inspect_archive(archive: &Path) -> Result<String> {
+    let file = fs::File::open(archive)
+        .with_context(|| format!("Failed to open skin archive: {}", archive.display()))?;
+    let mut zip = ZipArchive::new(file)
+        .with_context(|| format!("Not a valid zip archive: {}", archive.display()))?;
+    let fallback = archive
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "imported_skin".to_string());
+    match archive_prefix(&mut zip)? {
+        Some(prefix) => Ok(prefix),
+        None => Ok(fallback),
+    }
+}
+
+/// Picks `base`, or `base-2`, `base-3`, … if a folder with that name already exists.
+pub fn unique_folder_name(skins_root: &Path, base: &str) -> String {
+    if !skins_root.join(base).exists() {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !skins_root.join(candidate).exists())
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Extracts `archive` into `skins_root/folder_name`, validating the skin before it
+/// replaces anything on disk. Returns the installed folder name, which is the skin id.
+pub fn install_skin(
+    archive: &Path,
+    skins_root: &Path,
+    folder_name: &str,
+    overwrite: bool,
+) -> Result<String> {
+    validate_folder_name(folder_name)?;
+    let file = fs::File::open(archive)
+        .with_context(|| format!("Failed to open skin archive: {}", archive.display()))?;
+    install_from_reader(file, skins_root, folder_name, overwrite)
+}
+
+fn install_from_reader<R: Read + Seek>(
+    reader: R,
+    skins_root: &Path,
+    folder_name: &str,
+    overwrite: bool,
+) -> Result<String> {
+    let target = skins_root.join(folder_name);
+    if target.exists() && !overwrite {
+        bail!("A skin folder named '{folder_name}' already exists");
+    }
+
+    fs::create_dir_all(skins_root)
+        .with_context(|| format!("Failed to create skins directory: {}", skins_root.display()))?;
+    let staging = skins_root.join(format!(".import-{folder_name}"));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .with_context(|| format!("Failed to clear {}", staging.display()))?;
+    }
+
+    let result = extract_archive(reader, &staging, ARCHIVE_LIMITS)
+        .and_then(|()| validate_skin_dir(&staging))
+        .and_then(|()| {
+            if target.exists() {
+                fs::remove_dir_all(&target)
+                    .with_context(|| format!("Failed to replace {}", target.display()))?;
+            }
+            fs::rename(&staging, &target)
+                .with_context(|| format!("Failed to move skin into {}", target.display()))?;
+            Ok(())
+        });
+
+    match result {
+        Ok(()) => Ok(folder_name.to_string()),
+        Err(err) => {
+            let _ = fs::remove_dir_all(&staging);
+            Err(err)
+        }
+    }
+}
+
+fn extract_archive<R: Read + Seek>(reader: R, dest: &Path, limits: ArchiveLimits) -> Result<()> {
+    let mut zip = ZipArchive::new(reader).context("Not a valid zip archive")?;
+    if zip.len() > limits.max_entries {
+        bail!(
+            "Skin archive has {} entries; the limit is {}",
+            zip.len(),
+            limits.max_entries
+        );
+    }
+    let prefix = archive_prefix(&mut zip)?;
+    // Counted from the bytes actually written; the sizes in the zip headers can lie.
+    let mut remaining = limits.max_total_bytes;
+
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+        let name = entry.name().to_string();
+        let relative = safe_relative_path(&name)
+            .ok_or_else(|| anyhow!("Archive entry escapes the skin folder: {name}"))?;
+        let relative = match &prefix {
+            Some(prefix) => match relative.strip_prefix(prefix) {
+                Ok(stripped) => stripped.to_path_buf(),
+                Err(_) => continue,
+            },
+            None => relative,
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+
+        let out_path = dest.join(&relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out = fs::File::create(&out_path)
+            .with_context(|| format!("Failed to write {}", out_path.display()))?;
+        let written = std::io::copy(&mut (&mut entry).take(remaining + 1), &mut out)?;
+        if written > remaining {
+            bail!(
+                "Skin archive unpacks to more than {} MB",
+                limits.max_total_bytes / (1024 * 1024)
+            );
+        }
+        remaining -= written;
+    }
+
+    Ok(())
+}
+
+/// Returns the single top-level folder to strip, or `None` when `theme.toml` sits at
+/// the archive root.
+fn archive_prefix<R: Read + Seek>(zip: &mut ZipArchive<R>) -> Result<Option<String>> {
+    let names: Vec<PathBuf> = zip
+        .file_names()
+        .filter_map(safe_relative_path)
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect();
+
+    if names.iter().any(|path| path == Path::new("theme.toml")) {
+        return Ok(None);
+    }
+
+    let mut top_level = names.iter().filter_map(|path| path.components().next());
+    let Some(first) = top_level.next() else {
+        bail!("Skin archive is empty");
+    };
+    if top_level.any(|component| component != first) {
+        bail!("Skin archive must contain a single top-level folder or a root theme.toml");
+    }
+
+    let prefix = first.as_os_str().to_string_lossy().to_string();
+    if !names
+        .iter()
+        .any(|path| *path == Path::new(&prefix).join("theme.toml"))
+    {
+        bail!("Skin archive does not contain a theme.toml");
+    }
+    Ok(Some(prefix))
+}
+
+/// Converts an archive entry name into a relative path, rejecting anything that could
+/// land outside the destination (`..`, absolute paths, drive prefixes).
+fn safe_relative_path(name: &str) -> Option<PathBuf> {
+    let normalized = name.replace('\\', "/");
+    let mut path = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(part) => {
+                let part = part.to_string_lossy();
+                if part.contains(':') {
+                    return None;
+                }
+                path.push(part.as_ref());
+            }
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(path)
+}
+
+fn validate_folder_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\', ':']);
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("'{name}' is not a valid skin folder name"))
+    }
+}
+
+/// Runs the same loaders the skin manager uses so a broken skin never lands in the
+/// skins folder. Non-fatal warnings surface once the skin is selected.
+fn validate_skin_dir(dir: &Path) -> Result<()> {
+    if !dir.join("theme.toml").is_file() {
+        bail!("Skin archive does not contain a theme.toml");
+    }
+    load_theme_from_dir(dir)?;
+    load_layout_from_dir(dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    const THEME: &str = "[meta]\nname = \"test\"\ndisplay_name = \"Test\"\n";
+
+    fn build_archive(entries: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "nowplaying-skin-import-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn installs_archive_with_top_level_folder() {
+        let root = scratch_dir("top-level");
+        let archive =
+            build_archive(&[("neon/theme.toml", THEME), ("neon/assets/readme.txt", "hi")]);
+
+        install_from_reader(archive, &root, "neon", false).unwrap();
+
+        assert!(root.join("neon/theme.toml").is_file());
+        assert!(root.join("neon/assets/readme.txt").is_file());
+        assert!(!root.join(".import-neon").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn installs_archive_with_root_theme() {
+        let root = scratch_dir("root-theme");
+        let archive = build_archive(&[("theme.toml", THEME)]);
+
+        install_from_reader(archive, &root, "flat", false).unwrap();
+
+        assert!(root.join("flat/theme.toml").is_file());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rejects_path_traversal_entries() {
+        let root = scratch_dir("zip-slip");
+        let skins = root.join("skins");
+        let archive = build_archive(&[("theme.toml", THEME), ("../escaped.txt", "pwned")]);
+
+        let err = install_from_reader(archive, &skins, "evil", false).unwrap_err();
+
+        assert!(err.to_string().contains("escapes"));
+        assert!(!root.join("escaped.txt").exists());
+        assert!(!skins.join("evil").exists());
+        assert!(!skins.join(".import-evil").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn safe_relative_path_rejects_absolute_and_drive_paths() {
+        assert_eq!(
+            safe_relative_path("skin/./theme.toml"),
+            Some(PathBuf::from("skin/theme.toml"))
+        );
+        assert_eq!(safe_relative_path("/etc/passwd"), None);
+        assert_eq!(safe_relative_path("..\\..\\evil.txt"), None);
+        assert_eq!(safe_relative_path("C:/Windows/evil.txt"), None);
+    }
+
+    #[test]
+    fn conflicting_folder_requires_overwrite_or_rename() {
+        let root = scratch_dir("conflict");
+        fs::create_dir_all(root.join("neon")).unwrap();
+        let archive = build_archive(&[("neon/theme.toml", THEME)]);
+
+        assert!(install_from_reader(archive.clone(), &root, "neon", false).is_err());
+        assert_eq!(unique_folder_name(&root, "neon"), "neon-2");
+        install_from_reader(archive, &root, "neon", true).unwrap();
+        assert!(root.join("neon/theme.toml").is_file());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rejects_archives_over_the_entry_or_size_limit() {
+        let root = scratch_dir("limits");
+        let archive = build_archive(&[
+            ("theme.toml", THEME),
+            ("a.txt", "0123456789"),
+            ("b.txt", "0123456789"),
+        ]);
+        let roomy = ArchiveLimits {
+            max_entries: 3,
+            max_total_bytes: 1024,
+        };
+        let few_entries = ArchiveLimits {
+            max_entries: 2,
+            ..roomy
+        };
+        let few_bytes = ArchiveLimits {
+            max_total_bytes: THEME.len() as u64 + 15,
+            ..roomy
+        };
+
+        extract_archive(archive.clone(), &root.join("ok"), roomy).unwrap();
+        let err = extract_archive(archive.clone(), &root.join("count"), few_entries).unwrap_err();
+        assert!(err.to_string().contains("entries"));
+        let err = extract_archive(archive, &root.join("size"), few_bytes).unwrap_err();
+        assert!(err.to_string().contains("unpacks to more than"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rejects_archive_without_theme() {
+        let root = scratch_dir("no-theme");
+        let archive = build_archive(&[("neon/layout.toml", "")]);
+
+        assert!(install_from_reader(archive, &root, "neon", false).is_err());
+        assert!(!root.join("neon").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,181 @@
+//! Moves the window flush against a corner of its monitor's work area.
+
+use crate::config::SnapCorner;
+
+/// Screen rectangle in physical pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ScreenRect {
+    pub(crate) left: i32,
+    pub(crate) top: i32,
+    pub(crate) right: i32,
+    pub(crate) bottom: i32,
+}
+
+impl ScreenRect {
+    fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+}
+
+/// Where the window currently sits. `outer` is the rect the OS positions, `visible` the
+/// frame the user sees; they differ by the invisible resize borders DWM adds around
+/// decorated windows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct WindowPlacement {
+    /// Opaque id of the monitor the window is on.
+    pub(crate) monitor: isize,
+    pub(crate) work_area: ScreenRect,
+    pub(crate) outer: ScreenRect,
+    pub(crate) visible: ScreenRect,
+}
+
+/// Outer top-left position that puts the visible frame `margin` pixels inside `corner`
+/// of the work area.
+pub(crate) fn snap_position(
+    placement: &WindowPlacement,
+    corner: SnapCorner,
+    margin: i32,
+) -> (i32, i32) {
+    let WindowPlacement {
+        work_area: area,
+        outer,
+        visible,
+        ..
+    } = *placement;
+    let inset_x = visible.left - outer.left;
+    let inset_y = visible.top - outer.top;
+    let left = area.left + margin;
+    let top = area.top + margin;
+    let right = area.right - margin - visible.width();
+    let bottom = area.bottom - margin - visible.height();
+    let (x, y) = match corner {
+        SnapCorner::TopLeft => (left, top),
+        SnapCorner::TopRight => (right, top),
+        SnapCorner::BottomLeft => (left, bottom),
+        SnapCorner::BottomRight => (right, bottom),
+        SnapCorner::Center => (
+            area.left + (area.width() - visible.width()) / 2,
+            area.top + (area.height() - visible.height()) / 2,
+        ),
+    };
+    (x - inset_x, y - inset_y)
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn window_placement(hwnd: windows::Win32::Foundation::HWND) -> Option<WindowPlacement> {
+    use windows::Win32::{
+        Foundation::RECT,
+        Graphics::{
+            Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS},
+            Gdi::{GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITOR_DEFAULTTONEAREST},
+        },
+        UI::WindowsAndMessaging::GetWindowRect,
+    };
+
+    fn to_rect(rect: RECT) -> ScreenRect {
+        ScreenRect {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+
+    unsafe {
+        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+            return None;
+        }
+        let mut outer = RECT::default();
+        GetWindowRect(hwnd, &mut outer).ok()?;
+        // Falls back to the outer rect when DWM composition reports nothing.
+        let mut visible = outer;
+        let _ = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut visible as *mut RECT as *mut _,
+            std::mem::size_of::<RECT>() as u32,
+        );
+        Some(WindowPlacement {
+            monitor: monitor.0 as isize,
+            work_area: to_rect(info.rcWork),
+            outer: to_rect(outer),
+            visible: to_rect(visible),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> ScreenRect {
+        ScreenRect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    #[test]
+    fn snap_position_keeps_the_visible_frame_inside_the_work_area() {
+        // 1920x1080 monitor with a 40px taskbar at the bottom; the window has a 7px
+        // invisible border on the left, right, and bottom, as decorated windows do.
+        let placement = WindowPlacement {
+            monitor: 1,
+            work_area: rect(0, 0, 1920, 1040),
+            outer: rect(93, 100, 507, 407),
+            visible: rect(100, 100, 500, 400),
+        };
+        let margin = 10;
+
+        assert_eq!(
+            snap_position(&placement, SnapCorner::TopLeft, margin),
+            (3, 10)
+        );
+        assert_eq!(
+            snap_position(&placement, SnapCorner::TopRight, margin),
+            (1503, 10)
+        );
+        assert_eq!(
+            snap_position(&placement, SnapCorner::BottomLeft, margin),
+            (3, 730)
+        );
+        assert_eq!(
+            snap_position(&placement, SnapCorner::BottomRight, margin),
+            (1503, 730)
+        );
+        assert_eq!(
+            snap_position(&placement, SnapCorner::Center, margin),
+            (753, 370)
+        );
+    }
+
+    #[test]
+    fn snap_position_follows_offset_work_areas() {
+        // Secondary monitor left of the primary, taskbar docked at its top.
+        let placement = WindowPlacement {
+            monitor: 2,
+            work_area: rect(-1280, 48, 0, 1024),
+            outer: rect(-900, 300, -600, 500),
+            visible: rect(-900, 300, -600, 500),
+        };
+        assert_eq!(
+            snap_position(&placement, SnapCorner::TopLeft, 0),
+            (-1280, 48)
+        );
+        assert_eq!(
+            snap_position(&placement, SnapCorner::BottomRight, 8),
+            (-308, 816)
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Brings the player behind the current session to the front, launching it when it has
+//! no window to focus.
+
+use now_playing_gui::state::app_display_name;
+
+/// How to bring up the app behind a session's AppUserModelID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Activation {
+    /// Packaged (Store) app; activating its AUMID focuses a running instance too.
+    Packaged { aumid: String },
+    /// Desktop app reporting its executable; focus a window of `exe`, else try the AUMID.
+    Desktop { exe: String, aumid: String },
+}
+
+/// Top-level window seen while enumerating, reduced to what matching needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TopWindow {
+    pub(crate) handle: isize,
+    /// Executable file name of the owning process, e.g. `Spotify.exe`.
+    pub(crate) exe: String,
+}
+
+pub(crate) fn activation_for(app_id: &str) -> Option<Activation> {
+    let app_id = app_id.trim();
+    if app_id.is_empty() {
+        return None;
+    }
+    if app_id.contains('!') {
+        return Some(Activation::Packaged {
+            aumid: app_id.to_string(),
+        });
+    }
+    let file = app_id.rsplit(['\\', '/']).next().unwrap_or(app_id);
+    let mut exe = file.to_ascii_lowercase();
+    if !exe.ends_with(".exe") {
+        exe.push_str(".exe");
+    }
+    Some(Activation::Desktop {
+        exe,
+        aumid: app_id.to_string(),
+    })
+}
+
+/// Topmost window owned by `exe`; `windows` is in z-order, as `EnumWindows` reports it.
+pub(crate) fn pick_window(windows: &[TopWindow], exe: &str) -> Option<isize> {
+    windows
+        .iter()
+        .find(|window| window.exe.eq_ignore_ascii_case(exe))
+        .map(|window| window.handle)
+}
+
+/// Name to show for the app behind `app_id`, e.g. "Media Player" for `ZuneMusic`.
+pub(crate) fn friendly_app_name(app_id: &str) -> String {
+    let short = app_display_name(app_id);
+    let known = match short.to_ascii_lowercase().as_str() {
+        "spotify" => "Spotify",
+        "zunemusic" => "Media Player",
+        "zunevideo" => "Films & TV",
+        "msedge" => "Microsoft Edge",
+        "chrome" => "Google Chrome",
+        "firefox" => "Firefox",
+        "vlc" => "VLC",
+        "applemusic" | "itunes" => "Apple Music",
+        _ => short,
+    };
+    known.to_string()
+}
+
+/// Focuses or launches the player for `app_id`.
+#[cfg(target_os = "windows")]
+pub(crate) fn open(app_id: &str) -> Result<(), String> {
+    match activation_for(app_id).ok_or("The session doesn't name its player")? {
+        Activation::Desktop { exe, aumid } => match pick_window(&top_level_windows(), &exe) {
+            Some(handle) => {
+                focus_window(handle);
+                Ok(())
+            }
+            None => activate(&aumid),
+        },
+        Activation::Packaged { aumid } => activate(&aumid),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn top_level_windows() -> Vec<TopWindow> {
+    use windows::core::BOOL;
+    use windows::Win32::{
+        Foundation::{HWND, LPARAM},
+        UI::WindowsAndMessaging::{
+            EnumWindows, GetWindow, GetWindowTextLengthW, GetWindowThreadProcessId,
+            IsWindowVisible, GW_OWNER,
+        },
+    };
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam.0 as *mut Vec<TopWindow>);
+        let owned = GetWindow(hwnd, GW_OWNER).is_ok_and(|owner| !owner.is_invalid());
+        if IsWindowVisible(hwnd).as_bool() && !owned && GetWindowTextLengthW(hwnd) > 0 {
+            let mut pid = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            if let Some(exe) = process_exe_name(pid) {
+                windows.push(TopWindow {
+                    handle: hwnd.0 as isize,
+                    exe,
+                });
+            }
+        }
+        true.into()
+    }
+
+    let mut windows = Vec::new();
+    unsafe {
+        let _ = EnumWindows(
+            Some(collect),
+            LPARAM(&mut windows as *mut Vec<TopWindow> as isize),
+        );
+    }
+    windows
+}
+
+#[cfg(target_os = "windows")]
+fn process_exe_name(pid: u32) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::{
+        Foundation::CloseHandle,
+        System::Threading::{
+            OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+            PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+    };
+
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buffer.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        path.rsplit('\\').next().map(str::to_string)
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn focus_window(handle: isize) {
+    use windows::Win32::{
+        Foundation::HWND,
+        UI::WindowsAndMessaging::{IsIconic, SetForegroundWindow, ShowWindow, SW_RESTORE},
+    };
+
+    let hwnd = HWND(handle as *mut _);
+    unsafe {
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        let _ = SetForegroundWindow(hwnd);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn activate(aumid: &str) -> Result<(), String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::{
+        System::Com::{
+            CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_LOCAL_SERVER,
+            COINIT_APARTMENTTHREADED,
+        },
+        UI::Shell::{ApplicationActivationManager, IApplicationActivationManager, AO_NONE},
+    };
+
+    unsafe {
+        // The UI thread normally has COM already; this only balances our own init.
+        let initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+        let result = CoCreateInstance::<_, IApplicationActivationManager>(
+            &ApplicationActivationManager,
+            None,
+            CLSCTX_LOCAL_SERVER,
+        )
+        .and_then(|manager| {
+            manager.ActivateApplication(&HSTRING::from(aumid), PCWSTR::null(), AO_NONE)
+        });
+        if initialized {
+            CoUninitialize();
+        }
+        result.map(|_| ()).map_err(|err| {
+            format!(
+                "Couldn't open {}: {}",
+                friendly_app_name(aumid),
+                err.message()
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activation_separates_packaged_and_desktop_apps() {
+        let zune = "Microsoft.ZuneMusic_8wekyb3d8bbwe!Microsoft.ZuneMusic";
+        assert_eq!(
+            activation_for(zune),
+            Some(Activation::Packaged {
+                aumid: zune.to_string()
+            })
+        );
+        assert_eq!(
+            activation_for("Spotify.exe"),
+            Some(Activation::Desktop {
+                exe: "spotify.exe".to_string(),
+                aumid: "Spotify.exe".to_string()
+            })
+        );
+        assert!(matches!(
+            activation_for("chrome"),
+            Some(Activation::Desktop { exe, .. }) if exe == "chrome.exe"
+        ));
+        assert_eq!(activation_for("  "), None);
+    }
+
+    #[test]
+    fn pick_window_takes_the_topmost_match() {
+        let windows = [
+            TopWindow {
+                handle: 1,
+                exe: "explorer.exe".to_string(),
+            },
+            TopWindow {
+                handle: 2,
+                exe: "Spotify.exe".to_string(),
+            },
+            TopWindow {
+                handle: 3,
+                exe: "Spotify.exe".to_string(),
+            },
+        ];
+        assert_eq!(pick_window(&windows, "spotify.exe"), Some(2));
+        assert_eq!(pick_window(&windows, "vlc.exe"), None);
+    }
+
+    #[test]
+    fn friendly_names_cover_common_players() {
+        assert_eq!(friendly_app_name("Spotify.exe"), "Spotify");
+        assert_eq!(
+            friendly_app_name("Microsoft.ZuneMusic_8wekyb3d8bbwe!Microsoft.ZuneMusic"),
+            "Media Player"
+        );
+        assert_eq!(friendly_app_name("foobar2000.exe"), "foobar2000");
+    }
+}
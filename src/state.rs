@@ -0,0 +1,1215 @@
+//! Widget state that doesn't need a window: the current session, timeline prediction,
+//! seek bookkeeping and poll pacing. `App` wraps an [`AppCore`] and adds the UI.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    time::{Duration, Instant},
+};
+
+use eframe::egui::ColorImage;
+
+/// How long a provisional play/pause guess is shown without confirmation.
+pub const PROVISIONAL_STATE_GRACE: Duration = Duration::from_millis(2000);
+/// How long a committed seek waits for the session to report the new position.
+/// Shorter tracks wait less; see [`seek_commit_timeout`].
+pub const SEEK_COMMIT_TIMEOUT: Duration = Duration::from_secs(4);
+/// Shortest seek wait, long enough for one more poll of a playing session.
+const MIN_SEEK_COMMIT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Reported positions within this many seconds of a pending seek confirm it.
+const SEEK_CONFIRM_TOLERANCE: f64 = 0.5;
+/// Tightest confirm tolerance, for tracks a few seconds long.
+const MIN_SEEK_CONFIRM_TOLERANCE: f64 = 0.05;
+/// Time without playback after which the next Play starts a new listening session.
+pub const LISTENING_SESSION_GAP: Duration = Duration::from_secs(15 * 60);
+/// Default cadence of [`PlaybackClock::tick`] position updates.
+pub const POSITION_EMIT_INTERVAL: Duration = Duration::from_secs(1);
+/// How long a due layout rotation waits for a track change before switching anyway.
+pub const ROTATION_TRACK_WAIT: Duration = Duration::from_secs(60);
+/// How long a layout picked by hand holds off layout rotation.
+pub const ROTATION_MANUAL_HOLD: Duration = Duration::from_secs(10 * 60);
+/// Tracks longer than this count as long-form unless the setting says otherwise.
+pub const DEFAULT_LONG_FORM_THRESHOLD: Duration = Duration::from_secs(20 * 60);
+/// Genre fragments that mark a session as spoken content, matched case-insensitively.
+const LONG_FORM_GENRES: &[&str] = &["podcast", "audiobook", "audio book", "spoken word"];
+
+/// One poll of the media sessions: the current one plus the others running beside it.
+pub struct SessionSnapshot {
+    pub now: NowPlaying,
+    pub timeline: Option<Timeline>,
+    pub others: Vec<OtherSession>,
+    /// Upcoming track, for sources that expose their queue. The system media session
+    /// doesn't, so only the demo session fills this in.
+    pub next: Option<NowPlaying>,
+}
+
+/// A session other than the current one. Title and artist are only fetched while it is
+/// paused, since that is the only case the UI shows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OtherSession {
+    pub app_id: String,
+    pub title: String,
+    pub artist: String,
+    pub state: PlayState,
+}
+
+impl OtherSession {
+    pub fn app_name(&self) -> &str {
+        app_display_name(&self.app_id)
+    }
+}
+
+/// Short app name from a source app id such as `Spotify.exe` or
+/// `Microsoft.ZuneMusic_8wekyb3d8bbwe!Microsoft.ZuneMusic`.
+pub fn app_display_name(app_id: &str) -> &str {
+    let id = app_id.rsplit('!').next().unwrap_or(app_id);
+    let id = id
+        .strip_suffix(".exe")
+        .or_else(|| id.strip_suffix(".EXE"))
+        .unwrap_or(id);
+    id.rsplit('.').next().unwrap_or(id)
+}
+
+/// Fills `{title}`, `{artist}`, `{album}`, and `{app}` in `template` from `track`.
+/// Unknown placeholders are kept as written, and separators left dangling at either end
+/// by empty fields are trimmed.
+pub fn fill_track_template(template: &str, track: &NowPlaying) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open..];
+        let Some(close) = after.find('}') else {
+            rest = after;
+            break;
+        };
+        match &after[1..close] {
+            "title" => out.push_str(&track.title),
+            "artist" => out.push_str(&track.artist),
+            "album" => out.push_str(&track.album),
+            "app" => out.push_str(app_display_name(&track.app_id)),
+            _ => out.push_str(&after[..=close]),
+        }
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    out.trim_matches(|c: char| c.is_whitespace() || matches!(c, '—' | '–' | '-' | '|' | '·'))
+        .to_string()
+}
+
+/// Coarse duration for display: "47 m", "1 h 12 m", "2 d 3 h". Under a minute is "0 m".
+pub fn humanize_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{days} d {hours} h")
+    } else if hours > 0 {
+        format!("{hours} h {minutes} m")
+    } else {
+        format!("{minutes} m")
+    }
+}
+
+/// Track time as a screen reader should say it: "two minutes thirty", "forty seconds",
+/// "one hour five minutes". Past an hour the seconds are left out.
+pub fn spoken_timestamp(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    let (hours, minutes, secs) = (total / 3600, total / 60 % 60, total % 60);
+    let unit = |count: u64, name: &str| {
+        let plural = if count == 1 { "" } else { "s" };
+        format!("{} {name}{plural}", number_words(count))
+    };
+    if hours > 0 {
+        let mut text = unit(hours, "hour");
+        if minutes > 0 {
+            text = format!("{text} {}", unit(minutes, "minute"));
+        }
+        text
+    } else if minutes > 0 {
+        let mut text = unit(minutes, "minute");
+        if secs > 0 {
+            text = format!("{text} {}", number_words(secs));
+        }
+        text
+    } else {
+        unit(secs, "second")
+    }
+}
+
+/// "two minutes thirty of four minutes ten"; just the position when the length is unknown.
+pub fn spoken_position(position_secs: f64, duration_secs: f64) -> String {
+    let position = spoken_timestamp(position_secs);
+    if duration_secs > 0.0 {
+        format!("{position} of {}", spoken_timestamp(duration_secs))
+    } else {
+        position
+    }
+}
+
+/// English words for `n` below a thousand; larger numbers stay as digits.
+fn number_words(n: u64) -> String {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    match n {
+        0..=19 => ONES[n as usize].to_string(),
+        20..=99 => {
+            let tens = TENS[n as usize / 10];
+            match n % 10 {
+                0 => tens.to_string(),
+                ones => format!("{tens}-{}", ONES[ones as usize]),
+            }
+        }
+        100..=999 => {
+            let hundreds = ONES[n as usize / 100];
+            match n % 100 {
+                0 => format!("{hundreds} hundred"),
+                rest => format!("{hundreds} hundred {}", number_words(rest)),
+            }
+        }
+        _ => n.to_string(),
+    }
+}
+
+/// Picks the other session that paused most recently. `paused_since` remembers when each
+/// session was first seen paused, since the system doesn't report pause times.
+pub fn select_up_next(
+    others: &[OtherSession],
+    paused_since: &mut HashMap<String, Instant>,
+    now: Instant,
+) -> Option<OtherSession> {
+    let paused = |other: &&OtherSession| other.state == PlayState::Paused;
+    paused_since.retain(|app_id, _| {
+        others
+            .iter()
+            .filter(paused)
+            .any(|other| &other.app_id == app_id)
+    });
+    for other in others.iter().filter(paused) {
+        paused_since.entry(other.app_id.clone()).or_insert(now);
+    }
+    others
+        .iter()
+        .filter(paused)
+        .filter(|other| !other.title.is_empty())
+        .max_by_key(|other| paused_since.get(&other.app_id).copied())
+        .cloned()
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NowPlaying {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub state: PlayState,
+    pub caps: ControlsCaps,
+    /// AppUserModelID of the player that owns the session.
+    pub app_id: String,
+    pub genres: Vec<String>,
+    /// Filled in by [`AppCore::apply_snapshot`] once the timeline is known.
+    pub content: ContentKind,
+}
+
+/// What kind of listening the current session is, for components that behave
+/// differently on long spoken content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentKind {
+    #[default]
+    Music,
+    /// Podcasts, audiobooks and anything else longer than the long-form threshold.
+    LongForm,
+}
+
+impl ContentKind {
+    pub fn is_long_form(self) -> bool {
+        self == ContentKind::LongForm
+    }
+}
+
+/// Long-form when the track runs past `threshold` or a genre names spoken content.
+pub fn classify_content(
+    duration_secs: Option<f64>,
+    genres: &[String],
+    threshold: Duration,
+) -> ContentKind {
+    let long = duration_secs.is_some_and(|secs| secs > threshold.as_secs_f64());
+    let spoken = genres.iter().any(|genre| {
+        let genre = genre.to_lowercase();
+        LONG_FORM_GENRES
+            .iter()
+            .any(|keyword| genre.contains(keyword))
+    });
+    if long || spoken {
+        ContentKind::LongForm
+    } else {
+        ContentKind::Music
+    }
+}
+
+impl PartialEq for NowPlaying {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title && self.artist == other.artist && self.album == other.album
+    }
+}
+
+impl NowPlaying {
+    /// Identifier for external tools to correlate tracks by. Hashes the fields `eq`
+    /// compares, so it holds across polls and changes with any of them.
+    pub fn track_id(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (&self.title, &self.artist, &self.album).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_empty() && self.artist.is_empty() && self.album.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThumbnailOverlayAction {
+    Previous,
+    Play,
+    Pause,
+    Next,
+}
+
+/// Transport commands the session currently accepts. Everything is allowed until a
+/// session reports otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ControlsCaps {
+    pub previous: bool,
+    pub next: bool,
+    pub play: bool,
+    pub pause: bool,
+    pub play_pause_toggle: bool,
+    pub seek: bool,
+}
+
+impl Default for ControlsCaps {
+    fn default() -> Self {
+        Self {
+            previous: true,
+            next: true,
+            play: true,
+            pause: true,
+            play_pause_toggle: true,
+            seek: true,
+        }
+    }
+}
+
+impl ControlsCaps {
+    pub fn allows(&self, action: ThumbnailOverlayAction) -> bool {
+        match action {
+            ThumbnailOverlayAction::Previous => self.previous,
+            ThumbnailOverlayAction::Next => self.next,
+            ThumbnailOverlayAction::Play => self.play || self.play_pause_toggle,
+            ThumbnailOverlayAction::Pause => self.pause || self.play_pause_toggle,
+        }
+    }
+
+    /// True when `action` is only reachable through the play/pause toggle, so it has to
+    /// be sent as a toggle rather than as Play or Pause.
+    pub fn needs_toggle(&self, action: ThumbnailOverlayAction) -> bool {
+        match action {
+            ThumbnailOverlayAction::Play => !self.play && self.play_pause_toggle,
+            ThumbnailOverlayAction::Pause => !self.pause && self.play_pause_toggle,
+            ThumbnailOverlayAction::Previous | ThumbnailOverlayAction::Next => false,
+        }
+    }
+
+    /// True when the session accepts no transport command at all.
+    pub fn none_available(&self) -> bool {
+        !(self.previous || self.next || self.play || self.pause || self.play_pause_toggle)
+    }
+
+    pub fn disabled_hint(action: ThumbnailOverlayAction) -> &'static str {
+        match action {
+            ThumbnailOverlayAction::Previous => "The current player doesn't allow skipping back",
+            ThumbnailOverlayAction::Next => "The current player doesn't allow skipping ahead",
+            ThumbnailOverlayAction::Play => "The current player can't be resumed from here",
+            ThumbnailOverlayAction::Pause => "The current player can't be paused from here",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PlayState {
+    Closed,
+    Opened,
+    Changing,
+    Stopped,
+    Playing,
+    Paused,
+    #[default]
+    Unknown,
+}
+
+/// Play state assumed right after a play/pause click, shown until a snapshot confirms
+/// it or the grace period runs out. UI-only: history and events use `NowPlaying::state`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProvisionalPlayState {
+    pub state: PlayState,
+    pub expires: Instant,
+}
+
+impl ProvisionalPlayState {
+    pub fn new(state: PlayState, now: Instant) -> Self {
+        Self {
+            state,
+            expires: now + PROVISIONAL_STATE_GRACE,
+        }
+    }
+
+    pub fn state_at(&self, now: Instant) -> Option<PlayState> {
+        (now < self.expires).then_some(self.state)
+    }
+
+    /// Folds in a fresh snapshot. A matching state confirms (and clears) the guess; a
+    /// disagreeing one is tolerated until expiry, since snapshots taken right after the
+    /// command often predate it.
+    pub fn reconcile(self, confirmed: PlayState, now: Instant) -> Option<Self> {
+        if confirmed == self.state || now >= self.expires {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    pub start_secs: f64,
+    pub end_secs: f64,
+    pub position_secs: f64,
+    pub can_seek: bool,
+}
+
+impl Timeline {
+    pub fn duration_secs(&self) -> f64 {
+        (self.end_secs - self.start_secs).max(0.0)
+    }
+
+    /// Share of the track played, from 0 to 1; 0 for a timeline with no length.
+    pub fn progress_fraction(&self) -> f32 {
+        let duration = self.duration_secs();
+        if duration <= f64::EPSILON {
+            return 0.0;
+        }
+        ((self.position_secs - self.start_secs) / duration).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Artwork change waiting to be uploaded as a texture on the next frame.
+#[derive(Clone)]
+pub enum PendingThumbnail {
+    Update {
+        track: NowPlaying,
+        hash: u64,
+        base_image: ColorImage,
+        vinyl_image: Option<ColorImage>,
+    },
+    Clear {
+        track: Option<NowPlaying>,
+    },
+}
+
+/// What changed when a snapshot was applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotChange {
+    pub track_changed: bool,
+    pub state_changed: bool,
+}
+
+/// Playback position between snapshots: the last known position (`last_position_secs`
+/// at `last_position_update`) plus the wall clock while running, held at a pending seek
+/// until the session confirms it. The UI and every integration read positions from here.
+#[derive(Clone, Debug)]
+pub struct PlaybackClock {
+    pub last_position_secs: f64,
+    pub last_position_update: Instant,
+    pub pending_seek_target: Option<f64>,
+    pub pending_seek_deadline: Option<Instant>,
+    /// Timeline start and end; `None` while no timeline is known.
+    bounds: Option<(f64, f64)>,
+    running: bool,
+    /// How often [`tick`](Self::tick) reports the position while running.
+    pub emit_interval: Duration,
+    last_emit: Option<Instant>,
+}
+
+impl PlaybackClock {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            last_position_secs: 0.0,
+            last_position_update: now,
+            pending_seek_target: None,
+            pending_seek_deadline: None,
+            bounds: None,
+            running: false,
+            emit_interval: POSITION_EMIT_INTERVAL,
+            last_emit: None,
+        }
+    }
+
+    /// Position at `now`, clamped to the timeline. `None` without a timeline.
+    pub fn predicted_position(&self, now: Instant) -> Option<f64> {
+        let (start, end) = self.bounds?;
+        let mut position = self.last_position_secs;
+        if self.is_advancing() {
+            position += now
+                .saturating_duration_since(self.last_position_update)
+                .as_secs_f64();
+        }
+        Some(position.clamp(start, end.max(start)))
+    }
+
+    /// Position update for integrations, at most once per `emit_interval` and only
+    /// while running.
+    pub fn tick(&mut self, now: Instant) -> Option<f64> {
+        if !self.running
+            || self
+                .last_emit
+                .is_some_and(|last| now.saturating_duration_since(last) < self.emit_interval)
+        {
+            return None;
+        }
+        let position = self.predicted_position(now)?;
+        self.last_emit = Some(now);
+        Some(position)
+    }
+
+    /// Restarts the `tick` cadence after a position went out with some other update.
+    pub fn mark_emitted(&mut self, at: Instant) {
+        self.last_emit = Some(at);
+    }
+
+    /// True while the position follows the wall clock.
+    pub fn is_advancing(&self) -> bool {
+        self.running && self.pending_seek_target.is_none()
+    }
+
+    /// Re-anchors the prediction at `position` as of `at`.
+    pub fn anchor(&mut self, position: f64, at: Instant) {
+        self.last_position_secs = position;
+        self.last_position_update = at;
+    }
+
+    pub fn set_bounds(&mut self, bounds: Option<(f64, f64)>) {
+        self.bounds = bounds;
+    }
+
+    /// Starts or stops the clock at `at` without moving the position.
+    pub fn set_running(&mut self, running: bool, at: Instant) {
+        if running == self.running {
+            return;
+        }
+        let position = self
+            .predicted_position(at)
+            .unwrap_or(self.last_position_secs);
+        self.anchor(position, at);
+        self.running = running;
+    }
+
+    /// Holds the position at `target` until a report confirms it or the seek times out.
+    pub fn hold_seek(&mut self, target: f64, at: Instant) {
+        let duration = self.bounds.map(|(start, end)| end - start);
+        self.pending_seek_target = Some(target);
+        self.pending_seek_deadline = Some(at + seek_commit_timeout(duration));
+        self.anchor(target, at);
+    }
+
+    pub fn clear_seek(&mut self) {
+        self.pending_seek_target = None;
+        self.pending_seek_deadline = None;
+    }
+
+    pub fn seek_expired(&self, at: Instant) -> bool {
+        self.pending_seek_deadline
+            .is_some_and(|deadline| at >= deadline)
+    }
+}
+
+/// How long a seek on a track of `duration_secs` waits for confirmation: half the track,
+/// within [`SEEK_COMMIT_TIMEOUT`], so a short track never stays "seeking" past its end.
+pub fn seek_commit_timeout(duration_secs: Option<f64>) -> Duration {
+    match duration_secs.filter(|secs| secs.is_finite() && *secs > 0.0) {
+        Some(secs) => {
+            Duration::from_secs_f64(secs / 2.0).clamp(MIN_SEEK_COMMIT_TIMEOUT, SEEK_COMMIT_TIMEOUT)
+        }
+        None => SEEK_COMMIT_TIMEOUT,
+    }
+}
+
+/// Distance from a pending seek target that confirms it, scaled down on short tracks
+/// where half a second is a noticeable share of the whole.
+fn seek_confirm_tolerance(duration_secs: f64) -> f64 {
+    (duration_secs * 0.05).clamp(MIN_SEEK_CONFIRM_TOLERANCE, SEEK_CONFIRM_TOLERANCE)
+}
+
+/// Non-UI widget state, driven by snapshots and the clock passed in by the caller.
+pub struct AppCore {
+    pub now: NowPlaying,
+    pub last_pull: Instant,
+    pub err: Option<String>,
+    pub timeline: Option<Timeline>,
+    pub clock: PlaybackClock,
+    pub is_user_seeking: bool,
+    pub provisional_state: Option<ProvisionalPlayState>,
+    /// Most recently paused session other than the current one, for `up_next`.
+    pub up_next: Option<OtherSession>,
+    pub paused_sessions: HashMap<String, Instant>,
+    /// Track that was playing before the last track change.
+    pub previous_track: Option<NowPlaying>,
+    pub next_track: Option<NowPlaying>,
+    /// Chapter starts in seconds from the timeline start, marked on the seek bar.
+    /// Cleared on track change.
+    pub chapters: Vec<f64>,
+    /// When the current listening session started: the first Play after at least
+    /// [`LISTENING_SESSION_GAP`] without playback.
+    pub listening_since: Option<Instant>,
+    /// Last snapshot that reported Playing.
+    pub last_playing_at: Option<Instant>,
+    pub pending_thumbnail: Option<PendingThumbnail>,
+    pub current_thumbnail_track: Option<NowPlaying>,
+    pub thumbnail_hash: Option<u64>,
+    /// Duration past which a track is classified as [`ContentKind::LongForm`].
+    pub long_form_threshold: Duration,
+}
+
+impl AppCore {
+    /// Empty state whose first poll is due immediately.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            now: NowPlaying::default(),
+            last_pull: now.checked_sub(Duration::from_secs(1)).unwrap_or(now),
+            err: None,
+            timeline: None,
+            clock: PlaybackClock::new(now),
+            is_user_seeking: false,
+            provisional_state: None,
+            up_next: None,
+            paused_sessions: HashMap::new(),
+            previous_track: None,
+            next_track: None,
+            chapters: Vec::new(),
+            listening_since: None,
+            last_playing_at: None,
+            pending_thumbnail: None,
+            current_thumbnail_track: None,
+            thumbnail_hash: None,
+            long_form_threshold: DEFAULT_LONG_FORM_THRESHOLD,
+        }
+    }
+
+    /// Folds a fresh snapshot into the state. A track change drops the current artwork;
+    /// a pending seek holds the timeline at its target until the session catches up or
+    /// the seek times out; otherwise small drifts keep the locally predicted position.
+    pub fn apply_snapshot(&mut self, snapshot: SessionSnapshot, at: Instant) -> SnapshotChange {
+        let SessionSnapshot {
+            now,
+            timeline,
+            others,
+            next,
+        } = snapshot;
+        self.up_next = select_up_next(&others, &mut self.paused_sessions, at);
+        let change = SnapshotChange {
+            track_changed: self.now != now,
+            state_changed: self.now.state != now.state,
+        };
+        self.provisional_state = self
+            .provisional_state
+            .and_then(|provisional| provisional.reconcile(now.state, at));
+        self.next_track = next;
+        if change.track_changed {
+            if !self.now.title.is_empty() {
+                self.previous_track = Some(self.now.clone());
+            }
+            self.chapters.clear();
+            self.pending_thumbnail = Some(PendingThumbnail::Clear { track: None });
+            self.current_thumbnail_track = None;
+            self.thumbnail_hash = None;
+        }
+
+        if let Some(target) = self.clock.pending_seek_target {
+            if let Some(mut tl) = timeline {
+                let tolerance = seek_confirm_tolerance(tl.duration_secs());
+                if (tl.position_secs - target).abs() <= tolerance {
+                    self.clear_seek();
+                } else {
+                    tl.position_secs = target;
+                }
+                self.clock.anchor(tl.position_secs, at);
+                self.set_timeline(Some(tl));
+            } else {
+                self.clock.anchor(target, at);
+            }
+
+            if self.clock.seek_expired(at) {
+                self.clear_seek();
+            }
+        } else if let Some(mut tl) = timeline {
+            let predicted = self
+                .clock
+                .predicted_position(at)
+                .map(|position| position.clamp(tl.start_secs, tl.end_secs));
+            if let Some(predicted) = predicted.filter(|_| now.state == PlayState::Playing) {
+                let discrepancy = (predicted - tl.position_secs).abs();
+                let threshold = (tl.duration_secs() * 0.01).clamp(0.2, 7.0);
+                if discrepancy <= threshold || tl.duration_secs() <= f64::EPSILON {
+                    tl.position_secs = predicted;
+                }
+            }
+            self.clock.anchor(tl.position_secs, at);
+            self.set_timeline(Some(tl));
+        } else {
+            self.clock.anchor(self.clock.last_position_secs, at);
+            self.set_timeline(None);
+        }
+
+        if now.state == PlayState::Playing {
+            if self.listening_for(at).is_none() {
+                self.listening_since = Some(at);
+            }
+            self.last_playing_at = Some(at);
+        }
+
+        self.now = now;
+        self.now.content = classify_content(
+            self.timeline.as_ref().map(Timeline::duration_secs),
+            &self.now.genres,
+            self.long_form_threshold,
+        );
+        self.err = None;
+        self.last_pull = at;
+        self.sync_clock(at);
+        change
+    }
+
+    fn set_timeline(&mut self, timeline: Option<Timeline>) {
+        self.clock
+            .set_bounds(timeline.as_ref().map(|tl| (tl.start_secs, tl.end_secs)));
+        self.timeline = timeline;
+    }
+
+    /// Runs the clock exactly while the displayed state is Playing.
+    fn sync_clock(&mut self, at: Instant) {
+        let playing = self.display_state(at) == PlayState::Playing;
+        self.clock.set_running(playing, at);
+    }
+
+    /// Records a failed poll: the timeline is unknown until the next good snapshot.
+    pub fn apply_error(&mut self, err: String, at: Instant) {
+        self.err = Some(err);
+        self.set_timeline(None);
+        self.last_pull = at;
+    }
+
+    /// Play state for rendering: the provisional guess while it is live, otherwise the
+    /// last confirmed snapshot.
+    pub fn display_state(&self, at: Instant) -> PlayState {
+        self.provisional_state
+            .and_then(|provisional| provisional.state_at(at))
+            .unwrap_or(self.now.state)
+    }
+
+    /// Shows `state` right away after a play/pause request. The clock starts or stops
+    /// at `at`, so a resume counts from the click rather than the last paused frame.
+    pub fn expect_state(&mut self, state: PlayState, at: Instant) {
+        self.provisional_state = Some(ProvisionalPlayState::new(state, at));
+        self.sync_clock(at);
+    }
+
+    /// Moves the timeline to the clock's prediction for `at`. The position is derived
+    /// from the clock's anchor and the wall clock, so it does not depend on how often
+    /// frames are drawn.
+    pub fn advance_timeline(&mut self, at: Instant) {
+        self.sync_clock(at);
+        let Some(position) = self.clock.predicted_position(at) else {
+            return;
+        };
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.position_secs = position;
+        }
+    }
+
+    /// How long the current listening session has run, pauses included. `None` once
+    /// playback has been off for [`LISTENING_SESSION_GAP`].
+    pub fn listening_for(&self, at: Instant) -> Option<Duration> {
+        let last_playing = self.last_playing_at?;
+        if at.saturating_duration_since(last_playing) >= LISTENING_SESSION_GAP {
+            return None;
+        }
+        Some(at.saturating_duration_since(self.listening_since?))
+    }
+
+    /// Time until the advancing position crosses its next whole second, so slow repaint
+    /// cadences can still tick the timestamp on time. `None` when it isn't advancing.
+    pub fn until_next_position_second(&self, at: Instant) -> Option<Duration> {
+        let timeline = self.timeline.as_ref()?;
+        if self.display_state(at) != PlayState::Playing
+            || !self.clock.is_advancing()
+            || timeline.position_secs >= timeline.end_secs
+        {
+            return None;
+        }
+        let relative = (timeline.position_secs - timeline.start_secs).max(0.0);
+        Some(Duration::from_secs_f64(
+            (relative.floor() + 1.0 - relative).max(0.001),
+        ))
+    }
+
+    /// The user is dragging the seek slider to `position`.
+    pub fn drag_seek(&mut self, position: f64, at: Instant) {
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.position_secs = position;
+        }
+        self.is_user_seeking = true;
+        self.clock.clear_seek();
+        self.clock.anchor(position, at);
+    }
+
+    /// The user released the slider at `target`. Returns true when the position moved
+    /// from `previous` and a seek should be sent to the session.
+    pub fn commit_seek(&mut self, target: f64, previous: f64, at: Instant) -> bool {
+        if (target - previous).abs() > 0.001 {
+            self.clock.hold_seek(target, at);
+            self.is_user_seeking = true;
+            true
+        } else {
+            self.clear_seek();
+            false
+        }
+    }
+
+    fn clear_seek(&mut self) {
+        self.clock.clear_seek();
+        self.is_user_seeking = false;
+    }
+
+    /// Poll more aggressively while playback is active or changing, but back off in idle
+    /// states to avoid unnecessary COM traffic.
+    pub fn snapshot_poll_interval(&self) -> Duration {
+        match self.now.state {
+            PlayState::Playing => Duration::from_millis(800),
+            PlayState::Changing => Duration::from_millis(500),
+            PlayState::Opened => Duration::from_secs(2),
+            PlayState::Paused => Duration::from_secs(3),
+            PlayState::Stopped => Duration::from_secs(4),
+            PlayState::Closed | PlayState::Unknown => Duration::from_secs(5),
+        }
+    }
+}
+
+/// Schedule for ambient layout rotation. A switch falls due every `interval`, then
+/// waits for the next track change (at most [`ROTATION_TRACK_WAIT`]) so layouts don't
+/// jump mid-song, and never happens during a seek.
+#[derive(Clone, Debug)]
+pub struct LayoutRotation {
+    variants: Vec<String>,
+    interval: Duration,
+    next: usize,
+    due_at: Instant,
+    track_changed_at: Option<Instant>,
+}
+
+impl LayoutRotation {
+    /// Rotation through `variants` (not empty), continuing after `current`.
+    pub fn new(variants: Vec<String>, interval: Duration, current: &str, now: Instant) -> Self {
+        let mut rotation = Self {
+            variants,
+            interval,
+            next: 0,
+            due_at: now + interval,
+            track_changed_at: None,
+        };
+        rotation.continue_after(current);
+        rotation
+    }
+
+    pub fn variants(&self) -> &[String] {
+        &self.variants
+    }
+
+    pub fn track_changed(&mut self, now: Instant) {
+        self.track_changed_at = Some(now);
+    }
+
+    /// The user picked `layout` by hand: hold rotation off, then carry on after it.
+    pub fn manual_pick(&mut self, layout: &str, now: Instant) {
+        self.continue_after(layout);
+        self.due_at = now + self.interval.max(ROTATION_MANUAL_HOLD);
+    }
+
+    /// Holds the schedule while something else has the user's attention (the settings
+    /// panel); the next switch comes a full interval after the pause ends.
+    pub fn pause(&mut self, now: Instant) {
+        self.due_at = self.due_at.max(now + self.interval);
+    }
+
+    /// The variant to switch to at `now`, if a switch is due and allowed.
+    pub fn poll(&mut self, now: Instant, seeking: bool) -> Option<&str> {
+        if now < self.due_at || seeking {
+            return None;
+        }
+        let between_tracks = self.track_changed_at.is_some_and(|at| at >= self.due_at);
+        if !between_tracks && now < self.due_at + ROTATION_TRACK_WAIT {
+            return None;
+        }
+        let index = self.next;
+        self.next = (index + 1) % self.variants.len();
+        self.due_at = now + self.interval;
+        self.track_changed_at = None;
+        Some(&self.variants[index])
+    }
+
+    fn continue_after(&mut self, layout: &str) {
+        if let Some(index) = self.variants.iter().position(|id| id == layout) {
+            self.next = (index + 1) % self.variants.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_rotation_waits_for_track_changes_seeks_and_manual_picks() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let variants = vec![
+            "art_left".to_string(),
+            "art_top".to_string(),
+            "mini".to_string(),
+        ];
+        let mut rotation =
+            LayoutRotation::new(variants, Duration::from_secs(300), "art_left", at(0));
+
+        assert_eq!(rotation.poll(at(299), false), None);
+        // Due, but mid-song: wait for the track to change.
+        assert_eq!(rotation.poll(at(300), false), None);
+        rotation.track_changed(at(320));
+        assert_eq!(rotation.poll(at(320), true), None, "never during a seek");
+        assert_eq!(rotation.poll(at(321), false), Some("art_top"));
+
+        // Without a track change it switches once the wait runs out.
+        assert_eq!(rotation.poll(at(621 + 59), false), None);
+        assert_eq!(rotation.poll(at(621 + 60), false), Some("mini"));
+
+        // A hand-picked layout holds rotation off, then continues after it.
+        rotation.manual_pick("art_left", at(700));
+        rotation.track_changed(at(1000));
+        assert_eq!(rotation.poll(at(1000), false), None);
+        assert_eq!(rotation.poll(at(1300 + 60), false), Some("art_top"));
+
+        // The settings panel pushes the next switch a full interval out.
+        rotation.pause(at(1500));
+        rotation.track_changed(at(1700));
+        assert_eq!(rotation.poll(at(1700), false), None);
+        rotation.track_changed(at(1800));
+        assert_eq!(rotation.poll(at(1800), false), Some("mini"));
+    }
+
+    fn other(app_id: &str, title: &str, state: PlayState) -> OtherSession {
+        OtherSession {
+            app_id: app_id.to_string(),
+            title: title.to_string(),
+            artist: String::new(),
+            state,
+        }
+    }
+
+    #[test]
+    fn humanize_duration_keeps_the_two_largest_units() {
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert_eq!(humanize_duration(Duration::from_secs(59)), "0 m");
+        assert_eq!(humanize_duration(minutes(47)), "47 m");
+        assert_eq!(
+            humanize_duration(minutes(72) + Duration::from_secs(30)),
+            "1 h 12 m"
+        );
+        assert_eq!(humanize_duration(minutes(60)), "1 h 0 m");
+        assert_eq!(humanize_duration(minutes(51 * 60 + 59)), "2 d 3 h");
+    }
+
+    #[test]
+    fn spoken_timestamps_read_as_words() {
+        assert_eq!(spoken_timestamp(0.0), "zero seconds");
+        assert_eq!(spoken_timestamp(1.9), "one second");
+        assert_eq!(spoken_timestamp(42.0), "forty-two seconds");
+        assert_eq!(spoken_timestamp(60.0), "one minute");
+        assert_eq!(spoken_timestamp(65.0), "one minute five");
+        assert_eq!(spoken_timestamp(150.0), "two minutes thirty");
+        assert_eq!(
+            spoken_timestamp(3600.0 + 5.0 * 60.0 + 9.0),
+            "one hour five minutes"
+        );
+        assert_eq!(spoken_timestamp(2.0 * 3600.0), "two hours");
+        assert_eq!(number_words(115), "one hundred fifteen");
+        assert_eq!(number_words(300), "three hundred");
+        assert_eq!(
+            spoken_position(150.0, 250.0),
+            "two minutes thirty of four minutes ten"
+        );
+        assert_eq!(spoken_position(12.0, 0.0), "twelve seconds");
+    }
+
+    #[test]
+    fn up_next_picks_the_most_recently_paused_other_session() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(5);
+        let mut paused_since = HashMap::new();
+
+        assert_eq!(select_up_next(&[], &mut paused_since, start), None);
+
+        let first = [
+            other("Spotify.exe", "Song A", PlayState::Paused),
+            other("chrome.exe", "Video", PlayState::Playing),
+        ];
+        assert_eq!(
+            select_up_next(&first, &mut paused_since, start).map(|s| s.title),
+            Some("Song A".to_string())
+        );
+
+        let second = [
+            other("Spotify.exe", "Song A", PlayState::Paused),
+            other("chrome.exe", "Video", PlayState::Paused),
+        ];
+        assert_eq!(
+            select_up_next(&second, &mut paused_since, later).map(|s| s.title),
+            Some("Video".to_string())
+        );
+
+        // Resuming forgets the pause, so pausing again counts as the newest.
+        let third = [
+            other("Spotify.exe", "Song A", PlayState::Playing),
+            other("chrome.exe", "Video", PlayState::Paused),
+        ];
+        select_up_next(&third, &mut paused_since, later);
+        assert!(!paused_since.contains_key("Spotify.exe"));
+        let fourth = [
+            other("Spotify.exe", "Song A", PlayState::Paused),
+            other("chrome.exe", "Video", PlayState::Paused),
+        ];
+        let resumed_later = later + Duration::from_secs(1);
+        assert_eq!(
+            select_up_next(&fourth, &mut paused_since, resumed_later).map(|s| s.title),
+            Some("Song A".to_string())
+        );
+    }
+
+    #[test]
+    fn track_template_fills_known_placeholders() {
+        let track = NowPlaying {
+            title: "Song".to_string(),
+            artist: "Band".to_string(),
+            album: "Record".to_string(),
+            app_id: "Spotify.exe".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            fill_track_template("{title} — {artist}", &track),
+            "Song — Band"
+        );
+        assert_eq!(
+            fill_track_template("{album} ({app})", &track),
+            "Record (Spotify)"
+        );
+        assert_eq!(
+            fill_track_template("{title} {mood} {", &track),
+            "Song {mood} {"
+        );
+
+        let untitled_artist = NowPlaying {
+            title: "Song".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            fill_track_template("{title} — {artist}", &untitled_artist),
+            "Song"
+        );
+        assert_eq!(
+            fill_track_template("{artist} - {title}", &untitled_artist),
+            "Song"
+        );
+    }
+
+    #[test]
+    fn other_session_app_name_strips_package_and_extension() {
+        assert_eq!(
+            other("Spotify.exe", "", PlayState::Paused).app_name(),
+            "Spotify"
+        );
+        let zune = "Microsoft.ZuneMusic_8wekyb3d8bbwe!Microsoft.ZuneMusic";
+        assert_eq!(other(zune, "", PlayState::Paused).app_name(), "ZuneMusic");
+        assert_eq!(other("vlc", "", PlayState::Paused).app_name(), "vlc");
+    }
+
+    #[test]
+    fn controls_caps_report_when_nothing_is_available() {
+        assert!(!ControlsCaps::default().none_available());
+        let none = ControlsCaps {
+            previous: false,
+            next: false,
+            play: false,
+            pause: false,
+            play_pause_toggle: false,
+            seek: true,
+        };
+        assert!(none.none_available());
+        assert!(!ControlsCaps { next: true, ..none }.none_available());
+    }
+
+    #[test]
+    fn provisional_state_is_confirmed_kept_or_reverted_by_snapshots() {
+        let clicked = Instant::now();
+        let provisional = ProvisionalPlayState::new(PlayState::Paused, clicked);
+        let shortly = clicked + Duration::from_millis(100);
+        let later = clicked + PROVISIONAL_STATE_GRACE + Duration::from_millis(1);
+
+        assert_eq!(provisional.state_at(shortly), Some(PlayState::Paused));
+        assert_eq!(provisional.state_at(later), None);
+
+        // A snapshot taken before the session applied the pause must not flip the UI back.
+        assert_eq!(
+            provisional.reconcile(PlayState::Playing, shortly),
+            Some(provisional)
+        );
+        assert_eq!(provisional.reconcile(PlayState::Paused, shortly), None);
+        assert_eq!(provisional.reconcile(PlayState::Playing, later), None);
+    }
+
+    #[test]
+    fn provisional_state_only_affects_display() {
+        let clicked = Instant::now();
+        let mut core = AppCore::new(clicked);
+        core.now.state = PlayState::Playing;
+        core.expect_state(PlayState::Paused, clicked);
+
+        assert_eq!(core.display_state(clicked), PlayState::Paused);
+        assert_eq!(core.now.state, PlayState::Playing);
+        assert_eq!(
+            core.display_state(clicked + PROVISIONAL_STATE_GRACE),
+            PlayState::Playing
+        );
+    }
+
+    #[test]
+    fn controls_caps_map_to_enabled_actions() {
+        let all = ControlsCaps::default();
+        assert!(all.allows(ThumbnailOverlayAction::Previous));
+        assert!(all.allows(ThumbnailOverlayAction::Pause));
+
+        let limited = ControlsCaps {
+            previous: false,
+            next: true,
+            play: false,
+            pause: false,
+            play_pause_toggle: true,
+            seek: false,
+        };
+        assert!(!limited.allows(ThumbnailOverlayAction::Previous));
+        assert!(limited.allows(ThumbnailOverlayAction::Next));
+        // A toggle-only session still supports both halves of play/pause, sent as the
+        // toggle it accepts.
+        assert!(limited.allows(ThumbnailOverlayAction::Play));
+        assert!(limited.allows(ThumbnailOverlayAction::Pause));
+        assert!(limited.needs_toggle(ThumbnailOverlayAction::Play));
+        assert!(limited.needs_toggle(ThumbnailOverlayAction::Pause));
+        assert!(!limited.needs_toggle(ThumbnailOverlayAction::Next));
+        assert!(!all.needs_toggle(ThumbnailOverlayAction::Play));
+
+        let no_pause = ControlsCaps {
+            pause: false,
+            play_pause_toggle: false,
+            ..ControlsCaps::default()
+        };
+        assert!(no_pause.allows(ThumbnailOverlayAction::Play));
+        assert!(!no_pause.allows(ThumbnailOverlayAction::Pause));
+        assert!(!no_pause.needs_toggle(ThumbnailOverlayAction::Pause));
+    }
+
+    #[test]
+    fn long_tracks_and_spoken_genres_classify_as_long_form() {
+        let threshold = DEFAULT_LONG_FORM_THRESHOLD;
+        let genres = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            classify_content(Some(240.0), &[], threshold),
+            ContentKind::Music
+        );
+        assert_eq!(
+            classify_content(None, &genres(&["Rock"]), threshold),
+            ContentKind::Music
+        );
+        // Exactly at the threshold is still music; only longer tracks switch.
+        assert_eq!(
+            classify_content(Some(1200.0), &[], threshold),
+            ContentKind::Music
+        );
+        assert_eq!(
+            classify_content(Some(1201.0), &[], threshold),
+            ContentKind::LongForm
+        );
+        assert_eq!(
+            classify_content(Some(1201.0), &[], Duration::from_secs(60 * 60)),
+            ContentKind::Music
+        );
+        assert_eq!(
+            classify_content(Some(90.0), &genres(&["News", "Podcasts"]), threshold),
+            ContentKind::LongForm
+        );
+        assert_eq!(
+            classify_content(None, &genres(&["Audio Book"]), threshold),
+            ContentKind::LongForm
+        );
+    }
+
+    #[test]
+    fn progress_fraction_is_relative_to_the_start_and_clamped() {
+        let timeline = |position_secs| Timeline {
+            start_secs: 10.0,
+            end_secs: 210.0,
+            position_secs,
+            can_seek: true,
+        };
+        assert_eq!(timeline(10.0).progress_fraction(), 0.0);
+        assert_eq!(timeline(60.0).progress_fraction(), 0.25);
+        assert_eq!(timeline(500.0).progress_fraction(), 1.0);
+        assert_eq!(timeline(0.0).progress_fraction(), 0.0);
+        let empty = Timeline {
+            end_secs: 10.0,
+            ..timeline(10.0)
+        };
+        assert_eq!(empty.progress_fraction(), 0.0);
+    }
+}
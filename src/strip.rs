@@ -0,0 +1,184 @@
+//! Horizontal strips that hold a fixed-width content column inside a wider row, used to
+//! pad and anchor the timeline and playback rows. Skin components can use them too.
+
+use eframe::egui;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StripMetrics {
+    total_width: f32,
+    content_width: f32,
+    margin: f32,
+}
+
+impl StripMetrics {
+    /// Content of `content_width` centered in `total_width`.
+    pub fn from_content(total_width: f32, content_width: f32) -> Self {
+        let total = total_width.max(1.0);
+        let content = content_width.clamp(1.0, total);
+        let margin = ((total - content) / 2.0).max(0.0);
+        Self {
+            total_width: total,
+            content_width: content,
+            margin,
+        }
+    }
+
+    /// Content as wide as possible up to `max_content_width`, centered.
+    pub fn center_with_max(total_width: f32, max_content_width: f32) -> Self {
+        let target = max_content_width.max(1.0).min(total_width.max(1.0));
+        Self::from_content(total_width, target)
+    }
+
+    /// Content filling `total_width` less `padding` on each side.
+    pub fn padded(total_width: f32, padding: f32) -> Self {
+        let total = total_width.max(1.0);
+        let margin = padding.clamp(0.0, total / 2.0);
+        let content = (total - 2.0 * margin).max(1.0);
+        Self {
+            total_width: total,
+            content_width: content,
+            margin,
+        }
+    }
+
+    pub fn total_width(&self) -> f32 {
+        self.total_width
+    }
+
+    pub fn content_width(&self) -> f32 {
+        self.content_width
+    }
+
+    /// Space to the left and right of the content when anchored at `align`. `Min` is the
+    /// start of the reading direction, so it anchors right when `right_to_left`.
+    pub fn anchor_margins(&self, align: egui::Align, right_to_left: bool) -> (f32, f32) {
+        let extra = (self.total_width - self.content_width).max(0.0);
+        let align = match (align, right_to_left) {
+            (egui::Align::Min, true) => egui::Align::Max,
+            (egui::Align::Max, true) => egui::Align::Min,
+            (align, _) => align,
+        };
+        match align {
+            egui::Align::Min => (0.0, extra),
+            egui::Align::Center => (extra / 2.0, extra / 2.0),
+            egui::Align::Max => (extra, 0.0),
+        }
+    }
+
+    pub fn show<R>(&self, ui: &mut egui::Ui, builder: impl FnOnce(&mut egui::Ui) -> R) -> R {
+        ui.allocate_ui_with_layout(
+            egui::vec2(self.total_width, 0.0),
+            egui::Layout::left_to_right(egui::Align::Center),
+            |row| {
+                if self.margin > 0.0 {
+                    row.add_space(self.margin);
+                }
+                let result = row
+                    .allocate_ui_with_layout(
+                        egui::vec2(self.content_width, 0.0),
+                        egui::Layout::top_down(egui::Align::Center),
+                        builder,
+                    )
+                    .inner;
+                if self.margin > 0.0 {
+                    row.add_space(self.margin);
+                }
+                result
+            },
+        )
+        .inner
+    }
+
+    pub fn show_with_layout<R>(
+        &self,
+        ui: &mut egui::Ui,
+        layout: egui::Layout,
+        builder: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> R {
+        self.show(ui, |inner| inner.with_layout(layout, builder).inner)
+    }
+
+    /// Show the strip and anchor its content to the start, center or end of the total
+    /// width, following the parent layout's direction (see [`Self::anchor_margins`]).
+    pub fn show_anchored<R>(
+        &self,
+        ui: &mut egui::Ui,
+        align: egui::Align,
+        builder: impl FnOnce(&mut egui::Ui) -> R,
+    ) -> R {
+        let right_to_left = ui.layout().main_dir() == egui::Direction::RightToLeft;
+        let (left_space, right_space) = self.anchor_margins(align, right_to_left);
+        ui.allocate_ui_with_layout(
+            egui::vec2(self.total_width, 0.0),
+            egui::Layout::left_to_right(egui::Align::Center),
+            |row| {
+                if left_space > 0.0 {
+                    row.add_space(left_space);
+                }
+
+                let result = row
+                    .allocate_ui_with_layout(
+                        egui::vec2(self.content_width, 0.0),
+                        egui::Layout::top_down(egui::Align::Center),
+                        builder,
+                    )
+                    .inner;
+
+                if right_space > 0.0 {
+                    row.add_space(right_space);
+                }
+
+                result
+            },
+        )
+        .inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Align;
+
+    #[test]
+    fn constructors_clamp_content_and_margins() {
+        let centered = StripMetrics::from_content(300.0, 100.0);
+        assert_eq!(centered.content_width(), 100.0);
+        assert_eq!(
+            centered.anchor_margins(Align::Center, false),
+            (100.0, 100.0)
+        );
+
+        // Content never outgrows the strip, and padding never eats all of it.
+        assert_eq!(
+            StripMetrics::from_content(300.0, 500.0).content_width(),
+            300.0
+        );
+        assert_eq!(
+            StripMetrics::center_with_max(300.0, 120.0).content_width(),
+            120.0
+        );
+        assert_eq!(StripMetrics::padded(300.0, 20.0).content_width(), 260.0);
+        assert_eq!(StripMetrics::padded(30.0, 40.0).content_width(), 1.0);
+    }
+
+    #[test]
+    fn anchor_margins_follow_reading_direction() {
+        let strip = StripMetrics::from_content(300.0, 100.0);
+        for (align, ltr, rtl) in [
+            (Align::Min, (0.0, 200.0), (200.0, 0.0)),
+            (Align::Center, (100.0, 100.0), (100.0, 100.0)),
+            (Align::Max, (200.0, 0.0), (0.0, 200.0)),
+        ] {
+            assert_eq!(strip.anchor_margins(align, false), ltr, "{align:?}");
+            assert_eq!(
+                strip.anchor_margins(align, true),
+                rtl,
+                "{align:?} right-to-left"
+            );
+        }
+
+        let full = StripMetrics::from_content(300.0, 300.0);
+        assert_eq!(full.anchor_margins(Align::Max, true), (0.0, 0.0));
+    }
+}
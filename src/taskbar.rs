@@ -0,0 +1,377 @@
+//! Previous / play-pause / next buttons on the taskbar thumbnail preview.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use eframe::egui;
+use windows::{
+    core::{w, Error as WinError, Result as WinResult},
+    Win32::{
+        Foundation::{E_FAIL, HWND, LPARAM, LRESULT, WPARAM},
+        System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER},
+        UI::{
+            Shell::{
+                DefSubclassProc, ITaskbarList3, RemoveWindowSubclass, SetWindowSubclass,
+                TaskbarList, THBF_ENABLED, THBN_CLICKED, THB_FLAGS, THB_ICON, THB_TOOLTIP,
+                THUMBBUTTON,
+            },
+            WindowsAndMessaging::{
+                CreateIcon, DestroyIcon, RegisterWindowMessageW, HICON, WM_COMMAND,
+            },
+        },
+    },
+};
+
+use crate::ThumbnailOverlayAction;
+
+const BUTTON_PREVIOUS: u32 = 1;
+const BUTTON_PLAY_PAUSE: u32 = 2;
+const BUTTON_NEXT: u32 = 3;
+const SUBCLASS_ID: usize = 0x4e50_5442;
+const ICON_SIZE: usize = 16;
+const ICON_SUPERSAMPLE: usize = 4;
+
+/// Maps a `WM_COMMAND` wParam from the thumbnail toolbar to a playback action.
+/// The play/pause button resolves against the state shown when it was clicked.
+pub(crate) fn command_action(wparam: usize, playing: bool) -> Option<ThumbnailOverlayAction> {
+    let notification = ((wparam >> 16) & 0xFFFF) as u32;
+    if notification != THBN_CLICKED {
+        return None;
+    }
+    match (wparam & 0xFFFF) as u32 {
+        BUTTON_PREVIOUS => Some(ThumbnailOverlayAction::Previous),
+        BUTTON_PLAY_PAUSE if playing => Some(ThumbnailOverlayAction::Pause),
+        BUTTON_PLAY_PAUSE => Some(ThumbnailOverlayAction::Play),
+        BUTTON_NEXT => Some(ThumbnailOverlayAction::Next),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Glyph {
+    Previous,
+    Play,
+    Pause,
+    Next,
+}
+
+impl Glyph {
+    /// Hit test in unit coordinates, origin top-left.
+    fn contains(self, x: f32, y: f32) -> bool {
+        match self {
+            Glyph::Play => in_triangle((x, y), (0.3, 0.2), (0.3, 0.8), (0.78, 0.5)),
+            Glyph::Pause => {
+                in_rect(x, y, 0.28, 0.2, 0.43, 0.8) || in_rect(x, y, 0.57, 0.2, 0.72, 0.8)
+            }
+            Glyph::Previous => {
+                in_rect(x, y, 0.22, 0.22, 0.32, 0.78)
+                    || in_triangle((x, y), (0.78, 0.22), (0.78, 0.78), (0.34, 0.5))
+            }
+            Glyph::Next => Glyph::Previous.contains(1.0 - x, y),
+        }
+    }
+}
+
+fn in_rect(x: f32, y: f32, left: f32, top: f32, right: f32, bottom: f32) -> bool {
+    x >= left && x <= right && y >= top && y <= bottom
+}
+
+fn in_triangle(p: (f32, f32), a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> bool {
+    let edge = |from: (f32, f32), to: (f32, f32)| {
+        (to.0 - from.0) * (p.1 - from.1) - (to.1 - from.1) * (p.0 - from.0)
+    };
+    let (d1, d2, d3) = (edge(a, b), edge(b, c), edge(c, a));
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+/// Rasterizes a white glyph as top-down BGRA rows with supersampled edges.
+fn glyph_pixels(glyph: Glyph, size: usize) -> Vec<u8> {
+    let mut pixels = vec![0u8; size * size * 4];
+    let samples = (ICON_SUPERSAMPLE * ICON_SUPERSAMPLE) as f32;
+    let step = 1.0 / ICON_SUPERSAMPLE as f32;
+    for row in 0..size {
+        for col in 0..size {
+            let mut hits = 0usize;
+            for sy in 0..ICON_SUPERSAMPLE {
+                for sx in 0..ICON_SUPERSAMPLE {
+                    let x = (col as f32 + (sx as f32 + 0.5) * step) / size as f32;
+                    let y = (row as f32 + (sy as f32 + 0.5) * step) / size as f32;
+                    if glyph.contains(x, y) {
+                        hits += 1;
+                    }
+                }
+            }
+            let alpha = (hits as f32 / samples * 255.0).round() as u8;
+            if alpha > 0 {
+                let offset = (row * size + col) * 4;
+                pixels[offset..offset + 4].copy_from_slice(&[255, 255, 255, alpha]);
+            }
+        }
+    }
+    pixels
+}
+
+fn create_glyph_icon(glyph: Glyph) -> WinResult<HICON> {
+    let color = glyph_pixels(glyph, ICON_SIZE);
+    // The AND mask is 1bpp with WORD-aligned rows; all zeros lets the alpha channel decide.
+    let mask = vec![0u8; ICON_SIZE.div_ceil(16) * 2 * ICON_SIZE];
+    unsafe {
+        CreateIcon(
+            None,
+            ICON_SIZE as i32,
+            ICON_SIZE as i32,
+            1,
+            32,
+            mask.as_ptr(),
+            color.as_ptr(),
+        )
+    }
+}
+
+enum ThumbBarEvent {
+    Command(usize),
+    TaskbarRecreated,
+}
+
+struct SubclassData {
+    tx: Sender<ThumbBarEvent>,
+    ctx: egui::Context,
+    taskbar_created: u32,
+}
+
+unsafe extern "system" fn subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _id: usize,
+    data: usize,
+) -> LRESULT {
+    let data = unsafe { &*(data as *const SubclassData) };
+    if msg == WM_COMMAND && ((wparam.0 >> 16) & 0xFFFF) as u32 == THBN_CLICKED {
+        let _ = data.tx.send(ThumbBarEvent::Command(wparam.0));
+        data.ctx.request_repaint();
+        return LRESULT(0);
+    }
+    if data.taskbar_created != 0 && msg == data.taskbar_created {
+        let _ = data.tx.send(ThumbBarEvent::TaskbarRecreated);
+        data.ctx.request_repaint();
+    }
+    unsafe { DefSubclassProc(hwnd, msg, wparam, lparam) }
+}
+
+struct GlyphIcons {
+    previous: HICON,
+    play: HICON,
+    pause: HICON,
+    next: HICON,
+}
+
+impl GlyphIcons {
+    fn create() -> WinResult<Self> {
+        let mut created = Vec::with_capacity(4);
+        for glyph in [Glyph::Previous, Glyph::Play, Glyph::Pause, Glyph::Next] {
+            match create_glyph_icon(glyph) {
+                Ok(icon) => created.push(icon),
+                Err(err) => {
+                    for icon in created {
+                        unsafe {
+                            let _ = DestroyIcon(icon);
+                        }
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(Self {
+            previous: created[0],
+            play: created[1],
+            pause: created[2],
+            next: created[3],
+        })
+    }
+}
+
+impl Drop for GlyphIcons {
+    fn drop(&mut self) {
+        for icon in [self.previous, self.play, self.pause, self.next] {
+            unsafe {
+                let _ = DestroyIcon(icon);
+            }
+        }
+    }
+}
+
+/// Owns the thumbnail toolbar for one window and the subclass that receives its clicks.
+pub struct ThumbBar {
+    hwnd: HWND,
+    taskbar: ITaskbarList3,
+    icons: GlyphIcons,
+    events: Receiver<ThumbBarEvent>,
+    subclass: *mut SubclassData,
+    showing_pause: bool,
+}
+
+impl ThumbBar {
+    /// Adds the buttons to `hwnd`. Fails until the taskbar button exists, so callers retry.
+    pub fn install(hwnd: HWND, ctx: egui::Context, playing: bool) -> WinResult<Self> {
+        let taskbar: ITaskbarList3 =
+            unsafe { CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER)? };
+        unsafe { taskbar.HrInit()? };
+        let icons = GlyphIcons::create()?;
+        unsafe { taskbar.ThumbBarAddButtons(hwnd, &buttons(&icons, playing))? };
+
+        let (tx, events) = mpsc::channel();
+        let taskbar_created = unsafe { RegisterWindowMessageW(w!("TaskbarButtonCreated")) };
+        let subclass = Box::into_raw(Box::new(SubclassData {
+            tx,
+            ctx,
+            taskbar_created,
+        }));
+        let installed =
+            unsafe { SetWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID, subclass as usize) };
+        if !installed.as_bool() {
+            drop(unsafe { Box::from_raw(subclass) });
+            return Err(WinError::new(E_FAIL, "SetWindowSubclass failed"));
+        }
+
+        Ok(Self {
+            hwnd,
+            taskbar,
+            icons,
+            events,
+            subclass,
+            showing_pause: playing,
+        })
+    }
+
+    /// Swaps the play/pause image when the playback state changes.
+    pub fn set_playing(&mut self, playing: bool) {
+        if self.showing_pause == playing {
+            return;
+        }
+        let result = unsafe {
+            self.taskbar
+                .ThumbBarUpdateButtons(self.hwnd, &buttons(&self.icons, playing))
+        };
+        match result {
+            Ok(()) => self.showing_pause = playing,
+            Err(err) => eprintln!("Failed to update taskbar buttons: {err}"),
+        }
+    }
+
+    /// Drains clicks since the last frame. Explorer restarts drop the toolbar, so the
+    /// buttons are re-added when the taskbar announces itself again.
+    pub fn take_actions(&mut self, playing: bool) -> Vec<ThumbnailOverlayAction> {
+        let mut actions = Vec::new();
+        while let Ok(event) = self.events.try_recv() {
+            match event {
+                ThumbBarEvent::Command(wparam) => {
+                    actions.extend(command_action(wparam, self.showing_pause));
+                }
+                ThumbBarEvent::TaskbarRecreated => {
+                    let result = unsafe {
+                        self.taskbar
+                            .ThumbBarAddButtons(self.hwnd, &buttons(&self.icons, playing))
+                    };
+                    match result {
+                        Ok(()) => self.showing_pause = playing,
+                        Err(err) => eprintln!("Failed to restore taskbar buttons: {err}"),
+                    }
+                }
+            }
+        }
+        actions
+    }
+}
+
+impl Drop for ThumbBar {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = RemoveWindowSubclass(self.hwnd, Some(subclass_proc), SUBCLASS_ID);
+            drop(Box::from_raw(self.subclass));
+        }
+    }
+}
+
+fn buttons(icons: &GlyphIcons, playing: bool) -> [THUMBBUTTON; 3] {
+    let (play_pause_icon, play_pause_tip) = if playing {
+        (icons.pause, "Pause")
+    } else {
+        (icons.play, "Play")
+    };
+    [
+        button(BUTTON_PREVIOUS, icons.previous, "Previous"),
+        button(BUTTON_PLAY_PAUSE, play_pause_icon, play_pause_tip),
+        button(BUTTON_NEXT, icons.next, "Next"),
+    ]
+}
+
+fn button(id: u32, icon: HICON, tooltip: &str) -> THUMBBUTTON {
+    let mut button = THUMBBUTTON {
+        dwMask: THB_ICON | THB_TOOLTIP | THB_FLAGS,
+        iId: id,
+        hIcon: icon,
+        dwFlags: THBF_ENABLED,
+        ..Default::default()
+    };
+    let max_len = button.szTip.len() - 1;
+    for (slot, unit) in button
+        .szTip
+        .iter_mut()
+        .zip(tooltip.encode_utf16().take(max_len))
+    {
+        *slot = unit;
+    }
+    button
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clicked(id: u32) -> usize {
+        ((THBN_CLICKED as usize) << 16) | id as usize
+    }
+
+    #[test]
+    fn thumb_button_commands_map_to_playback_actions() {
+        assert_eq!(
+            command_action(clicked(BUTTON_PREVIOUS), false),
+            Some(ThumbnailOverlayAction::Previous)
+        );
+        assert_eq!(
+            command_action(clicked(BUTTON_NEXT), true),
+            Some(ThumbnailOverlayAction::Next)
+        );
+        assert_eq!(
+            command_action(clicked(BUTTON_PLAY_PAUSE), true),
+            Some(ThumbnailOverlayAction::Pause)
+        );
+        assert_eq!(
+            command_action(clicked(BUTTON_PLAY_PAUSE), false),
+            Some(ThumbnailOverlayAction::Play)
+        );
+        assert_eq!(command_action(clicked(42), false), None);
+        // Menu and accelerator commands share WM_COMMAND but carry other codes.
+        assert_eq!(command_action(BUTTON_NEXT as usize, false), None);
+    }
+
+    #[test]
+    fn glyphs_rasterize_distinct_shapes() {
+        let play = glyph_pixels(Glyph::Play, ICON_SIZE);
+        let pause = glyph_pixels(Glyph::Pause, ICON_SIZE);
+        assert_eq!(play.len(), ICON_SIZE * ICON_SIZE * 4);
+        assert!(play.chunks(4).any(|px| px[3] == 255));
+        assert_ne!(play, pause);
+
+        let previous = glyph_pixels(Glyph::Previous, ICON_SIZE);
+        let next = glyph_pixels(Glyph::Next, ICON_SIZE);
+        let mirrored: Vec<u8> = next
+            .chunks(ICON_SIZE * 4)
+            .flat_map(|row| row.chunks(4).rev().flatten().copied().collect::<Vec<_>>())
+            .collect();
+        assert_eq!(previous, mirrored);
+    }
+}
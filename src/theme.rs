@@ -1,13 +1,30 @@
 use anyhow::{anyhow, Context, Result};
 use eframe::egui::{self, Color32};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fs,
     path::{Path, PathBuf},
 };
 
+use crate::engine::{reconcile, unknown_keys, EngineAction, Migration};
+
 pub const THEME_ENGINE_VERSION: &str = "1";
+/// Keys renamed since older engine majors; empty until version 2 renames something.
+const THEME_MIGRATIONS: &[Migration] = &[];
+/// Upper bounds for `components.panel.inset` / `elevation`, in logical pixels.
+const MAX_PANEL_INSET: f32 = 48.0;
+const MAX_PANEL_ELEVATION: f32 = 24.0;
+/// Bounds for the `[window]` sizes, in logical pixels.
+const MIN_WINDOW_SIDE: f32 = 48.0;
+const MAX_WINDOW_SIDE: f32 = 4096.0;
+/// Largest `scale` a thumbnail overlay may ask for.
+const MAX_OVERLAY_SCALE: f32 = 4.0;
+/// How far disabled buttons fade the skin's regular colors when it picks no
+/// `disabled_*` colors of its own.
+pub const DISABLED_FADE: f32 = 0.45;
+/// `components.thumbnail.hover_scrim` when the skin doesn't set one.
+const DEFAULT_THUMBNAIL_HOVER_SCRIM: Color32 = Color32::from_black_alpha(64);
 
 #[derive(Debug, Clone)]
 pub struct LoadedTheme {
@@ -25,11 +42,148 @@ pub struct Theme {
     pub colors: HashMap<String, Color32>,
     pub vars: HashMap<String, f32>,
     pub use_gradient: bool,
+    /// Buttons and the slider fill take their accent from the artwork when readable.
+    pub dynamic_accent: bool,
     pub disable_vinyl_thumbnail: bool,
     pub transparent_background: bool,
+    /// Opacity of the root and panel backgrounds; 0 when `transparent_background` is set.
+    pub background_alpha: f32,
+    /// Windows 11 material requested behind a see-through window.
+    pub window_backdrop: WindowBackdrop,
+    pub window_size: WindowSizing,
+    pub overlay_controls: OverlayControlsStyle,
     pub components: Components,
 }
 
+/// Window size a skin asks for in its `[window]` section. Unset values leave the
+/// window as the user has it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WindowSizing {
+    pub width: Option<f32>,
+    pub height: Option<f32>,
+    pub min_width: Option<f32>,
+    pub min_height: Option<f32>,
+    pub max_height: Option<f32>,
+}
+
+impl WindowSizing {
+    fn min_size(&self) -> egui::Vec2 {
+        egui::vec2(
+            self.min_width.unwrap_or(0.0),
+            self.min_height.unwrap_or(0.0),
+        )
+    }
+
+    fn max_size(&self) -> egui::Vec2 {
+        egui::vec2(f32::INFINITY, self.max_height.unwrap_or(f32::INFINITY))
+    }
+
+    /// Commands that take the window from `previous`'s limits to these. Limits this skin
+    /// drops are lifted, and the size is only set when the skin gives one, keeping the
+    /// `current` size for the side it leaves out.
+    pub fn viewport_commands(
+        &self,
+        previous: &WindowSizing,
+        current: egui::Vec2,
+    ) -> Vec<egui::ViewportCommand> {
+        let mut commands = Vec::new();
+        if self.min_size() != previous.min_size() {
+            commands.push(egui::ViewportCommand::MinInnerSize(self.min_size()));
+        }
+        if self.max_size() != previous.max_size() {
+            commands.push(egui::ViewportCommand::MaxInnerSize(self.max_size()));
+        }
+        if self.width.is_some() || self.height.is_some() {
+            let size = egui::vec2(
+                self.width.unwrap_or(current.x),
+                self.height.unwrap_or(current.y),
+            );
+            let size = size.max(self.min_size()).min(self.max_size());
+            commands.push(egui::ViewportCommand::InnerSize(size));
+        }
+        commands
+    }
+}
+
+/// Placement and colors of the pin/gear buttons drawn over the widget.
+#[derive(Debug, Clone)]
+pub struct OverlayControlsStyle {
+    pub visible: bool,
+    pub position: OverlayControlsPosition,
+    /// Icon color; `None` uses the inactive widget foreground.
+    pub color: Option<Color32>,
+    /// Icon color while toggled on; `None` uses the active widget foreground.
+    pub active_color: Option<Color32>,
+}
+
+impl Default for OverlayControlsStyle {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            position: OverlayControlsPosition::TopLeft,
+            color: None,
+            active_color: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayControlsPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl OverlayControlsPosition {
+    pub const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+
+    /// Accepts `top_left` as well as the `left_top` order used by egui's `Align2`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "top_left" | "left_top" => Some(Self::TopLeft),
+            "top_right" | "right_top" => Some(Self::TopRight),
+            "bottom_left" | "left_bottom" => Some(Self::BottomLeft),
+            "bottom_right" | "right_bottom" => Some(Self::BottomRight),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::TopLeft => "top_left",
+            Self::TopRight => "top_right",
+            Self::BottomLeft => "bottom_left",
+            Self::BottomRight => "bottom_right",
+        }
+    }
+}
+
+/// DWM system backdrop drawn behind the window on Windows 11.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowBackdrop {
+    #[default]
+    None,
+    Mica,
+    Acrylic,
+}
+
+impl WindowBackdrop {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "mica" => Some(Self::Mica),
+            "acrylic" => Some(Self::Acrylic),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct Components {
@@ -52,6 +206,12 @@ pub struct AreaStyle {
     pub border_radius: f32,
     pub border_width: f32,
     pub show_border: bool,
+    /// Outline the borderless window in the artwork's accent (`root` only).
+    pub dynamic_border: bool,
+    /// Padding between the panel card's edge and its content (`panel` only).
+    pub inset: f32,
+    /// Shadow strength under the panel card; `0` keeps it flat (`panel` only).
+    pub elevation: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +243,18 @@ impl AreaBackground {
             AreaBackground::Gradient(gradient) => gradient.start,
         }
     }
+
+    /// The same fill with every color's opacity multiplied by `alpha`.
+    pub fn with_alpha(&self, alpha: f32) -> AreaBackground {
+        match self {
+            AreaBackground::Solid(color) => AreaBackground::Solid(color.gamma_multiply(alpha)),
+            AreaBackground::Gradient(gradient) => AreaBackground::Gradient(GradientSpec {
+                start: gradient.start.gamma_multiply(alpha),
+                end: gradient.end.gamma_multiply(alpha),
+                direction: gradient.direction,
+            }),
+        }
+    }
 }
 
 impl AreaStyle {
@@ -91,6 +263,509 @@ impl AreaStyle {
     }
 }
 
+impl Theme {
+    /// The built-in dark theme, resolved from the embedded defaults without touching disk.
+    pub fn default_dark() -> Theme {
+        resolve_builtin(builtin_theme_document())
+    }
+
+    /// Light counterpart of [`Theme::default_dark`]; only the palette differs.
+    pub fn default_light() -> Theme {
+        let mut doc = builtin_theme_document();
+        let light: ThemeDocument =
+            toml::from_str(LIGHT_THEME_TOML).expect("Embedded light theme must parse");
+        merge_documents(&mut doc, light);
+        resolve_builtin(doc)
+    }
+
+    /// The embedded `stealth` skin: the built-in theme with no backgrounds and no
+    /// pin/gear buttons, so only the artwork shows.
+    pub fn stealth() -> Theme {
+        let mut doc = builtin_theme_document();
+        let stealth: ThemeDocument =
+            toml::from_str(STEALTH_THEME_TOML).expect("Embedded stealth theme must parse");
+        merge_documents(&mut doc, stealth);
+        resolve_builtin(doc)
+    }
+
+    /// Substitutes a user-picked accent for the skin's `accent`, `accent_hover`, and
+    /// `accent_active` colors, including component colors that were resolved from them.
+    pub fn apply_accent_override(&mut self, accent: Color32) {
+        let (hover, active) = derive_accent_variants(accent);
+        let replacements: Vec<(Color32, Color32)> = [
+            ("accent", accent),
+            ("accent_hover", hover),
+            ("accent_active", active),
+        ]
+        .into_iter()
+        .filter_map(|(key, replacement)| {
+            let previous = self.colors.insert(key.to_string(), replacement)?;
+            Some((previous, replacement))
+        })
+        .collect();
+        let remap = |color: &mut Color32| {
+            if let Some((_, replacement)) = replacements.iter().find(|(old, _)| old == color) {
+                *color = *replacement;
+            }
+        };
+
+        let button = &mut self.components.button;
+        remap(&mut button.background);
+        remap(&mut button.hover_background);
+        remap(&mut button.active_background);
+        remap(&mut button.border_color);
+        // Disabled colors left at their default follow the accent they were faded from.
+        if let Some((_, replacement)) = replacements
+            .iter()
+            .find(|(old, _)| old.gamma_multiply(DISABLED_FADE) == button.disabled_background)
+        {
+            button.disabled_background = replacement.gamma_multiply(DISABLED_FADE);
+        }
+
+        let slider = &mut self.components.slider;
+        slider.track_fill = accent;
+        match &mut slider.thumb {
+            SliderThumb::Circle { color, .. } | SliderThumb::Image { color, .. } => remap(color),
+        }
+    }
+
+    /// The backdrop to apply right now: only see-through skins get one, since an opaque
+    /// background would hide it anyway.
+    pub fn effective_window_backdrop(&self) -> WindowBackdrop {
+        if self.background_alpha < 1.0 {
+            self.window_backdrop
+        } else {
+            WindowBackdrop::None
+        }
+    }
+
+    /// Lists every color, variable, flag, and component style where `self` differs from
+    /// `other`, one line each as `path: other → self`. Names and asset paths are ignored.
+    pub fn diff(&self, other: &Theme) -> Vec<String> {
+        let mut diff = ThemeDiff::default();
+        diff.map("colors", &self.colors, &other.colors);
+        diff.map("vars", &self.vars, &other.vars);
+        diff.field("use_gradient", &self.use_gradient, &other.use_gradient);
+        diff.field(
+            "dynamic_accent",
+            &self.dynamic_accent,
+            &other.dynamic_accent,
+        );
+        diff.field(
+            "disable_vinyl_thumbnail",
+            &self.disable_vinyl_thumbnail,
+            &other.disable_vinyl_thumbnail,
+        );
+        diff.field(
+            "transparent_background",
+            &self.transparent_background,
+            &other.transparent_background,
+        );
+        diff.field(
+            "background_alpha",
+            &self.background_alpha,
+            &other.background_alpha,
+        );
+        diff.field(
+            "window_backdrop",
+            &self.window_backdrop,
+            &other.window_backdrop,
+        );
+        let (ours, theirs) = (&self.window_size, &other.window_size);
+        diff.field("window.width", &ours.width, &theirs.width);
+        diff.field("window.height", &ours.height, &theirs.height);
+        diff.field("window.min_width", &ours.min_width, &theirs.min_width);
+        diff.field("window.min_height", &ours.min_height, &theirs.min_height);
+        diff.field("window.max_height", &ours.max_height, &theirs.max_height);
+
+        let (ours, theirs) = (&self.overlay_controls, &other.overlay_controls);
+        diff.field("overlay_controls.visible", &ours.visible, &theirs.visible);
+        diff.field(
+            "overlay_controls.position",
+            &ours.position,
+            &theirs.position,
+        );
+        diff.field("overlay_controls.color", &ours.color, &theirs.color);
+        diff.field(
+            "overlay_controls.active_color",
+            &ours.active_color,
+            &theirs.active_color,
+        );
+
+        let (ours, theirs) = (&self.components, &other.components);
+        diff.area("components.root", &ours.root, &theirs.root);
+        diff.area("components.panel", &ours.panel, &theirs.panel);
+
+        let (button, base) = (&ours.button, &theirs.button);
+        diff.field(
+            "components.button.background",
+            &button.background,
+            &base.background,
+        );
+        diff.field(
+            "components.button.foreground",
+            &button.foreground,
+            &base.foreground,
+        );
+        diff.field(
+            "components.button.hover_background",
+            &button.hover_background,
+            &base.hover_background,
+        );
+        diff.field(
+            "components.button.active_background",
+            &button.active_background,
+            &base.active_background,
+        );
+        diff.field(
+            "components.button.disabled_background",
+            &button.disabled_background,
+            &base.disabled_background,
+        );
+        diff.field(
+            "components.button.disabled_foreground",
+            &button.disabled_foreground,
+            &base.disabled_foreground,
+        );
+        diff.field(
+            "components.button.border_color",
+            &button.border_color,
+            &base.border_color,
+        );
+        diff.field(
+            "components.button.border_radius",
+            &button.border_radius,
+            &base.border_radius,
+        );
+        diff.field(
+            "components.button.border_width",
+            &button.border_width,
+            &base.border_width,
+        );
+        diff.field("components.button.shape", &button.shape, &base.shape);
+        diff.field(
+            "components.button.icon.color",
+            &ours.button_icon.color,
+            &theirs.button_icon.color,
+        );
+        diff.field(
+            "components.button.icon.size_scale",
+            &ours.button_icon.size_scale,
+            &theirs.button_icon.size_scale,
+        );
+
+        let (slider, base) = (&ours.slider, &theirs.slider);
+        diff.field(
+            "components.slider.track_fill",
+            &slider.track_fill,
+            &base.track_fill,
+        );
+        diff.field(
+            "components.slider.track_background",
+            &slider.track_background,
+            &base.track_background,
+        );
+        diff.field(
+            "components.slider.track_thickness",
+            &slider.track_thickness,
+            &base.track_thickness,
+        );
+        diff.field(
+            "components.slider.tick_color",
+            &slider.tick_color,
+            &base.tick_color,
+        );
+        diff.field("components.slider.thumb", &slider.thumb, &base.thumb);
+
+        let (thumbnail, base) = (&ours.thumbnail, &theirs.thumbnail);
+        diff.field(
+            "components.thumbnail.corner_radius",
+            &thumbnail.corner_radius,
+            &base.corner_radius,
+        );
+        diff.field(
+            "components.thumbnail.stroke_color",
+            &thumbnail.stroke_color,
+            &base.stroke_color,
+        );
+        diff.field(
+            "components.thumbnail.stroke_width",
+            &thumbnail.stroke_width,
+            &base.stroke_width,
+        );
+        diff.field(
+            "components.thumbnail.overlays",
+            &thumbnail.overlays,
+            &base.overlays,
+        );
+        diff.field(
+            "components.thumbnail.reflection",
+            &thumbnail.reflection,
+            &base.reflection,
+        );
+        diff.field(
+            "components.thumbnail.hover_scrim",
+            &thumbnail.hover_scrim,
+            &base.hover_scrim,
+        );
+
+        for (path, text, base) in [
+            (
+                "components.text.title",
+                &ours.text_title,
+                &theirs.text_title,
+            ),
+            ("components.text.body", &ours.text_body, &theirs.text_body),
+        ] {
+            diff.field(&format!("{path}.color"), &text.color, &base.color);
+            diff.field(&format!("{path}.size"), &text.size, &base.size);
+        }
+        diff.lines
+    }
+}
+
+/// Accumulates the human-readable lines for [`Theme::diff`].
+#[derive(Default)]
+struct ThemeDiff {
+    lines: Vec<String>,
+}
+
+impl ThemeDiff {
+    fn field<T: DescribeValue>(&mut self, path: &str, ours: &T, theirs: &T) {
+        let (ours, theirs) = (ours.describe(), theirs.describe());
+        if ours != theirs {
+            self.lines.push(format!("{path}: {theirs} → {ours}"));
+        }
+    }
+
+    fn map<T: DescribeValue>(
+        &mut self,
+        path: &str,
+        ours: &HashMap<String, T>,
+        theirs: &HashMap<String, T>,
+    ) {
+        let mut keys: Vec<&String> = ours.keys().chain(theirs.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            match (ours.get(key), theirs.get(key)) {
+                (Some(ours), Some(theirs)) => self.field(&format!("{path}.{key}"), ours, theirs),
+                (Some(ours), None) => self
+                    .lines
+                    .push(format!("{path}.{key}: added {}", ours.describe())),
+                (None, Some(theirs)) => self
+                    .lines
+                    .push(format!("{path}.{key}: removed (was {})", theirs.describe())),
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn area(&mut self, path: &str, ours: &AreaStyle, theirs: &AreaStyle) {
+        self.field(
+            &format!("{path}.background"),
+            &ours.background,
+            &theirs.background,
+        );
+        self.field(
+            &format!("{path}.foreground"),
+            &ours.foreground,
+            &theirs.foreground,
+        );
+        self.field(
+            &format!("{path}.border_color"),
+            &ours.border_color,
+            &theirs.border_color,
+        );
+        self.field(
+            &format!("{path}.border_radius"),
+            &ours.border_radius,
+            &theirs.border_radius,
+        );
+        self.field(
+            &format!("{path}.border_width"),
+            &ours.border_width,
+            &theirs.border_width,
+        );
+        self.field(
+            &format!("{path}.show_border"),
+            &ours.show_border,
+            &theirs.show_border,
+        );
+        self.field(
+            &format!("{path}.dynamic_border"),
+            &ours.dynamic_border,
+            &theirs.dynamic_border,
+        );
+        self.field(&format!("{path}.inset"), &ours.inset, &theirs.inset);
+        self.field(
+            &format!("{path}.elevation"),
+            &ours.elevation,
+            &theirs.elevation,
+        );
+    }
+}
+
+/// How a resolved theme value is written in a [`Theme::diff`] line; values compare equal
+/// when their descriptions do.
+trait DescribeValue {
+    fn describe(&self) -> String;
+}
+
+impl DescribeValue for Color32 {
+    /// Raw channels, which is how the theme parser stores what the author wrote.
+    fn describe(&self) -> String {
+        let [r, g, b, a] = self.to_array();
+        if a == u8::MAX {
+            format!("#{r:02x}{g:02x}{b:02x}")
+        } else {
+            format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+        }
+    }
+}
+
+impl DescribeValue for f32 {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DescribeValue for bool {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DescribeValue for Option<f32> {
+    fn describe(&self) -> String {
+        self.map(|value| value.describe())
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+impl DescribeValue for Option<Color32> {
+    fn describe(&self) -> String {
+        self.map(|color| color.describe())
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+impl DescribeValue for OverlayControlsPosition {
+    fn describe(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+impl DescribeValue for ButtonShape {
+    fn describe(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+impl DescribeValue for WindowBackdrop {
+    fn describe(&self) -> String {
+        match self {
+            Self::None => "none",
+            Self::Mica => "mica",
+            Self::Acrylic => "acrylic",
+        }
+        .to_string()
+    }
+}
+
+impl DescribeValue for AreaBackground {
+    fn describe(&self) -> String {
+        match self {
+            AreaBackground::Solid(color) => color.describe(),
+            AreaBackground::Gradient(gradient) => {
+                let direction = match gradient.direction {
+                    GradientDirection::Vertical => "vertical",
+                    GradientDirection::Horizontal => "horizontal",
+                };
+                format!(
+                    "{direction} gradient {} to {}",
+                    gradient.start.describe(),
+                    gradient.end.describe()
+                )
+            }
+        }
+    }
+}
+
+impl DescribeValue for SliderThumb {
+    fn describe(&self) -> String {
+        match self {
+            SliderThumb::Circle { color, radius } => {
+                format!("circle {} radius {radius}", color.describe())
+            }
+            SliderThumb::Image { color, path, size } => format!(
+                "image {} {}x{} tinted {}",
+                path.display(),
+                size.x,
+                size.y,
+                color.describe()
+            ),
+        }
+    }
+}
+
+impl DescribeValue for Vec<ThumbnailOverlay> {
+    fn describe(&self) -> String {
+        if self.is_empty() {
+            return "none".to_string();
+        }
+        self.iter()
+            .map(|overlay| overlay.path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// WCAG contrast ratio between two opaque colors, from 1 (identical) to 21.
+pub fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+    fn relative_luminance(color: Color32) -> f32 {
+        let linear = |channel: u8| {
+            let c = channel as f32 / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(color.r()) + 0.7152 * linear(color.g()) + 0.0722 * linear(color.b())
+    }
+
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Derives hover (lighter) and active (darker) variants from a single accent color.
+pub fn derive_accent_variants(accent: Color32) -> (Color32, Color32) {
+    const HOVER_LIGHTEN: f32 = 0.18;
+    const ACTIVE_DARKEN: f32 = 0.18;
+
+    let shift = |channel: u8, target: f32, amount: f32| -> u8 {
+        let value = channel as f32;
+        (value + (target - value) * amount)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+    let [r, g, b, a] = accent.to_srgba_unmultiplied();
+    let hover = Color32::from_rgba_unmultiplied(
+        shift(r, 255.0, HOVER_LIGHTEN),
+        shift(g, 255.0, HOVER_LIGHTEN),
+        shift(b, 255.0, HOVER_LIGHTEN),
+        a,
+    );
+    let active = Color32::from_rgba_unmultiplied(
+        shift(r, 0.0, ACTIVE_DARKEN),
+        shift(g, 0.0, ACTIVE_DARKEN),
+        shift(b, 0.0, ACTIVE_DARKEN),
+        a,
+    );
+    (hover, active)
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct ButtonStyle {
@@ -98,9 +773,40 @@ pub struct ButtonStyle {
     pub foreground: Color32,
     pub hover_background: Color32,
     pub active_background: Color32,
+    /// Fill and label of buttons the current player doesn't support.
+    pub disabled_background: Color32,
+    pub disabled_foreground: Color32,
     pub border_color: Color32,
     pub border_radius: f32,
     pub border_width: f32,
+    /// Outline of the playback buttons; other buttons stay pills.
+    pub shape: ButtonShape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ButtonShape {
+    /// Wide buttons with rounded ends, sized for a label.
+    #[default]
+    Pill,
+    /// Square, icon-only buttons drawn as a filled circle.
+    Circle,
+}
+
+impl ButtonShape {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pill" => Some(Self::Pill),
+            "circle" => Some(Self::Circle),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pill => "pill",
+            Self::Circle => "circle",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -116,6 +822,8 @@ pub struct SliderStyle {
     pub track_fill: Color32,
     pub track_background: Color32,
     pub track_thickness: f32,
+    /// Color of the interval/chapter tick marks drawn on the track.
+    pub tick_color: Color32,
     pub thumb: SliderThumb,
 }
 
@@ -140,12 +848,35 @@ pub struct ThumbnailStyle {
     pub stroke_color: Color32,
     pub stroke_width: f32,
     pub overlays: Vec<ThumbnailOverlay>,
+    /// Paint a fading mirror image of the artwork underneath it.
+    pub reflection: bool,
+    /// Tint over the artwork while the pointer brings up the transport overlay.
+    pub hover_scrim: Color32,
 }
 
 #[derive(Debug, Clone)]
 pub struct ThumbnailOverlay {
     pub path: PathBuf,
     pub offset: egui::Vec2,
+    /// Size relative to the image fitted inside the artwork.
+    pub scale: f32,
+    /// Clockwise turn about the overlay's center, in radians.
+    pub rotation: f32,
+    /// Point of the overlay placed on the same point of the artwork, before `offset`.
+    pub anchor: egui::Align2,
+}
+
+impl ThumbnailOverlay {
+    /// Centered, unscaled and unrotated overlay at `path`.
+    pub fn new(path: PathBuf, offset: egui::Vec2) -> Self {
+        Self {
+            path,
+            offset,
+            scale: 1.0,
+            rotation: 0.0,
+            anchor: egui::Align2::CENTER_CENTER,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -163,21 +894,9 @@ pub fn load_theme_from_dir(skin_dir: &Path) -> Result<LoadedTheme> {
     if theme_path.exists() {
         let data = fs::read_to_string(&theme_path)
             .with_context(|| format!("Failed to read theme file: {}", theme_path.display()))?;
-        match toml::from_str::<ThemeDocument>(&data) {
-            Ok(doc) => {
-                if let Some(engine) = doc.meta.engine.as_deref() {
-                    if engine != THEME_ENGINE_VERSION {
-                        warnings.push(format!(
-                            "Skin engine version {engine} does not match {THEME_ENGINE_VERSION}; using defaults"
-                        ));
-                    } else {
-                        merge_documents(&mut base, doc);
-                    }
-                } else {
-                    warnings.push("meta.engine missing; assuming version 1".to_string());
-                    merge_documents(&mut base, doc);
-                }
-            }
+        match parse_theme_document(&data, &mut warnings) {
+            Ok(Some(doc)) => merge_documents(&mut base, doc),
+            Ok(None) => {}
             Err(err) => {
                 warnings.push(format!("Failed to parse theme: {err}"));
             }
@@ -193,6 +912,47 @@ pub fn load_theme_from_dir(skin_dir: &Path) -> Result<LoadedTheme> {
     Ok(LoadedTheme { theme, warnings })
 }
 
+/// Parses `theme.toml`, reconciling its declared engine version with this build's.
+/// `None` means the document is ignored in favor of the defaults.
+fn parse_theme_document(
+    data: &str,
+    warnings: &mut Vec<String>,
+) -> std::result::Result<Option<ThemeDocument>, toml::de::Error> {
+    let mut table: toml::Table = toml::from_str(data)?;
+    let declared = table
+        .get("meta")
+        .and_then(|meta| meta.get("engine"))
+        .and_then(|engine| engine.as_str())
+        .map(str::to_string);
+    let action = match declared {
+        Some(engine) => reconcile(
+            &mut table,
+            &engine,
+            THEME_ENGINE_VERSION,
+            THEME_MIGRATIONS,
+            "Skin",
+            warnings,
+        ),
+        None => {
+            warnings.push("meta.engine missing; assuming version 1".to_string());
+            EngineAction::Load
+        }
+    };
+    if action == EngineAction::Ignore {
+        return Ok(None);
+    }
+
+    let doc: ThemeDocument = toml::Value::Table(table.clone()).try_into()?;
+    if action == EngineAction::LoadReportingUnknown {
+        if let Ok(known) = toml::Table::try_from(&doc) {
+            for key in unknown_keys(&table, &known) {
+                warnings.push(format!("Unknown theme setting '{key}' ignored"));
+            }
+        }
+    }
+    Ok(Some(doc))
+}
+
 fn resolve_document(
     doc: ThemeDocument,
     skin_dir: &Path,
@@ -215,6 +975,14 @@ fn resolve_document(
         }
         for (k, v) in doc.vars.iter() {
             let resolved = resolve_tokens_with_opts(v, &context, warnings, false);
+            // Evaluate once every token is substituted so later vars see a plain number.
+            let resolved = if is_var_expression(&resolved) {
+                evaluate_expression(&resolved)
+                    .map(|value| value.to_string())
+                    .unwrap_or(resolved)
+            } else {
+                resolved
+            };
             if context
                 .vars
                 .get(k)
@@ -251,7 +1019,12 @@ fn resolve_document(
         .filter_map(|(k, v)| match parse_number(v) {
             Some(num) => Some((k.clone(), num)),
             None => {
-                warnings.push(format!("Variable {k} could not be parsed as number: {v}"));
+                match evaluate_expression(v) {
+                    Err(err) if is_var_expression(v) => warnings.push(format!(
+                        "Variable {k} has a malformed expression '{v}': {err}"
+                    )),
+                    _ => warnings.push(format!("Variable {k} could not be parsed as number: {v}")),
+                }
                 None
             }
         })
@@ -277,6 +1050,9 @@ fn resolve_document(
         border_radius: radius_default,
         border_width: 0.0,
         show_border: false,
+        dynamic_border: false,
+        inset: 0.0,
+        elevation: 0.0,
     });
 
     let panel = resolve_area(
@@ -293,6 +1069,9 @@ fn resolve_document(
         border_radius: radius_default,
         border_width: 0.0,
         show_border: false,
+        dynamic_border: false,
+        inset: 0.0,
+        elevation: 0.0,
     });
 
     let button = resolve_button(
@@ -302,14 +1081,21 @@ fn resolve_document(
         radius_default,
         warnings,
     )
-    .unwrap_or_else(|_| ButtonStyle {
-        background: get_color("accent", Color32::from_rgb(0, 120, 212)),
-        foreground: get_color("text_on_accent", Color32::WHITE),
-        hover_background: get_color("accent_hover", Color32::from_rgb(15, 108, 189)),
-        active_background: get_color("accent_active", Color32::from_rgb(17, 94, 163)),
-        border_color: Color32::TRANSPARENT,
-        border_radius: radius_default,
-        border_width: 0.0,
+    .unwrap_or_else(|_| {
+        let background = get_color("accent", Color32::from_rgb(0, 120, 212));
+        let foreground = get_color("text_on_accent", Color32::WHITE);
+        ButtonStyle {
+            background,
+            foreground,
+            hover_background: get_color("accent_hover", Color32::from_rgb(15, 108, 189)),
+            active_background: get_color("accent_active", Color32::from_rgb(17, 94, 163)),
+            disabled_background: background.gamma_multiply(DISABLED_FADE),
+            disabled_foreground: foreground.gamma_multiply(DISABLED_FADE),
+            border_color: Color32::TRANSPARENT,
+            border_radius: radius_default,
+            border_width: 0.0,
+            shape: ButtonShape::Pill,
+        }
     });
 
     let button_icon = resolve_icon(&doc.components.button.icon, &context, &colors, warnings)
@@ -330,6 +1116,7 @@ fn resolve_document(
         track_fill: get_color("accent", Color32::from_rgb(0, 120, 212)),
         track_background: get_color("slider_track_bg", Color32::from_rgb(64, 64, 64)),
         track_thickness: 4.0,
+        tick_color: get_color("accent", Color32::from_rgb(0, 120, 212)).gamma_multiply(0.5),
         thumb: SliderThumb::Circle {
             color: get_color("accent", Color32::from_rgb(0, 120, 212)),
             radius: thumb_radius_default,
@@ -349,6 +1136,8 @@ fn resolve_document(
         stroke_color: Color32::TRANSPARENT,
         stroke_width: 0.0,
         overlays: Vec::new(),
+        reflection: false,
+        hover_scrim: DEFAULT_THUMBNAIL_HOVER_SCRIM,
     });
 
     let text_title = resolve_text(
@@ -383,8 +1172,38 @@ fn resolve_document(
         .clone()
         .unwrap_or_else(|| name.clone());
     let use_gradient = doc.use_gradient.unwrap_or(true);
+    let dynamic_accent = doc.dynamic_accent.unwrap_or(false);
     let disable_vinyl = doc.meta.disable_vinyl_thumbnail.unwrap_or(false);
     let transparent_bg = doc.transparent_background.or(doc.meta.transparent_background).unwrap_or(false);
+    let background_alpha = if transparent_bg {
+        0.0
+    } else {
+        match doc.background_alpha.or(doc.meta.background_alpha) {
+            Some(alpha) if (0.0..=1.0).contains(&alpha) => alpha,
+            Some(alpha) => {
+                warnings.push(format!(
+                    "background_alpha {alpha} is outside 0..1; clamping"
+                ));
+                if alpha.is_nan() {
+                    1.0
+                } else {
+                    alpha.clamp(0.0, 1.0)
+                }
+            }
+            None => 1.0,
+        }
+    };
+    let window_backdrop = match doc.window_backdrop.as_deref() {
+        None => WindowBackdrop::None,
+        Some(value) => WindowBackdrop::parse(value).unwrap_or_else(|| {
+            warnings.push(format!(
+                "Unknown window_backdrop '{value}'; expected none, mica, or acrylic"
+            ));
+            WindowBackdrop::None
+        }),
+    };
+    let window_size = resolve_window_sizing(&doc.window, &context, warnings);
+    let overlay_controls = resolve_overlay_controls(&doc.meta, &context, &colors, warnings);
 
     Ok(Theme {
         name,
@@ -398,8 +1217,13 @@ fn resolve_document(
         colors,
         vars,
         use_gradient,
+        dynamic_accent,
         disable_vinyl_thumbnail: disable_vinyl,
         transparent_background: transparent_bg,
+        background_alpha,
+        window_backdrop,
+        window_size,
+        overlay_controls,
         components: Components {
             root,
             panel,
@@ -413,6 +1237,68 @@ fn resolve_document(
     })
 }
 
+fn resolve_window_sizing(
+    section: &WindowSection,
+    ctx: &ValueContext,
+    warnings: &mut Vec<String>,
+) -> WindowSizing {
+    let mut side = |key: &str, value: &Option<String>| {
+        let value = resolve_number_field(value, ctx, warnings)?;
+        if (MIN_WINDOW_SIDE..=MAX_WINDOW_SIDE).contains(&value) {
+            return Some(value);
+        }
+        warnings.push(format!(
+            "window.{key} {value} is outside {MIN_WINDOW_SIDE}..{MAX_WINDOW_SIDE}; clamping"
+        ));
+        (!value.is_nan()).then(|| value.clamp(MIN_WINDOW_SIDE, MAX_WINDOW_SIDE))
+    };
+    let mut sizing = WindowSizing {
+        width: side("width", &section.width),
+        height: side("height", &section.height),
+        min_width: side("min_width", &section.min_width),
+        min_height: side("min_height", &section.min_height),
+        max_height: side("max_height", &section.max_height),
+    };
+    if let (Some(min), Some(max)) = (sizing.min_height, sizing.max_height) {
+        if min > max {
+            warnings.push(format!(
+                "window.min_height {min} is larger than max_height {max}; ignoring max_height"
+            ));
+            sizing.max_height = None;
+        }
+    }
+    sizing
+}
+
+fn resolve_overlay_controls(
+    meta: &MetaSection,
+    ctx: &ValueContext,
+    colors: &HashMap<String, Color32>,
+    warnings: &mut Vec<String>,
+) -> OverlayControlsStyle {
+    let defaults = OverlayControlsStyle::default();
+    let position = match meta.overlay_controls_position.as_deref() {
+        Some(value) => OverlayControlsPosition::parse(value).unwrap_or_else(|| {
+            warnings.push(format!(
+                "meta.overlay_controls_position: unknown value '{value}'; using top_left"
+            ));
+            defaults.position
+        }),
+        None => defaults.position,
+    };
+    OverlayControlsStyle {
+        visible: meta.show_overlay_controls.unwrap_or(defaults.visible),
+        position,
+        color: resolve_color_field(&meta.overlay_controls_color, ctx, colors, warnings),
+        active_color: resolve_color_field(
+            &meta.overlay_controls_active_color,
+            ctx,
+            colors,
+            warnings,
+        ),
+    }
+}
+
 fn resolve_area(
     cfg: &AreaConfig,
     ctx: &ValueContext,
@@ -432,6 +1318,12 @@ fn resolve_area(
     let show_border = cfg
         .show_border
         .unwrap_or(border_width > f32::EPSILON && border_color != Color32::TRANSPARENT);
+    let inset = resolve_number_field(&cfg.inset, ctx, warnings)
+        .unwrap_or(0.0)
+        .clamp(0.0, MAX_PANEL_INSET);
+    let elevation = resolve_number_field(&cfg.elevation, ctx, warnings)
+        .unwrap_or(0.0)
+        .clamp(0.0, MAX_PANEL_ELEVATION);
     Ok(AreaStyle {
         background,
         foreground,
@@ -439,6 +1331,9 @@ fn resolve_area(
         border_radius,
         border_width,
         show_border,
+        dynamic_border: cfg.dynamic_border.unwrap_or(false),
+        inset,
+        elevation,
     })
 }
 
@@ -547,20 +1442,35 @@ fn resolve_button(
     radius_default: f32,
     warnings: &mut Vec<String>,
 ) -> Result<ButtonStyle> {
+    let background = resolve_color_field(&cfg.background, ctx, colors, warnings)
+        .unwrap_or(Color32::from_rgb(0, 120, 212));
+    let foreground =
+        resolve_color_field(&cfg.foreground, ctx, colors, warnings).unwrap_or(Color32::WHITE);
     Ok(ButtonStyle {
-        background: resolve_color_field(&cfg.background, ctx, colors, warnings)
-            .unwrap_or(Color32::from_rgb(0, 120, 212)),
-        foreground: resolve_color_field(&cfg.foreground, ctx, colors, warnings)
-            .unwrap_or(Color32::WHITE),
+        background,
+        foreground,
         hover_background: resolve_color_field(&cfg.hover_background, ctx, colors, warnings)
             .unwrap_or(Color32::from_rgb(15, 108, 189)),
         active_background: resolve_color_field(&cfg.active_background, ctx, colors, warnings)
             .unwrap_or(Color32::from_rgb(17, 94, 163)),
+        disabled_background: resolve_color_field(&cfg.disabled_background, ctx, colors, warnings)
+            .unwrap_or(background.gamma_multiply(DISABLED_FADE)),
+        disabled_foreground: resolve_color_field(&cfg.disabled_foreground, ctx, colors, warnings)
+            .unwrap_or(foreground.gamma_multiply(DISABLED_FADE)),
         border_color: resolve_color_field(&cfg.border_color, ctx, colors, warnings)
             .unwrap_or(Color32::TRANSPARENT),
         border_radius: resolve_number_field(&cfg.border_radius, ctx, warnings)
             .unwrap_or(radius_default),
         border_width: resolve_number_field(&cfg.border_width, ctx, warnings).unwrap_or(0.0),
+        shape: match cfg.shape.as_deref() {
+            None => ButtonShape::Pill,
+            Some(value) => ButtonShape::parse(value).unwrap_or_else(|| {
+                warnings.push(format!(
+                    "components.button.shape: unknown value '{value}'; expected pill or circle"
+                ));
+                ButtonShape::Pill
+            }),
+        },
     })
 }
 
@@ -589,6 +1499,8 @@ fn resolve_slider(
     let track_background = resolve_color_field(&cfg.track_background, ctx, colors, warnings)
         .unwrap_or(Color32::from_rgb(64, 64, 64));
     let track_thickness = resolve_number_field(&cfg.track_thickness, ctx, warnings).unwrap_or(4.0);
+    let tick_color = resolve_color_field(&cfg.tick_color, ctx, colors, warnings)
+        .unwrap_or_else(|| track_fill.gamma_multiply(0.5));
 
     let thumb_shape = cfg
         .thumb_shape
@@ -647,6 +1559,7 @@ fn resolve_slider(
         track_fill,
         track_background,
         track_thickness,
+        tick_color,
         thumb,
     })
 }
@@ -691,6 +1604,9 @@ fn resolve_thumbnail(
         stroke_color,
         stroke_width,
         overlays,
+        reflection: cfg.reflection.unwrap_or(false),
+        hover_scrim: resolve_color_field(&cfg.hover_scrim, ctx, colors, warnings)
+            .unwrap_or(DEFAULT_THUMBNAIL_HOVER_SCRIM),
     })
 }
 
@@ -712,16 +1628,58 @@ fn build_thumbnail_overlay(
             path,
             offset_x,
             offset_y,
+            scale,
+            rotation_degrees,
+            anchor,
         } => {
             let offset = egui::vec2(
                 resolve_overlay_offset(offset_x, "offset_x", ctx, warnings),
                 resolve_overlay_offset(offset_y, "offset_y", ctx, warnings),
             );
-            build_overlay_from_components(path, offset, skin_dir, ctx, warnings)
+            let mut overlay = build_overlay_from_components(path, offset, skin_dir, ctx, warnings)?;
+            if let Some(scale) = resolve_number_field(scale, ctx, warnings) {
+                if scale > 0.0 {
+                    overlay.scale = scale.min(MAX_OVERLAY_SCALE);
+                } else {
+                    warnings.push(format!(
+                        "Thumbnail overlay scale must be positive, got {scale}; using 1"
+                    ));
+                }
+            }
+            if let Some(degrees) = resolve_number_field(rotation_degrees, ctx, warnings) {
+                overlay.rotation = degrees.to_radians();
+            }
+            if let Some(raw) = anchor {
+                match parse_overlay_anchor(raw) {
+                    Some(anchor) => overlay.anchor = anchor,
+                    None => warnings.push(format!(
+                        "Unknown thumbnail overlay anchor {raw}; using center"
+                    )),
+                }
+            }
+            Some(overlay)
         }
     }
 }
 
+/// `center`, `top`, `right_bottom` and so on; both `top_left` and `left_top` orders work.
+fn parse_overlay_anchor(value: &str) -> Option<egui::Align2> {
+    let value = value.trim().to_ascii_lowercase();
+    let mut x = egui::Align::Center;
+    let mut y = egui::Align::Center;
+    for part in value.split('_') {
+        match part {
+            "left" => x = egui::Align::Min,
+            "right" => x = egui::Align::Max,
+            "top" => y = egui::Align::Min,
+            "bottom" => y = egui::Align::Max,
+            "center" => {}
+            _ => return None,
+        }
+    }
+    Some(egui::Align2([x, y]))
+}
+
 fn build_overlay_from_components(
     raw_path: &str,
     offset: egui::Vec2,
@@ -738,7 +1696,7 @@ fn build_overlay_from_components(
     let mut path = skin_dir.join("assets").join(trimmed);
     if path.exists() {
         path = canonicalize_asset_path(path);
-        Some(ThumbnailOverlay { path, offset })
+        Some(ThumbnailOverlay::new(path, offset))
     } else {
         warnings.push(format!(
             "Thumbnail overlay image {} not found; skipping",
@@ -923,6 +1881,108 @@ fn parse_number(value: &str) -> Option<f32> {
     value.trim().parse::<f32>().ok()
 }
 
+/// True for fully substituted var values that use arithmetic, e.g. `8 / 2`.
+fn is_var_expression(value: &str) -> bool {
+    parse_number(value).is_none()
+        && !value.contains('{')
+        && value.contains(['+', '-', '*', '/', '('])
+}
+
+/// Evaluates `+ - * /` with parentheses and unary signs over plain numbers.
+fn evaluate_expression(input: &str) -> std::result::Result<f32, String> {
+    let mut parser = ExpressionParser {
+        chars: input.chars().collect(),
+        pos: 0,
+    };
+    let value = parser.expression()?;
+    if let Some(c) = parser.peek() {
+        return Err(format!("unexpected '{c}'"));
+    }
+    if !value.is_finite() {
+        return Err("result is not a finite number".to_string());
+    }
+    Ok(value)
+}
+
+struct ExpressionParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ExpressionParser {
+    /// Next non-whitespace character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+        self.chars.get(self.pos).copied()
+    }
+
+    fn expression(&mut self) -> std::result::Result<f32, String> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> std::result::Result<f32, String> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            if op == '*' {
+                value *= rhs;
+            } else if rhs == 0.0 {
+                return Err("division by zero".to_string());
+            } else {
+                value /= rhs;
+            }
+        }
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> std::result::Result<f32, String> {
+        match self.peek() {
+            Some('-') => {
+                self.pos += 1;
+                Ok(-self.factor()?)
+            }
+            Some('+') => {
+                self.pos += 1;
+                self.factor()
+            }
+            Some('(') => {
+                self.pos += 1;
+                let value = self.expression()?;
+                if self.peek() != Some(')') {
+                    return Err("missing ')'".to_string());
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.pos;
+                while self
+                    .chars
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                {
+                    self.pos += 1;
+                }
+                let literal: String = self.chars[start..self.pos].iter().collect();
+                literal
+                    .parse::<f32>()
+                    .map_err(|_| format!("invalid number '{literal}'"))
+            }
+            Some(c) => Err(format!("unexpected '{c}'")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
 fn resolve_tokens(value: &str, ctx: &ValueContext, warnings: &mut Vec<String>) -> String {
     resolve_tokens_with_opts(value, ctx, warnings, true)
 }
@@ -995,18 +2055,33 @@ impl ValueContext {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ThemeDocument {
     meta: MetaSection,
     colors: HashMap<String, String>,
     vars: HashMap<String, String>,
     use_gradient: Option<bool>,
+    dynamic_accent: Option<bool>,
     transparent_background: Option<bool>,
+    background_alpha: Option<f32>,
+    window_backdrop: Option<String>,
+    window: WindowSection,
     components: ComponentsConfig,
 }
 
-#[derive(Clone, Deserialize)]
+/// `[window]`: sizes in logical pixels, as numbers or `vars.` expressions.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct WindowSection {
+    width: Option<String>,
+    height: Option<String>,
+    min_width: Option<String>,
+    min_height: Option<String>,
+    max_height: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct MetaSection {
     engine: Option<String>,
@@ -1014,9 +2089,14 @@ struct MetaSection {
     display_name: Option<String>,
     disable_vinyl_thumbnail: Option<bool>,
     transparent_background: Option<bool>,
+    background_alpha: Option<f32>,
+    show_overlay_controls: Option<bool>,
+    overlay_controls_position: Option<String>,
+    overlay_controls_color: Option<String>,
+    overlay_controls_active_color: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ComponentsConfig {
     root: AreaConfig,
@@ -1027,7 +2107,7 @@ struct ComponentsConfig {
     text: TextComponents,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct AreaConfig {
     background: Option<BackgroundFieldConfig>,
@@ -1036,16 +2116,19 @@ struct AreaConfig {
     border_radius: Option<String>,
     border_width: Option<String>,
     show_border: Option<bool>,
+    dynamic_border: Option<bool>,
+    inset: Option<String>,
+    elevation: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 enum BackgroundFieldConfig {
     Simple(String),
     Table(BackgroundTableConfig),
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct BackgroundTableConfig {
     #[serde(rename = "type")]
@@ -1057,7 +2140,7 @@ struct BackgroundTableConfig {
     direction: GradientDirectionConfig,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 enum GradientDirectionConfig {
     Vertical,
@@ -1082,32 +2165,36 @@ impl Default for GradientDirectionConfig {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ButtonConfig {
     background: Option<String>,
     foreground: Option<String>,
     hover_background: Option<String>,
     active_background: Option<String>,
+    disabled_background: Option<String>,
+    disabled_foreground: Option<String>,
     border_color: Option<String>,
     border_radius: Option<String>,
     border_width: Option<String>,
+    shape: Option<String>,
     icon: IconConfig,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct IconConfig {
     color: Option<String>,
     size_scale: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct SliderConfig {
     track_fill: Option<String>,
     track_background: Option<String>,
     track_thickness: Option<String>,
+    tick_color: Option<String>,
     thumb_shape: Option<String>,
     thumb_color: Option<String>,
     thumb_radius: Option<String>,
@@ -1115,7 +2202,7 @@ struct SliderConfig {
     thumb_image: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct ThumbnailConfig {
     corner_radius: Option<String>,
@@ -1123,9 +2210,11 @@ struct ThumbnailConfig {
     stroke_color: Option<String>,
     stroke_width: Option<String>,
     overlay_images: Option<Vec<OverlayImageEntry>>,
+    reflection: Option<bool>,
+    hover_scrim: Option<String>,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(untagged)]
 enum OverlayImageEntry {
     Path(String),
@@ -1135,17 +2224,23 @@ enum OverlayImageEntry {
         offset_x: Option<String>,
         #[serde(default)]
         offset_y: Option<String>,
+        #[serde(default)]
+        scale: Option<String>,
+        #[serde(default)]
+        rotation_degrees: Option<String>,
+        #[serde(default)]
+        anchor: Option<String>,
     },
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct TextComponents {
     title: TextConfig,
     body: TextConfig,
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 struct TextConfig {
     color: Option<String>,
@@ -1159,7 +2254,11 @@ impl Default for ThemeDocument {
             colors: HashMap::new(),
             vars: HashMap::new(),
             use_gradient: None,
+            dynamic_accent: None,
             transparent_background: None,
+            background_alpha: None,
+            window_backdrop: None,
+            window: WindowSection::default(),
             components: ComponentsConfig::default(),
         }
     }
@@ -1173,6 +2272,11 @@ impl Default for MetaSection {
             display_name: None,
             disable_vinyl_thumbnail: None,
             transparent_background: None,
+            background_alpha: None,
+            show_overlay_controls: None,
+            overlay_controls_position: None,
+            overlay_controls_color: None,
+            overlay_controls_active_color: None,
         }
     }
 }
@@ -1199,6 +2303,9 @@ impl Default for AreaConfig {
             border_radius: None,
             border_width: None,
             show_border: None,
+            dynamic_border: None,
+            inset: None,
+            elevation: None,
         }
     }
 }
@@ -1210,9 +2317,12 @@ impl Default for ButtonConfig {
             foreground: None,
             hover_background: None,
             active_background: None,
+            disabled_background: None,
+            disabled_foreground: None,
             border_color: None,
             border_radius: None,
             border_width: None,
+            shape: None,
             icon: IconConfig::default(),
         }
     }
@@ -1233,6 +2343,7 @@ impl Default for SliderConfig {
             track_fill: None,
             track_background: None,
             track_thickness: None,
+            tick_color: None,
             thumb_shape: None,
             thumb_color: None,
             thumb_radius: None,
@@ -1250,6 +2361,8 @@ impl Default for ThumbnailConfig {
             stroke_color: None,
             stroke_width: None,
             overlay_images: None,
+            reflection: None,
+            hover_scrim: None,
         }
     }
 }
@@ -1282,9 +2395,51 @@ fn merge_documents(base: &mut ThemeDocument, overlay: ThemeDocument) {
     if overlay.meta.display_name.is_some() {
         base.meta.display_name = overlay.meta.display_name;
     }
+    if overlay.meta.show_overlay_controls.is_some() {
+        base.meta.show_overlay_controls = overlay.meta.show_overlay_controls;
+    }
+    if overlay.meta.overlay_controls_position.is_some() {
+        base.meta.overlay_controls_position = overlay.meta.overlay_controls_position;
+    }
+    if overlay.meta.overlay_controls_color.is_some() {
+        base.meta.overlay_controls_color = overlay.meta.overlay_controls_color;
+    }
+    if overlay.meta.overlay_controls_active_color.is_some() {
+        base.meta.overlay_controls_active_color = overlay.meta.overlay_controls_active_color;
+    }
     if overlay.use_gradient.is_some() {
         base.use_gradient = overlay.use_gradient;
     }
+    if overlay.dynamic_accent.is_some() {
+        base.dynamic_accent = overlay.dynamic_accent;
+    }
+    if overlay.transparent_background.is_some() {
+        base.transparent_background = overlay.transparent_background;
+    }
+    if overlay.meta.transparent_background.is_some() {
+        base.meta.transparent_background = overlay.meta.transparent_background;
+    }
+    if overlay.background_alpha.is_some() {
+        base.background_alpha = overlay.background_alpha;
+    }
+    if overlay.meta.background_alpha.is_some() {
+        base.meta.background_alpha = overlay.meta.background_alpha;
+    }
+    if overlay.window_backdrop.is_some() {
+        base.window_backdrop = overlay.window_backdrop;
+    }
+    let (window, overlay_window) = (&mut base.window, overlay.window);
+    for (field, value) in [
+        (&mut window.width, overlay_window.width),
+        (&mut window.height, overlay_window.height),
+        (&mut window.min_width, overlay_window.min_width),
+        (&mut window.min_height, overlay_window.min_height),
+        (&mut window.max_height, overlay_window.max_height),
+    ] {
+        if value.is_some() {
+            *field = value;
+        }
+    }
 
     base.colors.extend(overlay.colors);
     base.vars.extend(overlay.vars);
@@ -1320,6 +2475,15 @@ fn merge_area(base: &mut AreaConfig, overlay: AreaConfig) {
     if overlay.show_border.is_some() {
         base.show_border = overlay.show_border;
     }
+    if overlay.dynamic_border.is_some() {
+        base.dynamic_border = overlay.dynamic_border;
+    }
+    if overlay.inset.is_some() {
+        base.inset = overlay.inset;
+    }
+    if overlay.elevation.is_some() {
+        base.elevation = overlay.elevation;
+    }
 }
 
 fn merge_button(base: &mut ButtonConfig, overlay: ButtonConfig) {
@@ -1335,6 +2499,12 @@ fn merge_button(base: &mut ButtonConfig, overlay: ButtonConfig) {
     if overlay.active_background.is_some() {
         base.active_background = overlay.active_background;
     }
+    if overlay.disabled_background.is_some() {
+        base.disabled_background = overlay.disabled_background;
+    }
+    if overlay.disabled_foreground.is_some() {
+        base.disabled_foreground = overlay.disabled_foreground;
+    }
     if overlay.border_color.is_some() {
         base.border_color = overlay.border_color;
     }
@@ -1344,6 +2514,9 @@ fn merge_button(base: &mut ButtonConfig, overlay: ButtonConfig) {
     if overlay.border_width.is_some() {
         base.border_width = overlay.border_width;
     }
+    if overlay.shape.is_some() {
+        base.shape = overlay.shape;
+    }
     merge_icon(&mut base.icon, overlay.icon);
 }
 
@@ -1366,6 +2539,9 @@ fn merge_slider(base: &mut SliderConfig, overlay: SliderConfig) {
     if overlay.track_thickness.is_some() {
         base.track_thickness = overlay.track_thickness;
     }
+    if overlay.tick_color.is_some() {
+        base.tick_color = overlay.tick_color;
+    }
     if overlay.thumb_shape.is_some() {
         base.thumb_shape = overlay.thumb_shape;
     }
@@ -1399,6 +2575,12 @@ fn merge_thumbnail(base: &mut ThumbnailConfig, overlay: ThumbnailConfig) {
     if overlay.overlay_images.is_some() {
         base.overlay_images = overlay.overlay_images;
     }
+    if overlay.reflection.is_some() {
+        base.reflection = overlay.reflection;
+    }
+    if overlay.hover_scrim.is_some() {
+        base.hover_scrim = overlay.hover_scrim;
+    }
 }
 
 fn merge_text(base: &mut TextConfig, overlay: TextConfig) {
@@ -1410,6 +2592,14 @@ fn merge_text(base: &mut TextConfig, overlay: TextConfig) {
     }
 }
 
+fn resolve_builtin(doc: ThemeDocument) -> Theme {
+    let mut warnings = Vec::new();
+    let theme = resolve_document(doc, Path::new(""), &mut warnings)
+        .expect("Embedded default theme must resolve");
+    debug_assert!(warnings.is_empty(), "built-in theme warnings: {warnings:?}");
+    theme
+}
+
 fn builtin_theme_document() -> ThemeDocument {
     toml::from_str(DEFAULT_THEME_TOML).expect("Embedded default theme must parse")
 }
@@ -1485,3 +2675,365 @@ size = "20"
 color = "{colors.text_secondary}"
 size = "16"
 "##;
+
+/// Palette swapped over [`DEFAULT_THEME_TOML`] for [`Theme::default_light`].
+const LIGHT_THEME_TOML: &str = r##"
+[meta]
+name = "builtin-windows-light"
+display_name = "Windows 11 Light"
+
+[colors]
+background = "#f3f4f7"
+panel = "#ffffff"
+accent = "#2f6fe4"
+accent_hover = "#4680ea"
+accent_active = "#2258c4"
+text_primary = "#15161b"
+text_secondary = "#5b6475"
+text_on_accent = "#ffffff"
+slider_track_bg = "#d9dce3"
+outline = "rgba(47, 111, 228, 0.35)"
+"##;
+
+/// Overrides over [`DEFAULT_THEME_TOML`] for [`Theme::stealth`].
+const STEALTH_THEME_TOML: &str = r##"
+transparent_background = true
+
+[meta]
+name = "builtin-stealth"
+display_name = "Stealth"
+show_overlay_controls = false
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accent_variants_lighten_hover_and_darken_active() {
+        let accent = Color32::from_rgb(40, 120, 200);
+        let (hover, active) = derive_accent_variants(accent);
+        assert_eq!(hover, Color32::from_rgb(79, 144, 210));
+        assert_eq!(active, Color32::from_rgb(33, 98, 164));
+    }
+
+    #[test]
+    fn contrast_ratio_spans_one_to_twenty_one() {
+        assert!((contrast_ratio(Color32::BLACK, Color32::WHITE) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(Color32::RED, Color32::RED) - 1.0).abs() < 1e-6);
+        let grey = Color32::from_rgb(118, 118, 118);
+        assert_eq!(
+            contrast_ratio(grey, Color32::WHITE),
+            contrast_ratio(Color32::WHITE, grey)
+        );
+        assert!((contrast_ratio(grey, Color32::WHITE) - 4.54).abs() < 0.01);
+    }
+
+    #[test]
+    fn detailed_overlay_entries_resolve_scale_rotation_and_anchor() {
+        let skin_dir =
+            std::env::temp_dir().join(format!("nowplaying-overlay-{}", std::process::id()));
+        std::fs::create_dir_all(skin_dir.join("assets")).unwrap();
+        std::fs::write(skin_dir.join("assets").join("badge.png"), b"").unwrap();
+        let ctx = ValueContext::new(&HashMap::new(), &HashMap::new());
+        let entry = |toml: &str| -> OverlayImageEntry {
+            toml::from_str::<ThumbnailConfig>(&format!("overlay_images = [{toml}]"))
+                .unwrap()
+                .overlay_images
+                .unwrap()
+                .remove(0)
+        };
+        let mut warnings = Vec::new();
+        let mut build = |toml: &str| {
+            build_thumbnail_overlay(&entry(toml), &skin_dir, &ctx, &mut warnings).unwrap()
+        };
+
+        let plain = build(r#""badge.png""#);
+        assert_eq!((plain.scale, plain.rotation), (1.0, 0.0));
+        assert_eq!(plain.anchor, egui::Align2::CENTER_CENTER);
+
+        let badge = build(concat!(
+            r#"{ path = "badge.png", scale = "0.4", "#,
+            r#"rotation_degrees = "90", anchor = "top_right" }"#,
+        ));
+        assert_eq!(badge.scale, 0.4);
+        assert!((badge.rotation - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(badge.anchor, egui::Align2::RIGHT_TOP);
+
+        let bad = build(r#"{ path = "badge.png", scale = "-1", anchor = "middle" }"#);
+        assert_eq!((bad.scale, bad.anchor), (1.0, egui::Align2::CENTER_CENTER));
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        let anchor = parse_overlay_anchor;
+        assert_eq!(anchor("bottom"), Some(egui::Align2::CENTER_BOTTOM));
+        assert_eq!(anchor("left_top"), Some(egui::Align2::LEFT_TOP));
+        let _ = std::fs::remove_dir_all(&skin_dir);
+    }
+
+    #[test]
+    fn accent_variants_stay_in_range_at_extremes() {
+        let (hover, _) = derive_accent_variants(Color32::WHITE);
+        assert_eq!(hover, Color32::WHITE);
+        let (_, active) = derive_accent_variants(Color32::BLACK);
+        assert_eq!(active, Color32::BLACK);
+    }
+
+    #[test]
+    fn accent_override_rewrites_colors_resolved_from_accent() {
+        let mut theme = load_theme_from_dir(Path::new("."))
+            .expect("builtin theme")
+            .theme;
+        let picked = Color32::from_rgb(200, 40, 80);
+        let (hover, active) = derive_accent_variants(picked);
+        theme.apply_accent_override(picked);
+
+        assert_eq!(theme.colors["accent"], picked);
+        assert_eq!(theme.components.button.background, picked);
+        assert_eq!(theme.components.button.hover_background, hover);
+        assert_eq!(theme.components.button.active_background, active);
+        assert_eq!(theme.components.slider.track_fill, picked);
+    }
+
+    #[test]
+    fn overlay_controls_meta_parses_position_and_warns_on_unknown() {
+        let mut doc = builtin_theme_document();
+        let overlay: ThemeDocument = toml::from_str(
+            "[meta]\nshow_overlay_controls = false\noverlay_controls_position = \"bottom_right\"\n\
+             overlay_controls_color = \"#ff0000\"\n",
+        )
+        .unwrap();
+        merge_documents(&mut doc, overlay);
+        let mut warnings = Vec::new();
+        let theme = resolve_document(doc.clone(), Path::new("."), &mut warnings).unwrap();
+        let controls = &theme.overlay_controls;
+        assert!(!controls.visible);
+        assert_eq!(controls.position, OverlayControlsPosition::BottomRight);
+        assert_eq!(
+            OverlayControlsPosition::parse("left_bottom"),
+            Some(OverlayControlsPosition::BottomLeft)
+        );
+        assert_eq!(controls.color, Some(Color32::from_rgb(255, 0, 0)));
+        assert!(warnings.is_empty());
+
+        doc.meta.overlay_controls_position = Some("middle".to_string());
+        let theme = resolve_document(doc, Path::new("."), &mut warnings).unwrap();
+        assert_eq!(
+            theme.overlay_controls.position,
+            OverlayControlsPosition::TopLeft
+        );
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("overlay_controls_position")));
+    }
+
+    #[test]
+    fn var_expressions_evaluate_after_substitution() {
+        let mut doc = builtin_theme_document();
+        let overlay: ThemeDocument = toml::from_str(
+            "[vars]\nradius = \"12\"\nradius_small = \"{vars.radius} / 2\"\n\
+             inset = \"({vars.radius_small} - 4) * 3\"\nbroken = \"{vars.radius} * / 2\"\n",
+        )
+        .unwrap();
+        merge_documents(&mut doc, overlay);
+        let mut warnings = Vec::new();
+        let theme = resolve_document(doc, Path::new("."), &mut warnings).unwrap();
+
+        assert_eq!(theme.vars["radius_small"], 6.0);
+        assert_eq!(theme.vars["inset"], 6.0);
+        assert!(!theme.vars.contains_key("broken"));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("broken") && w.contains("malformed expression")));
+    }
+
+    #[test]
+    fn expression_evaluator_handles_precedence_and_errors() {
+        assert_eq!(evaluate_expression("2 + 3 * 4"), Ok(14.0));
+        assert_eq!(evaluate_expression("-(2 + 3) / 2"), Ok(-2.5));
+        assert!(evaluate_expression("4 / 0").is_err());
+        assert!(evaluate_expression("(1 + 2").is_err());
+        assert!(evaluate_expression("1 2").is_err());
+    }
+
+    #[test]
+    fn builtin_dark_and_light_themes_resolve_without_disk() {
+        let dark = Theme::default_dark();
+        let light = Theme::default_light();
+        assert_eq!(dark.name, "builtin-windows");
+        assert_eq!(light.name, "builtin-windows-light");
+        assert_eq!(dark.components.text_title.size, 20.0);
+        assert_eq!(
+            light.components.text_title.size,
+            dark.components.text_title.size
+        );
+        // Skins without disabled colors fade their regular ones.
+        let button = &dark.components.button;
+        let faded = button.foreground.gamma_multiply(DISABLED_FADE);
+        assert_eq!(button.disabled_foreground, faded);
+        let luminance =
+            |color: Color32| u32::from(color.r()) + u32::from(color.g()) + u32::from(color.b());
+        assert!(
+            luminance(light.components.root.background_color())
+                > luminance(dark.components.root.background_color())
+        );
+        assert!(
+            luminance(light.components.text_title.color)
+                < luminance(dark.components.text_title.color)
+        );
+    }
+
+    #[test]
+    fn stealth_theme_drops_backgrounds_and_overlay_buttons() {
+        let stealth = Theme::stealth();
+        assert_eq!(stealth.display_name, "Stealth");
+        assert!(stealth.transparent_background);
+        assert_eq!(stealth.background_alpha, 0.0);
+        assert!(!stealth.overlay_controls.visible);
+    }
+
+    #[test]
+    fn diff_lists_changed_fields_only() {
+        let dark = Theme::default_dark();
+        assert!(dark.diff(&Theme::default_dark()).is_empty());
+
+        let light = Theme::default_light();
+        let diff = light.diff(&dark);
+        assert!(diff.contains(&"colors.background: #15161b → #f3f4f7".to_string()));
+        assert!(diff.contains(&"components.root.background: #15161b → #f3f4f7".to_string()));
+        assert!(!diff.iter().any(|line| line.starts_with("vars.")));
+        assert!(!diff.iter().any(|line| line.contains(".size:")));
+
+        let mut extra = dark.clone();
+        extra
+            .colors
+            .insert("state_playing".to_string(), Color32::RED);
+        extra.colors.remove("outline");
+        let diff = extra.diff(&dark);
+        assert_eq!(
+            diff,
+            vec![
+                "colors.outline: removed (was #4c8dff73)".to_string(),
+                "colors.state_playing: added #ff0000".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn background_alpha_merges_from_skins_and_clamps() {
+        let resolve = |overlay: &str| {
+            let mut doc = builtin_theme_document();
+            merge_documents(&mut doc, toml::from_str(overlay).unwrap());
+            let mut warnings = Vec::new();
+            let theme = resolve_document(doc, Path::new("."), &mut warnings).unwrap();
+            (theme.background_alpha, warnings.len())
+        };
+        assert_eq!(resolve(""), (1.0, 0));
+        assert_eq!(resolve("background_alpha = 0.7"), (0.7, 0));
+        assert_eq!(resolve("[meta]\nbackground_alpha = 0.5"), (0.5, 0));
+        assert_eq!(resolve("background_alpha = 1.5"), (1.0, 1));
+        assert_eq!(
+            resolve("transparent_background = true\nbackground_alpha = 0.7"),
+            (0.0, 0)
+        );
+
+        let faded = AreaBackground::Solid(Color32::from_rgb(200, 100, 50)).with_alpha(0.5);
+        assert_eq!(faded.primary_color().a(), 128);
+    }
+
+    #[test]
+    fn thumbnail_hover_scrim_defaults_and_can_be_turned_off() {
+        let resolve = |overlay: &str| {
+            let mut doc = builtin_theme_document();
+            merge_documents(&mut doc, toml::from_str(overlay).unwrap());
+            let mut warnings = Vec::new();
+            let theme = resolve_document(doc, Path::new("."), &mut warnings).unwrap();
+            theme.components.thumbnail.hover_scrim
+        };
+        assert_eq!(resolve(""), DEFAULT_THUMBNAIL_HOVER_SCRIM);
+        let none = "[components.thumbnail]\nhover_scrim = \"transparent\"";
+        assert_eq!(resolve(none), Color32::TRANSPARENT);
+        let tinted = "[colors]\nscrim = \"#102030\"\n\
+                      [components.thumbnail]\nhover_scrim = \"{colors.scrim}\"";
+        assert_eq!(resolve(tinted), Color32::from_rgb(16, 32, 48));
+    }
+
+    #[test]
+    fn window_sizes_resolve_and_become_viewport_commands() {
+        use egui::{vec2, ViewportCommand};
+
+        let mut doc = builtin_theme_document();
+        let overlay = r#"
+[vars]
+bar_height = "140"
+
+[window]
+width = "560"
+height = "{vars.bar_height}"
+min_height = "300"
+max_height = "200"
+min_width = "9000"
+"#;
+        merge_documents(&mut doc, toml::from_str(overlay).unwrap());
+        let mut warnings = Vec::new();
+        let sizing = resolve_document(doc, Path::new("."), &mut warnings)
+            .unwrap()
+            .window_size;
+        assert_eq!(sizing.width, Some(560.0));
+        assert_eq!(sizing.height, Some(140.0));
+        assert_eq!(sizing.min_width, Some(MAX_WINDOW_SIDE));
+        assert_eq!(
+            sizing.max_height, None,
+            "min_height > max_height drops the max"
+        );
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+
+        let bar = WindowSizing {
+            height: Some(140.0),
+            max_height: Some(200.0),
+            ..WindowSizing::default()
+        };
+        let current = vec2(400.0, 300.0);
+        assert_eq!(
+            bar.viewport_commands(&WindowSizing::default(), current),
+            [
+                ViewportCommand::MaxInnerSize(vec2(f32::INFINITY, 200.0)),
+                ViewportCommand::InnerSize(vec2(400.0, 140.0)),
+            ]
+        );
+        // Leaving the bar lifts its limit without touching the size.
+        assert_eq!(
+            WindowSizing::default().viewport_commands(&bar, current),
+            [ViewportCommand::MaxInnerSize(egui::Vec2::INFINITY)]
+        );
+        assert_eq!(
+            bar.viewport_commands(&bar, current),
+            [ViewportCommand::InnerSize(vec2(400.0, 140.0))]
+        );
+    }
+
+    #[test]
+    fn window_backdrop_needs_a_see_through_background() {
+        let resolve = |overlay: &str| {
+            let mut doc = builtin_theme_document();
+            merge_documents(&mut doc, toml::from_str(overlay).unwrap());
+            let mut warnings = Vec::new();
+            let theme = resolve_document(doc, Path::new("."), &mut warnings).unwrap();
+            (
+                theme.window_backdrop,
+                theme.effective_window_backdrop(),
+                warnings.len(),
+            )
+        };
+        assert_eq!(
+            resolve("window_backdrop = \"acrylic\""),
+            (WindowBackdrop::Acrylic, WindowBackdrop::None, 0)
+        );
+        assert_eq!(
+            resolve("window_backdrop = \"Mica\"\nbackground_alpha = 0.6"),
+            (WindowBackdrop::Mica, WindowBackdrop::Mica, 0)
+        );
+        assert_eq!(
+            resolve("window_backdrop = \"frosted\"\ntransparent_background = true"),
+            (WindowBackdrop::None, WindowBackdrop::None, 1)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Short-lived notices for transient failures (a rejected command, a thumbnail that
+//! wouldn't load). Persistent problems stay with the layout's error components.
+
+use std::{
+    collections::VecDeque,
+    f32::consts::TAU,
+    time::{Duration, Instant},
+};
+
+use eframe::egui::{self, Color32, Stroke};
+
+/// How long a toast stays up unless clicked away.
+pub(crate) const TOAST_LIFETIME: Duration = Duration::from_secs(5);
+/// Toasts shown at once; older ones are dropped when more arrive.
+const MAX_TOASTS: usize = 3;
+const TOAST_WIDTH: f32 = 260.0;
+const TOAST_MARGIN: f32 = 10.0;
+const RING_RADIUS: f32 = 6.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ToastLevel {
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// Theme color key, with the fallback used when the skin doesn't define it.
+    fn color_key(self) -> (&'static str, Color32) {
+        match self {
+            ToastLevel::Warning => ("warning", Color32::from_rgb(240, 200, 80)),
+            ToastLevel::Error => ("error", Color32::from_rgb(220, 80, 80)),
+        }
+    }
+
+    /// Color for this level from the theme's `warning`/`error` keys.
+    pub(crate) fn color(self, colors: &std::collections::HashMap<String, Color32>) -> Color32 {
+        let (key, fallback) = self.color_key();
+        colors.get(key).copied().unwrap_or(fallback)
+    }
+}
+
+/// Where an error came from, which decides how loudly it is shown. Playback and skin
+/// errors stay on screen in red; artwork failures are warnings that pass as toasts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorKind {
+    Playback,
+    Thumbnail,
+    Skin,
+}
+
+impl ErrorKind {
+    pub(crate) fn level(self) -> ToastLevel {
+        match self {
+            ErrorKind::Playback | ErrorKind::Skin => ToastLevel::Error,
+            ErrorKind::Thumbnail => ToastLevel::Warning,
+        }
+    }
+
+    /// Source name in the diagnostics error history.
+    pub(crate) fn source(self) -> &'static str {
+        match self {
+            ErrorKind::Playback => "playback",
+            ErrorKind::Thumbnail => "thumbnail",
+            ErrorKind::Skin => "skin",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Toast {
+    pub(crate) id: u64,
+    pub(crate) level: ToastLevel,
+    pub(crate) message: String,
+    pub(crate) shown_at: Instant,
+}
+
+impl Toast {
+    /// Share of the lifetime left at `now`, from 1 (new) to 0 (expired).
+    pub(crate) fn remaining(&self, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.shown_at).as_secs_f32();
+        (1.0 - elapsed / TOAST_LIFETIME.as_secs_f32()).clamp(0.0, 1.0)
+    }
+}
+
+/// Newest-last queue of at most [`MAX_TOASTS`] live toasts.
+#[derive(Debug, Default)]
+pub(crate) struct ToastQueue {
+    toasts: VecDeque<Toast>,
+    next_id: u64,
+}
+
+impl ToastQueue {
+    /// Shows `message`. A repeat of a live toast restarts its timer instead of stacking.
+    pub(crate) fn push(&mut self, level: ToastLevel, message: impl Into<String>, now: Instant) {
+        let message = message.into();
+        if let Some(index) = self
+            .toasts
+            .iter()
+            .position(|toast| toast.level == level && toast.message == message)
+        {
+            let mut toast = self.toasts.remove(index).expect("index from position");
+            toast.shown_at = now;
+            self.toasts.push_back(toast);
+            return;
+        }
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            id: self.next_id,
+            level,
+            message,
+            shown_at: now,
+        });
+        self.next_id += 1;
+    }
+
+    /// Drops toasts whose lifetime has run out by `now`.
+    pub(crate) fn expire(&mut self, now: Instant) {
+        self.toasts
+            .retain(|toast| now.saturating_duration_since(toast.shown_at) < TOAST_LIFETIME);
+    }
+
+    pub(crate) fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|toast| toast.id != id);
+    }
+
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Draws the live toasts stacked in the bottom-right corner of `screen`, newest at the
+/// bottom, each with a ring counting down its lifetime. Clicking one dismisses it.
+pub(crate) fn show(
+    ctx: &egui::Context,
+    queue: &mut ToastQueue,
+    screen: egui::Rect,
+    colors: &std::collections::HashMap<String, Color32>,
+    now: Instant,
+) {
+    queue.expire(now);
+    let mut dismissed = None;
+    let mut bottom = screen.bottom() - TOAST_MARGIN;
+    for toast in queue.iter().rev() {
+        let color = toast.level.color(colors);
+        let area = egui::Area::new(egui::Id::new("toast").with(toast.id))
+            .order(egui::Order::Tooltip)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(egui::pos2(screen.right() - TOAST_MARGIN, bottom))
+            .interactable(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style())
+                    .stroke(Stroke::new(1.0, color))
+                    .show(ui, |ui| {
+                        ui.set_max_width(TOAST_WIDTH);
+                        ui.horizontal(|row| {
+                            let (ring, _) = row.allocate_exact_size(
+                                egui::Vec2::splat(RING_RADIUS * 2.0 + 2.0),
+                                egui::Sense::hover(),
+                            );
+                            paint_countdown_ring(row.painter(), ring.center(), toast, color, now);
+                            row.add(
+                                egui::Label::new(egui::RichText::new(&toast.message).color(color))
+                                    .wrap()
+                                    .selectable(false),
+                            );
+                        });
+                    });
+            });
+        let response = area.response.interact(egui::Sense::click());
+        if response.on_hover_text("Click to dismiss").clicked() {
+            dismissed = Some(toast.id);
+        }
+        bottom = area.response.rect.top() - TOAST_MARGIN / 2.0;
+    }
+    if let Some(id) = dismissed {
+        queue.dismiss(id);
+    }
+    if !queue.is_empty() {
+        ctx.request_repaint();
+    }
+}
+
+fn paint_countdown_ring(
+    painter: &egui::Painter,
+    center: egui::Pos2,
+    toast: &Toast,
+    color: Color32,
+    now: Instant,
+) {
+    const SEGMENTS: usize = 32;
+
+    painter.circle_stroke(
+        center,
+        RING_RADIUS,
+        Stroke::new(1.5, color.gamma_multiply(0.25)),
+    );
+    let sweep = toast.remaining(now) * TAU;
+    let steps = ((SEGMENTS as f32 * sweep / TAU).ceil() as usize).max(1);
+    let points: Vec<egui::Pos2> = (0..=steps)
+        .map(|step| {
+            // Clockwise from twelve o'clock.
+            let angle = -TAU / 4.0 + sweep * step as f32 / steps as f32;
+            center + RING_RADIUS * egui::vec2(angle.cos(), angle.sin())
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, Stroke::new(1.5, color)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(queue: &ToastQueue) -> Vec<&str> {
+        queue.iter().map(|toast| toast.message.as_str()).collect()
+    }
+
+    #[test]
+    fn queue_caps_dedupes_and_expires() {
+        let start = Instant::now();
+        let mut queue = ToastQueue::default();
+        for (offset, message) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            queue.push(
+                ToastLevel::Error,
+                message,
+                start + Duration::from_secs(offset as u64),
+            );
+        }
+        assert_eq!(messages(&queue), ["b", "c", "d"]);
+
+        // A repeat moves to the end with a fresh timer rather than stacking.
+        let repeat_at = start + Duration::from_secs(4);
+        queue.push(ToastLevel::Error, "b", repeat_at);
+        assert_eq!(messages(&queue), ["c", "d", "b"]);
+
+        queue.expire(start + Duration::from_secs(7) + Duration::from_millis(1));
+        assert_eq!(messages(&queue), ["d", "b"]);
+        queue.expire(repeat_at + TOAST_LIFETIME);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn dismiss_removes_only_that_toast() {
+        let now = Instant::now();
+        let mut queue = ToastQueue::default();
+        queue.push(ToastLevel::Warning, "thumbnail", now);
+        queue.push(ToastLevel::Error, "next", now);
+        let first = queue.iter().next().map(|toast| toast.id).unwrap();
+        queue.dismiss(first);
+        assert_eq!(messages(&queue), ["next"]);
+
+        let toast = queue.iter().next().unwrap();
+        assert_eq!(toast.remaining(now), 1.0);
+        assert!((toast.remaining(now + TOAST_LIFETIME / 2) - 0.5).abs() < 1e-6);
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
-    collections::HashMap,
+    cell::Cell,
+    collections::{BTreeMap, HashMap},
     fs,
     io::Cursor,
     path::{Path, PathBuf},
@@ -18,22 +19,179 @@ use eframe::egui::{
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 
 use crate::{
-    layout::{load_layout_from_dir, LayoutSet, LayoutVariant, LoadedLayout},
+    layout::{
+        builtin_layout, load_layout_from_dir, stealth_layout, LayoutSet, LayoutVariant,
+        LoadedLayout,
+    },
     theme::{
-        load_theme_from_dir, AreaBackground, GradientDirection, GradientSpec, LoadedTheme,
-        SliderThumb, Theme,
+        contrast_ratio, derive_accent_variants, load_theme_from_dir, AreaBackground,
+        GradientDirection, GradientSpec, LoadedTheme, SliderThumb, Theme, ThumbnailOverlay,
+        DISABLED_FADE,
     },
 };
 
+/// Range of the scale accepted by [`SkinManager::skin_button_scaled`].
+pub const MIN_BUTTON_SCALE: f32 = 0.6;
+pub const MAX_BUTTON_SCALE: f32 = 1.5;
+
+/// Id of the built-in artwork-only skin, listed whether or not a skins folder exists.
+pub const STEALTH_SKIN_ID: &str = "stealth";
+/// Id of the built-in skin that shows the light or dark default theme; see
+/// [`SkinManager::set_light_theme`].
+pub const AUTO_SKIN_ID: &str = "auto";
+
+/// Button labels must keep at least this contrast against an artwork-derived accent.
+const MIN_DYNAMIC_ACCENT_CONTRAST: f32 = 3.0;
+
+/// Seconds for the busy bar's segment to cross the track.
+const BUSY_SWEEP_PERIOD: f64 = 1.4;
+/// Marquee scroll speed in points per second, the gap between the text and its repeat,
+/// and the seconds it rests at the start of each pass.
+const MARQUEE_SPEED: f32 = 32.0;
+const MARQUEE_GAP: f32 = 48.0;
+const MARQUEE_PAUSE: f64 = 2.0;
+/// Share of the track the busy bar's segment covers.
+const BUSY_SWEEP_WIDTH: f32 = 0.3;
+/// Track tint for the busy bar when animations are off.
+const BUSY_STATIC_ALPHA: f32 = 0.4;
+/// Height of the clickable band along a `progress_edge` strip; thinner strips grow it
+/// into the panel so they stay easy to hit.
+const EDGE_STRIP_HIT_HEIGHT: f32 = 10.0;
+/// Share of the pointer's motion a Shift-drag on the slider moves the position by.
+const FINE_SEEK_SCALE: f32 = 0.1;
+/// Ctrl-seeks snap to this share of the track.
+const COARSE_SEEK_STEP: f32 = 0.05;
+
+/// Which panel edge a `progress_edge` strip hugs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StripEdge {
+    Top,
+    Bottom,
+}
+
 fn to_corner_radius(value: f32) -> CornerRadius {
     CornerRadius::same(value.clamp(0.0, u8::MAX as f32).round() as u8)
 }
 
+/// Button fills for the idle, hovered, and pressed states.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonFills {
+    pub idle: Color32,
+    pub hover: Color32,
+    pub active: Color32,
+}
+
 #[derive(Debug)]
 pub struct SkinInfo {
     pub id: String,
     pub display_name: String,
     pub path: PathBuf,
+    /// Synthesized in code instead of loaded from `path`.
+    pub embedded: bool,
+}
+
+impl SkinInfo {
+    fn stealth() -> Self {
+        Self {
+            id: STEALTH_SKIN_ID.to_string(),
+            display_name: "Stealth".to_string(),
+            path: PathBuf::new(),
+            embedded: true,
+        }
+    }
+
+    fn auto() -> Self {
+        Self {
+            id: AUTO_SKIN_ID.to_string(),
+            display_name: "Auto (system theme)".to_string(),
+            path: PathBuf::new(),
+            embedded: true,
+        }
+    }
+}
+
+/// The embedded skins, each listed unless a skins folder of the same id replaces it.
+fn embedded_skins() -> [SkinInfo; 2] {
+    [SkinInfo::auto(), SkinInfo::stealth()]
+}
+
+/// Reads a skin's theme and layout from its folder, or builds an embedded one. `light`
+/// picks the light theme for the `auto` skin.
+fn load_skin(info: &SkinInfo, light: bool) -> Result<(LoadedTheme, LoadedLayout)> {
+    if info.embedded && info.id == AUTO_SKIN_ID {
+        let theme = LoadedTheme {
+            theme: if light {
+                Theme::default_light()
+            } else {
+                Theme::default_dark()
+            },
+            warnings: Vec::new(),
+        };
+        let layout = LoadedLayout {
+            layout: builtin_layout(),
+            warnings: Vec::new(),
+        };
+        return Ok((theme, layout));
+    }
+    if info.embedded {
+        let theme = LoadedTheme {
+            theme: Theme::stealth(),
+            warnings: Vec::new(),
+        };
+        let layout = LoadedLayout {
+            layout: stealth_layout(),
+            warnings: Vec::new(),
+        };
+        return Ok((theme, layout));
+    }
+    Ok((
+        load_theme_from_dir(&info.path)?,
+        load_layout_from_dir(&info.path)?,
+    ))
+}
+
+/// How a skin's warnings changed across a hot reload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarningDelta {
+    /// Warnings the reload no longer reports.
+    pub fixed: Vec<String>,
+    /// Warnings the reload introduced.
+    pub new: Vec<String>,
+}
+
+impl WarningDelta {
+    pub fn is_empty(&self) -> bool {
+        self.fixed.is_empty() && self.new.is_empty()
+    }
+
+    /// Short count such as "2 new warnings, 1 resolved".
+    pub fn summary(&self) -> String {
+        let plural = |count: usize| if count == 1 { "warning" } else { "warnings" };
+        match (self.new.len(), self.fixed.len()) {
+            (0, 0) => "No warning changes".to_string(),
+            (0, fixed) => format!("{fixed} {} resolved", plural(fixed)),
+            (new, 0) => format!("{new} new {}", plural(new)),
+            (new, fixed) => format!("{new} new {}, {fixed} resolved", plural(new)),
+        }
+    }
+}
+
+/// Compares two warning lists as sets, keeping each side's order and listing
+/// duplicates once.
+pub fn diff_warnings(before: &[String], after: &[String]) -> WarningDelta {
+    fn missing_from(list: &[String], other: &[String]) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for warning in list {
+            if !other.contains(warning) && !out.contains(warning) {
+                out.push(warning.clone());
+            }
+        }
+        out
+    }
+    WarningDelta {
+        fixed: missing_from(before, after),
+        new: missing_from(after, before),
+    }
 }
 
 pub struct SkinManager {
@@ -42,47 +200,102 @@ pub struct SkinManager {
     current_index: usize,
     current_layout_index: usize,
     theme: Theme,
+    /// `theme` as the skin defines it, before the accent override.
+    skin_theme: Theme,
     layout: LayoutSet,
     warnings: Vec<String>,
     watcher: Option<RecommendedWatcher>,
     changes_rx: Option<Receiver<notify::Result<notify::Event>>>,
+    /// Set by watcher errors and failed asset loads, which is when the skins folder may
+    /// have gone away; [`take_lost_root`](Self::take_lost_root) then checks the disk.
+    root_suspect: bool,
+    /// What the last hot reload changed in `warnings`; cleared by switching skins.
+    warning_delta: WarningDelta,
     slider_textures: HashMap<PathBuf, TextureHandle>,
     thumbnail_overlay_textures: HashMap<PathBuf, TextureHandle>,
+    accent_override: Option<Color32>,
+    /// Accent picked from the current artwork; only used by `dynamic_accent` skins or
+    /// when `accent_from_art` is on.
+    artwork_accent: Option<Color32>,
+    /// User opt-in to the artwork accent regardless of the skin's `dynamic_accent`.
+    accent_from_art: bool,
+    /// Last layout the user picked, keyed by skin id.
+    layout_preferences: BTreeMap<String, String>,
+    /// `skin_text` renders selectable labels the mouse can highlight and copy.
+    selectable_text: bool,
+    /// Set when the pointer is over selectable text this frame; see
+    /// [`take_selectable_text_hovered`](Self::take_selectable_text_hovered).
+    selectable_text_hovered: Cell<bool>,
+    /// The `auto` skin shows the light default theme instead of the dark one.
+    light_theme: bool,
 }
 
 impl SkinManager {
     pub fn discover(root: impl AsRef<Path>, default_skin: Option<&str>) -> Result<Self> {
         let root = root.as_ref().to_path_buf();
         let mut entries = Vec::new();
-        if root.exists() {
-            for entry in fs::read_dir(&root)
-                .with_context(|| format!("Failed to list skins directory: {}", root.display()))?
-            {
-                let entry = entry?;
-                if !entry.file_type()?.is_dir() {
-                    continue;
+        let listing = if !root.exists() {
+            Err(format!(
+                "Skins directory {} does not exist; using the built-in skin",
+                root.display()
+            ))
+        } else if !root.is_dir() {
+            Err(format!(
+                "Skins path {} is not a directory; using the built-in skin",
+                root.display()
+            ))
+        } else {
+            fs::read_dir(&root).map_err(|err| {
+                format!(
+                    "Skins directory {} could not be read ({err}); using the built-in skin",
+                    root.display()
+                )
+            })
+        };
+
+        let listing = match listing {
+            Ok(listing) => listing,
+            Err(warning) => {
+                let mut manager = Self::fallback_with_root(root, default_skin)?;
+                manager.warnings.insert(0, warning);
+                return Ok(manager);
+            }
+        };
+
+        for entry in listing {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().map(|ty| ty.is_dir()).unwrap_or(false) {
+                continue;
+            }
+            let id = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            match load_theme_from_dir(&path) {
+                Ok(LoadedTheme { theme, .. }) => {
+                    entries.push(SkinInfo {
+                        id: id.clone(),
+                        display_name: theme.display_name.clone(),
+                        path,
+                        embedded: false,
+                    });
                 }
-                let id = entry.file_name().to_string_lossy().to_string();
-                let path = entry.path();
-                match load_theme_from_dir(&path) {
-                    Ok(LoadedTheme { theme, .. }) => {
-                        entries.push(SkinInfo {
-                            id: id.clone(),
-                            display_name: theme.display_name.clone(),
-                            path,
-                        });
-                    }
-                    Err(err) => {
-                        eprintln!("Failed to load skin {id}: {err:?}");
-                    }
+                Err(err) => {
+                    eprintln!("Failed to load skin {id}: {err:?}");
                 }
             }
         }
 
         if entries.is_empty() {
-            return Self::fallback_with_root(root);
+            return Self::fallback_with_root(root, default_skin);
         }
 
+        // A folder named like an embedded skin takes its place.
+        for embedded in embedded_skins() {
+            if !entries.iter().any(|skin| skin.id == embedded.id) {
+                entries.push(embedded);
+            }
+        }
         entries.sort_by(|a, b| a.display_name.cmp(&b.display_name));
 
         let initial_index = default_skin
@@ -91,28 +304,26 @@ impl SkinManager {
                     .iter()
                     .position(|s| s.id == name || s.display_name == name)
             })
+            // The embedded skins are opt-in; start on a skin from the folder.
+            .or_else(|| entries.iter().position(|s| !s.embedded))
             .unwrap_or(0);
 
-        let LoadedTheme {
-            theme,
-            warnings: mut theme_warnings,
-        } = load_theme_from_dir(&entries[initial_index].path).with_context(|| {
+        let (
+            LoadedTheme {
+                theme,
+                warnings: mut theme_warnings,
+            },
+            LoadedLayout {
+                layout,
+                warnings: mut layout_warnings,
+            },
+        ) = load_skin(&entries[initial_index], false).with_context(|| {
             format!(
                 "Failed to load initial skin: {}",
                 entries[initial_index].path.display()
             )
         })?;
 
-        let LoadedLayout {
-            layout,
-            warnings: mut layout_warnings,
-        } = load_layout_from_dir(&entries[initial_index].path).with_context(|| {
-            format!(
-                "Failed to load layout for initial skin: {}",
-                entries[initial_index].path.display()
-            )
-        })?;
-
         let mut warnings = Vec::new();
         warnings.append(&mut theme_warnings);
         warnings.append(&mut layout_warnings);
@@ -124,17 +335,28 @@ impl SkinManager {
             skins: entries,
             current_index: initial_index,
             current_layout_index: layout_index,
+            skin_theme: theme.clone(),
             theme,
             layout,
             warnings,
             watcher: None,
             changes_rx: None,
+            root_suspect: false,
+            warning_delta: WarningDelta::default(),
             slider_textures: HashMap::new(),
             thumbnail_overlay_textures: HashMap::new(),
+            accent_override: None,
+            artwork_accent: None,
+            accent_from_art: false,
+            layout_preferences: BTreeMap::new(),
+            selectable_text: false,
+            selectable_text_hovered: Cell::new(false),
+            light_theme: false,
         })
     }
 
-    fn fallback_with_root(root: PathBuf) -> Result<Self> {
+    /// The built-in skin, with the embedded skins still listed; `default_skin` may pick one.
+    fn fallback_with_root(root: PathBuf, default_skin: Option<&str>) -> Result<Self> {
         let LoadedTheme {
             theme,
             warnings: mut theme_warnings,
@@ -146,23 +368,38 @@ impl SkinManager {
         let mut warnings = Vec::new();
         warnings.append(&mut theme_warnings);
         warnings.append(&mut layout_warnings);
-        Ok(Self {
+        let mut manager = Self {
             root,
-            skins: Vec::new(),
-            current_index: 0,
+            skins: embedded_skins().into(),
+            // Nothing in `skins` is the built-in skin showing now.
+            current_index: usize::MAX,
             current_layout_index: layout_index_from_set(&layout, Some(&layout.default_variant)),
+            skin_theme: theme.clone(),
             theme,
             layout,
             warnings,
             watcher: None,
             changes_rx: None,
+            root_suspect: false,
+            warning_delta: WarningDelta::default(),
             slider_textures: HashMap::new(),
             thumbnail_overlay_textures: HashMap::new(),
-        })
+            accent_override: None,
+            artwork_accent: None,
+            accent_from_art: false,
+            layout_preferences: BTreeMap::new(),
+            selectable_text: false,
+            selectable_text_hovered: Cell::new(false),
+            light_theme: false,
+        };
+        if let Some(index) = default_skin.and_then(|name| manager.skin_index(name)) {
+            manager.select_skin(index)?;
+        }
+        Ok(manager)
     }
 
     pub fn fallback() -> Result<Self> {
-        Self::fallback_with_root(default_skin_root())
+        Self::fallback_with_root(default_skin_root(), None)
     }
 
     pub fn skin_list(&self) -> &[SkinInfo] {
@@ -223,6 +460,17 @@ impl SkinManager {
         &variants[idx]
     }
 
+    pub fn layout_for_width(&self, width: f32) -> &str {
+        self.layout.variant_for_width(width)
+    }
+
+    pub fn has_responsive_layouts(&self) -> bool {
+        self.layout
+            .variants()
+            .iter()
+            .any(|variant| variant.min_width.is_some())
+    }
+
     pub fn set_layout(&mut self, id: &str, ctx: &egui::Context) -> bool {
         if let Some(idx) = self
             .layout
@@ -245,36 +493,213 @@ impl SkinManager {
     }
 
     pub fn set_skin(&mut self, id_or_name: &str, ctx: &egui::Context) -> Result<()> {
-        if let Some((index, info)) = self
-            .skins
+        let index = self
+            .skin_index(id_or_name)
+            .ok_or_else(|| anyhow!("Skin '{id_or_name}' not found"))?;
+        self.select_skin(index)?;
+        ctx.request_repaint();
+        Ok(())
+    }
+
+    fn skin_index(&self, id_or_name: &str) -> Option<usize> {
+        self.skins
             .iter()
-            .enumerate()
-            .find(|(_, skin)| skin.id == id_or_name || skin.display_name == id_or_name)
-        {
-            let previous_layout = self.current_layout_id().to_string();
-            let LoadedTheme {
+            .position(|skin| skin.id == id_or_name || skin.display_name == id_or_name)
+    }
+
+    fn select_skin(&mut self, index: usize) -> Result<()> {
+        let info = &self.skins[index];
+        // Reloading the active skin keeps the current layout; switching skins uses the
+        // stored pick for the new skin, since layout ids mean different things per skin.
+        let preferred_layout = if index == self.current_index {
+            Some(self.current_layout_id().to_string())
+        } else {
+            self.layout_preferences.get(&info.id).cloned()
+        };
+        let (
+            LoadedTheme {
                 theme,
                 warnings: mut theme_warnings,
-            } = load_theme_from_dir(&info.path)?;
-            let LoadedLayout {
+            },
+            LoadedLayout {
                 layout,
                 warnings: mut layout_warnings,
-            } = load_layout_from_dir(&info.path)?;
-            let mut warnings = Vec::new();
-            warnings.append(&mut theme_warnings);
-            warnings.append(&mut layout_warnings);
-            self.current_index = index;
-            self.theme = theme;
-            self.layout = layout;
-            self.warnings = warnings;
-            self.slider_textures.clear();
-            self.thumbnail_overlay_textures.clear();
-            self.current_layout_index = layout_index_from_set(&self.layout, Some(&previous_layout));
-            ctx.request_repaint();
-            Ok(())
-        } else {
-            Err(anyhow!("Skin '{id_or_name}' not found"))
+            },
+        ) = load_skin(info, self.light_theme)?;
+        let mut warnings = Vec::new();
+        warnings.append(&mut theme_warnings);
+        warnings.append(&mut layout_warnings);
+        self.current_index = index;
+        self.skin_theme = theme;
+        self.theme = self.themed_with_accent();
+        self.layout = layout;
+        self.warnings = warnings;
+        self.warning_delta = WarningDelta::default();
+        self.slider_textures.clear();
+        self.thumbnail_overlay_textures.clear();
+        self.current_layout_index =
+            layout_index_from_set(&self.layout, preferred_layout.as_deref());
+        Ok(())
+    }
+
+    pub fn with_light_theme(mut self, light: bool) -> Self {
+        self.set_light_theme(light);
+        self
+    }
+
+    pub fn light_theme(&self) -> bool {
+        self.light_theme
+    }
+
+    /// Picks the light or dark default theme for the `auto` skin. Returns whether the
+    /// theme on screen changed, in which case the caller reapplies the style.
+    pub fn set_light_theme(&mut self, light: bool) -> bool {
+        if self.light_theme == light {
+            return false;
+        }
+        self.light_theme = light;
+        self.current_skin_id() == Some(AUTO_SKIN_ID)
+            // Embedded skins load without touching disk, so this can't fail.
+            && self.select_skin(self.current_index).is_ok()
+    }
+
+    /// Whether any listed skin was found in the skins folder.
+    pub fn has_folder_skins(&self) -> bool {
+        self.skins.iter().any(|skin| !skin.embedded)
+    }
+
+    pub fn with_accent_override(mut self, accent: Option<Color32>) -> Self {
+        self.set_accent_override(accent);
+        self
+    }
+
+    pub fn with_selectable_text(mut self, selectable: bool) -> Self {
+        self.selectable_text = selectable;
+        self
+    }
+
+    pub fn set_selectable_text(&mut self, selectable: bool) {
+        self.selectable_text = selectable;
+    }
+
+    /// Whether `skin_text` drew selectable text under the pointer since the last call,
+    /// so window dragging can leave that press to the text selection.
+    pub fn take_selectable_text_hovered(&self) -> bool {
+        self.selectable_text_hovered.take()
+    }
+
+    pub fn with_layout_preferences(mut self, preferences: BTreeMap<String, String>) -> Self {
+        self.layout_preferences = preferences;
+        if let Some(preferred) = self
+            .current_skin_id()
+            .and_then(|id| self.layout_preferences.get(id))
+        {
+            self.current_layout_index = layout_index_from_set(&self.layout, Some(preferred));
         }
+        self
+    }
+
+    pub fn layout_preferences(&self) -> &BTreeMap<String, String> {
+        &self.layout_preferences
+    }
+
+    /// Remembers (or with `None`, forgets) the layout picked for the current skin.
+    pub fn set_layout_preference(&mut self, layout_id: Option<String>) {
+        let Some(skin_id) = self.current_skin_id().map(str::to_owned) else {
+            return;
+        };
+        match layout_id {
+            Some(layout_id) => {
+                self.layout_preferences.insert(skin_id, layout_id);
+            }
+            None => {
+                self.layout_preferences.remove(&skin_id);
+            }
+        }
+    }
+
+    pub fn has_layout_preference(&self) -> bool {
+        self.current_skin_id()
+            .is_some_and(|id| self.layout_preferences.contains_key(id))
+    }
+
+    pub fn default_layout_id(&self) -> Option<&str> {
+        self.layout
+            .variants()
+            .get(layout_index_from_set(&self.layout, None))
+            .map(|variant| variant.id.as_str())
+    }
+
+    pub fn accent_override(&self) -> Option<Color32> {
+        self.accent_override
+    }
+
+    pub fn set_artwork_accent(&mut self, accent: Option<Color32>) {
+        self.artwork_accent = accent;
+    }
+
+    pub fn with_accent_from_art(mut self, enabled: bool) -> Self {
+        self.accent_from_art = enabled;
+        self
+    }
+
+    pub fn set_accent_from_art(&mut self, enabled: bool) {
+        self.accent_from_art = enabled;
+    }
+
+    /// Whether the artwork accent is wanted, by the skin or by the user.
+    pub fn wants_artwork_accent(&self) -> bool {
+        self.theme.dynamic_accent || self.accent_from_art
+    }
+
+    /// The artwork accent, if the skin opts in with `dynamic_accent = true` (or the user
+    /// with `accent_from_art`) and button labels stay readable on it. Checked against the
+    /// live theme so hot reloads and skin switches never leave an unreadable accent behind.
+    pub fn dynamic_accent(&self) -> Option<Color32> {
+        let accent = self
+            .artwork_accent
+            .filter(|_| self.wants_artwork_accent())?;
+        let foreground = self.theme.components.button.foreground;
+        (contrast_ratio(accent, foreground) >= MIN_DYNAMIC_ACCENT_CONTRAST).then_some(accent)
+    }
+
+    pub fn button_fills(&self) -> ButtonFills {
+        match self.dynamic_accent() {
+            Some(accent) => {
+                let (hover, active) = derive_accent_variants(accent);
+                ButtonFills {
+                    idle: accent,
+                    hover,
+                    active,
+                }
+            }
+            None => {
+                let button = &self.theme.components.button;
+                ButtonFills {
+                    idle: button.background,
+                    hover: button.hover_background,
+                    active: button.active_background,
+                }
+            }
+        }
+    }
+
+    /// Sets or clears the user accent color on the theme in memory; the skin's own
+    /// colors come back when the override is removed.
+    pub fn set_accent_override(&mut self, accent: Option<Color32>) {
+        if self.accent_override == accent {
+            return;
+        }
+        self.accent_override = accent;
+        self.theme = self.themed_with_accent();
+    }
+
+    fn themed_with_accent(&self) -> Theme {
+        let mut theme = self.skin_theme.clone();
+        if let Some(accent) = self.accent_override {
+            theme.apply_accent_override(accent);
+        }
+        theme
     }
 
     pub fn enable_hot_reload(&mut self) -> Result<()> {
@@ -299,6 +724,13 @@ impl SkinManager {
         Ok(())
     }
 
+    /// True once when the skins folder the current skins came from has disappeared, e.g.
+    /// deleted or on a drive that was unplugged. Only looks at the disk after a watcher
+    /// event or a failed asset load hinted at it.
+    pub fn take_lost_root(&mut self) -> bool {
+        std::mem::take(&mut self.root_suspect) && skins_root_lost(&self.root, &self.skins)
+    }
+
     pub fn disable_hot_reload(&mut self) {
         self.watcher = None;
         self.changes_rx = None;
@@ -308,7 +740,10 @@ impl SkinManager {
         self.watcher.is_some()
     }
 
-    pub fn poll_hot_reload(&mut self, ctx: &egui::Context) -> bool {
+    /// Reloads the current skin if its files changed. Returns how the warnings changed,
+    /// or `None` if nothing was reloaded.
+    pub fn poll_hot_reload(&mut self, ctx: &egui::Context) -> Option<WarningDelta> {
+        let previous_warnings = self.warnings.clone();
         let mut reloaded = false;
         let mut events = Vec::new();
         if let Some(rx) = self.changes_rx.as_ref() {
@@ -317,6 +752,12 @@ impl SkinManager {
             }
         }
 
+        if !events.is_empty() && !self.root.is_dir() {
+            // The folder itself went away; reloading would only fail once per event.
+            self.root_suspect = true;
+            return None;
+        }
+
         for event in events {
             match event {
                 Ok(evt) => {
@@ -339,7 +780,15 @@ impl SkinManager {
             }
         }
 
-        reloaded
+        if !reloaded {
+            return None;
+        }
+        self.warning_delta = diff_warnings(&previous_warnings, &self.warnings);
+        Some(self.warning_delta.clone())
+    }
+
+    pub fn warning_delta(&self) -> &WarningDelta {
+        &self.warning_delta
     }
 
     pub fn apply_style(&self, ctx: &egui::Context) {
@@ -418,63 +867,214 @@ impl SkinManager {
         ui: &mut egui::Ui,
         label: impl Into<String>,
         scale: f32,
+    ) -> egui::Response {
+        self.skin_button_scaled_enabled(ui, label, scale, true)
+    }
+
+    /// Like [`Self::skin_button_scaled`], but draws a faded, non-interactive button when
+    /// `enabled` is false.
+    pub fn skin_button_scaled_enabled(
+        &self,
+        ui: &mut egui::Ui,
+        label: impl Into<String>,
+        scale: f32,
+        enabled: bool,
     ) -> egui::Response {
         let label = label.into();
-        let clamped_scale = scale.clamp(0.6, 1.0);
+        let clamped_scale = scale.clamp(MIN_BUTTON_SCALE, MAX_BUTTON_SCALE);
         let button = &self.theme.components.button;
+        // A disabled button keeps the skin's disabled fill rather than the artwork accent.
+        let dynamic_fills = self
+            .dynamic_accent()
+            .filter(|_| enabled)
+            .map(|_| self.button_fills());
         let body_size = self.theme.components.text_body.size;
-        let border_stroke = Stroke::new(button.border_width.max(1.0), button.border_color);
+        let (background, foreground, border) = if enabled {
+            (button.background, button.foreground, button.border_color)
+        } else {
+            (
+                button.disabled_background,
+                button.disabled_foreground,
+                button.border_color.gamma_multiply(DISABLED_FADE),
+            )
+        };
+        let border_stroke = Stroke::new(button.border_width.max(1.0), border);
 
         let style = ui.style();
         let base_padding = style.spacing.button_padding;
-        let scaled_padding = Vec2::new(
-            (base_padding.x * clamped_scale).clamp(base_padding.x * 0.6, base_padding.x),
-            (base_padding.y * clamped_scale).clamp(base_padding.y * 0.6, base_padding.y),
-        );
+        let scaled_padding = base_padding * clamped_scale;
         let base_min_width = style.spacing.interact_size.x.max(96.0);
         let base_min_height = style.spacing.interact_size.y.max(40.0);
-        let min_width = (base_min_width * clamped_scale).clamp(60.0, base_min_width);
-        let min_height = (base_min_height * clamped_scale).clamp(28.0, base_min_height);
-        let text_scale = clamped_scale.clamp(0.75, 1.0);
+        let min_width = (base_min_width * clamped_scale).max(60.0);
+        let min_height = (base_min_height * clamped_scale).max(28.0);
+        let text_scale = clamped_scale.max(0.75);
         let rich = RichText::new(label.clone())
-            .color(button.foreground)
+            .color(foreground)
             .size((body_size + 2.0) * text_scale)
             .strong();
 
         ui.scope(|scaled_ui| {
             scaled_ui.spacing_mut().button_padding = scaled_padding;
-            scaled_ui.add_sized(
-                Vec2::new(min_width, min_height),
-                egui::Button::new(rich)
-                    .fill(button.background)
-                    .corner_radius(to_corner_radius(button.border_radius))
-                    .stroke(border_stroke)
-                    .wrap(),
-            )
+            if !enabled {
+                scaled_ui.disable();
+            }
+            let mut widget = egui::Button::new(rich)
+                .corner_radius(to_corner_radius(button.border_radius))
+                .stroke(border_stroke)
+                .wrap();
+            match dynamic_fills {
+                // Through the visuals rather than `Button::fill`, so the artwork accent
+                // lightens on hover and darkens while pressed.
+                Some(fills) => {
+                    let widgets = &mut scaled_ui.visuals_mut().widgets;
+                    widgets.inactive.weak_bg_fill = fills.idle;
+                    widgets.hovered.weak_bg_fill = fills.hover;
+                    widgets.active.weak_bg_fill = fills.active;
+                }
+                None => widget = widget.fill(background),
+            }
+            scaled_ui.add_sized(Vec2::new(min_width, min_height), widget)
         })
         .inner
     }
 
+    /// Icon-only circular button of `diameter`, painted with the theme's button colors
+    /// (or the artwork accent) around the centered glyph. Used for `shape = "circle"`
+    /// playback buttons and the hero play/pause button.
+    pub fn skin_icon_button(
+        &self,
+        ui: &mut egui::Ui,
+        label: impl Into<String>,
+        diameter: f32,
+        enabled: bool,
+    ) -> egui::Response {
+        const GLYPH_RATIO: f32 = 0.42;
+
+        let button = &self.theme.components.button;
+        let fills = self.button_fills();
+        // Allocated in a disabled scope when off, so disabled hover text still shows.
+        let (rect, response) = ui
+            .add_enabled_ui(enabled, |ui| {
+                ui.allocate_exact_size(Vec2::splat(diameter), Sense::click())
+            })
+            .inner;
+        if !ui.is_rect_visible(rect) {
+            return response;
+        }
+
+        let fill = if !enabled {
+            button.disabled_background
+        } else if response.is_pointer_button_down_on() {
+            fills.active
+        } else if response.hovered() {
+            fills.hover
+        } else {
+            fills.idle
+        };
+        let painter = ui.painter();
+        painter.circle(
+            rect.center(),
+            diameter / 2.0,
+            fill,
+            Stroke::new(
+                button.border_width.max(1.0),
+                if enabled {
+                    button.border_color
+                } else {
+                    button.border_color.gamma_multiply(DISABLED_FADE)
+                },
+            ),
+        );
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            label.into(),
+            egui::FontId::proportional(diameter * GLYPH_RATIO),
+            if enabled {
+                button.foreground
+            } else {
+                button.disabled_foreground
+            },
+        );
+        response
+    }
+
     pub fn skin_text(&self, ui: &mut egui::Ui, text: impl Into<String>, title: bool) {
         let style = if title {
             &self.theme.components.text_title
         } else {
             &self.theme.components.text_body
         };
-        ui.label(
-            RichText::new(text.into())
-                .color(style.color)
-                .size(style.size),
+        let text = RichText::new(text.into())
+            .color(style.color)
+            .size(style.size);
+        let response = ui.add(egui::Label::new(text).selectable(self.selectable_text));
+        if self.selectable_text && response.hovered() {
+            self.selectable_text_hovered.set(true);
+        }
+    }
+
+    /// One line of text that scrolls sideways when it is wider than the space left.
+    /// Without `animate` the overflow is clipped at the edge instead.
+    pub fn skin_marquee(&self, ui: &mut egui::Ui, text: &str, title: bool, animate: bool) {
+        let style = if title {
+            &self.theme.components.text_title
+        } else {
+            &self.theme.components.text_body
+        };
+        let galley = ui.painter().layout_no_wrap(
+            text.to_string(),
+            egui::FontId::proportional(style.size),
+            style.color,
         );
+        let width = ui.available_width();
+        let text_width = galley.size().x;
+        if text_width <= width {
+            self.skin_text(ui, text, title);
+            return;
+        }
+        let (rect, response) =
+            ui.allocate_exact_size(Vec2::new(width, galley.size().y), Sense::hover());
+        let offset = if animate {
+            ui.ctx().request_repaint();
+            marquee_offset(ui.input(|i| i.time), text_width)
+        } else {
+            0.0
+        };
+        let painter = ui.painter_at(rect);
+        painter.galley(
+            rect.min - Vec2::new(offset, 0.0),
+            galley.clone(),
+            style.color,
+        );
+        if offset > 0.0 {
+            let repeat = rect.min + Vec2::new(text_width + MARQUEE_GAP - offset, 0.0);
+            painter.galley(repeat, galley, style.color);
+        }
+        response.on_hover_text(text);
     }
 
+    /// Seek-style slider in the skin's colors. `ticks` are positions in `range` marked
+    /// on the track underneath the fill; they are purely visual.
     pub fn skin_slider(
         &mut self,
         ui: &mut egui::Ui,
         value: &mut f64,
         range: std::ops::RangeInclusive<f64>,
+        ticks: &[f64],
     ) -> egui::Response {
-        let slider = self.theme.components.slider.clone();
+        let mut slider = self.theme.components.slider.clone();
+        if let Some(accent) = self.dynamic_accent() {
+            let theme_accent = slider.track_fill;
+            slider.track_fill = accent;
+            match &mut slider.thumb {
+                SliderThumb::Circle { color, .. } | SliderThumb::Image { color, .. } => {
+                    if *color == theme_accent {
+                        *color = accent;
+                    }
+                }
+            }
+        }
         let min = *range.start();
         let max = *range.end();
         let span = (max - min).max(f64::MIN_POSITIVE);
@@ -501,8 +1101,18 @@ impl SkinManager {
         let track_width = (track_max_x - track_min_x).max(1.0);
 
         if response.dragged() || response.drag_started() || response.clicked() {
-            if let Some(pos) = ui.input(|input| input.pointer.interact_pos()) {
-                let t = ((pos.x - track_min_x) / track_width).clamp(0.0, 1.0);
+            let (pointer, delta, modifiers) = ui.input(|input| {
+                let pointer = &input.pointer;
+                (pointer.interact_pos(), pointer.delta(), input.modifiers)
+            });
+            if let Some(pos) = pointer {
+                let t = seek_fraction(
+                    fraction,
+                    (pos.x - track_min_x) / track_width,
+                    delta.x / track_width,
+                    modifiers,
+                    response.dragged() && !response.drag_started(),
+                );
                 let new_value = min + span * t as f64;
                 if (new_value - *value).abs() > f64::EPSILON {
                     *value = new_value;
@@ -519,6 +1129,18 @@ impl SkinManager {
         let rounding = to_corner_radius(slider.track_thickness / 2.0);
         painter.rect_filled(track_rect, rounding, slider.track_background);
 
+        let tick_height = slider.track_thickness + 4.0;
+        for tick in tick_fractions(ticks, min, max) {
+            let x = track_rect.min.x + track_width * tick;
+            painter.line_segment(
+                [
+                    Pos2::new(x, track_rect.center().y - tick_height / 2.0),
+                    Pos2::new(x, track_rect.center().y + tick_height / 2.0),
+                ],
+                egui::Stroke::new(1.0, slider.tick_color),
+            );
+        }
+
         if fraction > 0.0 {
             let fill_rect = Rect::from_min_max(
                 track_rect.min,
@@ -546,6 +1168,55 @@ impl SkinManager {
         response
     }
 
+    /// Indeterminate bar shown instead of the slider while the player is loading: a
+    /// segment in the slider's fill color sweeps along its track. Without animation the
+    /// whole track is tinted instead.
+    pub fn skin_busy_bar(&self, ui: &mut egui::Ui, time: f64, animate: bool) -> egui::Response {
+        let slider = &self.theme.components.slider;
+        let fill = self.dynamic_accent().unwrap_or(slider.track_fill);
+        let thumb_height = match &slider.thumb {
+            SliderThumb::Circle { radius, .. } => radius * 2.0,
+            SliderThumb::Image { size, .. } => size.y,
+        };
+        // Same footprint as the slider so the layout doesn't jump when loading ends.
+        let desired_height = thumb_height.max(slider.track_thickness) + 8.0;
+        let (rect, response) = ui.allocate_exact_size(
+            Vec2::new(ui.available_width(), desired_height),
+            Sense::hover(),
+        );
+
+        let painter = ui.painter_at(rect);
+        let track_rect = Rect::from_center_size(
+            rect.center(),
+            Vec2::new(rect.width(), slider.track_thickness),
+        );
+        let rounding = to_corner_radius(slider.track_thickness / 2.0);
+        painter.rect_filled(track_rect, rounding, slider.track_background);
+        if animate {
+            let (start, end) = busy_sweep_span(time);
+            let sweep = Rect::from_x_y_ranges(
+                track_rect.min.x + track_rect.width() * start
+                    ..=track_rect.min.x + track_rect.width() * end,
+                track_rect.y_range(),
+            );
+            painter.rect_filled(sweep, rounding, fill);
+        } else {
+            painter.rect_filled(track_rect, rounding, fill.gamma_multiply(BUSY_STATIC_ALPHA));
+        }
+        response
+    }
+
+    /// Square-ended progress line filling `strip` in the slider's colors, `fraction` of
+    /// it from the left in the fill color.
+    pub fn paint_progress_edge(&self, painter: &egui::Painter, strip: Rect, fraction: f32) {
+        let slider = &self.theme.components.slider;
+        let fill = self.dynamic_accent().unwrap_or(slider.track_fill);
+        painter.rect_filled(strip, CornerRadius::ZERO, slider.track_background);
+        let filled_x = strip.min.x + strip.width() * fraction.clamp(0.0, 1.0);
+        let filled = Rect::from_x_y_ranges(strip.min.x..=filled_x, strip.y_range());
+        painter.rect_filled(filled, CornerRadius::ZERO, fill);
+    }
+
     fn ensure_texture(
         &mut self,
         ctx: &egui::Context,
@@ -570,6 +1241,7 @@ impl SkinManager {
             }
             Err(err) => {
                 eprintln!("Failed to load texture {}: {err}", path.display());
+                self.root_suspect = true;
                 None
             }
         }
@@ -578,7 +1250,7 @@ impl SkinManager {
     pub fn thumbnail_overlay_textures(
         &mut self,
         ctx: &egui::Context,
-    ) -> Vec<(TextureHandle, egui::Vec2)> {
+    ) -> Vec<(TextureHandle, ThumbnailOverlay)> {
         self.theme
             .components
             .thumbnail
@@ -587,12 +1259,107 @@ impl SkinManager {
             .into_iter()
             .filter_map(|overlay| {
                 self.ensure_texture(ctx, overlay.path.as_path(), false)
-                    .map(|texture| (texture, overlay.offset))
+                    .map(|texture| (texture, overlay))
             })
             .collect()
     }
 }
 
+/// Where a thumbnail overlay lands on the artwork `frame`, before rotation: fitted inside
+/// the frame (never enlarged) and scaled by `overlay.scale`, with its `anchor` point on
+/// the frame's matching point, then nudged by `offset`. None for an empty texture.
+pub fn thumbnail_overlay_rect(
+    frame: Rect,
+    texture_size: Vec2,
+    overlay: &ThumbnailOverlay,
+) -> Option<Rect> {
+    if texture_size.x <= 0.0 || texture_size.y <= 0.0 {
+        return None;
+    }
+    let fit = (frame.width() / texture_size.x)
+        .min(frame.height() / texture_size.y)
+        .clamp(0.0, 1.0);
+    let size = texture_size * fit * overlay.scale;
+    if size.x <= 0.0 || size.y <= 0.0 {
+        return None;
+    }
+    let point = overlay.anchor.pos_in_rect(&frame) + overlay.offset;
+    Some(overlay.anchor.anchor_size(point, size))
+}
+
+/// Textured quad filling `rect`, turned clockwise by `angle` radians about its center.
+pub fn rotated_image_mesh(texture: egui::TextureId, rect: Rect, angle: f32, tint: Color32) -> Mesh {
+    let (sin, cos) = angle.sin_cos();
+    let half = rect.size() / 2.0;
+    let corners = [
+        (Vec2::new(-half.x, -half.y), Pos2::new(0.0, 0.0)),
+        (Vec2::new(half.x, -half.y), Pos2::new(1.0, 0.0)),
+        (Vec2::new(half.x, half.y), Pos2::new(1.0, 1.0)),
+        (Vec2::new(-half.x, half.y), Pos2::new(0.0, 1.0)),
+    ];
+    let mut mesh = Mesh::with_texture(texture);
+    for (offset, uv) in corners {
+        let rotated = Vec2::new(
+            offset.x * cos - offset.y * sin,
+            offset.x * sin + offset.y * cos,
+        );
+        mesh.vertices.push(Vertex {
+            pos: rect.center() + rotated,
+            uv,
+            color: tint,
+        });
+    }
+    mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+    mesh
+}
+
+/// Track fraction a slider press or drag moves to. Plain presses follow the pointer;
+/// while Shift is held an ongoing drag moves the position by a fraction of the pointer's
+/// motion for fine seeking, and Ctrl snaps to [`COARSE_SEEK_STEP`] increments.
+fn seek_fraction(
+    current: f32,
+    pointer: f32,
+    pointer_delta: f32,
+    modifiers: egui::Modifiers,
+    dragging: bool,
+) -> f32 {
+    let t = if modifiers.shift && dragging {
+        current + pointer_delta * FINE_SEEK_SCALE
+    } else {
+        pointer
+    };
+    let t = if modifiers.command {
+        (t / COARSE_SEEK_STEP).round() * COARSE_SEEK_STEP
+    } else {
+        t
+    };
+    t.clamp(0.0, 1.0)
+}
+
+/// Track fractions for tick positions in `min..=max`. Ticks on or beyond the ends are
+/// dropped since the track caps already mark them.
+fn tick_fractions(ticks: &[f64], min: f64, max: f64) -> Vec<f32> {
+    let span = max - min;
+    if span <= f64::EPSILON {
+        return Vec::new();
+    }
+    ticks
+        .iter()
+        .map(|tick| (tick - min) / span)
+        .filter(|fraction| *fraction > 0.0 && *fraction < 1.0)
+        .map(|fraction| fraction as f32)
+        .collect()
+}
+
+/// Track fractions covered by the busy bar's segment at `time`: it enters from the left
+/// edge and leaves past the right one, clipped to the track.
+fn busy_sweep_span(time: f64) -> (f32, f32) {
+    let phase = (time / BUSY_SWEEP_PERIOD).rem_euclid(1.0) as f32;
+    let head = phase * (1.0 + BUSY_SWEEP_WIDTH);
+    let tail = head - BUSY_SWEEP_WIDTH;
+    (tail.max(0.0), head.min(1.0))
+}
+
 pub fn paint_area_background(
     painter: &egui::Painter,
     rect: Rect,
@@ -609,6 +1376,34 @@ pub fn paint_area_background(
     }
 }
 
+/// Drop shadow for a panel card raised by `elevation` logical pixels; `None` when flat.
+/// `alpha` scales the darkness so translucent skins get a matching, lighter shadow.
+pub fn card_shadow(elevation: f32, alpha: f32) -> Option<egui::Shadow> {
+    if elevation <= f32::EPSILON || alpha <= 0.0 {
+        return None;
+    }
+    let elevation = elevation.min(24.0);
+    Some(egui::Shadow {
+        offset: [0, (elevation * 0.5).round() as i8],
+        blur: (elevation * 2.0).round() as u8,
+        spread: 0,
+        color: Color32::from_black_alpha((alpha.min(1.0) * (60.0 + elevation * 4.0)) as u8),
+    })
+}
+
+/// Paints the shadow under a raised card; call before its background.
+pub fn paint_area_shadow(
+    painter: &egui::Painter,
+    rect: Rect,
+    rounding: CornerRadius,
+    elevation: f32,
+    alpha: f32,
+) {
+    if let Some(shadow) = card_shadow(elevation, alpha) {
+        painter.add(shadow.as_shape(rect, rounding));
+    }
+}
+
 fn paint_gradient_rect(
     painter: &egui::Painter,
     rect: Rect,
@@ -620,9 +1415,20 @@ fn paint_gradient_rect(
         return;
     }
 
+    match gradient_mesh(rect, rounding, gradient) {
+        Some(mesh) => {
+            painter.add(egui::Shape::mesh(mesh));
+        }
+        None => {
+            painter.rect_filled(rect, rounding, gradient.start);
+        }
+    }
+}
+
+/// Builds the gradient mesh for `rect`, or `None` when a solid fill is equivalent.
+fn gradient_mesh(rect: Rect, rounding: CornerRadius, gradient: &GradientSpec) -> Option<Mesh> {
     if gradient.start == gradient.end {
-        painter.rect_filled(rect, rounding, gradient.start);
-        return;
+        return None;
     }
 
     let radii = CornerRadiiF32::from_rect(rounding, rect);
@@ -637,7 +1443,7 @@ fn paint_gradient_rect(
         }
     }
 
-    painter.add(egui::Shape::mesh(mesh));
+    Some(mesh)
 }
 
 fn tessellate_vertical_gradient(
@@ -914,6 +1720,47 @@ fn load_texture_from_path(ctx: &egui::Context, path: &Path) -> Result<TextureHan
     ))
 }
 
+/// How far a marquee of `text_width` has scrolled at `time`: it rests at 0, then moves
+/// until its repeat reaches the start, where the next pass begins seamlessly.
+fn marquee_offset(time: f64, text_width: f32) -> f32 {
+    let travel = text_width + MARQUEE_GAP;
+    let cycle = MARQUEE_PAUSE + f64::from(travel / MARQUEE_SPEED);
+    let moving = (time.rem_euclid(cycle) - MARQUEE_PAUSE).max(0.0) as f32;
+    (moving * MARQUEE_SPEED).min(travel)
+}
+
+/// The `thickness`-tall strip along `edge` of `bounds`, as wide as `bounds`.
+pub fn edge_strip_rect(bounds: Rect, edge: StripEdge, thickness: f32) -> Rect {
+    let thickness = thickness.clamp(0.0, bounds.height());
+    let y = match edge {
+        StripEdge::Top => bounds.min.y..=bounds.min.y + thickness,
+        StripEdge::Bottom => bounds.max.y - thickness..=bounds.max.y,
+    };
+    Rect::from_x_y_ranges(bounds.x_range(), y)
+}
+
+/// Where a press seeks along `strip`: the strip itself, grown away from its edge to at
+/// least [`EDGE_STRIP_HIT_HEIGHT`].
+pub fn edge_strip_hit_band(strip: Rect, edge: StripEdge) -> Rect {
+    let height = strip.height().max(EDGE_STRIP_HIT_HEIGHT);
+    let y = match edge {
+        StripEdge::Top => strip.min.y..=strip.min.y + height,
+        StripEdge::Bottom => strip.max.y - height..=strip.max.y,
+    };
+    Rect::from_x_y_ranges(strip.x_range(), y)
+}
+
+/// Share of `strip` left of the pointer at `x`, clamped to the strip.
+pub fn edge_strip_fraction(strip: Rect, x: f32) -> f32 {
+    ((x - strip.min.x) / strip.width().max(1.0)).clamp(0.0, 1.0)
+}
+
+/// Skins discovered under `root` can no longer load their assets once it is gone. The
+/// built-in skin has no files to lose.
+fn skins_root_lost(root: &Path, skins: &[SkinInfo]) -> bool {
+    skins.iter().any(|skin| !skin.embedded) && !root.is_dir()
+}
+
 pub fn default_skin_root() -> PathBuf {
     PathBuf::from("skins")
 }
@@ -939,3 +1786,337 @@ fn layout_index_from_set(layout: &LayoutSet, preferred: Option<&str>) -> usize {
 
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capsule_rect() -> Rect {
+        Rect::from_min_size(Pos2::new(10.0, 20.0), Vec2::new(100.0, 40.0))
+    }
+
+    fn gradient(start: Color32, end: Color32, direction: GradientDirection) -> GradientSpec {
+        GradientSpec {
+            start,
+            end,
+            direction,
+        }
+    }
+
+    #[test]
+    fn lerp_color_hits_endpoints_and_clamps() {
+        let start = Color32::from_rgb(0, 0, 0);
+        let end = Color32::from_rgb(255, 255, 255);
+        assert_eq!(lerp_color(start, end, 0.0), start);
+        assert_eq!(lerp_color(start, end, 1.0), end);
+        assert_eq!(lerp_color(start, end, -1.0), start);
+        assert_eq!(lerp_color(start, end, 2.0), end);
+    }
+
+    #[test]
+    fn zero_radius_spans_cover_full_rect() {
+        let rect = capsule_rect();
+        let radii = CornerRadiiF32::from_rect(CornerRadius::ZERO, rect);
+        for y in [rect.min.y, rect.center().y, rect.max.y] {
+            assert_eq!(horizontal_span(rect, &radii, y), (rect.min.x, rect.max.x));
+        }
+        for x in [rect.min.x, rect.center().x, rect.max.x] {
+            assert_eq!(vertical_span(rect, &radii, x), (rect.min.y, rect.max.y));
+        }
+    }
+
+    #[test]
+    fn half_height_radius_produces_capsule_spans() {
+        let rect = capsule_rect();
+        let radii = CornerRadiiF32::from_rect(CornerRadius::same(255), rect);
+        assert_eq!(radii.nw, 20.0);
+
+        let width_at = |y: f32| {
+            let (left, right) = horizontal_span(rect, &radii, y);
+            right - left
+        };
+        assert_eq!(width_at(rect.center().y), rect.width());
+        assert!(width_at(rect.min.y + 10.0) < width_at(rect.min.y + 15.0));
+        assert!(width_at(rect.min.y + 2.0) < width_at(rect.min.y + 10.0));
+        assert!((width_at(rect.min.y) - (rect.width() - 40.0)).abs() < 1e-3);
+        assert!((width_at(rect.min.y + 5.0) - width_at(rect.max.y - 5.0)).abs() < 1e-3);
+
+        let (top, bottom) = vertical_span(rect, &radii, rect.min.x);
+        assert!((top - rect.center().y).abs() < 1e-3);
+        assert!((bottom - rect.center().y).abs() < 1e-3);
+    }
+
+    #[test]
+    fn identical_stops_short_circuit_to_solid_fill() {
+        let color = Color32::from_rgb(40, 80, 120);
+        let spec = gradient(color, color, GradientDirection::Vertical);
+        assert!(gradient_mesh(capsule_rect(), CornerRadius::same(8), &spec).is_none());
+    }
+
+    #[test]
+    fn gradient_mesh_stays_inside_rect() {
+        let rect = capsule_rect();
+        for direction in [GradientDirection::Vertical, GradientDirection::Horizontal] {
+            let spec = gradient(Color32::RED, Color32::BLUE, direction);
+            let mesh = gradient_mesh(rect, CornerRadius::same(12), &spec).expect("mesh");
+            assert!(!mesh.vertices.is_empty());
+            assert_eq!(mesh.indices.len() / 3, mesh.vertices.len() / 2);
+            assert!(mesh
+                .vertices
+                .iter()
+                .all(|vertex| rect.expand(1e-3).contains(vertex.pos)));
+        }
+    }
+
+    #[test]
+    fn discover_falls_back_when_skins_path_is_a_file() {
+        let path =
+            std::env::temp_dir().join(format!("nowplaying-skins-file-{}.txt", std::process::id()));
+        fs::write(&path, "not a directory").unwrap();
+
+        let manager = SkinManager::discover(&path, None).expect("fallback skin");
+
+        assert!(!manager.has_folder_skins());
+        assert_eq!(manager.current_skin_id(), None);
+        let stealth = SkinManager::discover(&path, Some(STEALTH_SKIN_ID)).unwrap();
+        assert_eq!(stealth.current_skin_id(), Some(STEALTH_SKIN_ID));
+        assert!(!stealth.current_theme().overlay_controls.visible);
+        assert!(manager
+            .warnings()
+            .first()
+            .is_some_and(|warning| warning.contains("is not a directory")));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn auto_skin_swaps_between_the_light_and_dark_default_themes() {
+        let mut manager = SkinManager::fallback().unwrap().with_light_theme(true);
+        // Only the auto skin shows a change.
+        assert!(!manager.set_light_theme(false));
+        let ctx = egui::Context::default();
+        manager.set_skin(AUTO_SKIN_ID, &ctx).unwrap();
+        assert_eq!(manager.current_theme().name, Theme::default_dark().name);
+
+        assert!(manager.set_light_theme(true));
+        assert_eq!(manager.current_theme().name, Theme::default_light().name);
+        assert!(!manager.set_light_theme(true));
+        assert_eq!(manager.current_skin_id(), Some(AUTO_SKIN_ID));
+        assert!(manager.warnings().is_empty());
+    }
+
+    #[test]
+    fn accent_override_is_applied_and_cleared_in_memory() {
+        let mut manager = SkinManager::fallback().unwrap();
+        let skin_accent = manager.current_theme().colors.get("accent").copied();
+        let red = Color32::from_rgb(200, 40, 40);
+
+        manager.set_accent_override(Some(red));
+        assert_eq!(manager.current_theme().colors.get("accent"), Some(&red));
+        assert_eq!(manager.current_theme().components.slider.track_fill, red);
+
+        manager.set_accent_override(None);
+        assert_eq!(
+            manager.current_theme().colors.get("accent").copied(),
+            skin_accent
+        );
+    }
+
+    #[test]
+    fn marquee_rests_then_scrolls_one_full_pass() {
+        let width = 200.0;
+        let travel = width + MARQUEE_GAP;
+        let cycle = MARQUEE_PAUSE + f64::from(travel / MARQUEE_SPEED);
+        assert_eq!(marquee_offset(0.0, width), 0.0);
+        assert_eq!(marquee_offset(MARQUEE_PAUSE, width), 0.0);
+        assert_eq!(marquee_offset(MARQUEE_PAUSE + 1.0, width), MARQUEE_SPEED);
+        assert!((marquee_offset(cycle - 1e-6, width) - travel).abs() < 0.01);
+        assert_eq!(marquee_offset(cycle + 0.5, width), 0.0);
+    }
+
+    #[test]
+    fn progress_edge_strip_hugs_its_edge_and_maps_presses_along_it() {
+        let rows = |min_y, max_y| Rect::from_x_y_ranges(10.0..=210.0, min_y..=max_y);
+        let bounds = rows(20.0, 120.0);
+        let bottom = edge_strip_rect(bounds, StripEdge::Bottom, 3.0);
+        assert_eq!(bottom, rows(117.0, 120.0));
+        let top = edge_strip_rect(bounds, StripEdge::Top, 3.0);
+        assert_eq!(top, rows(20.0, 23.0));
+
+        // Thin strips get a band that grows into the panel; thick ones keep their own.
+        let band = edge_strip_hit_band(bottom, StripEdge::Bottom);
+        assert_eq!(band, rows(120.0 - EDGE_STRIP_HIT_HEIGHT, 120.0));
+        let band = edge_strip_hit_band(top, StripEdge::Top);
+        assert_eq!(band, rows(20.0, 20.0 + EDGE_STRIP_HIT_HEIGHT));
+        let thick = edge_strip_rect(bounds, StripEdge::Bottom, 12.0);
+        assert_eq!(edge_strip_hit_band(thick, StripEdge::Bottom), thick);
+        assert!(!band.contains(Pos2::new(110.0, 20.0 + EDGE_STRIP_HIT_HEIGHT + 1.0)));
+
+        assert_eq!(edge_strip_fraction(bottom, 10.0), 0.0);
+        assert_eq!(edge_strip_fraction(bottom, 60.0), 0.25);
+        assert_eq!(edge_strip_fraction(bottom, 260.0), 1.0);
+        assert_eq!(edge_strip_fraction(bottom, -5.0), 0.0);
+    }
+
+    #[test]
+    fn warning_diff_reports_fixed_and_new_in_order_without_duplicates() {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let before = strings(&["a", "b", "c"]);
+        let after = strings(&["c", "e", "d", "e"]);
+
+        let delta = diff_warnings(&before, &after);
+        assert_eq!(delta.fixed, strings(&["a", "b"]));
+        assert_eq!(delta.new, strings(&["e", "d"]));
+        assert_eq!(delta.summary(), "2 new warnings, 2 resolved");
+
+        assert!(diff_warnings(&after, &after).is_empty());
+        assert_eq!(diff_warnings(&after, &[]).summary(), "3 warnings resolved");
+        assert_eq!(
+            diff_warnings(&[], &strings(&["x"])).summary(),
+            "1 new warning"
+        );
+    }
+
+    #[test]
+    fn losing_the_skins_folder_is_noticed_once_something_fails() {
+        let root =
+            std::env::temp_dir().join(format!("nowplaying-skins-lost-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("gone")).unwrap();
+        fs::write(
+            root.join("gone/theme.toml"),
+            "[meta]\ndisplay_name = \"Gone\"\n",
+        )
+        .unwrap();
+
+        let mut manager = SkinManager::discover(&root, None).expect("skin loads");
+        assert_eq!(manager.current_skin_id(), Some("gone"));
+        manager.root_suspect = true;
+        assert!(!manager.take_lost_root(), "folder still there");
+
+        fs::remove_dir_all(&root).unwrap();
+        assert!(!manager.take_lost_root(), "nothing has failed yet");
+        manager.root_suspect = true;
+        assert!(manager.take_lost_root());
+        assert!(!manager.take_lost_root(), "reported once");
+
+        // The built-in fallback has nothing on disk to lose.
+        let mut fallback = SkinManager::fallback_with_root(root, None).unwrap();
+        fallback.root_suspect = true;
+        assert!(!fallback.take_lost_root());
+    }
+
+    #[test]
+    fn layout_resolution_prefers_stored_pick_then_skin_default_then_first() {
+        let mut layout = load_layout_from_dir(Path::new(".")).unwrap().layout;
+        let ids: Vec<String> = layout.variants().iter().map(|v| v.id.clone()).collect();
+        assert!(
+            ids.len() > 1,
+            "builtin layout should offer several variants"
+        );
+        let default_index = ids
+            .iter()
+            .position(|id| *id == layout.default_variant)
+            .unwrap();
+        let stored_index = (default_index + 1) % ids.len();
+
+        assert_eq!(
+            layout_index_from_set(&layout, Some(&ids[stored_index])),
+            stored_index
+        );
+        assert_eq!(
+            layout_index_from_set(&layout, Some("missing")),
+            default_index
+        );
+        assert_eq!(layout_index_from_set(&layout, None), default_index);
+
+        layout.default_variant = "missing".to_string();
+        assert_eq!(layout_index_from_set(&layout, None), 0);
+    }
+
+    #[test]
+    fn tick_fractions_map_into_the_track_interior() {
+        assert_eq!(
+            tick_fractions(&[30.0, 60.0, 90.0], 0.0, 120.0),
+            vec![0.25, 0.5, 0.75]
+        );
+        assert_eq!(tick_fractions(&[15.0], 10.0, 30.0), vec![0.25]);
+        // Ends and out-of-range positions are dropped.
+        assert_eq!(
+            tick_fractions(&[0.0, -5.0, 120.0, 150.0], 0.0, 120.0),
+            Vec::<f32>::new()
+        );
+        assert!(tick_fractions(&[1.0], 5.0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn thumbnail_overlays_fit_scale_and_anchor_on_the_artwork() {
+        let frame = Rect::from_min_size(Pos2::new(10.0, 20.0), Vec2::splat(100.0));
+        let mut overlay = ThumbnailOverlay::new(PathBuf::new(), Vec2::new(5.0, -5.0));
+        // Defaults: a large image shrinks to fit and centers, then takes the offset.
+        let rect = thumbnail_overlay_rect(frame, Vec2::new(200.0, 100.0), &overlay).unwrap();
+        assert_eq!(rect.size(), Vec2::new(100.0, 50.0));
+        assert_eq!(rect.center(), frame.center() + Vec2::new(5.0, -5.0));
+        // Small images are never enlarged.
+        let small = thumbnail_overlay_rect(frame, Vec2::splat(40.0), &overlay).unwrap();
+        assert_eq!(small.size(), Vec2::splat(40.0));
+
+        overlay.scale = 0.4;
+        overlay.anchor = egui::Align2::RIGHT_TOP;
+        let badge = thumbnail_overlay_rect(frame, Vec2::splat(100.0), &overlay).unwrap();
+        assert_eq!(badge.size(), Vec2::splat(40.0));
+        assert_eq!(badge.right_top(), frame.right_top() + Vec2::new(5.0, -5.0));
+        assert!(thumbnail_overlay_rect(frame, Vec2::ZERO, &overlay).is_none());
+
+        let rect = Rect::from_center_size(Pos2::ZERO, Vec2::new(4.0, 2.0));
+        let quarter_turn = std::f32::consts::FRAC_PI_2;
+        let texture = egui::TextureId::default();
+        let mesh = rotated_image_mesh(texture, rect, quarter_turn, Color32::WHITE);
+        // A quarter turn clockwise takes the top-left corner to the top-right.
+        assert!((mesh.vertices[0].pos - Pos2::new(1.0, -2.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn shift_drags_scale_pointer_motion_and_ctrl_snaps() {
+        let none = egui::Modifiers::NONE;
+        let shift = egui::Modifiers::SHIFT;
+        // Without modifiers the position follows the pointer, as before.
+        assert_eq!(seek_fraction(0.2, 0.7, 0.3, none, true), 0.7);
+        assert_eq!(seek_fraction(0.2, 1.4, 0.0, none, false), 1.0);
+        // Shift moves a tenth of the pointer's motion from where the thumb is.
+        assert!((seek_fraction(0.5, 0.9, 0.2, shift, true) - 0.52).abs() < 1e-6);
+        assert!((seek_fraction(0.5, 0.1, -0.3, shift, true) - 0.47).abs() < 1e-6);
+        assert_eq!(seek_fraction(0.99, 1.0, 0.5, shift, true), 1.0);
+        // A Shift press doesn't drag yet, so it still jumps to the pointer.
+        assert_eq!(seek_fraction(0.5, 0.9, 0.0, shift, false), 0.9);
+        // Ctrl snaps to 5% steps, also after fine scaling.
+        let ctrl = egui::Modifiers::COMMAND;
+        assert!((seek_fraction(0.0, 0.333, 0.0, ctrl, true) - 0.35).abs() < 1e-6);
+        assert!((seek_fraction(0.5, 0.0, 0.2, shift | ctrl, true) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn card_shadow_grows_with_elevation() {
+        assert!(card_shadow(0.0, 1.0).is_none());
+        assert!(card_shadow(8.0, 0.0).is_none());
+        let low = card_shadow(2.0, 1.0).unwrap();
+        let high = card_shadow(12.0, 1.0).unwrap();
+        assert!(high.blur > low.blur && high.offset[1] > low.offset[1]);
+        assert!(high.color.a() > low.color.a());
+        assert!(card_shadow(12.0, 0.5).unwrap().color.a() < high.color.a());
+        assert_eq!(card_shadow(100.0, 1.0), card_shadow(24.0, 1.0));
+    }
+
+    #[test]
+    fn busy_sweep_crosses_the_track_and_wraps() {
+        assert_eq!(busy_sweep_span(0.0), (0.0, 0.0));
+        let (start, end) = busy_sweep_span(BUSY_SWEEP_PERIOD * 0.5);
+        assert!((end - start - BUSY_SWEEP_WIDTH).abs() < 1e-5);
+        assert!(start > 0.0 && end < 1.0);
+        let (start, end) = busy_sweep_span(BUSY_SWEEP_PERIOD * 0.99);
+        assert!(start > 0.9 && end == 1.0);
+        assert_eq!(
+            busy_sweep_span(BUSY_SWEEP_PERIOD * 3.5),
+            busy_sweep_span(BUSY_SWEEP_PERIOD * 0.5)
+        );
+    }
+}
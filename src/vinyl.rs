@@ -4,6 +4,9 @@ use eframe::egui::{Color32, ColorImage, Vec2};
 
 use crate::config::VinylThumbnailConfig;
 
+/// Largest vinyl render, in pixels per side.
+pub const MAX_VINYL_OUTPUT_SIZE: usize = 1024;
+
 #[derive(Debug, Clone)]
 pub struct VinylThumbnailOptions {
     pub swirl_strength: f32,
@@ -18,19 +21,21 @@ impl VinylThumbnailOptions {
         source_width: usize,
         source_height: usize,
     ) -> Self {
-        let max_dim = source_width.max(source_height).max(128);
-        let mut output_size = max_dim.clamp(128, 1024);
-        if output_size % 2 == 1 {
-            output_size += 1;
-        }
+        let max_dim = source_width.max(source_height);
         Self {
             swirl_strength: config.swirl_strength(),
             label_ratio: config.label_ratio(),
-            output_size,
+            output_size: output_side(max_dim),
             groove_count: 12,
         }
     }
 
+    /// Raises `output_size` to at least `side` pixels, within [`MAX_VINYL_OUTPUT_SIZE`].
+    pub fn with_min_output_size(mut self, side: usize) -> Self {
+        self.output_size = self.output_size.max(output_side(side));
+        self
+    }
+
     #[allow(dead_code)]
     pub fn cache_key(&self, hash: u64) -> VinylCacheKey {
         VinylCacheKey {
@@ -74,6 +79,13 @@ impl VinylCache {
 pub struct VinylSpin {
     angle: f32,
     speed: f32,
+    reverse: bool,
+}
+
+impl Default for VinylSpin {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VinylSpin {
@@ -82,12 +94,23 @@ impl VinylSpin {
         Self {
             angle: 0.0,
             speed: 3.49,
+            reverse: false,
         }
     }
 
+    /// Spin counterclockwise instead of clockwise.
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = reverse;
+    }
+
     pub fn advance(&mut self, dt: f32, spinning: bool) {
         if spinning && dt > 0.0 {
-            self.angle = (self.angle + self.speed * dt).rem_euclid(TAU as f32);
+            let step = if self.reverse {
+                -self.speed
+            } else {
+                self.speed
+            } * dt;
+            self.angle = (self.angle + step).rem_euclid(TAU as f32);
         }
     }
 
@@ -100,6 +123,23 @@ impl VinylSpin {
     }
 }
 
+/// Render side for `side` pixels: clamped to the supported range and rounded up to even.
+fn output_side(side: usize) -> usize {
+    let side = side.clamp(128, MAX_VINYL_OUTPUT_SIZE);
+    side + side % 2
+}
+
+/// Side to re-render a `texture_side` pixel vinyl at so it stays sharp when drawn
+/// `display_side` physical pixels wide, or `None` if it already is or can't grow.
+pub fn vinyl_rerender_size(texture_side: usize, display_side: f32) -> Option<usize> {
+    // A pixel of slack so fractional layout sizes don't trigger a render.
+    if !display_side.is_finite() || display_side <= texture_side as f32 + 1.0 {
+        return None;
+    }
+    let side = output_side(display_side.ceil() as usize);
+    (side > texture_side).then_some(side)
+}
+
 pub fn render_vinyl(image: &ColorImage, options: &VinylThumbnailOptions) -> ColorImage {
     let size = options.output_size;
     let mut output = ColorImage::new([size, size], vec![Color32::TRANSPARENT; size * size]);
@@ -273,7 +313,7 @@ fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
     )
 }
 
-fn darken(color: Color32, amount: f32) -> Color32 {
+pub fn darken(color: Color32, amount: f32) -> Color32 {
     let amount = amount.clamp(0.0, 1.0);
     let r = (color.r() as f32 * (1.0 - amount))
         .round()
@@ -287,7 +327,7 @@ fn darken(color: Color32, amount: f32) -> Color32 {
     Color32::from_rgba_unmultiplied(r, g, b, color.a())
 }
 
-fn lighten(color: Color32, amount: f32) -> Color32 {
+pub fn lighten(color: Color32, amount: f32) -> Color32 {
     let amount = amount.clamp(0.0, 1.0);
     let r = (color.r() as f32 + (255.0 - color.r() as f32) * amount)
         .round()
@@ -339,6 +379,22 @@ mod tests {
         assert_eq!(spin.angle(), angle);
     }
 
+    #[test]
+    fn reverse_spin_turns_the_other_way() {
+        let mut forward = VinylSpin::new();
+        let mut reverse = VinylSpin::new();
+        reverse.set_reverse(true);
+        forward.advance(0.5, true);
+        reverse.advance(0.5, true);
+        assert!((forward.angle() + reverse.angle() - TAU).abs() < 1e-4);
+        assert!(reverse.angle() >= 0.0 && reverse.angle() < TAU);
+
+        reverse.reset();
+        assert_eq!(reverse.angle(), 0.0);
+        reverse.advance(0.1, false);
+        assert_eq!(reverse.angle(), 0.0);
+    }
+
     #[test]
     fn render_produces_expected_size() {
         let image = solid_image(128, Color32::from_rgb(120, 60, 20));
@@ -368,4 +424,35 @@ mod tests {
         let large = render_vinyl(&image, &opts);
         assert_eq!(large.size, [512, 512]);
     }
+
+    #[test]
+    fn rerender_only_when_the_display_outgrows_the_texture() {
+        // 220 points on a 150% monitor needs 330 pixels.
+        assert_eq!(vinyl_rerender_size(300, 220.0 * 1.5), Some(330));
+        assert_eq!(vinyl_rerender_size(300, 220.0 * 1.25), None);
+        assert_eq!(vinyl_rerender_size(300, 300.6), None);
+        assert_eq!(vinyl_rerender_size(300, 301.5), Some(302));
+
+        // Already at the cap, or asked for more than it allows.
+        assert_eq!(vinyl_rerender_size(MAX_VINYL_OUTPUT_SIZE, 2000.0), None);
+        assert_eq!(
+            vinyl_rerender_size(600, 2000.0),
+            Some(MAX_VINYL_OUTPUT_SIZE)
+        );
+        assert_eq!(vinyl_rerender_size(300, f32::NAN), None);
+    }
+
+    #[test]
+    fn min_output_size_only_grows_the_render() {
+        let config = VinylThumbnailConfig::default();
+        let small = VinylThumbnailOptions::from_config(&config, 300, 300);
+        assert_eq!(small.output_size, 300);
+        assert_eq!(small.clone().with_min_output_size(331).output_size, 332);
+        assert_eq!(small.with_min_output_size(200).output_size, 300);
+        let large = VinylThumbnailOptions::from_config(&config, 4000, 4000);
+        assert_eq!(
+            large.with_min_output_size(5000).output_size,
+            MAX_VINYL_OUTPUT_SIZE
+        );
+    }
 }
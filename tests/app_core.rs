@@ -0,0 +1,359 @@
+use std::time::{Duration, Instant};
+
+use now_playing_gui::state::{
+    seek_commit_timeout, AppCore, NowPlaying, PendingThumbnail, PlayState, PlaybackClock,
+    SessionSnapshot, Timeline, LISTENING_SESSION_GAP, SEEK_COMMIT_TIMEOUT,
+};
+
+fn track(title: &str, state: PlayState) -> NowPlaying {
+    NowPlaying {
+        title: title.to_string(),
+        artist: "Artist".to_string(),
+        album: "Album".to_string(),
+        state,
+        ..Default::default()
+    }
+}
+
+fn snapshot(now: NowPlaying, position_secs: f64) -> SessionSnapshot {
+    SessionSnapshot {
+        now,
+        timeline: Some(Timeline {
+            start_secs: 0.0,
+            end_secs: 200.0,
+            position_secs,
+            can_seek: true,
+        }),
+        others: Vec::new(),
+        next: None,
+    }
+}
+
+fn position(core: &AppCore) -> f64 {
+    core.timeline.as_ref().expect("timeline").position_secs
+}
+
+#[test]
+fn timeline_prediction_keeps_local_clock_within_tolerance() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 10.0), start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 10.0), start);
+    assert_eq!(position(&core), 10.0);
+
+    // Between snapshots the position follows the clock.
+    core.advance_timeline(start + Duration::from_secs(3));
+    assert!((position(&core) - 13.0).abs() < 1e-6);
+
+    // A report within 1% of the duration keeps the smoother local prediction...
+    let at = start + Duration::from_secs(5);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 14.5), at);
+    assert!((position(&core) - 15.0).abs() < 1e-6);
+
+    // ...while a larger jump is taken as-is.
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 90.0), at);
+    assert_eq!(position(&core), 90.0);
+
+    // Paused sessions never advance on their own.
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 90.0), at);
+    core.advance_timeline(at + Duration::from_secs(10));
+    assert_eq!(position(&core), 90.0);
+}
+
+#[test]
+fn timeline_prediction_is_independent_of_frame_cadence() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 10.0), start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 10.0), start);
+
+    // Frame gaps as the repaint interval swings between 16ms, 250ms, and stalls, run
+    // until the track ends.
+    let deltas_ms = [16, 16, 250, 3, 250, 16, 1000, 1, 120, 250, 16, 33, 2500, 16];
+    let mut at = start;
+    let mut previous = position(&core);
+    for delta in deltas_ms.iter().cycle().take(700) {
+        at += Duration::from_millis(*delta);
+        core.advance_timeline(at);
+        let current = position(&core);
+        assert!(
+            current >= previous,
+            "position went backwards: {previous} -> {current}"
+        );
+        let expected = (10.0 + at.duration_since(start).as_secs_f64()).min(200.0);
+        assert!(
+            (current - expected).abs() < 1e-6,
+            "drifted: {current} vs {expected}"
+        );
+        previous = current;
+    }
+    assert_eq!(position(&core), 200.0);
+}
+
+#[test]
+fn resume_counts_from_the_request_not_the_last_paused_frame() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 30.0), start);
+    core.advance_timeline(start);
+
+    // Paused frames are 250ms apart; play is pressed 200ms after the last one.
+    let pressed = start + Duration::from_millis(200);
+    core.expect_state(PlayState::Playing, pressed);
+    core.advance_timeline(pressed + Duration::from_millis(16));
+    assert!((position(&core) - 30.016).abs() < 1e-6);
+
+    let until = core
+        .until_next_position_second(pressed + Duration::from_millis(16))
+        .expect("advancing");
+    assert!((until.as_secs_f64() - 0.984).abs() < 1e-6);
+}
+
+#[test]
+fn playback_clock_predicts_holds_seeks_and_paces_updates() {
+    let start = Instant::now();
+    let secs = |s: u64| start + Duration::from_secs(s);
+    let mut clock = PlaybackClock::new(start);
+    assert_eq!(clock.predicted_position(start), None);
+
+    clock.set_bounds(Some((0.0, 100.0)));
+    clock.anchor(10.0, start);
+    assert_eq!(clock.predicted_position(secs(5)), Some(10.0));
+    assert_eq!(clock.tick(secs(5)), None, "stopped clocks stay quiet");
+
+    clock.set_running(true, secs(5));
+    assert_eq!(clock.predicted_position(secs(8)), Some(13.0));
+    assert_eq!(clock.predicted_position(secs(500)), Some(100.0));
+
+    // Updates go out at most once per interval.
+    assert_eq!(clock.tick(secs(6)), Some(11.0));
+    assert_eq!(clock.tick(secs(6) + Duration::from_millis(900)), None);
+    assert_eq!(clock.tick(secs(7)), Some(12.0));
+
+    // A pending seek holds the position until cleared, then resumes from the target.
+    clock.hold_seek(40.0, secs(8));
+    assert_eq!(clock.predicted_position(secs(10)), Some(40.0));
+    assert!(!clock.seek_expired(secs(10)));
+    assert!(clock.seek_expired(secs(8) + SEEK_COMMIT_TIMEOUT));
+    clock.clear_seek();
+    clock.anchor(40.0, secs(10));
+    assert_eq!(clock.predicted_position(secs(12)), Some(42.0));
+
+    // Stopping freezes the position where it was.
+    clock.set_running(false, secs(13));
+    assert_eq!(clock.predicted_position(secs(30)), Some(43.0));
+}
+
+#[test]
+fn listening_session_survives_pauses_but_not_long_stops() {
+    let start = Instant::now();
+    let mins = |m: u64| start + Duration::from_secs(m * 60);
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 0.0), start);
+    assert_eq!(core.listening_for(start), None);
+
+    for minute in [1, 10, 20] {
+        core.apply_snapshot(snapshot(track("A", PlayState::Playing), 0.0), mins(minute));
+    }
+    assert_eq!(
+        core.listening_for(mins(20)),
+        Some(Duration::from_secs(19 * 60))
+    );
+
+    // A short pause and a resume continue the same session.
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 0.0), mins(21));
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 0.0), mins(30));
+    assert_eq!(
+        core.listening_for(mins(30)),
+        Some(Duration::from_secs(29 * 60))
+    );
+
+    // A long stop ends it, and the next Play starts over.
+    core.apply_snapshot(snapshot(track("A", PlayState::Stopped), 0.0), mins(31));
+    let resumed = mins(30) + LISTENING_SESSION_GAP;
+    assert_eq!(core.listening_for(resumed), None);
+    core.apply_snapshot(snapshot(track("C", PlayState::Playing), 0.0), resumed);
+    assert_eq!(core.listening_for(resumed), Some(Duration::ZERO));
+}
+
+#[test]
+fn track_change_clears_thumbnail_bookkeeping() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    let first = core.apply_snapshot(snapshot(track("A", PlayState::Playing), 0.0), start);
+    assert!(first.track_changed);
+
+    core.current_thumbnail_track = Some(core.now.clone());
+    core.thumbnail_hash = Some(42);
+    core.pending_thumbnail = None;
+
+    let same = core.apply_snapshot(snapshot(track("A", PlayState::Paused), 0.0), start);
+    assert!(!same.track_changed && same.state_changed);
+    assert_eq!(core.thumbnail_hash, Some(42));
+    assert!(core.pending_thumbnail.is_none());
+
+    let next = core.apply_snapshot(snapshot(track("B", PlayState::Playing), 0.0), start);
+    assert!(next.track_changed);
+    assert_eq!(core.thumbnail_hash, None);
+    assert!(core.current_thumbnail_track.is_none());
+    assert!(matches!(
+        core.pending_thumbnail,
+        Some(PendingThumbnail::Clear { track: None })
+    ));
+}
+
+#[test]
+fn committed_seek_holds_until_confirmed_or_timed_out() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 10.0), start);
+
+    core.drag_seek(60.0, start);
+    assert!(core.is_user_seeking && core.clock.pending_seek_target.is_none());
+    assert_eq!(position(&core), 60.0);
+
+    // Releasing where the drag started sends nothing.
+    assert!(!core.commit_seek(10.0, 10.0, start));
+    assert!(!core.is_user_seeking);
+
+    assert!(core.commit_seek(60.0, 10.0, start));
+    assert_eq!(core.clock.pending_seek_target, Some(60.0));
+
+    // Stale reports are overridden by the target until the session catches up.
+    let stale = start + Duration::from_millis(500);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 11.0), stale);
+    assert_eq!(position(&core), 60.0);
+    assert!(core.is_user_seeking);
+
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 60.2), stale);
+    assert_eq!(position(&core), 60.2);
+    assert!(core.clock.pending_seek_target.is_none() && !core.is_user_seeking);
+
+    // A session that never reports the new position is given up on after the timeout.
+    assert!(core.commit_seek(120.0, 60.2, stale));
+    let expired = stale + SEEK_COMMIT_TIMEOUT;
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 61.0), expired);
+    assert!(core.clock.pending_seek_target.is_none() && !core.is_user_seeking);
+}
+
+#[test]
+fn seeks_on_short_tracks_resolve_within_the_track() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    let short = |position_secs: f64| SessionSnapshot {
+        timeline: Some(Timeline {
+            start_secs: 0.0,
+            end_secs: 3.0,
+            position_secs,
+            can_seek: true,
+        }),
+        ..snapshot(track("Jingle", PlayState::Playing), position_secs)
+    };
+    core.apply_snapshot(short(0.5), start);
+    assert!(seek_commit_timeout(Some(3.0)) < Duration::from_secs(3));
+
+    // Half a second is a sixth of this track, so a report that close doesn't confirm.
+    assert!(core.commit_seek(2.0, 0.5, start));
+    core.apply_snapshot(short(1.6), start + Duration::from_millis(200));
+    assert_eq!(position(&core), 2.0);
+    core.apply_snapshot(short(2.1), start + Duration::from_millis(400));
+    assert!(core.clock.pending_seek_target.is_none() && !core.is_user_seeking);
+
+    // A session that ignores the seek is given up on well before the track could end.
+    assert!(core.commit_seek(2.9, 2.1, start));
+    let expired = start + seek_commit_timeout(Some(3.0));
+    core.apply_snapshot(short(0.7), expired);
+    assert!(core.clock.pending_seek_target.is_none() && !core.is_user_seeking);
+}
+
+#[test]
+fn poll_interval_backs_off_when_idle() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    let mut interval_for = |state| {
+        core.apply_snapshot(snapshot(track("A", state), 0.0), start);
+        core.snapshot_poll_interval()
+    };
+
+    let playing = interval_for(PlayState::Playing);
+    let changing = interval_for(PlayState::Changing);
+    let paused = interval_for(PlayState::Paused);
+    let closed = interval_for(PlayState::Closed);
+    assert!(changing < playing);
+    assert!(playing < paused && paused < closed);
+    assert_eq!(closed, Duration::from_secs(5));
+}
+
+#[test]
+fn failed_poll_drops_the_timeline() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 10.0), start);
+
+    let later = start + Duration::from_secs(1);
+    core.apply_error("session went away".to_string(), later);
+    assert!(core.timeline.is_none());
+    assert_eq!(core.last_pull, later);
+    assert_eq!(core.err.as_deref(), Some("session went away"));
+
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 12.0), later);
+    assert!(core.err.is_none());
+}
+
+#[test]
+fn track_change_remembers_the_previous_track() {
+    let start = Instant::now();
+    let mut core = AppCore::new(start);
+    core.apply_snapshot(snapshot(track("A", PlayState::Playing), 0.0), start);
+    assert!(core.previous_track.is_none());
+
+    core.apply_snapshot(snapshot(track("A", PlayState::Paused), 0.0), start);
+    assert!(core.previous_track.is_none());
+
+    let mut with_queue = snapshot(track("B", PlayState::Playing), 0.0);
+    with_queue.next = Some(track("C", PlayState::Unknown));
+    core.apply_snapshot(with_queue, start);
+    assert_eq!(
+        core.previous_track.as_ref().map(|t| t.title.as_str()),
+        Some("A")
+    );
+    assert_eq!(
+        core.next_track.as_ref().map(|t| t.title.as_str()),
+        Some("C")
+    );
+
+    // Sources without a queue clear the stale next track.
+    core.apply_snapshot(snapshot(track("C", PlayState::Playing), 0.0), start);
+    assert_eq!(
+        core.previous_track.as_ref().map(|t| t.title.as_str()),
+        Some("B")
+    );
+    assert!(core.next_track.is_none());
+}
+
+#[test]
+fn track_id_follows_the_metadata_equality() {
+    let playing = track("A", PlayState::Playing);
+    let paused = NowPlaying {
+        app_id: "Spotify.exe".to_string(),
+        ..track("A", PlayState::Paused)
+    };
+    assert_eq!(playing, paused);
+    assert_eq!(playing.track_id(), paused.track_id());
+
+    let with = |title: &str, artist: &str, album: &str| NowPlaying {
+        title: title.to_string(),
+        artist: artist.to_string(),
+        album: album.to_string(),
+        ..Default::default()
+    };
+    // Any field change moves the id, and text can't shift between fields unnoticed.
+    for changed in [
+        with("B", "Artist", "Album"),
+        with("A", "Other", "Album"),
+        with("A", "Artist", ""),
+        with("AA", "rtist", "Album"),
+    ] {
+        assert_ne!(playing.track_id(), changed.track_id(), "{changed:?}");
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui::Color32;
+use now_playing_gui::{
+    layout::load_layout_from_dir,
+    theme::{load_theme_from_dir, WindowBackdrop},
+};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn theme_fixture_overlays_the_builtin_theme() {
+    // Declares engine "1", so it loads without version warnings.
+    let loaded = load_theme_from_dir(&fixture("compact")).expect("fixture theme loads");
+    assert!(loaded.warnings.is_empty(), "{:?}", loaded.warnings);
+
+    let theme = loaded.theme;
+    assert_eq!(theme.display_name, "Compact Fixture");
+    assert_eq!(theme.colors["accent"], Color32::from_rgb(0x34, 0x78, 0xF6));
+    assert_eq!(theme.background_alpha, 0.8);
+    assert_eq!(theme.effective_window_backdrop(), WindowBackdrop::Mica);
+    // Colours the fixture leaves out come from the built-in theme.
+    assert!(theme.colors.contains_key("text_primary"));
+}
+
+#[test]
+fn theme_fixture_with_bad_values_loads_with_warnings() {
+    let loaded = load_theme_from_dir(&fixture("out_of_range")).expect("fixture theme loads");
+    assert_eq!(loaded.theme.background_alpha, 1.0);
+    assert_eq!(loaded.theme.window_backdrop, WindowBackdrop::None);
+    assert_eq!(loaded.warnings.len(), 2, "{:?}", loaded.warnings);
+}
+
+#[test]
+fn layout_fixture_resolves_variants_and_breakpoints() {
+    let loaded = load_layout_from_dir(&fixture("compact")).expect("fixture layout loads");
+    assert!(loaded.warnings.is_empty(), "{:?}", loaded.warnings);
+
+    let layout = loaded.layout;
+    let ids: Vec<_> = layout.variants().iter().map(|v| v.id.as_str()).collect();
+    assert_eq!(ids, ["full", "wide"]);
+    assert_eq!(layout.default_variant, "full");
+    assert_eq!(layout.variant_for_width(400.0), "full");
+    assert_eq!(layout.variant_for_width(800.0), "wide");
+}
+
+#[test]
+fn same_major_engine_loads_with_a_warning() {
+    let theme = load_theme_from_dir(&fixture("engine_minor")).expect("fixture theme loads");
+    assert_eq!(theme.theme.display_name, "Minor Bump Fixture");
+    assert_eq!(
+        theme.theme.colors["accent"],
+        Color32::from_rgb(0xE0, 0x45, 0x7B)
+    );
+    assert_eq!(theme.warnings.len(), 1, "{:?}", theme.warnings);
+    assert!(theme.warnings[0].contains("1.1 is newer than 1"));
+
+    let layout = load_layout_from_dir(&fixture("engine_minor")).expect("fixture layout loads");
+    assert_eq!(layout.layout.default_variant, "main");
+    assert_eq!(layout.warnings.len(), 1, "{:?}", layout.warnings);
+}
+
+#[test]
+fn newer_major_engine_loads_known_keys_and_lists_the_rest() {
+    let theme = load_theme_from_dir(&fixture("engine_next")).expect("fixture theme loads");
+    assert_eq!(theme.theme.display_name, "Next Engine Fixture");
+    assert_eq!(
+        theme.theme.components.button.background,
+        Color32::from_rgb(0x34, 0xC7, 0x59)
+    );
+    let unknown: Vec<_> = theme
+        .warnings
+        .iter()
+        .filter(|warning| warning.starts_with("Unknown theme setting"))
+        .collect();
+    assert_eq!(unknown.len(), 2, "{:?}", theme.warnings);
+    assert!(unknown.iter().any(|w| w.contains("'meta.palette'")));
+    assert!(unknown
+        .iter()
+        .any(|w| w.contains("'components.button.haptics'")));
+
+    let layout = load_layout_from_dir(&fixture("engine_next")).expect("fixture layout loads");
+    let ids: Vec<_> = layout
+        .layout
+        .variants()
+        .iter()
+        .map(|v| v.id.as_str())
+        .collect();
+    assert_eq!(ids, ["main"]);
+    let unknown: Vec<_> = layout
+        .warnings
+        .iter()
+        .filter(|warning| warning.starts_with("Unknown layout setting"))
+        .collect();
+    assert_eq!(unknown.len(), 2, "{:?}", layout.warnings);
+    assert!(unknown
+        .iter()
+        .any(|w| w.contains("'layout.variants[0].transition'")));
+    assert!(unknown
+        .iter()
+        .any(|w| w.contains("'layout.variants[0].structure.children[0].tooltip'")));
+}
+
+#[test]
+fn missing_skin_files_fall_back_to_builtins() {
+    let empty = fixture("does_not_exist");
+    let theme = load_theme_from_dir(&empty).expect("builtin theme loads");
+    let layout = load_layout_from_dir(&empty).expect("builtin layout loads");
+    assert!(!theme.theme.colors.is_empty());
+    assert!(!layout.layout.variants().is_empty());
+}